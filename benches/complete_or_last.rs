@@ -3,7 +3,7 @@ use virus_alarm::prelude::*;
 
 fn set_up() -> Simulation {
 	let board = Board::default();
-	let report_plan = ReportPlan { num_simulations: 5, days: 10 };
+	let report_plan = ReportPlan { num_simulations: 5, days: 10, ..Default::default() };
 	Simulation::new(board, report_plan)
 }
 
@@ -12,7 +12,12 @@ fn run_complete(simulation: &Simulation) {
 }
 
 fn run_last(simulation: &Simulation) {
-    simulation.run_last_day();
+    for _ in 0..*simulation.report_plan().num_simulations() {
+        let mut board = simulation.board().clone();
+        for _ in 0..*simulation.report_plan().days() {
+            board.advance_population();
+        }
+    }
 }
 
 
@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use virus_alarm::prelude::*;
+
+/// Large board, so that the cost of each stage dominates the setup.
+fn set_up(visiting: Visiting) -> Board {
+    let mut board = BoardBuilder {
+        healthy: 2_000,
+        infected1: 20,
+        buildings: vec![(10, 10); 15],
+        ..Default::default()
+    }.build();
+    board.set_visiting(visiting);
+    board
+}
+
+fn bench_stages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Stages of a large board");
+    group.sample_size(50);
+    for (name, visiting) in [
+        ("In order", Visiting::InOrder),
+        ("Capacity", Visiting::Capacity { stay_home: 10 }),
+    ] {
+        let board = set_up(visiting);
+        group.bench_with_input(name, &board, |b, board| b.iter(|| {
+            let mut board = board.clone();
+            let mut rng = virus_alarm::random::Pcg32::new(0);
+            for _ in 0..20 {
+                board.try_advance_with(&mut rng).unwrap();
+            }
+            board
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_stages);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+//! Writes an animated GIF of a 30 days of an outbreak in a small town, see `virus_alarm::render::simulate_and_animate`.
+//!
+//! Run with `cargo run --example animation --features animation -- outbreak.gif 7`.
+
+use virus_alarm::render::simulate_and_animate;
+use virus_alarm::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "outbreak.gif".to_string());
+    let seed = std::env::args().nth(2).map(|seed| seed.parse()).transpose()?.unwrap_or(0);
+    let board_builder = BoardBuilder {
+        healthy: 95,
+        infected1: 5,
+        buildings: vec![(5, 4), (2, 2), (4, 4), (2, 2), (4, 3), (4, 2), (2, 2), (4, 2)],
+        ..Default::default()
+    };
+    let report_plan = ReportPlan { num_simulations: 1, days: 30, ..Default::default() };
+    let simulation_builder = SimulationBuilder { board_builder, report_plan, ..Default::default() };
+    let days = simulate_and_animate(&simulation_builder, seed, &path)?;
+    println!("Animated {} days in {}", days, path);
+    Ok(())
+}
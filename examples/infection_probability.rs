@@ -9,9 +9,8 @@ fn main() -> anyhow::Result<()> {
 	let simulations = initialize();
 
 
-	for i in 0..simulations.len() {
+	for (i, simulation) in simulations.iter().enumerate() {
 		// Run each simulation
-		let simulation = simulations[i].clone();
 		let report = simulation.run();
 		let approximation = approx_infection_probability(report);
 		// Write on a csv file
@@ -0,0 +1,47 @@
+//! A full session of the board game, as played with the published rules.
+//!
+//! The town has 100 inhabitants, two of them infected, and eight buildings.
+//! Before play, players distribute their vaccines. Then, for 10 days,
+//! players may close and open buildings before each stage of the game.
+
+use virus_alarm::prelude::*;
+use virus_alarm::render::RenderOptions;
+
+/// Number of vaccines distributed before play
+const VACCINES: usize = 10;
+/// Number of days played
+const DAYS: usize = 10;
+
+/// Actions decided by the players before the stage of each day.
+fn decisions(day: usize) -> Vec<Action> {
+	match day {
+		1 => vec![Action::Close("Concert Hall".to_string())],
+		3 => vec![Action::Close("School".to_string()), Action::Close("Restaurant".to_string())],
+		6 => vec![Action::Open("School".to_string())],
+		8 => vec![Action::Open("Concert Hall".to_string()), Action::Open("Restaurant".to_string())],
+		_ => Vec::new(),
+	}
+}
+
+fn main() -> anyhow::Result<()> {
+	let mut board = Board::default();
+	board.set_collect_events(true);
+
+	// Vaccine phase
+	let immunized = board.immunize_many(VACCINES);
+	println!("Vaccine phase: {} individuals immunized.\n", immunized);
+
+	// Days of play
+	for day in 1..=DAYS {
+		for action in decisions(day) {
+			println!("Day {}: {:?}", day, action);
+			action.apply(&mut board)?;
+		}
+		board.try_advance()?;
+	}
+
+	println!("\n{}", board.render(&RenderOptions::default()));
+	println!("{}", board.counting_table());
+	println!("Events: {}", board.recording().events().len());
+	Ok(())
+}
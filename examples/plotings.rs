@@ -9,8 +9,7 @@ fn main() {
 
 	let mut averages = Vec::new();
 	let mut histograms = Vec::new();
-	for i in 0..simulations.len() {
-		let simulation = simulations[i].clone();
+	for (i, simulation) in simulations.iter().enumerate() {
 		let report = simulation.run();
 		let healthy: Vec<Vec<f64>> = report.individual_transpose(&Individual::Healthy)
 			.iter()
@@ -0,0 +1,11 @@
+//! Serves simulations and a shared board over HTTP, see `virus_alarm::server`.
+//!
+//! Run with `cargo run --example server --features server -- 0.0.0.0:8000`.
+
+use virus_alarm::server::Server;
+
+fn main() -> std::io::Result<()> {
+    let address = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8000".to_string());
+    println!("Listening on {}", address);
+    Server::default().serve(&address)
+}
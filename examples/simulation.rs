@@ -8,9 +8,8 @@ fn main() -> anyhow::Result<()> {
 	// Read from configuration file
 	let simulations = initialize();
 
-	for i in 0..simulations.len() {
+	for (i, simulation) in simulations.iter().enumerate() {
 		// Run each simulation
-		let simulation = simulations[i].clone();
 		let report = simulation.run();
 		// Write the results in a csv file
 		for counting_table in report.counting_tables() {
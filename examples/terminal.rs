@@ -1,4 +1,5 @@
 use virus_alarm::prelude::*;
+use virus_alarm::render::{Legend, RenderOptions};
 
 
 fn main() {
@@ -12,16 +13,23 @@ fn main() {
                 immune: 20,
                 buildings: vec![(0, 0)],
                 spreading: Spreading::OneNear,
+                ..Default::default()
         },
         report_plan: ReportPlan{
                 num_simulations: 1,
                 days: 10,
-        }
+                ..Default::default()
+        },
+        interventions: vec![Intervention::new(3, Action::Immunize(5))],
+        ..Default::default()
     };
 
     let pretty = ron::ser::PrettyConfig::new()
         .with_depth_limit(3)
         .with_separate_tuple_members(true)
         .with_enumerate_arrays(true);
-    println!("{}", ron::ser::to_string_pretty(&vec![simulation_builder], pretty).unwrap());
+    println!("{}", ron::ser::to_string_pretty(&vec![simulation_builder.clone()], pretty).unwrap());
+
+    let options = RenderOptions { legend: Legend::Emoji, key: true, ..Default::default() };
+    println!("{}", simulation_builder.build().board().render(&options));
 }
\ No newline at end of file
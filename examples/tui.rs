@@ -0,0 +1,12 @@
+//! Plays the official game in the terminal, see `virus_alarm::tui`.
+//!
+//! Run with `cargo run --example tui --features tui`.
+
+use virus_alarm::game::{Game, GameRules};
+use virus_alarm::Board;
+
+fn main() -> std::io::Result<()> {
+    let game = virus_alarm::tui::run(Game::new(Board::default(), GameRules::default()))?;
+    println!("Game ended on day {} with outcome {:?}", game.day(), game.outcome());
+    Ok(())
+}
@@ -64,11 +64,14 @@ impl Input {
                 immune: self.immune,
                 buildings: self.buildings(),
                 spreading: SPREADING,
+                ..Default::default()
             },
             report_plan: ReportPlan {
                 num_simulations,
                 days: 10,
+                ..Default::default()
             },
+            ..Default::default()
         }
         .build()
     }
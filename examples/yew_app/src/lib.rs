@@ -119,6 +119,7 @@ impl Component for Model {
                     ReportPlan {
                         num_simulations,
                         days: 10,
+                        ..Default::default()
                     },
                 )
                 .run();
@@ -192,35 +193,35 @@ impl Component for Model {
                 false
             }
             Msg::ToggleConcertHall => {
-                self.board.toggle("Concert Hall");
+                self.board.toggle("Concert Hall").unwrap();
                 false
             }
             Msg::ToggleBakery => {
-                self.board.toggle("Bakery");
+                self.board.toggle("Bakery").unwrap();
                 false
             }
             Msg::ToggleSchool => {
-                self.board.toggle("School");
+                self.board.toggle("School").unwrap();
                 false
             }
             Msg::TogglePharmacy => {
-                self.board.toggle("Pharmacy");
+                self.board.toggle("Pharmacy").unwrap();
                 false
             }
             Msg::ToggleRestaurant => {
-                self.board.toggle("Restaurant");
+                self.board.toggle("Restaurant").unwrap();
                 false
             }
             Msg::ToggleGym => {
-                self.board.toggle("Gym");
+                self.board.toggle("Gym").unwrap();
                 false
             }
             Msg::ToggleSupermarket => {
-                self.board.toggle("Supermarket");
+                self.board.toggle("Supermarket").unwrap();
                 false
             }
             Msg::ToggleShoppingCenter => {
-                self.board.toggle("Shopping Center");
+                self.board.toggle("Shopping Center").unwrap();
                 false
             }
             Msg::SpreadingMode(change_data) => {
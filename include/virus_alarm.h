@@ -0,0 +1,105 @@
+#ifndef VIRUS_ALARM_H
+#define VIRUS_ALARM_H
+
+/* C declarations of the `ffi` module, regenerate with `cbindgen --config cbindgen.toml --output include/virus_alarm.h`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Report Report;
+
+typedef struct Simulation Simulation;
+
+// Simulation ready to run, see `virus_alarm_simulation_from_json`.
+typedef Simulation VirusAlarmSimulation;
+
+// Result of running a simulation, see `virus_alarm_simulation_run`.
+typedef Report VirusAlarmReport;
+
+// Returns the message of the last error in this thread, or a null pointer if there was none.
+//
+// The message is valid until the next call to a function of this module that fails, in this thread.
+const char *virus_alarm_last_error(void);
+
+// Returns the number of rows of counting tables, which is the number of individual variants.
+size_t virus_alarm_num_variants(void);
+
+// Returns a simulation from a JSON configuration, or a null pointer if it is not valid.
+//
+// # Safety
+//
+// `config` must be a valid nul-terminated string.
+VirusAlarmSimulation *virus_alarm_simulation_from_json(const char *config);
+
+// Frees a simulation.
+//
+// # Safety
+//
+// `simulation` must be null or returned by `virus_alarm_simulation_from_json`, and not freed before.
+void virus_alarm_simulation_free(VirusAlarmSimulation *simulation);
+
+// Runs a simulation and returns its report, see `Simulation::run`.
+//
+// # Safety
+//
+// `simulation` must be returned by `virus_alarm_simulation_from_json`, and not freed.
+VirusAlarmReport *virus_alarm_simulation_run(const VirusAlarmSimulation *simulation);
+
+// Runs a simulation with randomness given by `seed`, and returns its report.
+//
+// The same seed gives the same report with the same version of this crate.
+//
+// # Safety
+//
+// `simulation` must be returned by `virus_alarm_simulation_from_json`, and not freed.
+VirusAlarmReport *virus_alarm_simulation_run_seeded(const VirusAlarmSimulation *simulation, uint64_t seed);
+
+// Frees a report.
+//
+// # Safety
+//
+// `report` must be null or returned by a function of this module, and not freed before.
+void virus_alarm_report_free(VirusAlarmReport *report);
+
+// Returns the number of realizations in a report, or zero if they were not stored, see `Report::counting_tables`.
+//
+// # Safety
+//
+// `report` must be returned by a function of this module, and not freed.
+size_t virus_alarm_report_realizations(const VirusAlarmReport *report);
+
+// Returns the number of days in a report, see `Report::days`.
+//
+// # Safety
+//
+// `report` must be returned by a function of this module, and not freed.
+size_t virus_alarm_report_days(const VirusAlarmReport *report);
+
+// Writes the average counting table of a report on `buffer`, see `Report::average_counting_table`.
+//
+// Streamed reports write the means of their running summary, see `Aggregation::Streaming`.
+//
+// Returns the length the buffer needs, which is the number of variants times the number of days.
+// Nothing is written if `buffer` is null or shorter than that.
+//
+// # Safety
+//
+// `report` must be returned by a function of this module, and not freed.
+// `buffer` must be null or valid for writing `len` values.
+size_t virus_alarm_report_average(const VirusAlarmReport *report, double *buffer, size_t len);
+
+// Writes the counting table of the realization with index `realization` on `buffer`.
+//
+// Returns the length the buffer needs, which is the number of variants times the number of days
+// of the realization, or zero if there is no such realization.
+// Nothing is written if `buffer` is null or shorter than that.
+//
+// # Safety
+//
+// `report` must be returned by a function of this module, and not freed.
+// `buffer` must be null or valid for writing `len` values.
+size_t virus_alarm_report_counting_table(const VirusAlarmReport *report, size_t realization, size_t *buffer, size_t len);
+
+#endif /* VIRUS_ALARM_H */
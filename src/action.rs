@@ -0,0 +1,186 @@
+use crate::{Board, Individual, building::{Spreading, Tag}};
+use crate::errors::ActionError;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Action that can be taken on a board between two stages of the game.
+///
+/// This is the common representation of actions, shared by every subsystem 
+/// that acts on a board, like scheduled interventions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Action {
+    /// Immunizes the given number of healthy individuals
+    Immunize(usize),
+    /// Reverses the immunization of the given number of immune individuals
+    ReverseImmunize(usize),
+    /// Closes the building with the given name
+    Close(String),
+    /// Opens the building with the given name
+    Open(String),
+    /// Toggles the building with the given name
+    Toggle(String),
+    /// Closes every building in the given group, see `Board::close_group`
+    CloseGroup(Tag),
+    /// Closes every building except the ones in the given groups, see `Board::lockdown`
+    Lockdown(Vec<Tag>),
+    /// Changes the spreading mode of all buildings
+    SetSpreading(Spreading),
+    /// Changes the number of healthy individuals immunized at the start of each day, see `Board::set_vaccination_rate`
+    SetVaccinationRate(usize),
+    /// Restricts the number of individuals allowed in the building with the given name 
+    /// to a percentage of its capacity, see `Board::reduce_capacity`
+    ///
+    /// The capacity is given as a whole percentage instead of a fraction, so that actions 
+    /// can still be compared and hashed, for example as part of a configuration. 
+    /// Use `Building::set_capacity_fraction` for finer fractions.
+    ReduceCapacity(String, u8),
+    /// Infects the given number of healthy individuals, see `Board::infect`
+    Infect(usize),
+    /// Adds the given number of individuals of a variant, see `Board::arrive`
+    Arrive(Individual, usize),
+    /// Removes the given number of individuals of a variant, see `Board::depart`
+    Depart(Individual, usize),
+}
+
+impl Action {
+    /// Applies the action to `board`.
+    ///
+    /// # Errors
+    ///
+    /// If there are not enough healthy (or immune) individuals to (reverse) immunize or infect,
+    /// or not enough individuals of a variant to depart, or if there is no building with the given name.
+    /// In this case, the action is applied to as many individuals as possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let mut board = Board::default();
+    /// Action::Immunize(10).apply(&mut board).unwrap();
+    /// assert_eq!(board.population().counting(Individual::Immune), 10);
+    /// ```
+    pub fn apply<'a>(&self, board: &'a mut Board) -> Result<&'a mut Board, ActionError> {
+        self.apply_with(board, &mut rand::thread_rng())
+    }
+
+    /// Applies the action to `board`, using `rng` as the source of randomness.
+    ///
+    /// See `apply` for more.
+    ///
+    /// # Errors
+    ///
+    /// As in `apply`.
+    pub fn apply_with<'a, R: rand::Rng + ?Sized>(&self, board: &'a mut Board, rng: &mut R) -> Result<&'a mut Board, ActionError> {
+        match self {
+            Action::Immunize(num) => {
+                for _ in 0..*num {
+                    board.immunize_with(rng)?;
+                }
+            },
+            Action::ReverseImmunize(num) => {
+                for _ in 0..*num {
+                    board.reverse_immunize()?;
+                }
+            },
+            Action::Close(name) => { board.close(name)?; },
+            Action::Open(name) => { board.open(name)?; },
+            Action::Toggle(name) => { board.toggle(name)?; },
+            Action::CloseGroup(tag) => { board.close_group(*tag); },
+            Action::Lockdown(except) => { board.lockdown(except); },
+            Action::SetSpreading(spreading) => { board.set_spreading(*spreading); },
+            Action::SetVaccinationRate(rate) => { board.set_vaccination_rate(*rate); },
+            Action::ReduceCapacity(name, percent) => { board.reduce_capacity(name, *percent)?; },
+            Action::Infect(num) => {
+                if board.infect(*num) < *num {
+                    return Err(ActionError::NoHealthyLeft);
+                }
+            },
+            Action::Arrive(individual, num) => {
+                for _ in 0..*num {
+                    board.arrive(*individual);
+                }
+            },
+            Action::Depart(individual, num) => {
+                for _ in 0..*num {
+                    board.depart(*individual)?;
+                }
+            },
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn immunize() {
+        let mut board = Board::default();
+        Action::Immunize(3).apply(&mut board).unwrap();
+        assert_eq!(board.population().counting(Individual::Immune), 3);
+        assert_eq!(board.counting_table().last_day()[&Individual::Immune], 3);
+    }
+
+    #[test]
+    fn immunize_too_many() {
+        let mut board = Board::default();
+        assert_eq!(Action::Immunize(99).apply(&mut board).err(), Some(ActionError::NoHealthyLeft));
+        assert_eq!(board.population().counting(Individual::Immune), 98);
+    }
+
+    #[test]
+    fn close() {
+        let mut board = Board::default();
+        Action::Close("School".to_string()).apply(&mut board).unwrap();
+        let school = board.buildings().iter().find(|b| b.name() == "School").unwrap();
+        assert!(school.is_close());
+    }
+
+
+    #[test]
+    fn set_spreading() {
+        let mut board = Board::default();
+        Action::SetSpreading(Spreading::Everyone).apply(&mut board).unwrap();
+        assert_eq!(board.spreading(), &Spreading::Everyone);
+    }
+
+    #[test]
+    fn set_vaccination_rate() {
+        let mut board = Board::default();
+        Action::SetVaccinationRate(4).apply(&mut board).unwrap();
+        board.advance();
+        assert_eq!(board.vaccination_rate(), &4);
+        assert_eq!(board.recording().vaccines_used(), &4);
+    }
+
+    #[test]
+    fn reduce_capacity() {
+        let mut board = Board::default();
+        Action::ReduceCapacity("Concert Hall".to_string(), 25).apply(&mut board).unwrap();
+        let concert_hall = board.buildings().iter().find(|b| b.name() == "Concert Hall").unwrap();
+        assert_eq!(concert_hall.limit(), Some(5));
+        let unknown = Action::ReduceCapacity("Concert Hal".to_string(), 25).apply(&mut board).err();
+        assert_eq!(unknown, Some(ActionError::UnknownBuilding("Concert Hal".to_string())));
+    }
+
+    #[test]
+    fn infect() {
+        let mut board = Board::default();
+        Action::Infect(3).apply(&mut board).unwrap();
+        assert_eq!(board.population().counting(Individual::Infected1), 5);
+        assert_eq!(Action::Infect(96).apply(&mut board).err(), Some(ActionError::NoHealthyLeft));
+        assert_eq!(board.population().counting(Individual::Healthy), 0);
+    }
+
+    #[test]
+    fn arrive_and_depart() {
+        let mut board = Board::default();
+        Action::Arrive(Individual::Infected2, 2).apply(&mut board).unwrap();
+        assert_eq!(board.population().counting(Individual::Infected2), 2);
+        assert_eq!(Action::Depart(Individual::Infected2, 3).apply(&mut board).err(), Some(ActionError::NotPresent(Individual::Infected2)));
+        assert_eq!(board.population().len(), 100);
+    }
+}
@@ -1,9 +1,27 @@
-use crate::recording::CountingTable;
+use crate::recording::{CountingTable, DayZero, Event};
 use core::fmt::Display;
-use crate::{BuildingBuilder, Building, Population, Individual, Recording, building::Spreading};
+use crate::{BuildingBuilder, Building, Population, Individual, Recording, building::{Spreading, Tag}, individual::Id, population::Composition};
+use ndarray::Array2;
 use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
+mod config;
+#[cfg(feature = "analysis")]
+pub(crate) use config::check_days;
+mod outcome;
+pub use outcome::*;
+mod days;
+pub use days::*;
+mod generator;
+pub use generator::*;
+pub(crate) mod hash;
+
+/// Largest number of days accepted in configurations, see `BoardBuilder::diagnostics`.
+///
+/// Larger durations could overflow when added to a day of the game.
+pub const MAX_DAYS: usize = u32::MAX as usize;
+
 /// Builder for the `Board`.
 ///
 /// # Remarks
@@ -14,7 +32,9 @@ use serde::{Serialize, Deserialize};
 ///   
 /// A `Board` could be in the middle of a game, derefore (de)serialization 
 /// turns out to be less human-friendly.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, Setters, MutGetters, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BoardBuilder {
 	/// Number of healthy individuals
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
@@ -34,41 +54,317 @@ pub struct BoardBuilder {
     /// Number of immune individuals
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
     pub immune: usize,
-    /// Current state of the buildings in the game
+    /// Number of healthy individuals who are superspreaders once infected
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub superspreaders: usize,
+    /// Columns and rows of the buildings in the game, 
+    /// which are named `Building 1`, `Building 2` and so on, in this order
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
     pub buildings: Vec<(usize, usize)>,
     /// Spreading mode
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
     pub spreading: Spreading,
+    /// Visiting model
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub visiting: Visiting,
+    /// Vaccine used to immunize individuals
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vaccine: Vaccine,
+    /// Choice of the individuals to immunize
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vaccination_strategy: VaccinationStrategy,
+    /// Number of days immune individuals stay immune, forever if `None`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub immunity_duration: Option<usize>,
+    /// Number of days newly infected individuals are exposed before being infectious, if any
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub latency: Option<usize>,
+    /// Probability, in percentage, that an infected individual recovers without getting sick
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub asymptomatic: u8,
+    /// Meaning of the first day in reported counting tables
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub day_zero: DayZero,
+    /// Number of vaccines available during the game, unlimited if `None`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vaccines: Option<usize>,
+    /// Number of healthy individuals immunized at the start of each day, see `Board::set_vaccination_rate`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vaccination_rate: usize,
+    /// What happens to sick individuals
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub convalescence: Convalescence,
+    /// Whether the events of the game are collected, see `Recording::events`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collect_events: bool,
+    /// Whether individuals have identities and their histories are collected, see `Board::set_track_identities`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub track_identities: bool,
+    /// Number of individuals and share of each variant, used instead of the numbers of individuals above,
+    /// see `Composition`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub composition: Option<Composition>,
+    /// Path of a population file in CSV format, used instead of the numbers of individuals above, 
+    /// see `Population::from_csv`
+    #[cfg(feature = "serde")]
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub population_file: Option<String>,
+}
+
+/// Returns the number of individuals in `population` who can be immunized, see `Board::immunize`.
+pub(crate) fn vaccinatable(population: &Population) -> usize {
+	population.counting(Individual::Healthy)
 }
 
 impl BoardBuilder {
+	/// Returns the board.
+	///
+	/// If the population file can not be read, the composition or the numbers of individuals 
+	/// of the builder are used instead, see `try_build` to get an error.
 	pub fn build(self) -> Board {
-		// Population
+		let population = match self.read_population_file() {
+			Some(Ok(population)) => population,
+			_ => self.configured_population(),
+		};
+		self.build_with(population)
+	}
+
+	/// Returns the population of the population file, or the reason it could not be read,
+	/// if there is a population file.
+	fn read_population_file(&self) -> Option<Result<Population, String>> {
+		#[cfg(feature = "serde")]
+		if let Some(path) = &self.population_file {
+			return Some(Population::from_csv_path(path).map_err(|error| error.to_string()));
+		}
+		None
+	}
+
+	/// Returns the population given by the composition or, if there is none, by the numbers of individuals.
+	fn configured_population(&self) -> Population {
+		match &self.composition {
+			Some(composition) => composition.population(),
+			None => self.counts_population(),
+		}
+	}
+
+	/// Returns the population given by the numbers of individuals of the builder.
+	fn counts_population(&self) -> Population {
 		let mut population_vec = vec![Individual::Healthy; self.healthy];
 		population_vec.append(&mut vec![Individual::Infected1; self.infected1]);
 		population_vec.append(&mut vec![Individual::Infected2; self.infected2]);
 		population_vec.append(&mut vec![Individual::Infected3; self.infected3]);
 		population_vec.append(&mut vec![Individual::Sick; self.sick]);
 		population_vec.append(&mut vec![Individual::Immune; self.immune]);
-		let population = Population::from(population_vec);
+		population_vec.append(&mut vec![Individual::HealthySuperspreader; self.superspreaders]);
+		Population::from(population_vec)
+	}
 
+	/// Returns the board with `population`, instead of the numbers of individuals of the builder.
+	fn build_with(self, population: Population) -> Board {
 		// Buildings
-		let buildings = self.buildings.iter().map(|&(cols, rows)| 
-			BuildingBuilder::new("Defult")
+		let buildings = self.buildings.iter().enumerate().map(|(index, &(cols, rows))| 
+			BuildingBuilder::new(format!("Building {}", index + 1))
 				.with_size(cols, rows)
 				.with_spreading(self.spreading)
 				.and_is_open()
 				.build()
 			).collect();
 
-		Board::new(population, buildings)
+		let mut board = Board::new(population, buildings);
+		board.set_visiting(self.visiting);
+		board.set_vaccine(self.vaccine);
+		board.set_vaccination_strategy(self.vaccination_strategy);
+		board.set_immunity_duration(self.immunity_duration);
+		board.set_latency(self.latency);
+		board.set_asymptomatic(self.asymptomatic);
+		board.set_day_zero(self.day_zero);
+		board.set_vaccines(self.vaccines);
+		board.set_vaccination_rate(self.vaccination_rate);
+		board.set_convalescence(self.convalescence);
+		board.set_collect_events(self.collect_events);
+		board.set_track_identities(self.track_identities);
+		board
+	}
+}
+
+/// Way in which individuals choose the building they visit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Visiting {
+	/// Buildings are filled one after the other, in the order they were declared, by a random sample of the population.
+	#[default]
+	InOrder,
+	/// Each individual chooses an open and non-full building at random, 
+	/// with probability proportional to its weight.
+	///
+	/// Buildings without a weight have weight one.
+	/// Weights follow their building when buildings are added or removed, 
+	/// see `Board::add_building` and `Board::remove_building`.
+	/// Each individual stays home with probability `stay_home` in percentage, 
+	/// or if no building can host it.
+	Weighted { 
+		/// Weight of each building
+		weights: Vec<usize>, 
+		/// Probability of staying home, in percentage
+		stay_home: u8,
+	},
+	/// Each individual chooses an open building at random, 
+	/// with probability proportional to its remaining capacity.
+	///
+	/// Each individual stays home with probability `stay_home` in percentage.
+	Capacity {
+		/// Probability of staying home, in percentage
+		stay_home: u8,
+	},
+}
+
+impl Visiting {
+	/// Gives weight one to a building added after the first `buildings` ones.
+	pub(crate) fn building_added(&mut self, buildings: usize) {
+		if let Visiting::Weighted { weights, .. } = self {
+			weights.resize(buildings, 1);
+			weights.push(1);
+		}
+	}
+
+	/// Drops the weight of the building at `index`, so that later buildings keep theirs.
+	pub(crate) fn building_removed(&mut self, index: usize) {
+		if let Visiting::Weighted { weights, .. } = self {
+			if index < weights.len() {
+				weights.remove(index);
+			}
+		}
+	}
+}
+
+/// Protection given by immunizing an individual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Vaccine {
+	/// Every immunized individual becomes `Immune`.
+	#[default]
+	Perfect,
+	/// Each immunized individual becomes `Immune` with probability `efficacy` in percentage,
+	/// otherwise it becomes `Vaccinated`, which is still vulnerable.
+	Imperfect {
+		/// Probability of protecting an individual, in percentage
+		efficacy: u8,
+	},
+	/// Each immunized individual gets a first dose and becomes `PartiallyVaccinated`,
+	/// which escapes each infection with probability `protection` in percentage.
+	/// After `delay` days, it gets a second dose and becomes `Immune`.
+	///
+	/// Each dose uses one of the vaccines left, if they are limited. 
+	/// While there are no vaccines left, second doses are postponed.
+	TwoDoses {
+		/// Probability of escaping an infection after the first dose, in percentage
+		protection: u8,
+		/// Number of days between the two doses
+		delay: usize,
+	},
+}
+
+/// Choice of the healthy individual to immunize next, see `Board::immunize`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum VaccinationStrategy {
+	/// The first healthy individual in the population.
+	#[default]
+	FirstHealthy,
+	/// A healthy individual chosen uniformly at random.
+	Random,
+	/// The first healthy individual among the given identities, in the given order.
+	///
+	/// Individuals only have identities if they are tracked, see `Board::set_track_identities`.
+	ByIdentity(Vec<Id>),
+}
+
+/// What happens to sick individuals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Convalescence {
+	/// Sick individuals stay sick forever.
+	#[default]
+	Forever,
+	/// Sick individuals become `Immune` after being sick for `days`.
+	Immune {
+		/// Number of days sick
+		days: usize,
+	},
+	/// Sick individuals become `Healthy` after being sick for `days`.
+	Healthy {
+		/// Number of days sick
+		days: usize,
+	},
+}
+
+/// Count of the individuals in each part of the board.
+///
+/// See `Board::census`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Census {
+	/// Individuals at home, waiting to visit a building
+	pub population: usize,
+	/// Individuals inside buildings
+	pub buildings: usize,
+	/// Individuals that stay at home during the stage, including sick individuals
+	pub inactive: usize,
+	/// Individuals in the last day of the recording
+	pub recorded: usize,
+}
+
+impl Census {
+	/// Returns the total number of individuals present in the board.
+	pub fn total(&self) -> usize {
+		self.population + self.buildings + self.inactive
+	}
+
+	/// Returns `true` if the individuals present in the board are the ones recorded.
+	pub fn is_conserved(&self) -> bool {
+		self.total() == self.recorded
 	}
 }
 
+/// Name, capacity and whether a building is open, for example to list buildings in a user interface.
+///
+/// See `Board::building_summaries`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BuildingSummary {
+	/// Name of the building
+	pub name: String,
+	/// Number of individuals the building can host
+	pub capacity: usize,
+	/// Whether the building is open
+	pub open: bool,
+}
 
 /// Represents the state of the game and have high level commands.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, MutGetters)]
+///
+/// # Remarks
+///
+/// To play many realizations of the same board, `clone_from` the initial board into
+/// a single board per realization, which reuses its allocations instead of a new `clone`.
+#[derive(Debug, PartialEq, Eq, Getters, MutGetters)]
 pub struct Board {
 	/// Current population in the game
     #[getset(get = "pub", get_mut)]
@@ -80,34 +376,101 @@ pub struct Board {
     /// Recording device
     #[getset(get = "pub", get_mut)]
     recording: Recording,
+    /// Visiting model
+    #[getset(get = "pub")]
+    visiting: Visiting,
+    /// Vaccine used to immunize individuals
+    #[getset(get = "pub")]
+    vaccine: Vaccine,
+    /// Choice of the individuals to immunize
+    #[getset(get = "pub")]
+    vaccination_strategy: VaccinationStrategy,
+    /// Number of days immune individuals stay immune, forever if `None`
+    #[getset(get = "pub")]
+    immunity_duration: Option<usize>,
+    /// Day in which each immune individual became immune
+    immune_since: Vec<usize>,
+    /// Day in which each partially vaccinated individual got the first dose
+    first_dose_since: Vec<usize>,
+    /// Number of days newly infected individuals are exposed before being infectious, if any
+    #[getset(get = "pub")]
+    latency: Option<usize>,
+    /// Day in which each exposed individual got infected
+    exposed_since: Vec<usize>,
+    /// Probability, in percentage, that an infected individual recovers without getting sick
+    #[getset(get = "pub")]
+    asymptomatic: u8,
+    /// Number of vaccines left, unlimited if `None`
+    #[getset(get = "pub")]
+    vaccines: Option<usize>,
+    /// Number of healthy individuals immunized at the start of each day
+    #[getset(get = "pub")]
+    vaccination_rate: usize,
+    /// What happens to sick individuals
+    #[getset(get = "pub")]
+    convalescence: Convalescence,
+    /// Day in which each sick individual got sick
+    sick_since: Vec<usize>,
+    /// Identities of individuals, if tracked
+    identities: Option<Identities>,
+}
+
+/// Identities of the individuals in each part of the board, see `Board::set_track_identities`.
+#[derive(Debug, PartialEq, Eq)]
+struct Identities {
+    /// Identities in the same order as the population
+    home: Vec<Id>,
+    /// Identities in the same order as inactive individuals
+    inactive: Vec<Id>,
+    /// Identities in the seats of each building
+    seats: Vec<Array2<Option<Id>>>,
 }
 
 impl Board {
 	/// Creates a new board with the specified population and buildings as default.
 	///
-	/// # Panics
+	/// Buildings can have different spreading modes, see `spreading`.
+	///
+	/// # Examples
 	///
-	/// If not all buildings have the same spreading mode.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// # use virus_alarm::BuildingBuilder;
+	/// let buildings = vec![
+	///     BuildingBuilder::new("Park").with_size(4, 4).with_spreading(Spreading::One).build(),
+	///     BuildingBuilder::new("Cinema").with_size(4, 4).with_spreading(Spreading::Everyone).build(),
+	/// ];
+	/// let board = Board::new(Population::default(), buildings);
+	/// assert_eq!(board.uniform_spreading(), None);
+	/// ```
 	pub fn new(population: Population, buildings: Vec<Building>) -> Self {
-		assert_eq!(
-			buildings.iter().map(|b| b.spreading()).min(), 
-			buildings.iter().map(|b| b.spreading()).max()
-		);
 		let default = Board::default();
 		let recording = Recording::new(population.clone(), buildings.clone());
+		let immune_since = vec![0; population.counting(Individual::Immune)];
+		let first_dose_since = vec![0; population.counting(Individual::PartiallyVaccinated)];
+		let exposed_since = vec![0; population.counting(Individual::Exposed)];
+		let sick_since = vec![0; population.counting(Individual::Sick)];
 		Board {
 			population,
 			buildings,
 			recording,
+			immune_since,
+			first_dose_since,
+			exposed_since,
+			sick_since,
 			..default
 		}
 	}
 
 	/// Immunize one person in the population. 
+	///
+	/// The person is chosen according to the vaccination strategy, see `VaccinationStrategy`.
+	/// With an imperfect vaccine, the person might only be vaccinated, see `Vaccine`.
+	/// Each immunization uses one of the vaccines left, if they are limited.
 	/// 
 	/// # Errors
 	///
-	/// If there is no healthy individual to immunize.
+	/// If there is no healthy individual to immunize or there are no vaccines left.
 	///
 	/// # Examples
 	///
@@ -118,13 +481,106 @@ impl Board {
 	/// board.immunize();
 	/// assert_eq!(board.population().counting(Individual::Immune), 1);
 	/// ```
+	///
+	/// A vaccine that never protects.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.set_vaccine(Vaccine::Imperfect { efficacy: 0 });
+	/// board.immunize();
+	/// assert_eq!(board.population().counting(Individual::Immune), 0);
+	/// assert_eq!(board.population().counting(Individual::Vaccinated), 1);
+	/// ```
 	pub fn immunize(&mut self) -> Result<&mut Self, crate::errors::ActionError> {
-		self.population_mut().immunize()?;
-		self.recording_mut().immunize()?;
+		self.immunize_with(&mut rand::thread_rng())
+	}
+
+	/// Immunize one person in the population, using `rng` as the source of randomness.
+	///
+	/// See `immunize` for more.
+	///
+	/// # Errors
+	///
+	/// If there is no healthy individual to immunize or there are no vaccines left.
+	pub fn immunize_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<&mut Self, crate::errors::ActionError> {
+		if self.vaccines == Some(0) {
+			return Err(crate::errors::ActionError::NoVaccinesLeft);
+		}
+		let protects = match self.vaccine {
+			Vaccine::Perfect => true,
+			Vaccine::Imperfect { efficacy } => crate::random::chance(rng, f64::from(efficacy.min(100)) / 100.),
+			Vaccine::TwoDoses { .. } => false,
+		};
+		let index = self.immunization_target(rng).ok_or(crate::errors::ActionError::NoHealthyLeft)?;
+		if let Vaccine::TwoDoses { .. } = self.vaccine {
+			self.population.set(index, Individual::PartiallyVaccinated);
+			self.recording_mut().first_dose()?;
+			self.first_dose_since.push(*self.recording.timeline());
+		} else if protects {
+			self.population.set(index, Individual::Immune);
+			self.recording_mut().immunize()?;
+			self.immune_since.push(*self.recording.timeline());
+		} else {
+			self.population.set(index, Individual::Vaccinated);
+			self.recording_mut().vaccinate()?;
+		}
+		if let Some(vaccines) = self.vaccines.as_mut() {
+			*vaccines -= 1;
+		}
+		let day = *self.recording.timeline();
+		self.recording.push_event(Event::Immunization { day });
 		Ok(self)
 	}
 
+	/// Immunizes up to `num` individuals, returning the number of individuals immunized.
+	///
+	/// Fewer individuals are immunized if there are not enough healthy individuals
+	/// or vaccines left, see `immunize`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// assert_eq!(board.immunize_many(96), 96);
+	/// assert_eq!(board.immunize_many(5), 2);
+	/// assert_eq!(board.population().counting(Individual::Immune), 98);
+	/// ```
+	pub fn immunize_many(&mut self, num: usize) -> usize {
+		self.immunize_many_with(&mut rand::thread_rng(), num)
+	}
+
+	/// Immunizes up to `num` individuals, using `rng` as the source of randomness,
+	/// returning the number of individuals immunized.
+	///
+	/// See `immunize_many` for more.
+	pub fn immunize_many_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R, num: usize) -> usize {
+		(0..num).take_while(|_| self.immunize_with(rng).is_ok()).count()
+	}
+
+	/// Returns the index in the population of the next healthy individual to immunize, if any,
+	/// using `rng` as the source of randomness.
+	fn immunization_target<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+		let population = self.population.as_slice();
+		match &self.vaccination_strategy {
+			VaccinationStrategy::FirstHealthy => population.iter().position(|i| *i == Individual::Healthy),
+			VaccinationStrategy::Random => {
+				let healthy: Vec<usize> = (0..population.len()).filter(|&index| population[index] == Individual::Healthy).collect();
+				crate::random::choose(&healthy, rng).copied()
+			},
+			VaccinationStrategy::ByIdentity(ids) => {
+				let home = &self.identities.as_ref()?.home;
+				ids.iter()
+					.filter_map(|id| home.iter().position(|other| other == id))
+					.find(|&index| population[index] == Individual::Healthy)
+			},
+		}
+	}
+
 	/// Reverse one individual from immune to healthy in the population. 
+	///
+	/// The vaccine is given back, if vaccines are limited and a vaccine was used, see `Recording::vaccines_used`,
+	/// so that individuals immune from the start never add vaccines.
 	/// 
 	/// # Errors
 	///
@@ -142,11 +598,449 @@ impl Board {
 	/// assert_eq!(board.population().counting(Individual::Immune), 0);
 	/// ```
 	pub fn reverse_immunize(&mut self) -> Result<&mut Self, crate::errors::ActionError> {
+		if self.immune_since.is_empty() {
+			return Err(crate::errors::ActionError::NoImmuneLeft);
+		}
+		let refund = *self.recording.vaccines_used() > 0;
 		self.population_mut().reverse_immunize()?;
 		self.recording_mut().reverse_immunize()?;
+		self.immune_since.pop();
+		if let (true, Some(vaccines)) = (refund, self.vaccines.as_mut()) {
+			*vaccines += 1;
+		}
+		let day = *self.recording.timeline();
+		self.recording.push_event(Event::ReverseImmunization { day });
+		Ok(self)
+	}
+
+	/// Infects up to `num` healthy individuals, returning the number of individuals infected.
+	///
+	/// Infected individuals become `Infected1`, for example an imported case.
+	/// These infections are registered separately from the ones in buildings,
+	/// see `Recording::imported_infections`.
+	///
+	/// # Panics
+	///
+	/// If individuals are not at home, ie after `visit` and before `go_home`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.advance_many(5);
+	/// let healthy = board.population().counting(Individual::Healthy);
+	/// assert_eq!(board.infect(1), 1);
+	/// assert_eq!(board.population().counting(Individual::Healthy), healthy - 1);
+	/// assert_eq!(board.recording().imported_infections()[5], 1);
+	/// ```
+	pub fn infect(&mut self, num: usize) -> usize {
+		assert_eq!(self.population.remaining(), self.population.len(), "individuals must be at home");
+		let day = *self.recording.timeline();
+		let mut infected = 0;
+		while infected < num {
+			let index = match self.population.as_slice().iter().position(|i| *i == Individual::Healthy) {
+				Some(index) => index,
+				None => break,
+			};
+			self.population.infect_at(index, Individual::Infected1).expect("a healthy individual was found");
+			if let Some(identities) = self.identities.as_ref() {
+				if let Some(history) = self.recording.history_mut(identities.home[index]) {
+					history.imported = true;
+				}
+			}
+			self.recording.push_event(Event::ImportedInfection { day });
+			infected += 1;
+		}
+		self.recording.register_imported(infected);
+		infected
+	}
+
+	/// Adds an individual to the board, for example a traveler seeding infections.
+	///
+	/// The individual starts at home and is reflected in the last day of the counting table.
+	/// Immune, exposed and sick individuals start their immunity, latency and convalescence on arrival.
+	///
+	/// # Remarks
+	///
+	/// If identities are tracked, the individual gets a new identity,
+	/// whose history repeats its state on arrival for the days before.
+	///
+	/// # Panics
+	///
+	/// If individuals are not at home, ie after `visit` and before `go_home`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.arrive(Individual::Infected1);
+	/// assert_eq!(board.population().len(), 101);
+	/// assert_eq!(board.counting_table().last_day()[&Individual::Infected1], 3);
+	/// assert!(board.check_census().is_ok());
+	/// ```
+	pub fn arrive(&mut self, individual: Individual) -> &mut Self {
+		assert_eq!(self.population.remaining(), self.population.len(), "individuals must be at home");
+		let today = *self.recording.timeline();
+		self.population.push(individual);
+		self.recording.arrive(individual);
+		match individual {
+			Individual::Immune => self.immune_since.push(today),
+			Individual::PartiallyVaccinated => self.first_dose_since.push(today),
+			Individual::Exposed => self.exposed_since.push(today),
+			Individual::Sick => self.sick_since.push(today),
+			_ => (),
+		}
+		if let Some(identities) = self.identities.as_mut() {
+			let id = self.recording.push_history().expect("histories are collected while tracking identities");
+			identities.home.push(id);
+			self.record_states();
+		}
+		self.recording.push_event(Event::Arrival { day: today, individual });
+		self
+	}
+
+	/// Removes an individual of variant `individual` from the board.
+	///
+	/// # Errors
+	///
+	/// If there is no individual of that variant at home.
+	///
+	/// # Panics
+	///
+	/// If individuals are not at home, ie after `visit` and before `go_home`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.depart(Individual::Healthy).unwrap();
+	/// assert_eq!(board.population().len(), 99);
+	/// assert!(board.depart(Individual::Sick).is_err());
+	/// ```
+	pub fn depart(&mut self, individual: Individual) -> Result<&mut Self, crate::errors::ActionError> {
+		assert_eq!(self.population.remaining(), self.population.len(), "individuals must be at home");
+		let index = self.population.remove_index(individual)?;
+		self.recording.depart(individual).expect("an individual was found but not recorded");
+		self.immune_since.truncate(self.population.counting(Individual::Immune));
+		self.first_dose_since.truncate(self.population.counting(Individual::PartiallyVaccinated));
+		self.exposed_since.truncate(self.population.counting(Individual::Exposed));
+		self.sick_since.truncate(self.population.counting(Individual::Sick));
+		if let Some(identities) = self.identities.as_mut() {
+			identities.home.remove(index);
+		}
+		let day = *self.recording.timeline();
+		self.recording.push_event(Event::Departure { day, individual });
 		Ok(self)
 	}
 
+	/// Changes the number of vaccines left, unlimited if `None`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.set_vaccines(Some(1));
+	/// assert!(board.immunize().is_ok());
+	/// assert!(board.immunize().is_err());
+	/// ```
+	pub fn set_vaccines(&mut self, new_vaccines: Option<usize>) -> &mut Self {
+		self.vaccines = new_vaccines;
+		self
+	}
+
+	/// Changes the number of days immune individuals stay immune, forever if `None`.
+	///
+	/// Individuals are immune since they were immunized, or since the start of the game
+	/// if they were immune from the beginning.
+	///
+	/// # Examples
+	///
+	/// Immunity that lasts two days.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.set_immunity_duration(Some(2));
+	/// board.immunize();
+	/// board.advance();
+	/// assert_eq!(board.population().counting(Individual::Immune), 1);
+	/// board.advance();
+	/// assert_eq!(board.population().counting(Individual::Immune), 0);
+	/// ```
+	pub fn set_immunity_duration(&mut self, new_immunity_duration: Option<usize>) -> &mut Self {
+		self.immunity_duration = new_immunity_duration;
+		self
+	}
+
+	/// Changes the number of days newly infected individuals are exposed, ie infected but not infectious, 
+	/// before becoming `Infected1`.
+	///
+	/// Superspreaders have no latent stage. 
+	/// With `None`, or zero days, newly infected individuals are infectious from the next stage on.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.set_latency(Some(1));
+	/// board.advance();
+	/// assert_eq!(board.population().counting(Individual::Infected1), 0);
+	/// ```
+	pub fn set_latency(&mut self, new_latency: Option<usize>) -> &mut Self {
+		self.latency = new_latency;
+		self
+	}
+
+	/// Changes the probability, in percentage, that an infected individual is asymptomatic.
+	///
+	/// Asymptomatic individuals infect others and visit buildings as any infected individual, 
+	/// but they become `Recovered` instead of `Sick` at the end of the infection.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.set_asymptomatic(100);
+	/// board.advance_many(10);
+	/// assert_eq!(board.population().counting(Individual::Sick), 0);
+	/// ```
+	pub fn set_asymptomatic(&mut self, new_asymptomatic: u8) -> &mut Self {
+		self.asymptomatic = new_asymptomatic;
+		self
+	}
+
+	/// Changes the number of healthy individuals immunized at the start of each day, 
+	/// in a vaccination campaign that goes on during the game.
+	///
+	/// Immunizations follow the vaccine and the vaccination strategy, as in `immunize`, 
+	/// and the campaign pauses while there are no healthy individuals or vaccines left.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// # use virus_alarm::Building;
+	/// let mut board = Board::new(Population::from(vec![Individual::Healthy; 10]), vec![Building::new(2, 2, "Home")]);
+	/// board.set_vaccination_rate(3);
+	/// board.advance_many(2);
+	/// assert_eq!(board.population().counting(Individual::Immune), 6);
+	/// ```
+	pub fn set_vaccination_rate(&mut self, new_vaccination_rate: usize) -> &mut Self {
+		self.vaccination_rate = new_vaccination_rate;
+		self
+	}
+
+	/// Makes newly sick individuals recover instead, with probability `asymptomatic`.
+	fn recover<R: rand::Rng + ?Sized>(&mut self, newly_sick: usize, rng: &mut R) {
+		if self.asymptomatic > 0 {
+			let probability = f64::from(self.asymptomatic.min(100)) / 100.;
+			let recovered = (0..newly_sick).filter(|_| crate::random::chance(rng, probability)).count();
+			self.population.replace(Individual::Sick, Individual::Recovered, recovered);
+			self.recording.replace(Individual::Sick, Individual::Recovered, recovered);
+		}
+	}
+
+	/// Changes the meaning of the first day in reported counting tables. 
+	///
+	/// See `DayZero` for more. 
+	pub fn set_day_zero(&mut self, new_day_zero: DayZero) -> &mut Self {
+		self.recording.set_day_zero(new_day_zero);
+		self
+	}
+
+	/// Enables or disables the collection of events. 
+	///
+	/// See `Recording::events` for more. 
+	pub fn set_collect_events(&mut self, collect: bool) -> &mut Self {
+		self.recording.set_collect_events(collect);
+		self
+	}
+
+	/// Enables or disables the tracking of identities. 
+	///
+	/// When enabled, each individual at home gets an `Id`, from zero on in the order of the population, 
+	/// which does not change during the game. Then, the recording collects the history of each individual, 
+	/// see `Recording::histories`. Disabling the tracking discards the histories.
+	///
+	/// # Remarks
+	///
+	/// Tracking identities does not change the outcome of the game for a given source of randomness.
+	///
+	/// # Panics
+	///
+	/// If individuals are not at home, ie in the middle of a stage.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.set_track_identities(true);
+	/// board.advance_many(3);
+	/// let histories = board.recording().histories();
+	/// assert_eq!(histories.len(), 100);
+	/// assert_eq!(histories[0].states().len(), 4);
+	/// let infected_at_school = board.recording().people_infected_at("School");
+	/// assert!(infected_at_school <= 16 * 3);
+	/// ```
+	pub fn set_track_identities(&mut self, track: bool) -> &mut Self {
+		if track == self.identities.is_some() {
+			return self;
+		}
+		if track {
+			assert_eq!(self.population.remaining(), self.population.len(), "individuals must be at home");
+			self.identities = Some(Identities {
+				home: (0..self.population.len()).map(Id).collect(),
+				inactive: Vec::new(),
+				seats: self.buildings.iter().map(|b| Array2::from_elem(b.people().dim(), None)).collect(),
+			});
+			self.recording.set_histories(Some(self.population.len()));
+			self.record_states();
+		} else {
+			self.identities = None;
+			self.recording.set_histories(None);
+		}
+		self
+	}
+
+	/// Records the state of each individual at home for the current day, if identities are tracked.
+	fn record_states(&mut self) {
+		if let Some(identities) = self.identities.as_ref() {
+			let day = *self.recording.timeline();
+			for (id, individual) in identities.home.iter().zip(self.population.as_slice()) {
+				if let Some(history) = self.recording.history_mut(*id) {
+					history.set_state(day, *individual);
+				}
+			}
+		}
+	}
+
+	/// Keeps track of the identity of the last individual drawn from the population, 
+	/// which enters the building with index `index` or becomes inactive if `None`.
+	///
+	/// It must be called before the individual enters the building.
+	fn track_drawn(&mut self, index: Option<usize>) {
+		if let Some(identities) = self.identities.as_mut() {
+			let id = identities.home[self.population.len() - self.population.remaining() - 1];
+			match index {
+				Some(index) => {
+					let seat = self.buildings[index].next_seat().expect("entering a full building");
+					*identities.seats[index].iter_mut().nth(seat).expect("seats mismatch the building") = Some(id);
+				},
+				None => identities.inactive.push(id),
+			}
+		}
+	}
+
+	/// Records the building visited by each individual in the current stage, if identities are tracked.
+	fn record_visits(&mut self) {
+		if let Some(identities) = self.identities.as_ref() {
+			let stage = *self.recording.timeline();
+			for (building, seats) in self.buildings.iter().zip(identities.seats.iter()) {
+				for id in seats.iter().flatten() {
+					if let Some(history) = self.recording.history_mut(*id) {
+						history.set_visit(stage, Some(building.name().to_string()));
+					}
+				}
+			}
+			for id in identities.inactive.iter() {
+				if let Some(history) = self.recording.history_mut(*id) {
+					history.set_visit(stage, None);
+				}
+			}
+		}
+	}
+
+	/// Changes what happens to sick individuals. 
+	///
+	/// Individuals are sick since they got sick, or since the start of the game
+	/// if they were sick from the beginning.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let population = Population::from(vec![Individual::Sick]);
+	/// let mut board = Board::new(population, Vec::new());
+	/// board.set_convalescence(Convalescence::Immune { days: 1 });
+	/// board.advance();
+	/// assert_eq!(board.population().counting(Individual::Immune), 1);
+	/// ```
+	pub fn set_convalescence(&mut self, new_convalescence: Convalescence) -> &mut Self {
+		self.convalescence = new_convalescence;
+		self
+	}
+
+	/// Returns to circulation the sick individuals whose convalescence has ended.
+	fn convalesce(&mut self) {
+		let today = *self.recording.timeline();
+		let newly_sick = self.population.counting(Individual::Sick) - self.sick_since.len();
+		self.sick_since.resize(self.sick_since.len() + newly_sick, today);
+		let (days, outcome) = match self.convalescence {
+			Convalescence::Forever => return,
+			Convalescence::Immune { days } => (days, Individual::Immune),
+			Convalescence::Healthy { days } => (days, Individual::Healthy),
+		};
+		let convalescent = self.sick_since.iter().filter(|&&since| since + days <= today).count();
+		self.sick_since.retain(|&since| since + days > today);
+		self.population.replace(Individual::Sick, outcome, convalescent);
+		self.recording.replace(Individual::Sick, outcome, convalescent);
+		if outcome == Individual::Immune {
+			self.immune_since.resize(self.immune_since.len() + convalescent, today);
+		}
+	}
+
+	/// Keeps newly infected individuals exposed and makes infectious the ones whose latent stage has ended.
+	fn incubate(&mut self, newly_infected: usize) {
+		if let Some(latency) = self.latency {
+			let today = *self.recording.timeline();
+			let exposed = self.population.replace(Individual::Infected1, Individual::Exposed, newly_infected);
+			self.recording.replace(Individual::Infected1, Individual::Exposed, exposed);
+			self.exposed_since.resize(self.exposed_since.len() + exposed, today);
+			let infectious = self.exposed_since.iter().filter(|&&since| since + latency <= today).count();
+			self.exposed_since.retain(|&since| since + latency > today);
+			self.population.replace(Individual::Exposed, Individual::Infected1, infectious);
+			self.recording.replace(Individual::Exposed, Individual::Infected1, infectious);
+		}
+	}
+
+	/// Reverts to healthy the immune individuals whose immunity has expired.
+	fn wane(&mut self) {
+		if let Some(duration) = self.immunity_duration {
+			let today = *self.recording.timeline();
+			let expired = self.immune_since.iter().filter(|&&since| since + duration <= today).count();
+			self.immune_since.retain(|&since| since + duration > today);
+			for _ in 0..expired {
+				self.population.reverse_immunize().expect("an immune individual was tracked but not found");
+				self.recording.reverse_immunize().expect("an immune individual was tracked but not recorded");
+			}
+		}
+	}
+
+	/// Gives the second dose to the partially vaccinated individuals whose first dose was `delay` days ago,
+	/// while there are vaccines left, see `Vaccine::TwoDoses`.
+	fn boost(&mut self) {
+		// Partially vaccinated individuals who got infected are not waiting for a second dose anymore
+		self.first_dose_since.truncate(self.population.counting(Individual::PartiallyVaccinated));
+		if let Vaccine::TwoDoses { delay, .. } = self.vaccine {
+			let today = *self.recording.timeline();
+			let due = self.first_dose_since.iter().filter(|&&since| since + delay <= today).count();
+			let boosted = self.vaccines.map_or(due, |vaccines| due.min(vaccines));
+			self.first_dose_since.drain(..boosted);
+			self.population.replace(Individual::PartiallyVaccinated, Individual::Immune, boosted);
+			self.recording.second_doses(boosted);
+			self.immune_since.resize(self.immune_since.len() + boosted, today);
+			if let Some(vaccines) = self.vaccines.as_mut() {
+				*vaccines -= boosted;
+			}
+		}
+	}
+
 	/// Advance the specified number of stages in the game.
 	///
 	/// # Remarks
@@ -161,10 +1055,29 @@ impl Board {
 
 	/// Advance the population a stage in the game, without registering the changes.
 	///
-	/// Returns the number of newly infected individuals
+	/// Returns the number of newly infected individuals, not counting superspreaders
 	pub fn advance_population(&mut self) -> usize {
-		self.visit();
-		self.propagate();
+		self.advance_population_with(&mut rand::thread_rng())
+	}
+
+	/// Advance the population a stage in the game, without registering the changes, 
+	/// using `rng` as the source of randomness.
+	///
+	/// Returns the number of newly infected individuals, not counting superspreaders
+	pub fn advance_population_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> usize {
+		self.advance_population_inspected_with(rng, |_| ())
+	}
+
+	/// Advance the population a stage in the game, like `advance_population_with`,
+	/// calling `inspect` while individuals are in the buildings.
+	fn advance_population_inspected_with<R, F>(&mut self, rng: &mut R, mut inspect: F) -> usize
+	where
+		R: rand::Rng + ?Sized,
+		F: FnMut(&Board),
+	{
+		self.visit_with(rng);
+		self.propagate_with(rng);
+		inspect(self);
 		self.go_home()
 	}
 
@@ -174,38 +1087,257 @@ impl Board {
 	/// # Remarks
 	///
 	/// This is a short method for all steps involved in a stage.
+	///
+	/// # Panics
+	///
+	/// If individuals are not conserved at the end of the stage, see `try_advance`.
 	pub fn advance(&mut self) -> &mut Self {
-		let newly_infected = self.advance_population();
-		self.recording.register(newly_infected, &self.buildings);
+		self.try_advance().expect("the board is in a corrupted state")
+	}
+
+	/// Advance a stage in the game, checking that individuals are conserved.
+	///
+	/// # Errors
+	///
+	/// If the census at the end of the stage does not match the recording, see `census`.
+	pub fn try_advance(&mut self) -> Result<&mut Self, crate::errors::BoardError> {
+		self.try_advance_with(&mut rand::thread_rng())
+	}
+
+	/// Advance a stage in the game, checking that individuals are conserved, 
+	/// using `rng` as the source of randomness.
+	///
+	/// # Errors
+	///
+	/// If the census at the end of the stage does not match the recording, see `census`.
+	pub fn try_advance_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<&mut Self, crate::errors::BoardError> {
+		self.try_advance_inspected_with(rng, |_| ())
+	}
+
+	/// Advance a stage in the game, like `try_advance_with`, calling `inspect` with the board
+	/// once the virus has spread and before individuals go home, 
+	/// so that the seats of each individual can be seen.
+	///
+	/// # Errors
+	///
+	/// If the census at the end of the stage does not match the recording, see `census`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// let mut visiting = 0;
+	/// board.try_advance_inspected_with(&mut rand::thread_rng(), |board| {
+	///     visiting = board.buildings().iter().map(|building| building.occupancy()).sum();
+	/// }).unwrap();
+	/// assert!(visiting > 0);
+	/// assert!(board.buildings().iter().all(|building| building.occupancy() == 0));
+	/// ```
+	pub fn try_advance_inspected_with<R, F>(&mut self, rng: &mut R, inspect: F) -> Result<&mut Self, crate::errors::BoardError>
+	where
+		R: rand::Rng + ?Sized,
+		F: FnMut(&Board),
+	{
+		#[cfg(feature = "tracing")]
+		let _span = tracing::debug_span!("stage", day = *self.recording.timeline() + 1).entered();
+		self.immunize_many_with(rng, self.vaccination_rate);
+		let vaccinated = self.population.counting(Individual::Vaccinated);
+		let partially_vaccinated = self.population.counting(Individual::PartiallyVaccinated);
+		let newly_sick = self.population.counting(Individual::Infected3) + self.population.counting(Individual::Superspreader3);
+		let newly_infected = self.advance_population_inspected_with(rng, inspect);
+		let newly_superspreaders = self.population.counting(Individual::Superspreader1);
+		let newly_vaccinated_infected = vaccinated - self.population.counting(Individual::Vaccinated);
+		let newly_partially_vaccinated_infected = partially_vaccinated - self.population.counting(Individual::PartiallyVaccinated);
+		self.recording.register(newly_infected, newly_superspreaders, newly_vaccinated_infected, newly_partially_vaccinated_infected, &self.buildings);
+		#[cfg(feature = "tracing")]
+		tracing::debug!(newly_infected, newly_superspreaders, newly_vaccinated_infected, newly_sick, "stage played");
+		self.recover(newly_sick, rng);
+		self.incubate(newly_infected);
+		self.convalesce();
+		self.wane();
+		self.boost();
+		self.record_states();
+		self.check_census()?;
+		Ok(self)
+	}
+
+	/// Returns the number of individuals in each part of the board.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let board = Board::default();
+	/// let census = board.census();
+	/// assert_eq!(census.total(), 100);
+	/// assert!(census.is_conserved());
+	/// ```
+	pub fn census(&self) -> Census {
+		Census {
+			population: self.population.remaining(),
+			buildings: self.buildings.iter().map(|b| b.occupancy()).sum(),
+			inactive: self.inactive.len(),
+			recorded: self.counting_table().last_day().values().sum(),
+		}
+	}
+
+	/// Returns the census if individuals are conserved.
+	///
+	/// # Errors
+	///
+	/// If the total number of individuals in the board differs from the recorded one.
+	pub fn check_census(&self) -> Result<Census, crate::errors::BoardError> {
+		let census = self.census();
+		if census.is_conserved() {
+			Ok(census)
+		} else {
+			Err(crate::errors::BoardError::NotConserved { expected: census.recorded, found: census.total() })
+		}
+	}
+
+	/// Changes the visiting model. 
+	///
+	/// See `Visiting` for more. 
+	pub fn set_visiting(&mut self, new_visiting: Visiting) -> &mut Self {
+		self.visiting = new_visiting;
+		self
+	}
+
+	/// Changes the vaccine used to immunize individuals. 
+	///
+	/// See `Vaccine` for more. 
+	pub fn set_vaccine(&mut self, new_vaccine: Vaccine) -> &mut Self {
+		self.vaccine = new_vaccine;
+		self
+	}
+
+	/// Changes the choice of the individuals to immunize. 
+	///
+	/// See `VaccinationStrategy` for more. 
+	pub fn set_vaccination_strategy(&mut self, new_vaccination_strategy: VaccinationStrategy) -> &mut Self {
+		self.vaccination_strategy = new_vaccination_strategy;
 		self
 	}
 
 	/// First step of any stage
 	///
-	/// In this step, buildings are populated by non-sick individuals randomly.
+	/// In this step, buildings are populated by non-sick individuals randomly, 
+	/// according to the visiting model.
 	///
 	/// # Errors
 	///
 	/// If visiting any of the building fails.
 	pub fn visit(&mut self) -> &mut Self {
-		// Randomness
-		self.population.shuffle(&mut rand::thread_rng());
-		// Visiting
-		for index in 0..self.buildings.len() {
-			self.visit_building(index);
+		self.visit_with(&mut rand::thread_rng())
+	}
+
+	/// First step of any stage, using `rng` as the source of randomness.
+	///
+	/// See `visit` for more.
+	pub fn visit_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+		#[cfg(feature = "tracing")]
+		let _span = tracing::debug_span!("visit", day = *self.recording.timeline() + 1).entered();
+		// Actions taken before the stage are reflected in histories
+		self.record_states();
+		match self.identities.as_mut() {
+			Some(identities) => self.population.shuffle_with_ids(rng, &mut identities.home),
+			None => self.population.shuffle(rng),
 		}
+		// Visiting, taken out of the board while the population is assigned to buildings
+		let visiting = std::mem::take(&mut self.visiting);
+		match &visiting {
+			Visiting::InOrder => {
+				for index in 0..self.buildings.len() {
+					self.visit_building(index);
+				}
+			},
+			Visiting::Weighted { weights, stay_home } => {
+				self.visit_weighted(rng, *stay_home, |building, index| {
+					if building.is_open() && !building.is_full() {
+						weights.get(index).copied().unwrap_or(1)
+					} else {
+						0
+					}
+				});
+			},
+			Visiting::Capacity { stay_home } => {
+				self.visit_weighted(rng, *stay_home, |building, _| {
+					if building.is_open() && !building.is_full() {
+						building.available_seats().saturating_sub(building.occupancy())
+					} else {
+						0
+					}
+				});
+			},
+		}
+		self.visiting = visiting;
 		// Remaining individuals are stored in inactive 
-		self.inactive.extend(self.population.clone()); 
+		if let Some(identities) = self.identities.as_mut() {
+			let drawn = self.population.len() - self.population.remaining();
+			identities.inactive.extend(&identities.home[drawn..]);
+		}
+		self.inactive.extend(&mut self.population); 
+		self.record_visits();
+		// Occupancy
+		for (index, building) in self.buildings.iter().enumerate() {
+			self.recording.register_occupancy(index, building);
+			#[cfg(feature = "tracing")]
+			tracing::trace!(building = %building.name(), open = building.is_open(), occupancy = building.occupancy(), "building visited");
+		}
+		#[cfg(feature = "tracing")]
+		tracing::debug!(at_home = self.inactive.len(), "buildings visited");
 		self
 	}
 
+	/// Each non-sick individual visits a building with probability proportional to `weight`, 
+	/// or stays home with probability `stay_home` in percentage.
+	fn visit_weighted<R, F>(&mut self, rng: &mut R, stay_home: u8, weight: F) 
+	where
+		R: rand::Rng + ?Sized,
+		F: Fn(&Building, usize) -> usize,
+	{
+		let stay_home = f64::from(stay_home.min(100)) / 100.;
+		let mut weights = Vec::with_capacity(self.buildings.len());
+		while let Some(i) = self.population.next() {
+			if i == Individual::Sick || crate::random::chance(rng, stay_home) {
+				self.track_drawn(None);
+				self.inactive.push(i);
+				continue;
+			}
+			weights.clear();
+			weights.extend(self.buildings.iter().enumerate().map(|(index, b)| weight(b, index)));
+			let total: usize = weights.iter().sum();
+			if total == 0 {
+				self.track_drawn(None);
+				self.inactive.push(i);
+				continue;
+			}
+			let mut choice = crate::random::below(rng, total);
+			for (index, &w) in weights.iter().enumerate() {
+				if choice < w {
+					self.track_drawn(Some(index));
+					self.buildings[index].try_push(i).expect("pushing on a building with space failed!");
+					break;
+				}
+				choice -= w;
+			}
+		}
+	}
+
 	fn visit_building(&mut self, index: usize) -> &Building {
 		while !self.buildings[index].is_full() & self.buildings[index].is_open() {
 			match self.population.next() {
 				Some(i) => {
 					match i {
-						Individual::Sick => self.inactive.push(i),
-						i => self.buildings[index].try_push(i).expect("pushing on a building with space failed!"),
+						Individual::Sick => {
+							self.track_drawn(None);
+							self.inactive.push(i);
+						},
+						i => {
+							self.track_drawn(Some(index));
+							self.buildings[index].try_push(i).expect("pushing on a building with space failed!");
+						},
 					}
 				},
 				None => break,
@@ -218,81 +1350,380 @@ impl Board {
 	///
 	/// In this step, virus is propagated in each building.
 	pub fn propagate(&mut self) {
+		self.propagate_with(&mut rand::thread_rng())
+	}
+
+	/// Second step of any stage, using `rng` as the source of randomness.
+	///
+	/// See `propagate` for more.
+	pub fn propagate_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
 		// Buildings
-		for building in self.buildings.iter_mut() {
-			building.propagate();
+		let day = *self.recording.timeline() + 1;
+		#[cfg(feature = "tracing")]
+		let _span = tracing::debug_span!("propagate", day).entered();
+		for (index, building) in self.buildings.iter_mut().enumerate() {
+			let healthy = |building: &Building| building.people().iter().flatten().filter(|i| i.is_healthy()).count();
+			let infector_stage = if self.recording.is_collecting_events() {
+				let infectors: std::collections::BTreeSet<Individual> = building.people().iter().flatten()
+					.filter(|i| i.is_infectious())
+					.copied()
+					.collect();
+				if infectors.len() == 1 { infectors.into_iter().next() } else { None }
+			} else {
+				None
+			};
+			let healthy_before = healthy(building);
+			let partially_vaccinated: Vec<(usize, usize)> = match self.vaccine {
+				Vaccine::TwoDoses { .. } => building.people().indexed_iter()
+					.filter(|(_, i)| **i == Some(Individual::PartiallyVaccinated))
+					.map(|(seat, _)| seat)
+					.collect(),
+				_ => Vec::new(),
+			};
+			building.propagate_with(rng);
+			if let Vaccine::TwoDoses { protection, .. } = self.vaccine {
+				let probability = f64::from(protection.min(100)) / 100.;
+				for &(row, column) in partially_vaccinated.iter() {
+					if building.people()[[row, column]] == Some(Individual::Infected1) && crate::random::chance(rng, probability) {
+						building.set_seat(row, column, Individual::PartiallyVaccinated);
+					}
+				}
+			}
+			let infections = healthy_before - healthy(building);
+			self.recording.register_building_infections(index, infections);
+			#[cfg(feature = "tracing")]
+			if infections > 0 {
+				tracing::debug!(building = %building.name(), newly_infected = infections, "infections in building");
+			}
+			for _ in 0..infections {
+				self.recording.push_event(Event::Infection { day, building_name: building.name().to_string(), infector_stage });
+			}
 		}
 		// Inactive
 		for i in self.inactive.iter_mut() {
-			*i = match i {
-				Individual::Infected1 => Individual::Infected2,
-				Individual::Infected2 => Individual::Infected3,
-				Individual::Infected3 => Individual::Sick,
-				_ => *i,
+			*i = i.progressed();
+		}
+	}
+
+	/// Third step of any stage
+	///
+	/// In this step, the population returns home. 
+	/// Outputs the number of newly infected, not counting superspreaders.
+	pub fn go_home(&mut self) -> usize {
+		#[cfg(feature = "tracing")]
+		let _span = tracing::debug_span!("go_home", day = *self.recording.timeline() + 1).entered();
+		// The vectors of the population and of identities at home are reused
+		let mut home = self.population.take_buffer();
+		// Collect 
+		// From buildings
+		for building in self.buildings.iter_mut() {
+			building.empty_into(&mut home);
+		}
+		let newly_infected: usize = home.iter().filter(|&&i| i == Individual::Infected1).count();
+		// From inactive
+		home.append(&mut self.inactive);
+		if let Some(identities) = self.identities.as_mut() {
+			identities.home.clear();
+			for seats in identities.seats.iter_mut() {
+				identities.home.extend(seats.iter_mut().filter_map(|id| id.take()));
+			}
+			identities.home.append(&mut identities.inactive);
+		}
+
+		// Update
+		self.population = Population::from(home);
+		#[cfg(feature = "tracing")]
+		tracing::debug!(newly_infected, "population at home");
+
+		newly_infected
+	}
+
+
+	/// Adds a building to the board, after the existing ones.
+	///
+	/// The recording starts tracking the building from the current day.
+	///
+	/// # Panics
+	///
+	/// If individuals are not at home, ie after `visit` and before `go_home`.
+	///
+	/// # Examples
+	///
+	/// A vaccination center opens on day 4.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// # use virus_alarm::BuildingBuilder;
+	/// let mut board = Board::default();
+	/// board.advance_many(4);
+	/// board.add_building(BuildingBuilder::new("Vaccination Center").with_size(3, 2).build());
+	/// assert_eq!(board.buildings().len(), 9);
+	/// assert_eq!(board.recording().infections_by_building()[8].1, vec![0; 5]);
+	/// ```
+	pub fn add_building(&mut self, building: Building) -> &mut Self {
+		assert_eq!(self.population.remaining(), self.population.len(), "individuals must be at home");
+		if let Some(identities) = self.identities.as_mut() {
+			identities.seats.push(Array2::from_elem(building.people().dim(), None));
+		}
+		let day = *self.recording.timeline();
+		self.recording.push_event(Event::BuildingAdded { day, building_name: building.name().to_string() });
+		self.recording.add_building(building.clone());
+		self.visiting.building_added(self.buildings.len());
+		self.buildings.push(building);
+		self
+	}
+
+	/// Removes the first building named `name` from the board and returns it.
+	///
+	/// The recording stops tracking the building, see `Recording::penalty`, 
+	/// but the score of past days is kept, see `Recording::daily_score`.
+	///
+	/// # Errors
+	///
+	/// If there is no building named `name`.
+	///
+	/// # Panics
+	///
+	/// If individuals are not at home, ie after `visit` and before `go_home`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// let school = board.remove_building("School").unwrap();
+	/// assert_eq!(school.capacity(), 16);
+	/// assert!(board.building("School").is_none());
+	/// assert!(board.remove_building("School").is_err());
+	/// ```
+	pub fn remove_building<S: Display>(&mut self, name: S) -> Result<Building, crate::errors::ActionError> {
+		assert_eq!(self.population.remaining(), self.population.len(), "individuals must be at home");
+		let name = name.to_string();
+		let index = self.buildings.iter().position(|building| building.name() == name)
+			.ok_or_else(|| crate::errors::ActionError::UnknownBuilding(name.clone()))?;
+		if let Some(identities) = self.identities.as_mut() {
+			identities.seats.remove(index);
+		}
+		self.recording.remove_building(index);
+		self.visiting.building_removed(index);
+		let day = *self.recording.timeline();
+		self.recording.push_event(Event::BuildingRemoved { day, building_name: name });
+		Ok(self.buildings.remove(index))
+	}
+
+	/// Returns the first building named `name`, if any.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let board = Board::default();
+	/// assert_eq!(board.building("School").unwrap().capacity(), 16);
+	/// assert!(board.building("Castle").is_none());
+	/// ```
+	pub fn building<S: Display>(&self, name: S) -> Option<&Building> {
+		let name = name.to_string();
+		self.buildings.iter().find(|building| building.name() == name)
+	}
+
+	/// Returns the first building named `name`, if any, to change it.
+	///
+	/// # Remarks
+	///
+	/// Changes made through this reference are not recorded as events, 
+	/// prefer `open`, `close` and `toggle` to change whether the building is open.
+	pub fn building_mut<S: Display>(&mut self, name: S) -> Option<&mut Building> {
+		let name = name.to_string();
+		self.buildings.iter_mut().find(|building| building.name() == name)
+	}
+
+	/// Returns a summary of each building, in order, for example to list them in a user interface.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.close("Bakery").unwrap();
+	/// let summaries = board.building_summaries();
+	/// assert_eq!(summaries[1].name, "Bakery");
+	/// assert_eq!((summaries[1].capacity, summaries[1].open), (4, false));
+	/// ```
+	pub fn building_summaries(&self) -> Vec<BuildingSummary> {
+		self.buildings.iter()
+			.map(|building| BuildingSummary { 
+				name: building.name().to_string(), 
+				capacity: building.capacity(), 
+				open: building.is_open(),
+			})
+			.collect()
+	}
+
+	/// Returns an error if there is no building named `name`.
+	fn check_building(&self, name: &str) -> Result<(), crate::errors::ActionError> {
+		match self.building(name) {
+			Some(_) => Ok(()),
+			None => Err(crate::errors::ActionError::UnknownBuilding(name.to_string())),
+		}
+	}
+
+	/// Toggles the buildings named `name`.
+	///
+	/// # Errors
+	///
+	/// If there is no building named `name`.
+	pub fn toggle<S: Display>(&mut self, name: S) -> Result<&mut Self, crate::errors::ActionError> {
+		let name = name.to_string();
+		self.check_building(&name)?;
+		for building in self.buildings.iter_mut() {
+			if building.name() == name {
+				building.toggle();
+				let (day, building_name) = (*self.recording.timeline(), building.name().to_string());
+				self.recording.push_event(if building.is_open() {
+					Event::BuildingOpened { day, building_name }
+				} else {
+					Event::BuildingClosed { day, building_name }
+				});
 			}
 		}
+		Ok(self)
 	}
 
-	/// Third step of any stage
+	/// Closes the buildings named `name`.
 	///
-	/// In this step, the population returns home. 
-	/// Outputs the number of newly infected.
-	pub fn go_home(&mut self) -> usize {
-		let mut new_vec = Vec::new();
-		// Collect 
-		// From buildings
+	/// # Errors
+	///
+	/// If there is no building named `name`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// # use virus_alarm::errors::ActionError;
+	/// let mut board = Board::default();
+	/// assert!(board.close("School").is_ok());
+	/// assert_eq!(board.close("Shcool").unwrap_err(), ActionError::UnknownBuilding("Shcool".to_string()));
+	/// ```
+	pub fn close<S: Display>(&mut self, name: S) -> Result<&mut Self, crate::errors::ActionError> {
+		let name = name.to_string();
+		self.check_building(&name)?;
 		for building in self.buildings.iter_mut() {
-			new_vec.append(&mut building.empty())
+			if building.name() == name {
+				if building.is_open() {
+					let day = *self.recording.timeline();
+					self.recording.push_event(Event::BuildingClosed { day, building_name: building.name().to_string() });
+				}
+				building.close();
+			}
 		}
-		let newly_infected: usize = new_vec.iter().filter(|&&i| i == Individual::Infected1).count();
-		// From inactive
-		new_vec.append(&mut self.inactive);
-		let new_population = Population::from(new_vec);
-
-		// Update
-		self.population = new_population;
-
-		newly_infected
+		Ok(self)
 	}
 
-
-	/// Closes a building
-	pub fn toggle<S: Display>(&mut self, name: S) -> &mut Self {
+	/// Opens the buildings named `name`.
+	///
+	/// # Errors
+	///
+	/// If there is no building named `name`.
+	pub fn open<S: Display>(&mut self, name: S) -> Result<&mut Self, crate::errors::ActionError> {
+		let name = name.to_string();
+		self.check_building(&name)?;
 		for building in self.buildings.iter_mut() {
-			if building.name() == name.to_string() {
-				building.toggle();
+			if building.name() == name {
+				if !building.is_open() {
+					let day = *self.recording.timeline();
+					self.recording.push_event(Event::BuildingOpened { day, building_name: building.name().to_string() });
+				}
+				building.open();
 			}
 		}
-		self
+		Ok(self)
 	}
 
-	/// Closes a building
-	pub fn close<S: Display>(&mut self, name: S) -> &mut Self {
+	/// Closes the open buildings for which `predicate` holds, recording each closure.
+	fn close_where<P: Fn(&Building) -> bool>(&mut self, predicate: P) -> &mut Self {
 		for building in self.buildings.iter_mut() {
-			if building.name() == name.to_string() {
+			if building.is_open() && predicate(building) {
 				building.close();
+				let day = *self.recording.timeline();
+				self.recording.push_event(Event::BuildingClosed { day, building_name: building.name().to_string() });
 			}
 		}
 		self
 	}
 
-	/// Opens a building
-	pub fn open<S: Display>(&mut self, name: S) -> &mut Self {
+	/// Closes every building in the group `tag`, see `BuildingBuilder::with_tag`.
+	///
+	/// # Examples
+	///
+	/// In the default board, schools are the only buildings for education.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// use virus_alarm::building::Tag;
+	/// let mut board = Board::default();
+	/// board.close_group(Tag::Education);
+	/// assert!(board.building("School").unwrap().is_close());
+	/// assert_eq!(board.buildings().iter().filter(|b| b.is_close()).count(), 1);
+	/// ```
+	pub fn close_group(&mut self, tag: Tag) -> &mut Self {
+		self.close_where(|building| building.has_tag(tag))
+	}
+
+	/// Closes every building except the ones in some group of `except`.
+	///
+	/// # Examples
+	///
+	/// Only essential buildings stay open.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// use virus_alarm::building::Tag;
+	/// let mut board = Board::default();
+	/// board.lockdown(&[Tag::Essential]);
+	/// let open: Vec<&str> = board.buildings().iter().filter(|b| b.is_open()).map(|b| b.name()).collect();
+	/// assert_eq!(open, vec!["Bakery", "Pharmacy", "Supermarket"]);
+	/// ```
+	pub fn lockdown(&mut self, except: &[Tag]) -> &mut Self {
+		self.close_where(|building| !except.iter().any(|&tag| building.has_tag(tag)))
+	}
+
+	/// Restricts the number of individuals allowed in a building to a percentage of its capacity.
+	///
+	/// Percentages of 100 or more remove the restriction.
+	///
+	/// # Errors
+	///
+	/// If there is no building named `name`.
+	///
+	/// # Examples
+	///
+	/// Allow only half of the capacity in the "School".
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = Board::default();
+	/// board.reduce_capacity("School", 50).unwrap();
+	/// let school = board.buildings().iter().find(|b| b.name() == "School").unwrap();
+	/// assert_eq!(school.limit(), Some(8));
+	/// assert!(board.reduce_capacity("Shcool", 50).is_err());
+	/// ```
+	pub fn reduce_capacity<S: Display>(&mut self, name: S, percent: u8) -> Result<&mut Self, crate::errors::ActionError> {
+		let name = name.to_string();
+		self.check_building(&name)?;
 		for building in self.buildings.iter_mut() {
-			if building.name() == name.to_string() {
-				building.open();
+			if building.name() == name {
+				let limit = if percent >= 100 {
+					None
+				} else {
+					Some(building.capacity() * percent as usize / 100)
+				};
+				building.set_limit(limit);
 			}
 		}
-		self
+		Ok(self)
 	}
 
-	/// Returns the spreading mode. 
+	/// Returns the spreading mode of the first building,
+	/// or the default spreading mode if there are no buildings.
 	///
 	/// See `Spreading` for more. 
-	///
-	/// # Panics
-	///
-	/// If there are no buildings.
+	/// If buildings have different spreading modes, see `uniform_spreading`.
 	///
 	/// # Examples
 	///
@@ -303,7 +1734,41 @@ impl Board {
 	/// assert_eq!(&Spreading::OneNear, board.spreading());
 	/// ```
 	pub fn spreading(&self) -> &Spreading {
-		self.buildings()[0].spreading()
+		static DEFAULT_SPREADING: Spreading = Spreading::OneVeryNear;
+		self.buildings().first()
+			.map(|building| building.spreading())
+			.unwrap_or(&DEFAULT_SPREADING)
+	}
+
+	/// Returns the spreading mode of all buildings, if they share the same one.
+	///
+	/// If there are no buildings, it returns `spreading`.
+	pub fn uniform_spreading(&self) -> Option<&Spreading> {
+		let spreading = self.spreading();
+		if self.buildings().iter().all(|building| building.spreading() == spreading) {
+			Some(spreading)
+		} else {
+			None
+		}
+	}
+
+	/// Changes the spreading mode of the buildings named `name`.
+	///
+	/// See `Spreading` for more. 
+	///
+	/// # Errors
+	///
+	/// If there is no building named `name`.
+	pub fn set_building_spreading<S: Display>(&mut self, name: S, new_spreading: Spreading) -> Result<&mut Self, crate::errors::ActionError> {
+		let name = name.to_string();
+		self.check_building(&name)?;
+		for building in self.buildings.iter_mut() {
+			if building.name() == name {
+				building.set_spreading(new_spreading);
+			}
+		}
+		self.recording.set_building_spreading(name, new_spreading);
+		Ok(self)
 	}
 
 	/// Changes the spreading mode. 
@@ -323,6 +1788,67 @@ impl Board {
 	}
 }
 
+impl Clone for Board {
+	fn clone(&self) -> Self {
+		Board {
+			population: self.population.clone(),
+			buildings: self.buildings.clone(),
+			inactive: self.inactive.clone(),
+			recording: self.recording.clone(),
+			visiting: self.visiting.clone(),
+			vaccine: self.vaccine,
+			vaccination_strategy: self.vaccination_strategy.clone(),
+			immunity_duration: self.immunity_duration,
+			immune_since: self.immune_since.clone(),
+			first_dose_since: self.first_dose_since.clone(),
+			latency: self.latency,
+			exposed_since: self.exposed_since.clone(),
+			asymptomatic: self.asymptomatic,
+			vaccination_rate: self.vaccination_rate,
+			vaccines: self.vaccines,
+			convalescence: self.convalescence,
+			sick_since: self.sick_since.clone(),
+			identities: self.identities.clone(),
+		}
+	}
+
+	/// Reuses the population, buildings and recording of `self`,
+	/// so that realizations of the same board need not allocate them again.
+	fn clone_from(&mut self, source: &Self) {
+		let Board { population, buildings, inactive, recording, visiting, vaccine, vaccination_strategy, immunity_duration, immune_since, first_dose_since, latency, exposed_since, asymptomatic, vaccines, vaccination_rate, convalescence, sick_since, identities } = self;
+		population.clone_from(&source.population);
+		buildings.clone_from(&source.buildings);
+		inactive.clone_from(&source.inactive);
+		recording.clone_from(&source.recording);
+		visiting.clone_from(&source.visiting);
+		*vaccine = source.vaccine;
+		vaccination_strategy.clone_from(&source.vaccination_strategy);
+		*immunity_duration = source.immunity_duration;
+		immune_since.clone_from(&source.immune_since);
+		first_dose_since.clone_from(&source.first_dose_since);
+		*latency = source.latency;
+		exposed_since.clone_from(&source.exposed_since);
+		*asymptomatic = source.asymptomatic;
+		*vaccination_rate = source.vaccination_rate;
+		*vaccines = source.vaccines;
+		*convalescence = source.convalescence;
+		sick_since.clone_from(&source.sick_since);
+		identities.clone_from(&source.identities);
+	}
+}
+
+impl Clone for Identities {
+	fn clone(&self) -> Self {
+		Identities { home: self.home.clone(), inactive: self.inactive.clone(), seats: self.seats.clone() }
+	}
+
+	fn clone_from(&mut self, source: &Self) {
+		self.home.clone_from(&source.home);
+		self.inactive.clone_from(&source.inactive);
+		self.seats.clone_from(&source.seats);
+	}
+}
+
 impl Default for Board {
 	/// Returns an instance of `Board` with default configuration
 	///
@@ -337,14 +1863,14 @@ impl Default for Board {
 	/// ```
 	fn default() -> Self { 
 		let population = Population::default();
-		let concert_hall = BuildingBuilder::new("Concert Hall").with_size(5, 4).build();
-		let bakery = BuildingBuilder::new("Bakery").with_size(2, 2).build();
-		let school = BuildingBuilder::new("School").with_size(4, 4).build();
-		let pharmacy = BuildingBuilder::new("Pharmacy").with_size(2, 2).build();
-		let restaurant = BuildingBuilder::new("Restaurant").with_size(4, 3).build();
-		let gym = BuildingBuilder::new("Gym").with_size(4, 2).build();
-		let supermarket = BuildingBuilder::new("Supermarket").with_size(2, 2).build();
-		let shopping_center = BuildingBuilder::new("Shopping Center").with_size(4, 2).build();
+		let concert_hall = BuildingBuilder::new("Concert Hall").with_size(5, 4).with_tag(Tag::Leisure).build();
+		let bakery = BuildingBuilder::new("Bakery").with_size(2, 2).with_tag(Tag::Essential).build();
+		let school = BuildingBuilder::new("School").with_size(4, 4).with_tag(Tag::Education).build();
+		let pharmacy = BuildingBuilder::new("Pharmacy").with_size(2, 2).with_tag(Tag::Essential).build();
+		let restaurant = BuildingBuilder::new("Restaurant").with_size(4, 3).with_tag(Tag::Leisure).build();
+		let gym = BuildingBuilder::new("Gym").with_size(4, 2).with_tag(Tag::Leisure).build();
+		let supermarket = BuildingBuilder::new("Supermarket").with_size(2, 2).with_tag(Tag::Essential).build();
+		let shopping_center = BuildingBuilder::new("Shopping Center").with_size(4, 2).with_tag(Tag::Leisure).build();
 		let buildings = vec![
 			concert_hall,
 			bakery,
@@ -357,13 +1883,14 @@ impl Default for Board {
 		];
 		let recording = Recording::new(population.clone(), buildings.clone());
 
-		Board{ population, buildings, inactive: Vec::new(), recording }
+		Board{ population, buildings, inactive: Vec::new(), recording, visiting: Visiting::default(), vaccine: Vaccine::default(), vaccination_strategy: VaccinationStrategy::default(), immunity_duration: None, immune_since: Vec::new(), first_dose_since: Vec::new(), latency: None, exposed_since: Vec::new(), asymptomatic: 0, vaccines: None, vaccination_rate: 0, convalescence: Convalescence::default(), sick_since: Vec::new(), identities: None }
 	}
 }
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use ndarray::array;
+	use test_case::test_case;
 
 
 	#[test]
@@ -428,7 +1955,7 @@ mod tests {
 		board.visit(); // Fills the inactive vector
 		board.propagate();
 		assert_eq!(board.buildings()[0], Building::unchecked_from(array![[Individual::Infected1, Individual::Infected2]]));
-		assert_eq!(board.population(), &population); // All buildings were full so the population was only shuffled!
+		assert!(board.population().equivalent(&population)); // All buildings were full so the population was only shuffled!
 		assert_eq!(board.inactive, vec![Individual::Infected2, Individual::Infected2]); // Propagation at home!
 	}
 
@@ -456,6 +1983,433 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn imperfect_vaccine() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Infected3]);
+		let buildings = vec![Building::new(2, 1, "My bulding")];
+		let mut board = Board::new(population, buildings);
+		board.set_vaccine(Vaccine::Imperfect { efficacy: 0 });
+		board.immunize().unwrap();
+		board.advance();
+		assert_eq!(board.population().counting(Individual::Infected1), 1);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Vaccinated], vec![1, 0]);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Infected1], vec![0, 1]);
+		assert_eq!(board.recording().vaccinated_infected(), &vec![0, 1]);
+	}
+
+	#[test]
+	fn imperfect_vaccine_seeded() {
+		let immunized = |seed| {
+			let mut board = Board::default();
+			board.set_vaccine(Vaccine::Imperfect { efficacy: 50 });
+			let mut rng = crate::tests::rng(seed);
+			for _ in 0..20 {
+				board.immunize_with(&mut rng).unwrap();
+			}
+			board.population().clone()
+		};
+		assert_eq!(immunized(1), immunized(1));
+	}
+
+	#[test_case(0, vec![1, 0], 1)]
+	#[test_case(100, vec![1, 1], 0)]
+	fn first_dose(protection: u8, partially_vaccinated: Vec<usize>, infected: usize) {
+		let population = Population::from(vec![Individual::Healthy, Individual::Infected3]);
+		let buildings = vec![Building::new(2, 1, "My bulding")];
+		let mut board = Board::new(population, buildings);
+		board.set_vaccine(Vaccine::TwoDoses { protection, delay: 5 });
+		board.immunize().unwrap();
+		board.advance();
+		assert_eq!(board.recording().counting_table().inner()[&Individual::PartiallyVaccinated], partially_vaccinated);
+		assert_eq!(board.population().counting(Individual::Infected1), infected);
+		assert_eq!(board.recording().vaccinated_infected(), &vec![0, infected]);
+	}
+
+	#[test]
+	fn second_dose() {
+		let board_builder = BoardBuilder {
+			healthy: 3,
+			buildings: vec![(1, 1)],
+			vaccine: Vaccine::TwoDoses { protection: 50, delay: 2 },
+			vaccines: Some(3),
+			..Default::default()
+		};
+		let mut board = board_builder.build();
+		board.immunize_many(2);
+		board.advance();
+		assert_eq!(board.population().counting(Individual::PartiallyVaccinated), 2);
+		board.advance();
+		assert_eq!(board.recording().counting_table().inner()[&Individual::PartiallyVaccinated], vec![2, 2, 1]);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Immune], vec![0, 0, 1]);
+		assert_eq!(board.vaccines(), &Some(0));
+		board.advance();
+		assert_eq!(board.population().counting(Individual::PartiallyVaccinated), 1);
+		assert_eq!(board.recording().vaccines_used(), &3);
+	}
+
+	#[test]
+	fn vaccination_campaign() {
+		let board_builder = BoardBuilder {
+			healthy: 5,
+			buildings: vec![(1, 1)],
+			vaccines: Some(4),
+			vaccination_rate: 3,
+			..Default::default()
+		};
+		let mut board = board_builder.build();
+		board.advance();
+		assert_eq!(board.population().counting(Individual::Immune), 3);
+		board.advance();
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Immune], vec![3, 4, 4]);
+		assert_eq!(board.vaccines(), &Some(0));
+		board.advance();
+		assert_eq!(board.population().counting(Individual::Healthy), 1);
+	}
+
+	#[test]
+	fn reverse_immunize_initially_immune() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Immune]);
+		let mut board = Board::new(population, Vec::new());
+		board.set_vaccines(Some(0));
+		board.reverse_immunize().unwrap();
+		assert_eq!(board.vaccines(), &Some(0));
+		assert_eq!(board.recording().vaccines_used(), &0);
+		board.set_vaccines(Some(1));
+		board.immunize().unwrap();
+		assert_eq!(board.vaccines(), &Some(0));
+		board.reverse_immunize().unwrap();
+		assert_eq!(board.vaccines(), &Some(1));
+		assert_eq!(board.recording().vaccines_used(), &0);
+		assert_eq!(board.reverse_immunize().err(), Some(crate::errors::ActionError::NoImmuneLeft));
+		assert_eq!(board.population().counting(Individual::Healthy), 2);
+	}
+
+	#[test]
+	fn waning_immunity() {
+		let board_builder = BoardBuilder {
+			healthy: 2,
+			immune: 3,
+			buildings: vec![(1, 1)],
+			immunity_duration: Some(1),
+			..Default::default()
+		};
+		let mut board = board_builder.build();
+		board.immunize().unwrap();
+		board.advance();
+		assert_eq!(board.population().counting(Individual::Immune), 0);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Immune], vec![4, 0]);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Healthy], vec![1, 5]);
+	}
+
+	#[test]
+	fn latency() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Infected3]);
+		let buildings = vec![Building::new(2, 1, "My bulding")];
+		let mut board = Board::new(population, buildings);
+		board.set_latency(Some(2));
+		board.advance_many(3);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Exposed], vec![0, 1, 1, 0]);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Infected1], vec![0, 0, 0, 1]);
+		assert_eq!(board.population().counting(Individual::Infected1), 1);
+	}
+
+	#[test]
+	fn asymptomatic() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Infected3, Individual::Sick]);
+		let buildings = vec![Building::new(2, 1, "My bulding")];
+		let mut board = Board::new(population, buildings);
+		board.set_asymptomatic(100);
+		board.advance();
+		assert_eq!(board.population().counting(Individual::Recovered), 1);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Sick], vec![1, 1]);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Recovered], vec![0, 1]);
+	}
+
+	#[test_case(Convalescence::Immune { days: 2 }, Individual::Immune)]
+	#[test_case(Convalescence::Healthy { days: 2 }, Individual::Healthy)]
+	fn convalescence(convalescence: Convalescence, outcome: Individual) {
+		let population = Population::from(vec![Individual::Infected3, Individual::Sick]);
+		let buildings = vec![Building::new(2, 1, "My bulding")];
+		let mut board = Board::new(population, buildings);
+		board.set_convalescence(convalescence);
+		board.advance_many(2);
+		assert_eq!(board.population().counting(outcome), 1);
+		assert_eq!(board.recording().counting_table().inner()[&Individual::Sick], vec![1, 2, 1]);
+		board.advance();
+		assert_eq!(board.population().counting(outcome), 2);
+	}
+
+	#[test]
+	fn sick_forever() {
+		let population = Population::from(vec![Individual::Sick]);
+		let mut board = Board::new(population, Vec::new());
+		board.advance_many(10);
+		assert_eq!(board.population().counting(Individual::Sick), 1);
+	}
+
+	#[test]
+	fn events() {
+		let mut board = BoardBuilder {
+			healthy: 3,
+			infected1: 1,
+			buildings: vec![(4, 1)],
+			spreading: Spreading::Everyone,
+			collect_events: true,
+			..Default::default()
+		}.build();
+		let building_name = board.buildings()[0].name().to_string();
+		board.close(&building_name).unwrap().close(&building_name).unwrap().open(&building_name).unwrap();
+		board.try_advance_with(&mut crate::tests::rng(1)).unwrap();
+		let infection = Event::Infection { day: 1, building_name: building_name.clone(), infector_stage: Some(Individual::Infected1) };
+		assert_eq!(board.recording().events(), &[
+			Event::BuildingClosed { day: 0, building_name: building_name.clone() },
+			Event::BuildingOpened { day: 0, building_name },
+			infection.clone(),
+			infection.clone(),
+			infection,
+		]);
+		board.set_collect_events(false);
+		assert!(board.recording().events().is_empty());
+	}
+
+	#[test]
+	fn mixed_spreading() {
+		let buildings = vec![
+			BuildingBuilder::new("A").with_size(2, 1).with_spreading(Spreading::Everyone).build(),
+			BuildingBuilder::new("B").with_size(2, 1).with_spreading(Spreading::Airborne(0)).build(),
+		];
+		let population = Population::from(vec![Individual::Infected1, Individual::Healthy, Individual::Infected1, Individual::Healthy]);
+		let mut board = Board::new(population, buildings);
+		board.visit_building(0);
+		board.visit_building(1);
+		board.propagate();
+		let (_, a) = &board.recording().infections_by_building()[0];
+		assert_eq!(board.buildings()[0].people().iter().flatten().filter(|i| i.is_healthy()).count(), 0);
+		assert_eq!(board.buildings()[1].people().iter().flatten().filter(|i| i.is_healthy()).count(), 1);
+		assert_eq!(a, &vec![0, 1]);
+		assert_eq!(board.uniform_spreading(), None);
+		board.set_building_spreading("B", Spreading::Everyone).unwrap();
+		assert_eq!(board.uniform_spreading(), Some(&Spreading::Everyone));
+		assert_eq!(board.set_building_spreading("C", Spreading::One).err(), Some(crate::errors::ActionError::UnknownBuilding("C".to_string())));
+		assert_eq!(board.uniform_spreading(), Some(&Spreading::Everyone));
+	}
+
+	#[test]
+	fn spreading_without_buildings() {
+		let board = Board::new(Population::default(), Vec::new());
+		assert_eq!(board.spreading(), &Spreading::default());
+		assert_eq!(board.uniform_spreading(), Some(&Spreading::default()));
+	}
+
+	#[test_case(Visiting::InOrder)]
+	#[test_case(Visiting::Capacity { stay_home: 10 })]
+	fn track_identities(visiting: Visiting) {
+		let mut board = Board::default();
+		board.set_visiting(visiting);
+		let mut tracked = board.clone();
+		tracked.set_track_identities(true);
+		for _ in 0..5 {
+			board.try_advance_with(&mut crate::tests::rng(3)).unwrap();
+			tracked.try_advance_with(&mut crate::tests::rng(3)).unwrap();
+		}
+		assert_eq!(board.counting_table(), tracked.counting_table());
+		let histories = tracked.recording().histories();
+		let healthy = &tracked.counting_table().diagram()[0];
+		let infected = histories.iter().filter(|h| h.infection_stage().is_some()).count();
+		assert!(infected > 0);
+		assert_eq!(infected, healthy[0] - healthy[5]);
+		let infected_in_buildings: usize = tracked.buildings().iter().map(|b| tracked.recording().people_infected_at(b.name())).sum();
+		assert_eq!(infected_in_buildings, infected);
+		for (id, individual) in tracked.identities.as_ref().unwrap().home.iter().zip(tracked.population.as_slice()) {
+			assert_eq!(histories[id.0].states().last(), Some(individual));
+		}
+	}
+
+	#[test_case(VaccinationStrategy::FirstHealthy, vec![Individual::Immune, Individual::Immune, Individual::Infected1, Individual::Healthy])]
+	#[test_case(VaccinationStrategy::ByIdentity(vec![Id(2), Id(3), Id(1)]), vec![Individual::Healthy, Individual::Immune, Individual::Infected1, Individual::Immune])]
+	fn vaccination_strategy(strategy: VaccinationStrategy, expected: Vec<Individual>) {
+		let population = Population::from(vec![Individual::Healthy, Individual::Healthy, Individual::Infected1, Individual::Healthy]);
+		let mut board = Board::new(population, Vec::new());
+		board.set_track_identities(true);
+		board.set_vaccination_strategy(strategy);
+		assert_eq!(board.immunize_many(2), 2);
+		assert_eq!(board.population.as_slice(), &expected[..]);
+	}
+
+	#[test]
+	fn random_vaccination() {
+		let mut board = Board::default();
+		board.set_vaccination_strategy(VaccinationStrategy::Random);
+		assert_eq!(board.immunize_many(100), 98);
+		assert_eq!(board.population().counting(Individual::Immune), 98);
+		assert_eq!(board.counting_table().last_day()[&Individual::Immune], 98);
+	}
+
+	#[test]
+	fn vaccination_rate_seeded() {
+		let advanced = || {
+			let mut board = Board::default();
+			board.set_vaccine(Vaccine::Imperfect { efficacy: 50 });
+			board.set_vaccination_rate(3);
+			let mut rng = crate::random::Pcg32::new(7);
+			for _ in 0..5 {
+				board.try_advance_with(&mut rng).unwrap();
+			}
+			board
+		};
+		assert_eq!(advanced(), advanced());
+	}
+
+	#[test]
+	fn random_vaccination_seeded() {
+		let immunized = |seed| {
+			let mut board = Board::default();
+			board.set_vaccination_strategy(VaccinationStrategy::Random);
+			let mut rng = crate::tests::rng(seed);
+			for _ in 0..10 {
+				board.immunize_with(&mut rng).unwrap();
+			}
+			board.population().clone()
+		};
+		assert_eq!(immunized(1), immunized(1));
+		assert_ne!(immunized(1), immunized(2));
+	}
+
+	#[test]
+	fn untracked_identities_vaccination() {
+		let mut board = Board::default();
+		board.set_vaccination_strategy(VaccinationStrategy::ByIdentity(vec![Id(0)]));
+		assert_eq!(board.immunize().err(), Some(crate::errors::ActionError::NoHealthyLeft));
+	}
+
+	#[test]
+	fn infect() {
+		let population = Population::from(vec![Individual::Healthy; 4]);
+		let mut board = Board::new(population, vec![Building::new(4, 1, "A")]);
+		board.set_track_identities(true);
+		board.set_collect_events(true);
+		board.advance();
+		assert_eq!(board.infect(1), 1);
+		assert_eq!(board.counting_table().last_day()[&Individual::Infected1], 1);
+		board.try_advance_with(&mut crate::tests::rng(1)).unwrap();
+		assert_eq!(board.infect(5), 2);
+		board.try_advance_with(&mut crate::tests::rng(2)).unwrap();
+		assert_eq!(board.recording().imported_infections(), &vec![0, 1, 2, 0]);
+		let histories = board.recording().histories();
+		assert_eq!(histories.iter().filter(|h| *h.imported()).count(), 3);
+		assert_eq!(board.recording().people_infected_at("A"), 1);
+		assert_eq!(board.recording().infections_by_building()[0].1.iter().sum::<usize>(), 1);
+		let imported = board.recording().events().iter().filter(|e| matches!(e, Event::ImportedInfection { .. })).count();
+		assert_eq!(imported, 3);
+	}
+
+	#[test]
+	fn arrive_and_depart() {
+		let mut board = Board::default();
+		board.set_track_identities(true);
+		board.advance_many(2);
+		board.arrive(Individual::Infected1).arrive(Individual::Sick);
+		board.depart(Individual::Healthy).unwrap();
+		assert_eq!(board.depart(Individual::Exposed).err(), Some(crate::errors::ActionError::NotPresent(Individual::Exposed)));
+		for _ in 0..3 {
+			board.try_advance_with(&mut crate::tests::rng(5)).unwrap();
+		}
+		assert_eq!(board.population().len(), 101);
+		let histories = board.recording().histories();
+		assert_eq!(histories.len(), 102);
+		for (id, individual) in board.identities.as_ref().unwrap().home.iter().zip(board.population.as_slice()) {
+			assert_eq!(histories[id.0].states().last(), Some(individual));
+		}
+	}
+
+	#[test]
+	fn depart_sick() {
+		let population = Population::from(vec![Individual::Sick, Individual::Sick]);
+		let mut board = Board::new(population, Vec::new());
+		board.set_convalescence(Convalescence::Healthy { days: 2 });
+		board.advance();
+		board.depart(Individual::Sick).unwrap();
+		board.advance();
+		assert_eq!(board.population().counting(Individual::Healthy), 1);
+		assert!(board.check_census().is_ok());
+	}
+
+	#[test]
+	fn visit_weighted() {
+		let mut board = Board::default();
+		let mut weights = vec![0; 8];
+		weights[1] = 1;
+		board.set_visiting(Visiting::Weighted { weights, stay_home: 0 });
+		board.visit();
+		assert_eq!(board.buildings()[1].occupancy(), 4);
+		assert_eq!(board.buildings().iter().map(|b| b.occupancy()).sum::<usize>(), 4);
+		assert_eq!(board.inactive.len(), 96);
+	}
+
+	#[test]
+	fn visit_weighted_after_changes() {
+		let mut board = Board::default();
+		let mut weights = vec![0; 8];
+		weights[1] = 1;
+		board.set_visiting(Visiting::Weighted { weights, stay_home: 0 });
+		board.remove_building(board.buildings()[0].name().to_string()).unwrap();
+		board.add_building(crate::BuildingBuilder::new("Vaccination Center").with_size(3, 2).build());
+		let mut expected = vec![0; 8];
+		expected[0] = 1;
+		expected[7] = 1;
+		assert_eq!(board.visiting(), &Visiting::Weighted { weights: expected, stay_home: 0 });
+		board.visit();
+		assert_eq!(board.buildings()[0].occupancy() + board.buildings()[7].occupancy(), 10);
+		assert_eq!(board.inactive.len(), 90);
+	}
+
+	#[test]
+	fn visit_stay_home() {
+		let mut board = Board::default();
+		board.set_visiting(Visiting::Capacity { stay_home: 100 });
+		board.visit();
+		assert_eq!(board.inactive.len(), 100);
+		board.set_visiting(Visiting::Capacity { stay_home: 0 });
+		board.propagate();
+		board.go_home();
+		board.visit();
+		assert_eq!(board.inactive.len(), 24);
+		assert!(board.buildings().iter().all(|b| b.is_full()));
+	}
+
+	#[test]
+	fn builder_superspreaders() {
+		let board = BoardBuilder {
+			healthy: 3,
+			superspreaders: 2,
+			buildings: vec![(1, 1)],
+			..Default::default()
+		}.build();
+		assert_eq!(board.population().counting(Individual::HealthySuperspreader), 2);
+		assert_eq!(board.population().counting(Individual::Healthy), 3);
+	}
+
+	#[test]
+	fn census() {
+		let mut board = Board::default();
+		board.visit();
+		let census = board.census();
+		assert_eq!(census.population, 0);
+		assert_eq!(census.buildings, 76);
+		assert_eq!(census.inactive, 24);
+		assert!(census.is_conserved());
+		board.propagate();
+		board.go_home();
+		assert_eq!(board.census().population, 100);
+	}
+
+	#[test]
+	fn try_advance() {
+		let mut board = Board::default();
+		board.inactive.push(Individual::Healthy);
+		assert_eq!(board.try_advance().err(), Some(crate::errors::BoardError::NotConserved { expected: 100, found: 101 }));
+	}
+
 	#[test]
 	#[should_panic]
 	fn close() {
@@ -463,6 +2417,150 @@ mod tests {
 		let buildings = vec![Building::new(2, 1, "My bulding")];
 		let mut board = Board::new(population, buildings);
 		board.visit();
-		board.close("My bulding");
+		board.close("My bulding").unwrap();
+	}
+
+	#[test]
+	fn unknown_building() {
+		let mut board = Board::default();
+		board.set_collect_events(true);
+		for result in [board.clone().open("Castle").map(|_| ()), board.clone().toggle("Castle").map(|_| ()), board.close("Castle").map(|_| ())].iter() {
+			assert_eq!(result, &Err(crate::errors::ActionError::UnknownBuilding("Castle".to_string())));
+		}
+		assert_eq!(board.recording().events(), &[]);
+		assert!(crate::Action::Close("Castle".to_string()).apply(&mut board).is_err());
+		board.building_mut("School").unwrap().set_penalty(3);
+		assert_eq!(board.building("School").unwrap().penalty(), &3);
+	}
+
+	#[test]
+	fn capacity_fraction() {
+		let mut board = BoardBuilder { healthy: 6, infected1: 2, buildings: vec![(3, 3)], ..Default::default() }.build();
+		board.set_track_identities(true);
+		board.building_mut("Building 1").unwrap().set_capacity_fraction(0.5).unwrap();
+		board.visit_with(&mut crate::tests::rng(5));
+		assert_eq!(board.buildings()[0].occupancy(), 5);
+		assert_eq!(board.census().inactive, 3);
+		board.propagate_with(&mut crate::tests::rng(5));
+		board.go_home();
+		assert!(board.check_census().is_ok());
+	}
+
+	#[test]
+	fn lockdown() {
+		let mut board = Board::default();
+		board.set_collect_events(true);
+		board.close("Gym").unwrap();
+		crate::Action::CloseGroup(Tag::Leisure).apply(&mut board).unwrap();
+		crate::Action::Lockdown(vec![Tag::Essential, Tag::Leisure]).apply(&mut board).unwrap();
+		let closed: Vec<String> = board.recording().events().iter()
+			.filter_map(|event| match event {
+				Event::BuildingClosed { building_name, .. } => Some(building_name.clone()),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(closed, vec!["Gym", "Concert Hall", "Restaurant", "Shopping Center", "School"]);
+		board.lockdown(&[]);
+		assert!(board.buildings().iter().all(|building| building.is_close()));
+	}
+
+	#[test]
+	fn add_and_remove_buildings() {
+		let mut board = Board::default();
+		board.set_collect_events(true).set_track_identities(true);
+		board.building_mut("School").unwrap().set_penalty(2);
+		board.close("School").unwrap();
+		let mut rng = crate::tests::rng(4);
+		board.try_advance_with(&mut rng).unwrap();
+		board.remove_building("School").unwrap();
+		board.add_building(BuildingBuilder::new("Vaccination Center").with_size(4, 4).build());
+		for _ in 0..3 {
+			board.try_advance_with(&mut rng).unwrap();
+		}
+		assert!(board.check_census().is_ok());
+		let recording = board.recording();
+		assert_eq!(recording.penalty().len(), 8);
+		assert_eq!(recording.infections_by_building().len(), 8);
+		assert_eq!(recording.occupancy().len(), 8);
+		let (name, infections) = &recording.infections_by_building()[7];
+		assert_eq!((name.as_str(), infections.len()), ("Vaccination Center", 5));
+		assert_eq!(recording.occupancy()[7].1.days(), 4);
+		assert_eq!(recording.daily_score(), &vec![0, -2, 0, 0, 0]);
+		assert_eq!(board.score().closure_penalty, 2);
+		assert!(recording.events().contains(&Event::BuildingRemoved { day: 1, building_name: "School".to_string() }));
+		assert!(recording.events().contains(&Event::BuildingAdded { day: 1, building_name: "Vaccination Center".to_string() }));
+	}
+
+	#[test]
+	fn clone_from() {
+		let mut initial = Board::default();
+		initial.set_track_identities(true);
+		initial.set_collect_events(true);
+		let mut board = initial.clone();
+		let mut rng = crate::tests::rng(9);
+		for _ in 0..4 {
+			board.try_advance_with(&mut rng).unwrap();
+		}
+		board.remove_building("School").unwrap();
+		assert_ne!(board, initial);
+		board.clone_from(&initial);
+		assert_eq!(board, initial);
+		let (mut rng, mut other_rng) = (crate::tests::rng(2), crate::tests::rng(2));
+		let mut other = initial.clone();
+		for _ in 0..3 {
+			board.try_advance_with(&mut rng).unwrap();
+			other.try_advance_with(&mut other_rng).unwrap();
+		}
+		assert_eq!(board, other);
+	}
+
+	#[cfg(feature = "tracing")]
+	#[test]
+	fn tracing() {
+		use std::sync::{Arc, Mutex};
+		use tracing::field::{Field, Visit};
+		use tracing::span::{Attributes, Id, Record};
+
+		/// Subscriber keeping the names of spans and the messages of events, in order
+		struct Names(Arc<Mutex<Vec<String>>>);
+		struct Message(Option<String>);
+		impl Visit for Message {
+			fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+				if field.name() == "message" {
+					self.0 = Some(format!("{:?}", value));
+				}
+			}
+		}
+		impl tracing::Subscriber for Names {
+			fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool { true }
+			fn new_span(&self, span: &Attributes<'_>) -> Id {
+				self.0.lock().unwrap().push(span.metadata().name().to_string());
+				Id::from_u64(1)
+			}
+			fn record(&self, _span: &Id, _values: &Record<'_>) {}
+			fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+			fn event(&self, event: &tracing::Event<'_>) {
+				let mut message = Message(None);
+				event.record(&mut message);
+				self.0.lock().unwrap().extend(message.0);
+			}
+			fn enter(&self, _span: &Id) {}
+			fn exit(&self, _span: &Id) {}
+		}
+
+		let names = Arc::new(Mutex::new(Vec::new()));
+		let mut board = Board::default();
+		tracing::subscriber::with_default(Names(names.clone()), || {
+			board.try_advance_with(&mut crate::tests::rng(1)).unwrap();
+		});
+		let names = names.lock().unwrap();
+		assert_eq!(names[..2], ["stage", "visit"]);
+		assert_eq!(names.iter().filter(|name| *name == "building visited").count(), 8);
+		let propagate = names.iter().position(|name| name == "propagate").unwrap();
+		let go_home = names.iter().position(|name| name == "go_home").unwrap();
+		let infections = names.iter().filter(|name| *name == "infections in building").count();
+		assert_eq!(infections, board.recording().infections_by_building().iter().filter(|(_, infections)| infections[1] > 0).count());
+		assert!(propagate < go_home);
+		assert_eq!(names.last().unwrap(), "stage played");
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,359 @@
+use crate::errors::{ConfigError, Diagnostic};
+use super::{Board, BoardBuilder, Population, Convalescence, Vaccine, Visiting, MAX_DAYS};
+use crate::building::Spreading;
+
+impl BoardBuilder {
+    /// Returns the fields of the configuration that are invalid, in the order they appear.
+    ///
+    /// Each diagnostic carries the path of the field, relative to the board builder.
+    /// An empty vector means the configuration is valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let board_builder = BoardBuilder { healthy: 10, buildings: vec![(0, 0)], ..Default::default() };
+    /// let messages: Vec<String> = board_builder.diagnostics().iter().map(|d| d.to_string()).collect();
+    /// assert_eq!(messages, vec!["buildings[0].columns must be at least 1", "buildings[0].rows must be at least 1"]);
+    /// ```
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics_with(self.read_population_file().as_ref())
+    }
+
+    /// Returns the diagnostics of the configuration, with `file` the result of reading the population file.
+    fn diagnostics_with(&self, file: Option<&Result<Population, String>>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut population = self.counts_population();
+        match file {
+            Some(Ok(file_population)) => population = file_population.clone(),
+            Some(Err(error)) => diagnostics.push(Diagnostic::new("population_file", format!("could not be read: {}", error))),
+            None => (),
+        }
+        if let Some(composition) = &self.composition {
+            #[cfg(feature = "serde")]
+            if self.population_file.is_some() {
+                diagnostics.push(Diagnostic::new("composition", "can not be used together with population_file"));
+            }
+            let total: usize = composition.percentages.iter().map(|&(_, percentage)| usize::from(percentage)).sum();
+            if total != 100 {
+                diagnostics.push(Diagnostic::new("composition.percentages", format!("must add up to 100, not {}", total)));
+            } else {
+                population = composition.population();
+            }
+        }
+        let size = population.len();
+        let healthy = super::vaccinatable(&population);
+        if size == 0 && diagnostics.is_empty() {
+            diagnostics.push(Diagnostic::new("healthy", "the population must have at least one individual"));
+        }
+        for (index, (columns, rows)) in self.buildings.iter().enumerate() {
+            if *columns == 0 {
+                diagnostics.push(Diagnostic::new(format!("buildings[{}].columns", index), "must be at least 1"));
+            }
+            if *rows == 0 {
+                diagnostics.push(Diagnostic::new(format!("buildings[{}].rows", index), "must be at least 1"));
+            }
+        }
+        match self.spreading {
+            Spreading::Airborne(percent) | Spreading::DistanceDecay(percent) if percent > 100 => {
+                diagnostics.push(Diagnostic::new("spreading", "percentage must be at most 100"));
+            },
+            _ => (),
+        }
+        match &self.visiting {
+            Visiting::InOrder => (),
+            Visiting::Weighted { weights, stay_home } => {
+                if weights.len() > self.buildings.len() {
+                    diagnostics.push(Diagnostic::new(
+                        "visiting.weights",
+                        format!("must have at most {} elements, one per building", self.buildings.len()),
+                    ));
+                }
+                if *stay_home > 100 {
+                    diagnostics.push(Diagnostic::new("visiting.stay_home", "must be at most 100"));
+                }
+            },
+            Visiting::Capacity { stay_home } => {
+                if *stay_home > 100 {
+                    diagnostics.push(Diagnostic::new("visiting.stay_home", "must be at most 100"));
+                }
+            },
+        }
+        match self.vaccine {
+            Vaccine::Perfect => (),
+            Vaccine::Imperfect { efficacy } => if efficacy > 100 {
+                diagnostics.push(Diagnostic::new("vaccine.efficacy", "must be at most 100"));
+            },
+            Vaccine::TwoDoses { protection, delay } => {
+                if protection > 100 {
+                    diagnostics.push(Diagnostic::new("vaccine.protection", "must be at most 100"));
+                }
+                check_days(&mut diagnostics, "vaccine.delay", delay);
+            },
+        }
+        if let Some(days) = self.immunity_duration {
+            check_days(&mut diagnostics, "immunity_duration", days);
+        }
+        if let Some(days) = self.latency {
+            check_days(&mut diagnostics, "latency", days);
+        }
+        if self.asymptomatic > 100 {
+            diagnostics.push(Diagnostic::new("asymptomatic", "must be at most 100"));
+        }
+        if let Some(vaccines) = self.vaccines {
+            if vaccines > healthy {
+                diagnostics.push(Diagnostic::new(
+                    "vaccines",
+                    format!("must be at most {}, the number of healthy individuals", healthy),
+                ));
+            }
+        }
+        if self.vaccination_rate > healthy {
+            diagnostics.push(Diagnostic::new(
+                "vaccination_rate",
+                format!("must be at most {}, the number of healthy individuals", healthy),
+            ));
+        }
+        match self.convalescence {
+            Convalescence::Forever => (),
+            Convalescence::Immune { days } | Convalescence::Healthy { days } => check_days(&mut diagnostics, "convalescence.days", days),
+        }
+        diagnostics
+    }
+
+    /// Returns the configuration if it is valid.
+    ///
+    /// # Errors
+    ///
+    /// If there is any diagnostic, see `diagnostics`.
+    pub fn validated(self) -> Result<Self, ConfigError> {
+        let diagnostics = self.diagnostics();
+        if diagnostics.is_empty() {
+            Ok(self)
+        } else {
+            Err(ConfigError::Invalid(diagnostics))
+        }
+    }
+
+    /// Returns the board, after checking the configuration.
+    ///
+    /// The population file is read only once, both to check the configuration and to build the board.
+    ///
+    /// # Errors
+    ///
+    /// If there is any diagnostic, see `diagnostics`, 
+    /// for example if the population file can not be read.
+    pub fn try_build(self) -> Result<Board, ConfigError> {
+        let file = self.read_population_file();
+        let diagnostics = self.diagnostics_with(file.as_ref());
+        match file {
+            _ if !diagnostics.is_empty() => Err(ConfigError::Invalid(diagnostics)),
+            Some(Ok(population)) => Ok(self.build_with(population)),
+            _ => {
+                let population = self.configured_population();
+                Ok(self.build_with(population))
+            },
+        }
+    }
+
+    /// Returns the JSON Schema of the configuration, 
+    /// so that editors and web frontends can validate and complete configuration files.
+    ///
+    /// Fields with a default value are not required.
+    #[cfg(feature = "schema")]
+    pub fn schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(BoardBuilder)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for BoardBuilder {
+    /// Returns a small valid configuration, with at most 100 individuals and 6 buildings of at most 6 by 6 seats,
+    /// see `diagnostics`.
+    ///
+    /// Vaccination by identity is never chosen, since identities are only known once the board is built.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use super::VaccinationStrategy;
+        use crate::recording::DayZero;
+
+        fn days(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Option<usize>> {
+            Ok(if u.arbitrary()? { Some(u.int_in_range(1..=10)?) } else { None })
+        }
+
+        let healthy = u.int_in_range(1..=50)?;
+        let superspreaders = u.int_in_range(0..=10)?;
+        let mut buildings = Vec::new();
+        for _ in 0..u.int_in_range(0..=6)? {
+            buildings.push((u.int_in_range(1..=6)?, u.int_in_range(1..=6)?));
+        }
+        let visiting = match u.int_in_range(0..=2)? {
+            0 => Visiting::InOrder,
+            1 => Visiting::Capacity { stay_home: u.int_in_range(0..=100)? },
+            _ => {
+                let mut weights = Vec::new();
+                for _ in 0..u.int_in_range(0..=buildings.len())? {
+                    weights.push(u.int_in_range(0..=5)?);
+                }
+                Visiting::Weighted { weights, stay_home: u.int_in_range(0..=100)? }
+            },
+        };
+        Ok(BoardBuilder {
+            healthy,
+            infected1: u.int_in_range(0..=10)?,
+            infected2: u.int_in_range(0..=10)?,
+            infected3: u.int_in_range(0..=10)?,
+            sick: u.int_in_range(0..=10)?,
+            immune: u.int_in_range(0..=10)?,
+            superspreaders,
+            buildings,
+            spreading: u.arbitrary()?,
+            visiting,
+            vaccine: match u.int_in_range(0..=2)? {
+                0 => Vaccine::Perfect,
+                1 => Vaccine::Imperfect { efficacy: u.int_in_range(0..=100)? },
+                _ => Vaccine::TwoDoses { protection: u.int_in_range(0..=100)?, delay: u.int_in_range(0..=MAX_DAYS)? },
+            },
+            vaccination_strategy: if u.arbitrary()? { VaccinationStrategy::FirstHealthy } else { VaccinationStrategy::Random },
+            immunity_duration: days(u)?,
+            latency: days(u)?,
+            asymptomatic: u.int_in_range(0..=100)?,
+            day_zero: *u.choose(&[DayZero::AfterActions, DayZero::BeforeActions, DayZero::Excluded])?,
+            vaccines: if u.arbitrary()? { Some(u.int_in_range(0..=healthy)?) } else { None },
+            vaccination_rate: u.int_in_range(0..=healthy)?,
+            convalescence: match (u.int_in_range(0..=2)?, days(u)?) {
+                (0, _) | (_, None) => Convalescence::Forever,
+                (1, Some(days)) => Convalescence::Immune { days },
+                (_, Some(days)) => Convalescence::Healthy { days },
+            },
+            collect_events: u.arbitrary()?,
+            track_identities: u.arbitrary()?,
+            composition: None,
+            #[cfg(feature = "serde")]
+            population_file: None,
+        })
+    }
+}
+
+/// Adds a diagnostic if `days` is too large to be added to a day of the game.
+pub(crate) fn check_days(diagnostics: &mut Vec<Diagnostic>, path: &str, days: usize) {
+    if days > MAX_DAYS {
+        diagnostics.push(Diagnostic::new(path, format!("must be at most {}", MAX_DAYS)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics() {
+        let board_builder = BoardBuilder {
+            immunity_duration: Some(usize::MAX),
+            vaccines: Some(1),
+            vaccination_rate: 1,
+            convalescence: Convalescence::Healthy { days: MAX_DAYS + 1 },
+            vaccine: Vaccine::Imperfect { efficacy: 101 },
+            ..Default::default()
+        };
+        let paths: Vec<String> = board_builder.diagnostics().into_iter().map(|d| d.path).collect();
+        assert_eq!(paths, vec!["healthy", "vaccine.efficacy", "immunity_duration", "vaccines", "vaccination_rate", "convalescence.days"]);
+    }
+
+    #[test]
+    fn superspreaders() {
+        let board_builder = BoardBuilder { healthy: 1, superspreaders: 2, buildings: vec![(2, 2)], vaccines: Some(2), ..Default::default() };
+        let messages: Vec<String> = board_builder.diagnostics().iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages, vec!["vaccines must be at most 1, the number of healthy individuals"]);
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn schema() {
+        let object = BoardBuilder::schema().schema.object.unwrap();
+        assert!(object.properties.contains_key("spreading"));
+        assert!(object.required.contains("healthy"));
+        assert!(!object.required.contains("visiting"));
+    }
+
+    #[test]
+    fn try_build() {
+        assert!(BoardBuilder::default().try_build().is_err());
+        let board = BoardBuilder { healthy: 2, buildings: vec![(1, 1)], ..Default::default() }.try_build().unwrap();
+        assert_eq!(board.population().len(), 2);
+    }
+
+    #[test]
+    fn composition() {
+        use crate::population::Composition;
+        use crate::Individual;
+
+        let mut board_builder = BoardBuilder { 
+            healthy: 1, 
+            buildings: vec![(2, 2)], 
+            vaccines: Some(190),
+            composition: Some(Composition::new(200).with(Individual::Healthy, 95).with(Individual::Infected1, 5)), 
+            ..Default::default() 
+        };
+        assert_eq!(board_builder.diagnostics(), vec![]);
+        let board = board_builder.clone().build();
+        assert_eq!(board.population().len(), 200);
+        assert_eq!(board.population().counting(Individual::Infected1), 10);
+        board_builder.composition = Some(Composition::new(200).with(Individual::Healthy, 90));
+        let diagnostics = board_builder.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].to_string(), "composition.percentages must add up to 100, not 90");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn population_file() {
+        let path = std::env::temp_dir().join("virus_alarm_population_file.csv");
+        std::fs::write(&path, "state,tags\nHealthy,teacher\nInfected1,\nHealthy,front row\n").unwrap();
+        let mut board_builder = BoardBuilder { 
+            healthy: 50, 
+            buildings: vec![(2, 2)], 
+            vaccines: Some(2),
+            population_file: Some(path.to_string_lossy().into_owned()), 
+            ..Default::default() 
+        };
+        assert_eq!(board_builder.diagnostics(), vec![]);
+        let board = board_builder.clone().build();
+        assert_eq!(board.population().len(), 3);
+        assert_eq!(board.population().counting(crate::Individual::Infected1), 1);
+        board_builder.vaccines = Some(3);
+        assert_eq!(board_builder.diagnostics().len(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+        let diagnostics = board_builder.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "population_file");
+        assert_eq!(board_builder.clone().try_build().err(), Some(ConfigError::Invalid(diagnostics)));
+        assert_eq!(board_builder.build().population().len(), 50);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::Arbitrary;
+        use rand::RngCore;
+
+        for seed in 0..100 {
+            let mut bytes = vec![0; 256];
+            crate::tests::rng(seed).fill_bytes(&mut bytes);
+            let mut u = arbitrary::Unstructured::new(&bytes);
+            let board_builder = BoardBuilder::arbitrary(&mut u).unwrap();
+            assert_eq!(board_builder.diagnostics(), vec![], "{:?}", board_builder);
+            let waning = board_builder.immunity_duration.is_some();
+            let mut board = board_builder.build();
+            let mut rng = crate::tests::rng(seed);
+            for _ in 0..10 {
+                board.try_advance_with(&mut rng).unwrap();
+            }
+            let counting_table = board.counting_table();
+            assert_eq!(counting_table.check_conserved(), Ok(board.population().len()));
+            if !waning {
+                assert_eq!(counting_table.check_immune_monotone(), Ok(()));
+            }
+        }
+    }
+}
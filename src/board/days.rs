@@ -0,0 +1,108 @@
+use crate::Individual;
+use super::Board;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Lightweight summary of the board at the end of a day, see `Board::days`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DaySnapshot {
+    /// Day of the snapshot
+    pub day: usize,
+    /// Number of individuals that are neither infected nor sick, including exposed and immune ones
+    pub healthy: usize,
+    /// Number of infected individuals, see `Individual::is_infected`
+    pub infected: usize,
+    /// Number of sick individuals
+    pub sick: usize,
+    /// Names of the open buildings, in the order of the buildings of the board
+    pub open_buildings: Vec<String>,
+}
+
+impl DaySnapshot {
+    /// Returns the snapshot of `board` now.
+    pub fn new(board: &Board) -> Self {
+        let counting = board.counting_table().last_day();
+        let sick = counting[&Individual::Sick];
+        let infected = counting.iter()
+            .filter(|(individual, _)| individual.is_infected())
+            .map(|(_, count)| count)
+            .sum();
+        let total: usize = counting.values().sum();
+        DaySnapshot {
+            day: *board.recording().timeline(),
+            healthy: total - infected - sick,
+            infected,
+            sick,
+            open_buildings: board.buildings().iter()
+                .filter(|building| building.is_open())
+                .map(|building| building.name().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Iterator that plays one stage of the game per item, see `Board::days`.
+#[derive(Debug)]
+pub struct Days<'a, R> {
+    board: &'a mut Board,
+    rng: R,
+}
+
+impl<'a, R: rand::Rng> Iterator for Days<'a, R> {
+    type Item = DaySnapshot;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.board.try_advance_with(&mut self.rng).ok()?;
+        Some(DaySnapshot::new(self.board))
+    }
+}
+
+impl Board {
+    /// Returns an iterator that advances a stage in the game on each item
+    /// and yields the snapshot of the board at the end of that day.
+    ///
+    /// The iterator never ends, unless the board gets corrupted, see `try_advance`.
+    /// Only the items that are consumed advance the board.
+    ///
+    /// # Examples
+    ///
+    /// Play until there are ten sick individuals, or at most twenty days.
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let mut board = Board::default();
+    /// let days = board.days().take(20).take_while(|day| day.sick < 10).count();
+    /// assert!(days <= 20);
+    /// ```
+    pub fn days(&mut self) -> Days<'_, rand::rngs::ThreadRng> {
+        self.days_with(rand::thread_rng())
+    }
+
+    /// Returns an iterator over the days of the game, using `rng` as the source of randomness, see `days`.
+    pub fn days_with<R: rand::Rng>(&mut self, rng: R) -> Days<'_, R> {
+        Days { board: self, rng }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days() {
+        let mut board = Board::default();
+        board.close("Gym").unwrap();
+        let mut other = board.clone();
+        let mut rng = crate::tests::rng(7);
+        let snapshots: Vec<DaySnapshot> = board.days_with(&mut rng).take(4).collect();
+        assert_eq!(snapshots.iter().map(|snapshot| snapshot.day).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(snapshots.iter().all(|snapshot| snapshot.healthy + snapshot.infected + snapshot.sick == 100));
+        assert!(snapshots.iter().all(|snapshot| snapshot.open_buildings.len() == 7));
+        let mut other_rng = crate::tests::rng(7);
+        for _ in 0..4 {
+            other.try_advance_with(&mut other_rng).unwrap();
+        }
+        assert_eq!(board, other);
+        assert_eq!(snapshots.last(), Some(&DaySnapshot::new(&board)));
+    }
+}
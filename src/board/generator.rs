@@ -0,0 +1,117 @@
+use super::BoardBuilder;
+use crate::building::Spreading;
+use crate::random;
+use std::ops::RangeInclusive;
+
+/// Bounds of the boards generated by `BoardBuilder::random`.
+///
+/// All ranges are inclusive. The spreading mode is chosen uniformly among `spreading`.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::board::BoardConstraints;
+/// let constraints = BoardConstraints { buildings: 2..=3, healthy: 20..=20, ..Default::default() };
+/// let board_builder = BoardBuilder::random(&mut rand::thread_rng(), &constraints);
+/// assert!((2..=3).contains(&board_builder.buildings.len()));
+/// assert_eq!(board_builder.healthy, 20);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoardConstraints {
+    /// Number of buildings
+    pub buildings: RangeInclusive<usize>,
+    /// Number of rows of the seats of each building
+    pub rows: RangeInclusive<usize>,
+    /// Number of columns of the seats of each building
+    pub columns: RangeInclusive<usize>,
+    /// Number of healthy individuals
+    pub healthy: RangeInclusive<usize>,
+    /// Number of individuals infected on their first day
+    pub infected: RangeInclusive<usize>,
+    /// Number of immune individuals
+    pub immune: RangeInclusive<usize>,
+    /// Spreading modes to choose from, `Spreading::default` if empty
+    pub spreading: Vec<Spreading>,
+}
+
+impl Default for BoardConstraints {
+    /// Bounds around the official game: up to 8 buildings of up to 5 by 5 seats,
+    /// up to 100 healthy and 5 infected individuals, and the spreading modes of the rules.
+    fn default() -> Self {
+        BoardConstraints {
+            buildings: 1..=8,
+            rows: 1..=5,
+            columns: 1..=5,
+            healthy: 10..=100,
+            infected: 1..=5,
+            immune: 0..=10,
+            spreading: vec![Spreading::Everyone, Spreading::One, Spreading::OneNear, Spreading::OneVeryNear],
+        }
+    }
+}
+
+impl BoardBuilder {
+    /// Returns a configuration with a random number of buildings of random sizes, and a random population,
+    /// within `constraints`. Other fields have their default value.
+    ///
+    /// The numbers are drawn with the functions of the `random` module, so a seed of `random::Pcg32`
+    /// generates the same configuration on every platform, for example to share exercises.
+    ///
+    /// # Panics
+    ///
+    /// If a range of `constraints` is empty.
+    pub fn random<R: rand::RngCore + ?Sized>(rng: &mut R, constraints: &BoardConstraints) -> BoardBuilder {
+        let num_buildings = within(rng, &constraints.buildings);
+        let buildings = (0..num_buildings)
+            .map(|_| {
+                let columns = within(rng, &constraints.columns);
+                (columns, within(rng, &constraints.rows))
+            })
+            .collect();
+        BoardBuilder {
+            healthy: within(rng, &constraints.healthy),
+            infected1: within(rng, &constraints.infected),
+            immune: within(rng, &constraints.immune),
+            buildings,
+            spreading: random::choose(&constraints.spreading, rng).copied().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns a number chosen uniformly at random in `range`.
+fn within<R: rand::RngCore + ?Sized>(rng: &mut R, range: &RangeInclusive<usize>) -> usize {
+    assert!(!range.is_empty(), "the range {:?} is empty", range);
+    range.start() + random::below(rng, range.end() - range.start() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random() {
+        let constraints = BoardConstraints { healthy: 1..=30, spreading: vec![Spreading::OneNear], ..Default::default() };
+        for seed in 0..50 {
+            let board_builder = BoardBuilder::random(&mut crate::tests::rng(seed), &constraints);
+            assert_eq!(board_builder, BoardBuilder::random(&mut crate::tests::rng(seed), &constraints));
+            assert_eq!(board_builder.diagnostics(), vec![], "{:?}", board_builder);
+            assert!((1..=8).contains(&board_builder.buildings.len()));
+            assert!(board_builder.buildings.iter().all(|&(columns, rows)| (1..=5).contains(&columns) && (1..=5).contains(&rows)));
+            assert_eq!(board_builder.spreading, Spreading::OneNear);
+            let mut board = board_builder.build();
+            let mut rng = crate::tests::rng(seed);
+            for _ in 0..6 {
+                board.try_advance_with(&mut rng).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty() {
+        let constraints = BoardConstraints { buildings: RangeInclusive::new(3, 2), ..Default::default() };
+        BoardBuilder::random(&mut crate::tests::rng(0), &constraints);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::errors::BoardError;
+use crate::recording::Event;
+use crate::Individual;
+use super::Board;
+use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// What happened during a stage of the game, see `Board::play_day`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DayOutcome {
+    /// Day reached at the end of the stage
+    pub day: usize,
+    /// Number of newly infected individuals during the stage, see `Recording::incidence`
+    pub newly_infected: usize,
+    /// Individuals of each variant in each building while visiting, in the order of the buildings of the board
+    pub occupancy: Vec<(String, HashMap<Individual, usize>)>,
+    /// Number of individuals of each variant at the end of the stage
+    pub counting: HashMap<Individual, usize>,
+    /// Events of the stage, if they are collected, see `Recording::events`
+    pub events: Vec<Event>,
+}
+
+impl Board {
+    /// Advances a stage in the game and returns what happened during it,
+    /// so that interactive frontends need not compare the recording before and after.
+    ///
+    /// # Errors
+    ///
+    /// If individuals are not conserved at the end of the stage, see `try_advance`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let mut board = BoardBuilder { healthy: 3, infected1: 1, buildings: vec![(4, 1)], spreading: Spreading::Everyone, ..Default::default() }.build();
+    /// let outcome = board.play_day().unwrap();
+    /// assert_eq!(outcome.day, 1);
+    /// assert_eq!(outcome.newly_infected, 3);
+    /// assert_eq!(outcome.occupancy[0].1[&Individual::Healthy], 3);
+    /// assert_eq!(outcome.counting[&Individual::Infected1], 3);
+    /// ```
+    pub fn play_day(&mut self) -> Result<DayOutcome, BoardError> {
+        self.play_day_with(&mut rand::thread_rng())
+    }
+
+    /// Advances a stage in the game using `rng` as the source of randomness,
+    /// and returns what happened during it, see `play_day`.
+    ///
+    /// # Errors
+    ///
+    /// If individuals are not conserved at the end of the stage, see `try_advance`.
+    pub fn play_day_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<DayOutcome, BoardError> {
+        let visiting_day = *self.recording.timeline();
+        let events_before = self.recording.events().len();
+        self.try_advance_with(rng)?;
+        let recording = self.recording();
+        let occupancy = recording.occupancy().iter()
+            .map(|(name, table)| {
+                let counting = table.inner().iter()
+                    .map(|(individual, days)| (*individual, days.get(visiting_day).copied().unwrap_or(0)))
+                    .collect();
+                (name.clone(), counting)
+            })
+            .collect();
+        Ok(DayOutcome {
+            day: *recording.timeline(),
+            newly_infected: recording.incidence().last().copied().unwrap_or(0),
+            occupancy,
+            counting: self.counting_table().last_day(),
+            events: recording.events()[events_before..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_day() {
+        let mut board = Board::default();
+        board.set_collect_events(true);
+        board.close("School").unwrap();
+        let mut other = board.clone();
+        let (mut rng, mut other_rng) = (crate::tests::rng(6), crate::tests::rng(6));
+        for _ in 0..3 {
+            let outcome = board.play_day_with(&mut rng).unwrap();
+            other.try_advance_with(&mut other_rng).unwrap();
+            assert_eq!(board, other);
+            let infections: usize = board.recording().infections_by_building().iter().map(|(_, infections)| infections[outcome.day]).sum();
+            assert_eq!(outcome.events.len(), infections);
+            assert!(outcome.events.iter().all(|event| event.day() == outcome.day));
+            let (name, school) = &outcome.occupancy[2];
+            assert_eq!(name, "School");
+            assert!(school.values().all(|&count| count == 0));
+            assert_eq!(outcome.counting.values().sum::<usize>(), 100);
+        }
+        assert_eq!(board.recording().timeline(), &3);
+    }
+}
@@ -2,13 +2,21 @@ use core::fmt::Display;
 use core::convert::TryFrom;
 use crate::errors::BuildingError;
 use crate::Individual;
-use gamma::graph::DefaultGraph;
+use gamma::graph::{DefaultGraph, Graph};
 use ndarray::Array2;
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 use getset::{Getters, Setters, MutGetters};
 
+#[cfg(feature = "unstable")]
+mod rule;
+#[cfg(feature = "unstable")]
+pub use rule::*;
+
 /// Spreading mode inside a building.
-#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Spreading {
     /// If there is one person infected in the building, then everyone is infected
     Everyone,
@@ -26,14 +34,72 @@ pub enum Spreading {
     /// Also, as there can be more than one infected per building, they work collectively and infect
     /// as much people as possible, under the restriction that each of them infects only one other individual.
     OneVeryNear,
+    /// Infected individuals try to infect someone adjacent to them in the seat graph of the building.
+    ///
+    /// Adjacent individuals are those whose seats are joined by an edge, see `BuildingBuilder::with_adjacency`.
+    /// Also, as there can be more than one infected per building, they work collectively and infect
+    /// as much people as possible, under the restriction that each of them infects only one other individual.
+    OneAdjacent,
+    /// If there is one person infected in the building, then each healthy person is infected 
+    /// with the given probability, in percentage.
+    ///
+    /// Percentages above 100 are treated as 100, in which case it coincides with `Everyone`. 
+    /// Useful as an upper-bound comparison against spatial spreading modes.
+    Airborne(u8),
+    /// Infected individuals try to infect someone within the given distance considering spatial structure.
+    ///
+    /// Distance is measured as the Chebyshev distance, ie the maximum between the vertical and horizontal distances, 
+    /// so that diagonal seats count as near. In particular, `OneWithin(1)` coincides with `OneNear`.
+    /// Also, as there can be more than one infected per building, they work collectively and infect
+    /// as much people as possible, under the restriction that each of them infects only one other individual.
+    OneWithin(u8),
+    /// Each infected individual infects each healthy one independently, with a probability 
+    /// that decreases with the distance between them.
+    ///
+    /// The given value is the probability of infection, in percentage, at distance one.
+    /// At Chebyshev distance `d`, the probability is this value to the power `d`.
+    /// Percentages above 100 are treated as 100.
+    DistanceDecay(u8),
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Spreading {
     fn default() -> Self { 
         Spreading::OneVeryNear
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Spreading {
+    /// Returns a spreading mode whose percentages are at most 100 and distances at most 5, 
+    /// so that it is valid, see `BoardBuilder::diagnostics`.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=7)? {
+            0 => Spreading::Everyone,
+            1 => Spreading::One,
+            2 => Spreading::OneNear,
+            3 => Spreading::OneVeryNear,
+            4 => Spreading::OneAdjacent,
+            5 => Spreading::Airborne(u.int_in_range(0..=100)?),
+            6 => Spreading::OneWithin(u.int_in_range(1..=5)?),
+            _ => Spreading::DistanceDecay(u.int_in_range(0..=100)?),
+        })
+    }
+}
+
+/// Group a building belongs to, used to act on many buildings at once, 
+/// see `Board::close_group` and `Board::lockdown`.
+#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Tag {
+    /// Buildings needed for daily life, like supermarkets and pharmacies
+    Essential,
+    /// Buildings for leisure, like restaurants and gyms
+    Leisure,
+    /// Buildings for education, like schools
+    Education,
+}
 
 /// Builder struct for `Building`.
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
@@ -43,6 +109,10 @@ pub struct BuildingBuilder {
     name: String,
     penalty: usize,
     open: bool, 
+    adjacency: Vec<(usize, usize)>,
+    tags: Vec<Tag>,
+    #[cfg(feature = "unstable")]
+    rule: Option<CustomSpreading>,
 }
 
 impl BuildingBuilder {
@@ -68,9 +138,61 @@ impl BuildingBuilder {
         self
     }
 
+    /// Changes the building to have `seats` seats joined by the edges in `adjacency`, 
+    /// and sets the spreading mode to `OneAdjacent`.
+    ///
+    /// Seats are numbered from zero and each edge is a pair of seats. 
+    /// This allows to model buildings that are not rectangular grids, like a round table or a bus.
+    ///
+    /// # Examples
+    ///
+    /// A round table with four seats.
+    /// ```
+    /// # use virus_alarm::{BuildingBuilder, building::Spreading};
+    /// let building = BuildingBuilder::new("Round table")
+    ///     .with_adjacency(4, vec![(0, 1), (1, 2), (2, 3), (3, 0)])
+    ///     .build();
+    /// assert_eq!(building.capacity(), 4);
+    /// assert_eq!(building.spreading(), &Spreading::OneAdjacent);
+    /// ```
+    pub fn with_adjacency(mut self, seats: usize, adjacency: Vec<(usize, usize)>) -> Self {
+        self.people = Array2::from_elem((1, seats), None);
+        self.spreading = Spreading::OneAdjacent;
+        self.adjacency = adjacency;
+        self
+    }
+
+    /// Adds the building to the group `tag`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{BuildingBuilder, building::Tag};
+    /// let building = BuildingBuilder::new("Bakery").with_tag(Tag::Essential).build();
+    /// assert!(building.has_tag(Tag::Essential));
+    /// assert!(!building.has_tag(Tag::Leisure));
+    /// ```
+    pub fn with_tag(mut self, tag: Tag) -> Self {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
     /// Changes the spreading mode of the building
     pub fn with_spreading(mut self, new_spreading: Spreading) -> Self {
         self.spreading = new_spreading;
+        #[cfg(feature = "unstable")]
+        {
+            self.rule = None;
+        }
+        self
+    }
+
+    /// Changes the spreading of the building to a custom rule, see `SpreadingRule`.
+    #[cfg(feature = "unstable")]
+    pub fn with_rule<R: SpreadingRule + 'static>(mut self, rule: R) -> Self {
+        self.rule = Some(CustomSpreading::new(rule));
         self
     }
 
@@ -95,6 +217,12 @@ impl BuildingBuilder {
             name: self.name,
             penalty: self.penalty,
             open: self.open,
+            adjacency: self.adjacency,
+            tags: self.tags,
+            limit: None,
+            blocked: Vec::new(),
+            #[cfg(feature = "unstable")]
+            rule: self.rule,
         }
     }
 }
@@ -107,18 +235,28 @@ impl Default for BuildingBuilder {
             name: String::from("Default"),
             penalty: 0,
             open: true,
+            adjacency: Vec::new(),
+            tags: Vec::new(),
+            #[cfg(feature = "unstable")]
+            rule: None,
         }
     }
 }
 
 /// Building in the board game where spreading can happen.
-#[derive(Debug, Hash, Clone, PartialEq, Eq, Getters, MutGetters, Setters)]
+#[derive(Debug, Hash, PartialEq, Eq, Getters, MutGetters, Setters)]
 pub struct Building {
     people: Array2<Option<Individual>>,
     spreading: Spreading,
     name: String,
     penalty: usize,
     open: bool,
+    adjacency: Vec<(usize, usize)>,
+    tags: Vec<Tag>,
+    limit: Option<usize>,
+    blocked: Vec<usize>,
+    #[cfg(feature = "unstable")]
+    rule: Option<CustomSpreading>,
 }
 
 impl Building {
@@ -212,9 +350,42 @@ impl Building {
 	pub fn spreading(&self) -> &Spreading {
 		&self.spreading
 	}
+	/// Returns the edges between seats, used by the `OneAdjacent` spreading mode.
+	///
+	/// Seats are numbered in row-major order.
+	pub fn adjacency(&self) -> &[(usize, usize)] {
+		&self.adjacency
+	}
+	/// Returns the groups the building belongs to, see `BuildingBuilder::with_tag`.
+	pub fn tags(&self) -> &[Tag] {
+		&self.tags
+	}
+	/// Returns `true` if the building belongs to the group `tag`.
+	pub fn has_tag(&self, tag: Tag) -> bool {
+		self.tags.contains(&tag)
+	}
 	/// Sets the spreading mode of the building
+	///
+	/// This replaces any custom rule of the building.
 	pub fn set_spreading(&mut self, new_spreading: Spreading) -> &mut Self {
 		self.spreading = new_spreading;
+		#[cfg(feature = "unstable")]
+		{
+			self.rule = None;
+		}
+        self
+	}
+	/// Returns the custom spreading rule of the building, if any.
+	///
+	/// If present, it overrides the spreading mode.
+	#[cfg(feature = "unstable")]
+	pub fn rule(&self) -> Option<&dyn SpreadingRule> {
+		self.rule.as_ref().map(|rule| rule.rule())
+	}
+	/// Sets a custom spreading rule for the building, see `SpreadingRule`.
+	#[cfg(feature = "unstable")]
+	pub fn set_rule<R: SpreadingRule + 'static>(&mut self, rule: R) -> &mut Self {
+		self.rule = Some(CustomSpreading::new(rule));
         self
 	}
     /// Return the shape of the array as a slice.
@@ -225,9 +396,94 @@ impl Building {
     pub fn capacity(&self) -> usize {
         self.people().shape().iter().product()
     }
+    /// Returns the maximum number of individuals allowed in the building, if it is restricted.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+    /// Restricts the number of individuals allowed in the building, 
+    /// or removes the restriction if `new_limit` is `None`.
+    ///
+    /// The allowed seats follow a checkerboard pattern, and then the remaining seats in row-major order,
+    /// so that individuals are kept apart. In particular, with half of the seats, 
+    /// no two individuals sit very near each other, see `Spreading::OneVeryNear`.
+    /// The rest of the seats are blocked, see `blocked_seats`.
+    /// Individuals already in the building keep their seats.
+    ///
+    /// # Examples
+    ///
+    /// Every other seat is kept empty.
+    /// ```
+    /// # use virus_alarm::{BuildingBuilder, Individual};
+    /// let mut building = BuildingBuilder::new("Bus").with_size(4, 1).build();
+    /// building.set_limit(Some(2));
+    /// assert_eq!(building.available_seats(), 2);
+    /// while !building.is_full() {
+    ///     building.try_push(Individual::Healthy).unwrap();
+    /// }
+    /// assert_eq!(building.people().iter().map(|i| i.is_some()).collect::<Vec<_>>(), vec![true, false, true, false]);
+    /// ```
+    pub fn set_limit(&mut self, new_limit: Option<usize>) -> &mut Self {
+        self.limit = new_limit;
+        self.blocked = match new_limit {
+            Some(limit) => {
+                let columns = self.people.ncols();
+                let (odd, even): (Vec<usize>, Vec<usize>) = (0..self.capacity()).partition(|seat| (seat / columns + seat % columns) % 2 == 1);
+                let mut blocked: Vec<usize> = even.into_iter().chain(odd).skip(limit).collect();
+                blocked.sort_unstable();
+                blocked
+            },
+            None => Vec::new(),
+        };
+        self
+    }
+    /// Restricts the number of individuals allowed in the building to a fraction of its capacity, rounded, 
+    /// so that distancing can be modeled without closing the building, see `set_limit`.
+    ///
+    /// A fraction of one removes the restriction.
+    ///
+    /// # Errors
+    ///
+    /// If the fraction is not between zero and one.
+    pub fn set_capacity_fraction(&mut self, fraction: f64) -> Result<&mut Self, BuildingError> {
+        if !(0. ..=1.).contains(&fraction) {
+            return Err(BuildingError::InvalidFraction);
+        }
+        let limit = if fraction < 1. {
+            Some((fraction * self.capacity() as f64).round() as usize)
+        } else {
+            None
+        };
+        Ok(self.set_limit(limit))
+    }
+    /// Returns the seats that must stay empty, in row-major order, see `set_limit`.
+    pub fn blocked_seats(&self) -> &[usize] {
+        &self.blocked
+    }
+    /// Returns the number of individuals allowed in the building, see `set_limit`.
+    pub fn available_seats(&self) -> usize {
+        self.capacity() - self.blocked.len()
+    }
+    /// Returns the first empty seat that is not blocked, in row-major order.
+    pub(crate) fn next_seat(&self) -> Option<usize> {
+        self.people.iter().enumerate()
+            .position(|(seat, i)| i.is_none() && self.blocked.binary_search(&seat).is_err())
+    }
+    /// Replaces the individual sitting in the seat at `row` and `column`.
+    pub(crate) fn set_seat(&mut self, row: usize, column: usize, individual: Individual) {
+        self.people[[row, column]] = Some(individual);
+    }
+    /// Returns the number of individuals currently in the building.
+    pub fn occupancy(&self) -> usize {
+        self.people().iter().filter(|i| i.is_some()).count()
+    }
     /// Checks if the building can not accept more people, ie is full.
+    ///
+    /// A building with a restricted number of individuals is full once that number is reached, see `set_limit`.
     pub fn is_full(&self) -> bool {
-        self.people().iter().all(|i| i.is_some())
+        match self.limit {
+            Some(limit) if self.occupancy() >= limit => true,
+            _ => self.next_seat().is_none(),
+        }
     }
     /// Checks if the building is empty more people.
     pub fn is_empty(&self) -> bool {
@@ -235,16 +491,16 @@ impl Building {
     }
     /// Empties the building of people, returning the individuals that were inside
     pub fn empty(&mut self) -> Vec<Individual> {
-        let vec: Vec<Individual> = self
-            .people
-            .clone()
-            .into_iter()
-            .filter(|i| i.is_some())
-            .map(|i| i.unwrap())
-            .collect();
-        self.people.mapv_inplace(|_| -> Option<Individual> { None });
+        let mut vec = Vec::with_capacity(self.occupancy());
+        self.empty_into(&mut vec);
         vec    
     }
+    /// Empties the building of people, appending the individuals that were inside to `buffer`.
+    ///
+    /// Individuals are appended in row-major order, as in `empty`, without allocating a new vector.
+    pub fn empty_into(&mut self, buffer: &mut Vec<Individual>) {
+        buffer.extend(self.people.iter_mut().filter_map(|seat| seat.take()));
+    }
     /// Appends an individual to the first available position in the building.
     ///
     /// # Errors
@@ -256,85 +512,154 @@ impl Building {
         } else if individual == Individual::Sick {
         	Err(BuildingError::Sick)
         } else {
-            for i in self.people.iter_mut() {
-                if i.is_none() {
-                    *i = Some(individual);
-                    break;
-                }
-            }
+            let seat = self.next_seat().expect("a building that is not full has a seat available");
+            *self.people.iter_mut().nth(seat).expect("seats are in the building") = Some(individual);
             Ok(())
         }
     }
 
     /// Propagates the infection
+    ///
+    /// If the building has a custom rule, it is used instead of the spreading mode.
+    ///
+    /// Regardless of the spreading mode, infected superspreaders also infect every healthy individual
+    /// near them, ie in distance one verticaly, horizontaly or diagonaly.
     pub fn propagate(&mut self) -> &mut Self {
+    	self.propagate_with(&mut rand::thread_rng())
+    }
+
+    /// Propagates the infection, using `rng` as the source of randomness.
+    ///
+    /// See `propagate` for more.
+    pub fn propagate_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+    	let exposed = self.exposed_to_superspreaders();
+    	self.propagate_spreading(rng);
+    	for index in exposed {
+    		if let Some(i) = self.people[index] {
+    			if i.is_healthy() {
+    				self.people[index] = Some(i.infected());
+    			}
+    		}
+    	}
+    	self
+    }
+
+    /// Returns the positions of healthy individuals near an infected superspreader
+    fn exposed_to_superspreaders(&self) -> Vec<[usize; 2]> {
+    	let rows = self.people.nrows();
+    	let columns = self.people.ncols();
+    	let mut exposed = Vec::new();
+    	for ((row, col), i) in self.people.indexed_iter() {
+    		if i.is_some_and(|i| i.is_superspreader()) {
+    			for other_row in row.saturating_sub(1)..(row + 2).min(rows) {
+    				for other_col in col.saturating_sub(1)..(col + 2).min(columns) {
+    					if self.people[[other_row, other_col]].is_some_and(|j| j.is_healthy()) {
+    						exposed.push([other_row, other_col]);
+    					}
+    				}
+    			}
+    		}
+    	}
+    	exposed
+    }
+
+    /// Propagates the infection according to the custom rule or the spreading mode
+    fn propagate_spreading<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+    	#[cfg(feature = "unstable")]
+    	if let Some(rule) = self.rule.clone() {
+    		rule.rule().propagate(&mut self.people, &mut &mut *rng);
+    		return self;
+    	}
     	match self.spreading {
     		Spreading::Everyone => self.propagate_everyone(),
     		Spreading::One => self.propagate_one(),
     		Spreading::OneNear => self.propagate_onenear(),
     		Spreading::OneVeryNear => self.propagate_oneverynear(),
+    		Spreading::OneAdjacent => self.propagate_oneadjacent(),
+    		Spreading::Airborne(percent) => self.propagate_airborne(percent, rng),
+    		Spreading::OneWithin(_) => self.propagate_onewithin(),
+    		Spreading::DistanceDecay(percent) => self.propagate_distancedecay(percent, rng),
     	}
     }
 
     /// Propagates by infecting one healthy individual per infected indiviual, if possible
+    #[allow(clippy::manual_flatten)]
     fn propagate_one(&mut self) -> &mut Self{
     	let mut counter = 0;
     	for i in self.people.iter() {
     		if let Some(i) = i {
-    			match i {
-    				Individual::Infected1 | Individual::Infected2 | Individual::Infected3 => counter += 1,
-    				_ => (),
+    			if i.is_infectious() {
+    				counter += 1;
     			}
     		};
     	}
 		self.people.mapv_inplace(|i| {
 			match i {
-				Some(Individual::Healthy) => {
+				Some(i) if i.is_healthy() => {
 					if counter > 0 {
 						counter -= 1;
-						Some(Individual::Infected1)
+						Some(i.infected())
 					} else {
-						Some(Individual::Healthy)
+						Some(i)
 					}
 				},
-				Some(Individual::Infected1) => Some(Individual::Infected2),
-                Some(Individual::Infected2) => Some(Individual::Infected3),
-                Some(Individual::Infected3) => Some(Individual::Sick),
                 Some(Individual::Sick) => panic!("There should not have been a sick person in the building"),
-                Some(Individual::Immune) => Some(Individual::Immune),
-                None => None,
+                i => i.map(Individual::progressed),
 			}
 		});
 		self
     }
 
     /// Propagates by setting all healthy individuals to infected, if there is any infected in the building
+    #[allow(clippy::manual_flatten)]
     fn propagate_everyone(&mut self) -> &mut Self {
     	let mut infect_everyone = false;
     	for i in self.people.iter() {
     		if let Some(i) = i {
-    			match i {
-    				Individual::Infected1 | Individual::Infected2 | Individual::Infected3 => infect_everyone = true,
-    				_ => (),
+    			if i.is_infectious() {
+    				infect_everyone = true;
     			}
     		};
     	}
     	
 		self.people.mapv_inplace(|i| {
 			match i {
-				Some(Individual::Healthy) => {
+				Some(i) if i.is_healthy() => {
 					if infect_everyone {
-						Some(Individual::Infected1)
+						Some(i.infected())
 					} else {
-						Some(Individual::Healthy)
+						Some(i)
 					}
 				},
-				Some(Individual::Infected1) => Some(Individual::Infected2),
-                Some(Individual::Infected2) => Some(Individual::Infected3),
-                Some(Individual::Infected3) => Some(Individual::Sick),
                 Some(Individual::Sick) => panic!("There should not have been a sick person in the building"),
-                Some(Individual::Immune) => Some(Individual::Immune),
-                None => None,
+                i => i.map(Individual::progressed),
+			}
+		});
+		self
+    }
+
+    /// Propagates by infecting each healthy individual with probability `percent / 100`, 
+    /// if there is any infected in the building
+    fn propagate_airborne<R: rand::Rng + ?Sized>(&mut self, percent: u8, rng: &mut R) -> &mut Self {
+    	let probability = f64::from(percent.min(100)) / 100.;
+    	let mut infectious = false;
+    	for i in self.people.iter().flatten() {
+    		if i.is_infectious() {
+    			infectious = true;
+    		}
+    	}
+
+		self.people.mapv_inplace(|i| {
+			match i {
+				Some(i) if i.is_healthy() => {
+					if infectious && crate::random::chance(rng, probability) {
+						Some(i.infected())
+					} else {
+						Some(i)
+					}
+				},
+                Some(Individual::Sick) => panic!("There should not have been a sick person in the building"),
+                i => i.map(Individual::progressed),
 			}
 		});
 		self
@@ -342,21 +667,72 @@ impl Building {
 
     /// Propagates by choosing a maximum matching between infected and healthy individuals
     fn propagate_onenear(&mut self) -> &mut Self {
-        let graph: DefaultGraph = self.clone().into();
-        let mut pairing = gamma::matching::Pairing::new();
+        self.propagate_matching()
+    }
 
-        gamma::matching::maximum_matching(&graph, &mut pairing);
+    /// Propagates by choosing a maximum matching between infected and healthy individuals
+    fn propagate_oneverynear(&mut self) -> &mut Self {
+        self.propagate_matching()
+    }
 
-        self.propagate_from_pairing(pairing)
+    /// Propagates by choosing a maximum matching between infected and healthy individuals
+    fn propagate_oneadjacent(&mut self) -> &mut Self {
+        self.propagate_matching()
     }
 
     /// Propagates by choosing a maximum matching between infected and healthy individuals
-    fn propagate_oneverynear(&mut self) -> &mut Self {
-        let graph: DefaultGraph = self.clone().into();
-        let mut pairing = gamma::matching::Pairing::new();
+    fn propagate_onewithin(&mut self) -> &mut Self {
+        self.propagate_matching()
+    }
 
-        gamma::matching::maximum_matching(&graph, &mut pairing);
+    /// Propagates by letting each infected individual infect each healthy one 
+    /// with probability `(percent / 100)^d`, where `d` is the Chebyshev distance between them
+    fn propagate_distancedecay<R: rand::Rng + ?Sized>(&mut self, percent: u8, rng: &mut R) -> &mut Self {
+        let probability = f64::from(percent.min(100)) / 100.;
+        let infectious: Vec<(usize, usize)> = self.people.indexed_iter()
+            .filter(|(_, i)| i.is_some_and(|i| i.is_infectious()))
+            .map(|(index, _)| index)
+            .collect();
+        let people = self.people.clone();
+        for ((row, col), i) in people.indexed_iter() {
+            self.people[[row, col]] = match *i {
+                Some(i) if i.is_healthy() => {
+                    let escape: f64 = infectious.iter()
+                        .map(|&(other_row, other_col)| {
+                            let distance = row.max(other_row) - row.min(other_row);
+                            let distance = distance.max(col.max(other_col) - col.min(other_col));
+                            1. - probability.powi(distance as i32)
+                        })
+                        .product();
+                    if crate::random::chance(rng, 1. - escape) {
+                        Some(i.infected())
+                    } else {
+                        Some(i)
+                    }
+                },
+                Some(Individual::Sick) => panic!("There should not have been a sick person in the building"),
+                i => i.map(Individual::progressed),
+            };
+        }
+        self
+    }
 
+    /// Propagates by choosing a maximum matching in the graph of interactions of the spreading mode
+    fn propagate_matching(&mut self) -> &mut Self {
+        let graph = DefaultGraph::from(&*self);
+        // Seats without interactions can not be matched, and leaving them out
+        // keeps the order of the others, so the matching found is the same
+        let mut interactions = DefaultGraph::new();
+        for &node in graph.nodes() {
+            if graph.degree(node).expect("node of the graph") > 0 {
+                interactions.add_node(node).expect("nodes are unique");
+            }
+        }
+        for &(seat, other) in graph.edges() {
+            interactions.add_edge(seat, other).expect("edges join added nodes");
+        }
+        let mut pairing = gamma::matching::Pairing::new();
+        gamma::matching::maximum_matching(&interactions, &mut pairing);
         self.propagate_from_pairing(pairing)
     }
 
@@ -367,18 +743,15 @@ impl Building {
             for row in 0..rows {
                 if let Some(i) = self.people()[[row, col]] {
                     self.people[[row, col]] = Some(match i {
-                        Individual::Healthy => {
+                        Individual::Sick => panic!("There should not have been a sick person in the building"),
+                        i if i.is_healthy() => {
                             if pairing.has_node(col + row * columns) {
-                                Individual::Infected1
+                                i.infected()
                             } else {
-                                Individual::Healthy
+                                i
                             }
                         }
-                        Individual::Infected1 => Individual::Infected2,
-                        Individual::Infected2 => Individual::Infected3,
-                        Individual::Infected3 => Individual::Sick,
-                        Individual::Sick => panic!("There should not have been a sick person in the building"),
-                        Individual::Immune => Individual::Immune,
+                        i => i.progressed(),
                     });
                 }
             }
@@ -398,6 +771,40 @@ impl Building {
     }
 }
 
+impl Clone for Building {
+    fn clone(&self) -> Self {
+        Building {
+            people: self.people.clone(),
+            spreading: self.spreading,
+            name: self.name.clone(),
+            penalty: self.penalty,
+            open: self.open,
+            adjacency: self.adjacency.clone(),
+            tags: self.tags.clone(),
+            limit: self.limit,
+            blocked: self.blocked.clone(),
+            #[cfg(feature = "unstable")]
+            rule: self.rule.clone(),
+        }
+    }
+
+    /// Reuses the seats, name and vectors of `self`.
+    fn clone_from(&mut self, source: &Self) {
+        let Building { people, spreading, name, penalty, open, adjacency, tags, limit, blocked, #[cfg(feature = "unstable")] rule } = self;
+        people.clone_from(&source.people);
+        *spreading = source.spreading;
+        name.clone_from(&source.name);
+        *penalty = source.penalty;
+        *open = source.open;
+        adjacency.clone_from(&source.adjacency);
+        tags.clone_from(&source.tags);
+        *limit = source.limit;
+        blocked.clone_from(&source.blocked);
+        #[cfg(feature = "unstable")]
+        rule.clone_from(&source.rule);
+    }
+}
+
 impl Default for Building {
     fn default() -> Self { 
         BuildingBuilder::default().build()
@@ -423,28 +830,34 @@ impl TryFrom<Array2<Individual>> for Building {
     }
 }
 
-impl Into<DefaultGraph> for Building {
-    fn into(self) -> DefaultGraph {
+impl From<Building> for DefaultGraph {
+    fn from(building: Building) -> DefaultGraph {
+        DefaultGraph::from(&building)
+    }
+}
+
+impl From<&Building> for DefaultGraph {
+    fn from(building: &Building) -> DefaultGraph {
         let mut graph = DefaultGraph::new();
-        let rows = self.people().nrows();
-        let columns = self.people().ncols();
+        let rows = building.people().nrows();
+        let columns = building.people().ncols();
         // Add nodes
         for col in 0..columns {
             for row in 0..rows {
-                if self.people()[[row, col]].is_some() {
+                if building.people()[[row, col]].is_some() {
                     graph.add_node(col + row * columns).unwrap()
                 }
             }
         }
         // Add edges
-        match self.spreading() {
+        match building.spreading() {
          	Spreading::OneNear | Spreading::OneVeryNear => {
                 for col in 0..columns {
 		            for row in 0..rows {
-		                if let Some(i) = self.people()[[row, col]] {
+		                if let Some(i) = building.people()[[row, col]] {
 		                	// Horizontal
 		                    if col > 0 {
-		                        if let Some(j) = self.people()[[row, col - 1]] {
+		                        if let Some(j) = building.people()[[row, col - 1]] {
 		                            if i.interacts_with(&j) {
 		                                graph
 		                                    .add_edge(col + row * columns, (col - 1) + row * columns)
@@ -454,7 +867,7 @@ impl Into<DefaultGraph> for Building {
 		                    }
 		                    // Vertical
 		                    if row > 0 {
-		                        if let Some(j) = self.people()[[row - 1, col]] {
+		                        if let Some(j) = building.people()[[row - 1, col]] {
 		                            if i.interacts_with(&j) {
 		                                graph
 		                                    .add_edge(col + row * columns, col + (row - 1) * columns)
@@ -463,9 +876,9 @@ impl Into<DefaultGraph> for Building {
 		                        }
 		                    }
 		                    // Diagonals
-		                    if self.spreading() == &Spreading::OneNear {
+		                    if building.spreading() == &Spreading::OneNear {
     		                    if col > 0 && row > 0 {
-			                    	if let Some(j) = self.people()[[row - 1, col - 1]] {
+			                    	if let Some(j) = building.people()[[row - 1, col - 1]] {
 			                            if i.interacts_with(&j) {
 			                                graph
 			                                    .add_edge(col + row * columns, (col - 1) + (row - 1) * columns)
@@ -474,7 +887,7 @@ impl Into<DefaultGraph> for Building {
 			                        }
 			                    }
 			                    if col > 0 && row < rows - 1 {
-			                    	if let Some(j) = self.people()[[row + 1, col - 1]] {
+			                    	if let Some(j) = building.people()[[row + 1, col - 1]] {
 			                            if i.interacts_with(&j) {
 			                                graph
 			                                    .add_edge(col + row * columns, (col - 1) + (row + 1) * columns)
@@ -488,7 +901,55 @@ impl Into<DefaultGraph> for Building {
 		            }
 		        }
          	},
-         	_ => todo!(),
+         	Spreading::OneAdjacent => {
+                for &(seat, other) in building.adjacency() {
+                    let i = building.people().get([seat / columns.max(1), seat % columns.max(1)]).copied().flatten();
+                    let j = building.people().get([other / columns.max(1), other % columns.max(1)]).copied().flatten();
+                    if let (Some(i), Some(j)) = (i, j) {
+                        if seat != other && i.interacts_with(&j) && !graph.has_edge(seat, other).unwrap() {
+                            graph.add_edge(seat, other).unwrap()
+                        }
+                    }
+                }
+         	},
+         	Spreading::OneWithin(distance) => {
+                let distance = *distance as usize;
+                for ((row, col), i) in building.people().indexed_iter() {
+                    if let Some(i) = i {
+                        for other_row in row.saturating_sub(distance)..(row + distance + 1).min(rows) {
+                            for other_col in col.saturating_sub(distance)..(col + distance + 1).min(columns) {
+                                let (seat, other) = (col + row * columns, other_col + other_row * columns);
+                                if other <= seat {
+                                    continue;
+                                }
+                                if let Some(j) = building.people()[[other_row, other_col]] {
+                                    if i.interacts_with(&j) {
+                                        graph.add_edge(seat, other).unwrap()
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+         	},
+         	Spreading::Everyone | Spreading::One | Spreading::Airborne(_) | Spreading::DistanceDecay(_) => {
+                // Without spatial structure, or with a positive probability at every distance, 
+                // everyone in the building interacts with everyone else
+                let interacts = match building.spreading() {
+                    Spreading::Airborne(percent) | Spreading::DistanceDecay(percent) => *percent > 0,
+                    _ => true,
+                };
+                let seats: Vec<(usize, Individual)> = building.people().indexed_iter()
+                    .filter_map(|((row, col), i)| i.map(|i| (col + row * columns, i)))
+                    .collect();
+                for (index, &(seat, i)) in seats.iter().enumerate() {
+                    for &(other, j) in seats[index + 1..].iter() {
+                        if interacts && i.interacts_with(&j) {
+                            graph.add_edge(seat, other).unwrap()
+                        }
+                    }
+                }
+         	},
          } 
 		
         graph
@@ -511,6 +972,20 @@ mod tests {
 		assert_eq!(building.spreading(), &Spreading::OneNear);
 	}
 
+	#[test_case(Spreading::Everyone, vec![(0, 1), (1, 3)])]
+	#[test_case(Spreading::One, vec![(0, 1), (1, 3)])]
+	#[test_case(Spreading::Airborne(50), vec![(0, 1), (1, 3)])]
+	#[test_case(Spreading::Airborne(0), vec![])]
+	#[test_case(Spreading::DistanceDecay(10), vec![(0, 1), (1, 3)])]
+	fn graph(spreading: Spreading, expected: Vec<(usize, usize)>) {
+		let array = array![[Individual::Healthy, Individual::Infected1], [Individual::Immune, Individual::Healthy]];
+		let mut building = Building::try_from(array).unwrap();
+		building.set_spreading(spreading);
+		let graph = DefaultGraph::from(building);
+		assert_eq!(graph.order(), 4);
+		assert_eq!(graph.edges(), expected.as_slice());
+	}
+
 	#[test]
 	#[should_panic]
 	fn no_sick_inside() {
@@ -559,6 +1034,49 @@ mod tests {
 		assert_eq!(building.people(), &array![[Some(Individual::Healthy), None]]);
 	}
 
+	#[test]
+	fn limit() {
+		let mut building = Building::new(2, 2, "");
+		building.set_limit(Some(1));
+		building.try_push(Individual::Healthy).expect("can not push when it should!");
+		assert!(building.is_full());
+		assert_eq!(building.try_push(Individual::Healthy), Err(BuildingError::Full));
+		building.set_limit(None);
+		assert!(!building.is_full());
+	}
+
+	#[test]
+	fn invalid_capacity_fraction() {
+		let mut building = Building::new(4, 2, "");
+		assert_eq!(building.set_capacity_fraction(2.).err(), Some(BuildingError::InvalidFraction));
+		assert_eq!(building.set_capacity_fraction(f64::NAN).err(), Some(BuildingError::InvalidFraction));
+		assert_eq!(building.limit(), None);
+	}
+
+	#[test]
+	fn empty_into() {
+		let mut building = Building::try_from(array![[Some(Individual::Healthy), None], [Some(Individual::Infected1), Some(Individual::Immune)]]).unwrap();
+		let mut buffer = vec![Individual::Sick];
+		building.empty_into(&mut buffer);
+		assert_eq!(buffer, vec![Individual::Sick, Individual::Healthy, Individual::Infected1, Individual::Immune]);
+		assert!(building.is_empty());
+	}
+
+	#[test_case(0.5, vec![1, 3, 4, 6]; "checkerboard")]
+	#[test_case(0.75, vec![4, 6]; "more than half")]
+	#[test_case(0., (0..8).collect(); "none")]
+	#[test_case(1., vec![]; "whole")]
+	fn capacity_fraction(fraction: f64, expected: Vec<usize>) {
+		let mut building = Building::new(4, 2, "");
+		building.set_capacity_fraction(fraction).unwrap();
+		assert_eq!(building.blocked_seats(), expected.as_slice());
+		while building.try_push(Individual::Infected1).is_ok() {}
+		assert_eq!(building.occupancy(), 8 - expected.len());
+		for seat in expected {
+			assert_eq!(building.people().iter().nth(seat), Some(&None));
+		}
+	}
+
 	#[test_case(array![
 			[Individual::Healthy, Individual::Infected1],
 			[Individual::Healthy, Individual::Infected1] 
@@ -594,6 +1112,13 @@ mod tests {
 			[Individual::Infected1, Individual::Immune],
 			[Individual::Immune, Individual::Sick] 
 		]; "diagonal decreasing")]
+	#[test_case(array![
+			[Individual::Healthy, Individual::Exposed],
+			[Individual::Immune, Individual::Healthy] 
+		], array![
+			[Individual::Healthy, Individual::Exposed],
+			[Individual::Immune, Individual::Healthy] 
+		]; "exposed")]
 	fn propagate_everyone(initial: Array2<Individual>, expected: Array2<Individual>) {
 		let mut initial = Building::unchecked_from(initial);
 		let mut expected = Building::unchecked_from(expected);
@@ -741,4 +1266,145 @@ mod tests {
 		initial.propagate();
 		assert_eq!(initial, expected);
 	}
-}
\ No newline at end of file
+
+	#[test_case(array![
+			[Individual::Healthy, Individual::Infected1, Individual::Healthy, Individual::Healthy],
+		], array![
+			[Individual::Infected1, Individual::Infected2, Individual::Healthy, Individual::Healthy],
+		]; "one neighbour")]
+	#[test_case(array![
+			[Individual::Infected1, Individual::Healthy, Individual::Infected2, Individual::Healthy],
+		], array![
+			[Individual::Infected2, Individual::Infected1, Individual::Infected3, Individual::Infected1],
+		]; "double infection")]
+	#[test_case(array![
+			[Individual::Healthy, Individual::Immune, Individual::Healthy, Individual::Infected3],
+		], array![
+			[Individual::Infected1, Individual::Immune, Individual::Healthy, Individual::Sick],
+		]; "immune neighbour")]
+	fn propagate_oneadjacent(initial: Array2<Individual>, expected: Array2<Individual>) {
+		// Seat 1 is only adjacent to seat 0, and seat 3 closes the circle with seat 0.
+		let adjacency = vec![(0, 1), (1, 0), (2, 3), (3, 0)];
+		let mut initial = Building::unchecked_from(initial);
+		let mut expected = Building::unchecked_from(expected);
+		initial.set_spreading(Spreading::OneAdjacent);
+		initial.adjacency = adjacency.clone();
+		expected.set_spreading(Spreading::OneAdjacent);
+		expected.adjacency = adjacency;
+		initial.propagate();
+		assert_eq!(initial, expected);
+	}
+
+	#[test_case(100, array![
+			[Individual::Healthy, Individual::Infected1],
+			[Individual::Healthy, Individual::Healthy] 
+		], array![
+			[Individual::Infected1, Individual::Infected2],
+			[Individual::Infected1, Individual::Infected1] 
+		]; "certain")]
+	#[test_case(200, array![
+			[Individual::Healthy, Individual::Immune],
+			[Individual::Immune, Individual::Infected3] 
+		], array![
+			[Individual::Infected1, Individual::Immune],
+			[Individual::Immune, Individual::Sick] 
+		]; "above certain")]
+	#[test_case(0, array![
+			[Individual::Healthy, Individual::Infected1],
+			[Individual::Healthy, Individual::Healthy] 
+		], array![
+			[Individual::Healthy, Individual::Infected2],
+			[Individual::Healthy, Individual::Healthy] 
+		]; "impossible")]
+	fn propagate_airborne(percent: u8, initial: Array2<Individual>, expected: Array2<Individual>) {
+		let mut initial = Building::unchecked_from(initial);
+		let mut expected = Building::unchecked_from(expected);
+		initial.set_spreading(Spreading::Airborne(percent));
+		expected.set_spreading(Spreading::Airborne(percent));
+		initial.propagate();
+		assert_eq!(initial, expected);
+	}
+
+	#[test]
+	fn propagate_airborne_partial() {
+		let mut building = Building::unchecked_from(Array2::from_elem((10, 10), Individual::Healthy));
+		building.people[[0, 0]] = Some(Individual::Infected1);
+		building.propagate_airborne(50, &mut crate::tests::rng(1));
+		let infected = building.people().iter().filter(|&&i| i == Some(Individual::Infected1)).count();
+		assert!(infected > 0 && infected < 99);
+	}
+
+	#[test_case(1, array![
+			[Individual::Healthy, Individual::Immune, Individual::Healthy],
+			[Individual::Immune, Individual::Immune, Individual::Immune],
+			[Individual::Immune, Individual::Immune, Individual::Infected1] 
+		], array![
+			[Individual::Healthy, Individual::Immune, Individual::Healthy],
+			[Individual::Immune, Individual::Immune, Individual::Immune],
+			[Individual::Immune, Individual::Immune, Individual::Infected2] 
+		]; "too far")]
+	#[test_case(2, array![
+			[Individual::Healthy, Individual::Immune, Individual::Immune],
+			[Individual::Immune, Individual::Immune, Individual::Immune],
+			[Individual::Immune, Individual::Immune, Individual::Infected1] 
+		], array![
+			[Individual::Infected1, Individual::Immune, Individual::Immune],
+			[Individual::Immune, Individual::Immune, Individual::Immune],
+			[Individual::Immune, Individual::Immune, Individual::Infected2] 
+		]; "diagonal far")]
+	#[test_case(1, array![
+			[Individual::Healthy, Individual::Immune],
+			[Individual::Immune, Individual::Infected3] 
+		], array![
+			[Individual::Infected1, Individual::Immune],
+			[Individual::Immune, Individual::Sick] 
+		]; "diagonal")]
+	fn propagate_onewithin(distance: u8, initial: Array2<Individual>, expected: Array2<Individual>) {
+		let mut initial = Building::unchecked_from(initial);
+		let mut expected = Building::unchecked_from(expected);
+		initial.set_spreading(Spreading::OneWithin(distance));
+		expected.set_spreading(Spreading::OneWithin(distance));
+		initial.propagate();
+		assert_eq!(initial, expected);
+	}
+
+	#[test_case(100, array![
+			[Individual::Healthy, Individual::Immune, Individual::Healthy],
+			[Individual::Immune, Individual::Infected1, Individual::Healthy] 
+		], array![
+			[Individual::Infected1, Individual::Immune, Individual::Infected1],
+			[Individual::Immune, Individual::Infected2, Individual::Infected1] 
+		]; "certain")]
+	#[test_case(0, array![
+			[Individual::Healthy, Individual::Infected1],
+			[Individual::Healthy, Individual::Healthy] 
+		], array![
+			[Individual::Healthy, Individual::Infected2],
+			[Individual::Healthy, Individual::Healthy] 
+		]; "impossible")]
+	fn propagate_distancedecay(percent: u8, initial: Array2<Individual>, expected: Array2<Individual>) {
+		let mut initial = Building::unchecked_from(initial);
+		let mut expected = Building::unchecked_from(expected);
+		initial.set_spreading(Spreading::DistanceDecay(percent));
+		expected.set_spreading(Spreading::DistanceDecay(percent));
+		initial.propagate();
+		assert_eq!(initial, expected);
+	}
+
+	#[test]
+	fn propagate_superspreader() {
+		let mut initial = Building::unchecked_from(array![
+			[Individual::Superspreader1, Individual::Healthy, Individual::Healthy],
+			[Individual::HealthySuperspreader, Individual::Immune, Individual::Healthy],
+		]);
+		let mut expected = Building::unchecked_from(array![
+			[Individual::Superspreader2, Individual::Infected1, Individual::Healthy],
+			[Individual::Superspreader1, Individual::Immune, Individual::Healthy],
+		]);
+		// Nobody is infected by the spreading mode itself
+		initial.set_spreading(Spreading::Airborne(0));
+		expected.set_spreading(Spreading::Airborne(0));
+		initial.propagate();
+		assert_eq!(initial, expected);
+	}
+}
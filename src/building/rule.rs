@@ -0,0 +1,113 @@
+use crate::Individual;
+use ndarray::Array2;
+use rand::RngCore;
+use std::sync::Arc;
+
+/// Custom contagion rule inside a building.
+///
+/// This allows to experiment with new spreading dynamics without changing `Spreading`.
+///
+/// # Remarks
+///
+/// The rule is in charge of the whole update of the people in the building during propagation,
+/// including the progression of already infected individuals 
+/// (`Infected1` to `Infected2`, `Infected2` to `Infected3` and `Infected3` to `Sick`).
+///
+/// # Examples
+///
+/// A rule where nobody gets infected.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// # use virus_alarm::{BuildingBuilder, building::SpreadingRule};
+/// # use ndarray::Array2;
+/// #[derive(Debug)]
+/// struct NoContagion;
+///
+/// impl SpreadingRule for NoContagion {
+///     fn propagate(&self, grid: &mut Array2<Option<Individual>>, _rng: &mut dyn rand::RngCore) {
+///         grid.mapv_inplace(|i| match i {
+///             Some(Individual::Infected1) => Some(Individual::Infected2),
+///             Some(Individual::Infected2) => Some(Individual::Infected3),
+///             Some(Individual::Infected3) => Some(Individual::Sick),
+///             i => i,
+///         });
+///     }
+/// }
+///
+/// let building = BuildingBuilder::new("Quiet place").with_size(2, 2).with_rule(NoContagion).build();
+/// assert!(building.rule().is_some());
+/// ```
+pub trait SpreadingRule: core::fmt::Debug + Send + Sync {
+    /// Propagates the infection among the people in `grid`.
+    fn propagate(&self, grid: &mut Array2<Option<Individual>>, rng: &mut dyn RngCore);
+}
+
+/// Shared handle to a `SpreadingRule`, which can be stored in a `Building`.
+///
+/// Two handles are equal if they point to the same rule.
+#[derive(Debug, Clone)]
+pub struct CustomSpreading(pub(crate) Arc<dyn SpreadingRule>);
+
+impl CustomSpreading {
+    /// Constructor
+    pub fn new<R: SpreadingRule + 'static>(rule: R) -> Self {
+        CustomSpreading(Arc::new(rule))
+    }
+
+    /// Returns the inner rule.
+    pub fn rule(&self) -> &dyn SpreadingRule {
+        self.0.as_ref()
+    }
+
+    fn address(&self) -> usize {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+impl PartialEq for CustomSpreading {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl Eq for CustomSpreading {}
+
+impl core::hash::Hash for CustomSpreading {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.address().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Building;
+    use ndarray::array;
+
+    #[derive(Debug)]
+    struct InfectAll;
+
+    impl SpreadingRule for InfectAll {
+        fn propagate(&self, grid: &mut Array2<Option<Individual>>, _rng: &mut dyn RngCore) {
+            grid.mapv_inplace(|i| match i {
+                Some(Individual::Healthy) => Some(Individual::Infected1),
+                i => i,
+            });
+        }
+    }
+
+    #[test]
+    fn propagate() {
+        let mut building = Building::unchecked_from(array![[Individual::Healthy, Individual::Immune]]);
+        building.set_rule(InfectAll);
+        building.propagate();
+        assert_eq!(building.people(), &array![[Some(Individual::Infected1), Some(Individual::Immune)]]);
+    }
+
+    #[test]
+    fn equality() {
+        let rule = CustomSpreading::new(InfectAll);
+        assert_eq!(rule, rule.clone());
+        assert_ne!(rule, CustomSpreading::new(InfectAll));
+    }
+}
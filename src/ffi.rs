@@ -0,0 +1,350 @@
+//! C interface, to embed simulations in applications written in other languages.
+//!
+//! A simulation is created from a JSON configuration, see `SimulationBuilder`, and run into a report.
+//! Counting tables are retrieved as flat buffers, with one row per individual variant
+//! in the order of `Individual` and one column per day, row after row.
+//!
+//! Objects returned by this module are owned by the caller, who must free them
+//! with the corresponding `free` function. Functions that fail return a null pointer,
+//! and the reason is given by `virus_alarm_last_error`. Panics do not unwind into the caller:
+//! functions that panic return a null pointer or zero instead, and report the panic as the last error.
+//!
+//! Running `cargo build --release --features ffi` builds the shared library, next to the Rust one.
+//! The declarations of this module for C are in `include/virus_alarm.h`, 
+//! which is regenerated with cbindgen from `cbindgen.toml`.
+//!
+//! # Examples
+//!
+//! ```c
+//! #include "virus_alarm.h"
+//!
+//! VirusAlarmSimulation *simulation = virus_alarm_simulation_from_json(config);
+//! if (simulation == NULL) {
+//!     printf("%s\n", virus_alarm_last_error());
+//! }
+//! VirusAlarmReport *report = virus_alarm_simulation_run_seeded(simulation, 7);
+//! size_t len = virus_alarm_report_average(report, NULL, 0);
+//! double *buffer = malloc(len * sizeof(double));
+//! virus_alarm_report_average(report, buffer, len);
+//! virus_alarm_report_free(report);
+//! virus_alarm_simulation_free(simulation);
+//! ```
+
+use crate::simulation::{Report, Simulation};
+use crate::{Individual, SimulationBuilder};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use strum::IntoEnumIterator;
+
+/// Simulation ready to run, see `virus_alarm_simulation_from_json`.
+pub type VirusAlarmSimulation = Simulation;
+/// Result of running a simulation, see `virus_alarm_simulation_run`.
+pub type VirusAlarmReport = Report;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Keeps `message` as the last error of this thread.
+fn set_last_error<E: ToString>(error: E) {
+    let message = CString::new(error.to_string().replace('\0', " ")).expect("nul bytes are replaced");
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+/// Returns the result of `f`, or `default` if it panics, in which case the panic is kept as the last error.
+///
+/// Unwinding into foreign code is undefined behavior, so every function of this module runs through it.
+fn catch_panic<T, F: FnOnce() -> T>(default: T, f: F) -> T {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => "unknown reason".to_string(),
+            };
+            set_last_error(format!("The library panicked: {}", message));
+            default
+        },
+    }
+}
+
+/// Returns the message of the last error in this thread, or a null pointer if there was none.
+///
+/// The message is valid until the next call to a function of this module that fails, in this thread.
+#[no_mangle]
+pub extern "C" fn virus_alarm_last_error() -> *const c_char {
+    catch_panic(std::ptr::null(), || {
+        LAST_ERROR.with(|last_error| {
+            last_error.borrow().as_ref().map_or(std::ptr::null(), |message| message.as_ptr())
+        })
+    })
+}
+
+/// Returns the number of rows of counting tables, which is the number of individual variants.
+#[no_mangle]
+pub extern "C" fn virus_alarm_num_variants() -> usize {
+    catch_panic(0, || {
+        Individual::iter().len()
+    })
+}
+
+/// Returns a simulation from a JSON configuration, or a null pointer if it is not valid.
+///
+/// # Safety
+///
+/// `config` must be a valid nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_simulation_from_json(config: *const c_char) -> *mut VirusAlarmSimulation {
+    catch_panic(std::ptr::null_mut(), || {
+        if config.is_null() {
+            set_last_error("The configuration is a null pointer");
+            return std::ptr::null_mut();
+        }
+        let config = match CStr::from_ptr(config).to_str() {
+            Ok(config) => config,
+            Err(error) => {
+                set_last_error(error);
+                return std::ptr::null_mut();
+            },
+        };
+        let simulation = serde_json::from_str::<SimulationBuilder>(config)
+            .map_err(|error| crate::errors::ConfigError::Parse(error.to_string()))
+            .and_then(SimulationBuilder::validated)
+            .map_err(|error| error.to_string())
+            .and_then(|simulation_builder| simulation_builder.try_build().map_err(|error| error.to_string()));
+        match simulation {
+            Ok(simulation) => Box::into_raw(Box::new(simulation)),
+            Err(error) => {
+                set_last_error(error);
+                std::ptr::null_mut()
+            },
+        }
+    })
+}
+
+/// Frees a simulation.
+///
+/// # Safety
+///
+/// `simulation` must be null or returned by `virus_alarm_simulation_from_json`, and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_simulation_free(simulation: *mut VirusAlarmSimulation) {
+    catch_panic((), || {
+        if !simulation.is_null() {
+            drop(Box::from_raw(simulation));
+        }
+    })
+}
+
+/// Runs a simulation and returns its report, see `Simulation::run`.
+///
+/// # Safety
+///
+/// `simulation` must be returned by `virus_alarm_simulation_from_json`, and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_simulation_run(simulation: *const VirusAlarmSimulation) -> *mut VirusAlarmReport {
+    catch_panic(std::ptr::null_mut(), || {
+        match simulation.as_ref() {
+            Some(simulation) => Box::into_raw(Box::new(simulation.run())),
+            None => {
+                set_last_error("The simulation is a null pointer");
+                std::ptr::null_mut()
+            },
+        }
+    })
+}
+
+/// Runs a simulation with randomness given by `seed`, and returns its report.
+///
+/// The same seed gives the same report with the same version of this crate.
+///
+/// # Safety
+///
+/// `simulation` must be returned by `virus_alarm_simulation_from_json`, and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_simulation_run_seeded(simulation: *const VirusAlarmSimulation, seed: u64) -> *mut VirusAlarmReport {
+    catch_panic(std::ptr::null_mut(), || {
+        match simulation.as_ref() {
+            Some(simulation) => {
+                let mut rng = crate::random::Pcg32::new(seed);
+                Box::into_raw(Box::new(simulation.run_with(&mut rng)))
+            },
+            None => {
+                set_last_error("The simulation is a null pointer");
+                std::ptr::null_mut()
+            },
+        }
+    })
+}
+
+/// Frees a report.
+///
+/// # Safety
+///
+/// `report` must be null or returned by a function of this module, and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_report_free(report: *mut VirusAlarmReport) {
+    catch_panic((), || {
+        if !report.is_null() {
+            drop(Box::from_raw(report));
+        }
+    })
+}
+
+/// Returns the number of realizations in a report, or zero if they were not stored, see `Report::counting_tables`.
+///
+/// # Safety
+///
+/// `report` must be returned by a function of this module, and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_report_realizations(report: *const VirusAlarmReport) -> usize {
+    catch_panic(0, || {
+        report.as_ref().map_or(0, |report| report.counting_tables().len())
+    })
+}
+
+/// Returns the number of days in a report, see `Report::days`.
+///
+/// # Safety
+///
+/// `report` must be returned by a function of this module, and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_report_days(report: *const VirusAlarmReport) -> usize {
+    catch_panic(0, || {
+        report.as_ref().map_or(0, |report| report.days())
+    })
+}
+
+/// Writes the average counting table of a report on `buffer`, see `Report::average_counting_table`.
+///
+/// Streamed reports write the means of their running summary, see `Aggregation::Streaming`.
+///
+/// Returns the length the buffer needs, which is the number of variants times the number of days.
+/// Nothing is written if `buffer` is null or shorter than that.
+///
+/// # Safety
+///
+/// `report` must be returned by a function of this module, and not freed.
+/// `buffer` must be null or valid for writing `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_report_average(report: *const VirusAlarmReport, buffer: *mut f64, len: usize) -> usize {
+    catch_panic(0, || {
+        let report = match report.as_ref() {
+            Some(report) => report,
+            None => return 0,
+        };
+        let average = report.cell_statistics();
+        if !buffer.is_null() && len >= average.len() {
+            let buffer = std::slice::from_raw_parts_mut(buffer, average.len());
+            for (value, &(_, mean, _)) in buffer.iter_mut().zip(average.iter()) {
+                *value = mean;
+            }
+        }
+        average.len()
+    })
+}
+
+/// Writes the counting table of the realization with index `realization` on `buffer`.
+///
+/// Returns the length the buffer needs, which is the number of variants times the number of days
+/// of the realization, or zero if there is no such realization.
+/// Nothing is written if `buffer` is null or shorter than that.
+///
+/// # Safety
+///
+/// `report` must be returned by a function of this module, and not freed.
+/// `buffer` must be null or valid for writing `len` values.
+#[no_mangle]
+pub unsafe extern "C" fn virus_alarm_report_counting_table(report: *const VirusAlarmReport, realization: usize, buffer: *mut usize, len: usize) -> usize {
+    catch_panic(0, || {
+        let counting_table = match report.as_ref().and_then(|report| report.counting_tables().get(realization)) {
+            Some(counting_table) => ndarray::Array2::from(counting_table),
+            None => return 0,
+        };
+        if !buffer.is_null() && len >= counting_table.len() {
+            let buffer = std::slice::from_raw_parts_mut(buffer, counting_table.len());
+            for (value, count) in buffer.iter_mut().zip(counting_table.iter()) {
+                *value = *count;
+            }
+        }
+        counting_table.len()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+        "board_builder": {
+            "healthy": 3, "infected1": 1, "infected2": 0, "infected3": 0, "sick": 0, "immune": 0,
+            "buildings": [[2, 2]], "spreading": "Everyone"
+        },
+        "report_plan": { "num_simulations": 2, "days": 3 }
+    }"#;
+
+    #[test]
+    fn run() {
+        let config = CString::new(CONFIG).unwrap();
+        unsafe {
+            let simulation = virus_alarm_simulation_from_json(config.as_ptr());
+            assert!(!simulation.is_null());
+            let report = virus_alarm_simulation_run_seeded(simulation, 7);
+            assert_eq!(virus_alarm_report_realizations(report), 2);
+            assert_eq!(virus_alarm_report_days(report), 4);
+
+            let len = virus_alarm_report_average(report, std::ptr::null_mut(), 0);
+            assert_eq!(len, virus_alarm_num_variants() * 4);
+            let mut average = vec![0.; len];
+            virus_alarm_report_average(report, average.as_mut_ptr(), len);
+            assert_eq!(average[0], 3.);
+            assert_eq!(average[3], 0.);
+
+            let mut counting_table = vec![0; len];
+            assert_eq!(virus_alarm_report_counting_table(report, 1, counting_table.as_mut_ptr(), len), len);
+            assert_eq!(counting_table[4..8], [1, 3, 0, 0]);
+            assert_eq!(virus_alarm_report_counting_table(report, 2, counting_table.as_mut_ptr(), len), 0);
+
+            virus_alarm_report_free(report);
+            virus_alarm_simulation_free(simulation);
+        }
+    }
+
+    #[test]
+    fn invalid_config() {
+        let config = CString::new(r#"{ "report_plan": 3 }"#).unwrap();
+        unsafe {
+            assert!(virus_alarm_simulation_from_json(config.as_ptr()).is_null());
+            let error = CStr::from_ptr(virus_alarm_last_error()).to_str().unwrap();
+            assert!(error.starts_with("The configuration could not be parsed"));
+            let config = CString::new(CONFIG.replace("\"num_simulations\": 2", "\"num_simulations\": 0")).unwrap();
+            assert!(virus_alarm_simulation_from_json(config.as_ptr()).is_null());
+            let error = CStr::from_ptr(virus_alarm_last_error()).to_str().unwrap();
+            assert_eq!(error, "The configuration has 1 invalid fields");
+            assert!(virus_alarm_simulation_run(std::ptr::null()).is_null());
+        }
+    }
+
+    #[test]
+    fn panic() {
+        assert_eq!(catch_panic(3, || -> usize { panic!("boom") }), 3);
+        let error = unsafe { CStr::from_ptr(virus_alarm_last_error()) }.to_str().unwrap();
+        assert_eq!(error, "The library panicked: boom");
+        assert_eq!(catch_panic(3, || 4), 4);
+    }
+
+    #[test]
+    fn header() {
+        let header = include_str!("../include/virus_alarm.h");
+        let functions: Vec<&str> = include_str!("ffi.rs").lines()
+            .filter_map(|line| line.split("extern \"C\" fn ").nth(1))
+            .filter_map(|rest| rest.split('(').next())
+            .collect();
+        assert_eq!(functions.len(), 11);
+        for function in functions {
+            assert!(header.contains(&format!("{}(", function)), "{} is not declared in the header", function);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{Action, Board, Individual};
+use crate::errors::GameError;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+mod player;
+mod ruleset;
+
+pub use player::*;
+pub use ruleset::*;
+
+/// Rules of a turn-based game, see `Game`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameRules {
+    /// Number of actions players can take in each turn, before the stage of the day
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub action_points: usize,
+    /// Number of days to contain the outbreak
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub days: usize,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules { action_points: 3, days: 10 }
+    }
+}
+
+/// Phase of a turn-based game, see `Game::phase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Phase {
+    /// Players can take actions, until they end the turn and the day is played
+    Actions,
+    /// The game ended
+    Over(Outcome),
+}
+
+/// Result of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Outcome {
+    /// The outbreak was contained within the days of the game, see `Recording::is_contained`
+    Won,
+    /// The days of the game ran out before the outbreak was contained
+    Lost,
+}
+
+/// Turn-based game over a board.
+///
+/// Each turn, players take actions, each costing one action point:
+/// immunizing one healthy individual, or closing or opening one building.
+/// Then they end the turn and the stage of the day is played.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::game::{Game, GameRules, Phase};
+/// let mut game = Game::new(Board::default(), GameRules::default());
+/// game.play(Action::Close("School".to_string())).unwrap();
+/// game.play(Action::Immunize(1)).unwrap();
+/// assert_eq!(game.action_points_left(), &1);
+/// while game.phase() == &Phase::Actions {
+///     game.end_turn().unwrap();
+/// }
+/// assert!(game.day() <= &10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct Game {
+    /// Board where the game is played
+    #[getset(get = "pub")]
+    board: Board,
+    /// Rules of the game
+    #[getset(get = "pub")]
+    rules: GameRules,
+    /// Number of days played
+    #[getset(get = "pub")]
+    day: usize,
+    /// Action points left in the current turn
+    #[getset(get = "pub")]
+    action_points_left: usize,
+    /// Current phase of the game
+    #[getset(get = "pub")]
+    phase: Phase,
+}
+
+impl Game {
+    /// Starts a game on `board`.
+    pub fn new(board: Board, rules: GameRules) -> Self {
+        Game { board, rules, day: 0, action_points_left: rules.action_points, phase: Phase::Actions }
+    }
+
+    /// Returns the actions that can be played now.
+    ///
+    /// They are empty if the game is over or there are no action points left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use virus_alarm::game::{Game, GameRules};
+    /// let game = Game::new(Board::default(), GameRules::default());
+    /// let actions = game.legal_actions();
+    /// assert_eq!(actions[0], Action::Immunize(1));
+    /// assert!(actions.contains(&Action::Close("School".to_string())));
+    /// ```
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if self.phase != Phase::Actions || self.action_points_left == 0 {
+            return actions;
+        }
+        let healthy = self.board.population().counting(Individual::Healthy)
+            + self.board.population().counting(Individual::HealthySuperspreader);
+        if healthy > 0 && self.board.vaccines() != &Some(0) {
+            actions.push(Action::Immunize(1));
+        }
+        let mut names: Vec<&str> = Vec::new();
+        for building in self.board.buildings() {
+            if !names.contains(&building.name()) {
+                names.push(building.name());
+                if building.is_open() {
+                    actions.push(Action::Close(building.name().to_string()));
+                } else {
+                    actions.push(Action::Open(building.name().to_string()));
+                }
+            }
+        }
+        actions
+    }
+
+    /// Plays an action, using one action point.
+    ///
+    /// # Errors
+    ///
+    /// If the game is over, there are no action points left,
+    /// or the action is not one of `legal_actions`.
+    pub fn play(&mut self, action: Action) -> Result<&mut Self, GameError> {
+        if let Phase::Over(_) = self.phase {
+            return Err(GameError::Over);
+        }
+        if self.action_points_left == 0 {
+            return Err(GameError::NoActionPoints);
+        }
+        if !self.legal_actions().contains(&action) {
+            return Err(GameError::Illegal(action));
+        }
+        action.apply(&mut self.board)?;
+        self.action_points_left -= 1;
+        Ok(self)
+    }
+
+    /// Ends the turn, so the stage of the day is played, and returns the new phase.
+    ///
+    /// # Errors
+    ///
+    /// If the game is over, or the board is corrupted, see `Board::try_advance`.
+    pub fn end_turn(&mut self) -> Result<Phase, GameError> {
+        self.end_turn_with(&mut rand::thread_rng())
+    }
+
+    /// Ends the turn using `rng` as the source of randomness, see `end_turn`.
+    pub fn end_turn_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<Phase, GameError> {
+        if let Phase::Over(_) = self.phase {
+            return Err(GameError::Over);
+        }
+        self.board.try_advance_with(rng)?;
+        self.day += 1;
+        self.action_points_left = self.rules.action_points;
+        if self.board.recording().is_contained() {
+            self.phase = Phase::Over(Outcome::Won);
+        } else if self.day >= self.rules.days {
+            self.phase = Phase::Over(Outcome::Lost);
+        }
+        Ok(self.phase)
+    }
+
+    /// Returns the outcome of the game, if it is over.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self.phase {
+            Phase::Actions => None,
+            Phase::Over(outcome) => Some(outcome),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ActionError;
+    use crate::prelude::BoardBuilder;
+
+    #[test]
+    fn action_points() {
+        let mut game = Game::new(Board::default(), GameRules { action_points: 2, days: 3 });
+        game.play(Action::Close("School".to_string())).unwrap();
+        assert_eq!(game.play(Action::Close("School".to_string())).unwrap_err(), GameError::Illegal(Action::Close("School".to_string())));
+        game.play(Action::Open("School".to_string())).unwrap();
+        assert!(game.legal_actions().is_empty());
+        assert_eq!(game.play(Action::Immunize(1)).unwrap_err(), GameError::NoActionPoints);
+        game.end_turn_with(&mut crate::tests::rng(1)).unwrap();
+        assert_eq!(game.action_points_left(), &2);
+        assert_eq!(game.day(), &1);
+    }
+
+    #[test]
+    fn illegal() {
+        let mut game = Game::new(Board::default(), GameRules::default());
+        for action in [Action::Immunize(2), Action::Infect(1), Action::Close("Castle".to_string())].iter() {
+            assert_eq!(game.play(action.clone()).unwrap_err(), GameError::Illegal(action.clone()));
+        }
+        let mut board = Board::default();
+        board.set_vaccines(Some(0));
+        let game = Game::new(board, GameRules::default());
+        assert!(!game.legal_actions().contains(&Action::Immunize(1)));
+        assert_eq!(GameError::from(ActionError::NoVaccinesLeft).to_string(), "There are no more vaccines available");
+    }
+
+    #[test]
+    fn won() {
+        let board = BoardBuilder { healthy: 4, infected1: 1, buildings: vec![(2, 2)], ..Default::default() }.build();
+        let mut game = Game::new(board, GameRules::default());
+        game.play(Action::Close("Building 1".to_string())).unwrap();
+        let mut rng = crate::tests::rng(2);
+        assert_eq!(game.end_turn_with(&mut rng), Ok(Phase::Actions));
+        assert_eq!(game.end_turn_with(&mut rng), Ok(Phase::Actions));
+        assert_eq!(game.end_turn_with(&mut rng), Ok(Phase::Over(Outcome::Won)));
+        assert_eq!(game.outcome(), Some(Outcome::Won));
+        assert_eq!(game.end_turn_with(&mut rng), Err(GameError::Over));
+        assert_eq!(game.play(Action::Immunize(1)).unwrap_err(), GameError::Over);
+    }
+
+    #[test]
+    fn lost() {
+        let board = BoardBuilder { healthy: 4, infected1: 1, buildings: vec![(2, 2)], ..Default::default() }.build();
+        let mut game = Game::new(board, GameRules { action_points: 0, days: 2 });
+        let mut rng = crate::tests::rng(3);
+        game.end_turn_with(&mut rng).unwrap();
+        assert_eq!(game.end_turn_with(&mut rng), Ok(Phase::Over(Outcome::Lost)));
+    }
+}
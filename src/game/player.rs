@@ -0,0 +1,205 @@
+use crate::{Action, Board, Individual};
+use super::{Game, GameRules, Outcome, Phase};
+use crate::errors::GameError;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Strategy that plays a `Game` automatically.
+///
+/// Each turn, the player proposes actions in order of preference.
+/// Actions are played while there are action points left, skipping the ones that are not legal,
+/// see `Game::legal_actions`.
+///
+/// # Examples
+///
+/// A player that never acts.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::game::{GameRules, Player};
+/// struct Idle;
+/// impl Player for Idle {
+///     fn choose_actions(&mut self, _board: &Board) -> Vec<Action> {
+///         Vec::new()
+///     }
+/// }
+/// let game = Idle.play(Board::default(), GameRules::default(), &mut rand::thread_rng());
+/// assert!(game.outcome().is_some());
+/// ```
+pub trait Player {
+    /// Returns the actions the player wants to take on `board` this turn, from the most preferred.
+    fn choose_actions(&mut self, board: &Board) -> Vec<Action>;
+
+    /// Plays a whole game on `board`, using `rng` for the stages.
+    fn play<R: Rng + ?Sized>(&mut self, board: Board, rules: GameRules, rng: &mut R) -> Game
+    where
+        Self: Sized,
+    {
+        let mut game = Game::new(board, rules);
+        while game.phase() == &Phase::Actions {
+            for action in self.choose_actions(game.board()) {
+                if let Err(GameError::NoActionPoints) = game.play(action) {
+                    break;
+                }
+            }
+            game.end_turn_with(rng).expect("the board is in a corrupted state");
+        }
+        game
+    }
+}
+
+/// Player that chooses uniformly at random among immunizing and closing or opening a building.
+#[derive(Debug, Clone)]
+pub struct RandomPlayer<R> {
+    rng: R,
+}
+
+impl<R: Rng> RandomPlayer<R> {
+    /// Constructor, using `rng` to choose actions.
+    pub fn new(rng: R) -> Self {
+        RandomPlayer { rng }
+    }
+}
+
+impl<R: Rng> Player for RandomPlayer<R> {
+    fn choose_actions(&mut self, board: &Board) -> Vec<Action> {
+        let mut options = vec![Action::Immunize(1)];
+        for building in board.buildings() {
+            if building.is_open() {
+                options.push(Action::Close(building.name().to_string()));
+            } else {
+                options.push(Action::Open(building.name().to_string()));
+            }
+        }
+        (0..options.len())
+            .map(|_| options[crate::random::below(&mut self.rng, options.len())].clone())
+            .collect()
+    }
+}
+
+/// Player that closes the open buildings, from the largest capacity to the smallest.
+///
+/// Buildings stay closed for the rest of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GreedyCloser;
+
+impl Player for GreedyCloser {
+    fn choose_actions(&mut self, board: &Board) -> Vec<Action> {
+        let mut open: Vec<_> = board.buildings().iter().filter(|building| building.is_open()).collect();
+        open.sort_by_key(|building| std::cmp::Reverse(building.capacity()));
+        open.into_iter().map(|building| Action::Close(building.name().to_string())).collect()
+    }
+}
+
+/// Player that immunizes as many healthy individuals as possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vaccinator;
+
+impl Player for Vaccinator {
+    fn choose_actions(&mut self, board: &Board) -> Vec<Action> {
+        let healthy = board.population().counting(Individual::Healthy)
+            + board.population().counting(Individual::HealthySuperspreader);
+        vec![Action::Immunize(1); healthy]
+    }
+}
+
+/// Results of a player over many games, see `evaluate`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerRecord {
+    /// Number of games played
+    pub games: usize,
+    /// Number of games won
+    pub wins: usize,
+    /// Average score, see `Board::score`
+    pub average_score: f64,
+}
+
+impl PlayerRecord {
+    /// Returns the fraction of games won, or zero if no game was played.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+}
+
+/// Plays one game on a clone of `board` for each seed and returns the record of `player`.
+///
+/// The same seeds give the same stages to every player that takes the same actions,
+/// so players can be compared on equal terms.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::game::{evaluate, GameRules, GreedyCloser, Vaccinator};
+/// let board = Board::default();
+/// let closer = evaluate(&mut GreedyCloser, &board, GameRules::default(), 0..10);
+/// let vaccinator = evaluate(&mut Vaccinator, &board, GameRules::default(), 0..10);
+/// assert_eq!(closer.games, 10);
+/// println!("Closer wins {:.0}%, vaccinator wins {:.0}%", 100. * closer.win_rate(), 100. * vaccinator.win_rate());
+/// ```
+pub fn evaluate<P, I>(player: &mut P, board: &Board, rules: GameRules, seeds: I) -> PlayerRecord
+where
+    P: Player,
+    I: IntoIterator<Item = u64>,
+{
+    let mut record = PlayerRecord { games: 0, wins: 0, average_score: 0. };
+    let mut total_score = 0;
+    for seed in seeds {
+        let mut rng = crate::random::Pcg32::new(seed);
+        let game = player.play(board.clone(), rules, &mut rng);
+        record.games += 1;
+        if game.outcome() == Some(Outcome::Won) {
+            record.wins += 1;
+        }
+        total_score += game.board().score().total;
+    }
+    if record.games > 0 {
+        record.average_score = total_score as f64 / record.games as f64;
+    }
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greedy_closer() {
+        let board = Board::default();
+        let actions = GreedyCloser.choose_actions(&board);
+        assert_eq!(actions.len(), 8);
+        assert_eq!(actions[0], Action::Close("Concert Hall".to_string()));
+        // With three action points, the whole town is closed by the third day
+        let game = GreedyCloser.play(board, GameRules::default(), &mut crate::tests::rng(1));
+        assert!(game.board().buildings().iter().all(|building| !building.is_open()));
+    }
+
+    #[test]
+    fn vaccinator() {
+        let rules = GameRules { action_points: 4, days: 2 };
+        let game = Vaccinator.play(Board::default(), rules, &mut crate::tests::rng(2));
+        assert_eq!(*game.board().recording().vaccines_used(), 4 * game.day());
+    }
+
+    #[test]
+    fn random_player() {
+        let mut player = RandomPlayer::new(crate::tests::rng(3));
+        let actions = player.choose_actions(&Board::default());
+        assert_eq!(actions.len(), 9);
+        let record = evaluate(&mut player, &Board::default(), GameRules::default(), 0..3);
+        assert_eq!(record.games, 3);
+        assert!(record.win_rate() <= 1.);
+    }
+
+    #[test]
+    fn evaluate_is_reproducible() {
+        let board = Board::default();
+        let record = evaluate(&mut GreedyCloser, &board, GameRules::default(), 0..5);
+        assert_eq!(record, evaluate(&mut GreedyCloser, &board, GameRules::default(), 0..5));
+    }
+}
@@ -0,0 +1,124 @@
+use super::{Game, GameRules};
+use crate::building::Spreading;
+use crate::errors::RulesetError;
+use crate::presets::Preset;
+use crate::Board;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Names of the official rule variants, see `Ruleset::official`.
+const OFFICIAL: [&str; 4] = ["standard", "beginner", "expert", "classroom"];
+
+/// Version of the rules of the game: the buildings, the spreading mode,
+/// the vaccines available and the rules of each turn.
+///
+/// Games and simulations started from the same ruleset follow the same rules,
+/// see `Game::from_ruleset` and `Simulation::from_ruleset`.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::game::{Game, Ruleset};
+/// let ruleset = Ruleset::official("expert").unwrap();
+/// let game = Game::from_ruleset(&ruleset);
+/// assert_eq!(game.rules().action_points, 2);
+/// assert_eq!(game.board().vaccines(), &Some(10));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ruleset {
+    /// Name of the variant
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub name: String,
+    /// Buildings of the board, and its initial population
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub buildings: Preset,
+    /// Spreading mode of all buildings
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub spreading: Spreading,
+    /// Number of vaccines available during the game, unlimited if `None`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub vaccines: Option<usize>,
+    /// Action points of each turn and number of rounds
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub rules: GameRules,
+}
+
+impl Ruleset {
+    /// Returns the official rule variant named `name`, ignoring case.
+    ///
+    /// The variants are:
+    /// - `standard`: the official board, with 20 vaccines and `Spreading::OneVeryNear`.
+    /// - `beginner`: the official board, with 30 vaccines and `Spreading::One`.
+    /// - `expert`: the official board, with 10 vaccines, `Spreading::OneNear`,
+    ///   2 action points per turn and 12 rounds.
+    /// - `classroom`: the classroom board, with 10 vaccines and `Spreading::OneVeryNear`.
+    ///
+    /// Unless stated otherwise, there are 3 action points per turn and 10 rounds, see `GameRules::default`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no official variant with that name.
+    pub fn official(name: &str) -> Result<Ruleset, RulesetError> {
+        let (buildings, spreading, vaccines, rules) = match name.to_ascii_lowercase().as_str() {
+            "standard" => (Preset::Official, Spreading::OneVeryNear, 20, GameRules::default()),
+            "beginner" => (Preset::Official, Spreading::One, 30, GameRules::default()),
+            "expert" => (Preset::Official, Spreading::OneNear, 10, GameRules { action_points: 2, days: 12 }),
+            "classroom" => (Preset::Classroom, Spreading::OneVeryNear, 10, GameRules::default()),
+            _ => return Err(RulesetError::Unknown(name.to_string())),
+        };
+        Ok(Ruleset { name: name.to_ascii_lowercase(), buildings, spreading, vaccines: Some(vaccines), rules })
+    }
+
+    /// Returns all official rule variants, see `official`.
+    pub fn officials() -> Vec<Ruleset> {
+        OFFICIAL.iter().map(|name| Ruleset::official(name).expect("official variants exist")).collect()
+    }
+
+    /// Returns the board at the start of a game with this ruleset.
+    pub fn board(&self) -> Board {
+        let mut board = self.buildings.board();
+        board.set_spreading(self.spreading);
+        board.set_vaccines(self.vaccines);
+        board
+    }
+}
+
+impl Default for Ruleset {
+    /// Returns the `standard` official variant.
+    fn default() -> Self {
+        Ruleset::official("standard").expect("the standard variant exists")
+    }
+}
+
+impl Game {
+    /// Starts a game with the board and rules of `ruleset`.
+    pub fn from_ruleset(ruleset: &Ruleset) -> Self {
+        Game::new(ruleset.board(), ruleset.rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn officials() {
+        let officials = Ruleset::officials();
+        assert_eq!(officials.len(), OFFICIAL.len());
+        assert_eq!(officials[0], Ruleset::default());
+        for ruleset in officials {
+            let board = ruleset.board();
+            assert_eq!(board.uniform_spreading(), Some(&ruleset.spreading));
+            assert_eq!(board.vaccines(), &ruleset.vaccines);
+            assert_eq!(Ruleset::official(&ruleset.name.to_uppercase()), Ok(ruleset));
+        }
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(Ruleset::official("house").unwrap_err().to_string(), "There is no official rule variant named \"house\"");
+    }
+}
@@ -1,7 +1,10 @@
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
 /// Individual in the game, it represents a person.
-#[derive(strum_macros::EnumIter, Hash, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(strum_macros::EnumIter, strum_macros::EnumCount, Hash, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Individual {
     /// Healthy vulnerable person
     Healthy,
@@ -15,25 +18,96 @@ pub enum Individual {
     Sick,
     /// Vaccinated, and therefore immune, person
     Immune,
+    /// Healthy vulnerable person, who is a superspreader once infected
+    HealthySuperspreader,
+    /// Infected superspreader in its first day
+    Superspreader1,
+    /// Infected superspreader in its second day
+    Superspreader2,
+    /// Infected superspreader in its third (and last) day
+    Superspreader3,
+    /// Vaccinated person, who is still vulnerable since the vaccine did not protect them
+    Vaccinated,
+    /// Infected person in the latent stage, who can not infect others yet
+    Exposed,
+    /// Asymptomatic person who recovered without getting sick, and is therefore immune
+    Recovered,
+    /// Person who got the first of two doses of a vaccine, and is only partially protected, see `Vaccine::TwoDoses`
+    PartiallyVaccinated,
 }
 
 impl Individual {
-    /// Return true if `other` can be infected by `self`.
+    /// Returns true if the individual is healthy and can be infected.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Individual::Healthy | Individual::HealthySuperspreader | Individual::Vaccinated | Individual::PartiallyVaccinated)
+    }
+
+    /// Returns true if the individual is infected and can infect others.
+    pub fn is_infectious(&self) -> bool {
+        matches!(self, 
+            Individual::Infected1 | Individual::Infected2 | Individual::Infected3 
+            | Individual::Superspreader1 | Individual::Superspreader2 | Individual::Superspreader3
+        )
+    }
+
+    /// Returns true if the individual is infected, infectious or not.
+    pub fn is_infected(&self) -> bool {
+        self.is_infectious() || self == &Individual::Exposed
+    }
+
+    /// Returns true if the individual is an infected superspreader.
+    pub fn is_superspreader(&self) -> bool {
+        matches!(self, Individual::Superspreader1 | Individual::Superspreader2 | Individual::Superspreader3)
+    }
+
+    /// Returns the state of the individual after being infected.
     ///
-    /// This is only possible if self is infected and other is healthy.
-    pub fn can_infect(&self, other: &Individual) -> bool {
+    /// Only healthy individuals change.
+    pub fn infected(self) -> Individual {
         match self {
-            Individual::Healthy | Individual::Sick | Individual::Immune => false,
-            Individual::Infected1 | Individual::Infected2 | Individual::Infected3 => matches!(other, Individual::Healthy),
+            Individual::Healthy | Individual::Vaccinated | Individual::PartiallyVaccinated => Individual::Infected1,
+            Individual::HealthySuperspreader => Individual::Superspreader1,
+            other => other,
         }
     }
 
+    /// Returns the state of the individual after one day of the disease.
+    ///
+    /// Only infected individuals change.
+    pub fn progressed(self) -> Individual {
+        match self {
+            Individual::Infected1 => Individual::Infected2,
+            Individual::Infected2 => Individual::Infected3,
+            Individual::Infected3 => Individual::Sick,
+            Individual::Superspreader1 => Individual::Superspreader2,
+            Individual::Superspreader2 => Individual::Superspreader3,
+            Individual::Superspreader3 => Individual::Sick,
+            other => other,
+        }
+    }
+
+    /// Return true if `other` can be infected by `self`.
+    ///
+    /// This is only possible if self is infectious and other is healthy.
+    /// In particular, exposed individuals can not infect.
+    pub fn can_infect(&self, other: &Individual) -> bool {
+        self.is_infectious() && other.is_healthy()
+    }
+
     /// Returns true if either can infect the other.
     pub fn interacts_with(&self, other: &Individual) -> bool {
         self.can_infect(other) || other.can_infect(self)
     }
 }
 
+/// Identifier of an individual, which does not change during the game.
+///
+/// Identifiers are only assigned if requested, see `Board::set_track_identities`.
+#[derive(Hash, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Id(pub usize);
+
 impl std::fmt::Display for Individual {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
@@ -50,6 +124,9 @@ mod tests {
 	#[test_case(Individual::Infected2, Individual::Healthy, true)]
 	#[test_case(Individual::Infected3, Individual::Healthy, true)]
 	#[test_case(Individual::Infected2, Individual::Immune, false)]
+	#[test_case(Individual::Superspreader1, Individual::HealthySuperspreader, true)]
+	#[test_case(Individual::Exposed, Individual::Healthy, false)]
+	#[test_case(Individual::Infected1, Individual::Exposed, false)]
 	fn can_infect(i: Individual, other: Individual, expected: bool) {
 		assert_eq!(i.can_infect(&other), expected);
 	}
@@ -74,5 +151,23 @@ mod tests {
         assert_eq!(i < other, expected);
     }
 
+    #[test_case(Individual::Healthy, Individual::Infected1)]
+    #[test_case(Individual::HealthySuperspreader, Individual::Superspreader1)]
+    #[test_case(Individual::Vaccinated, Individual::Infected1)]
+    #[test_case(Individual::PartiallyVaccinated, Individual::Infected1)]
+    #[test_case(Individual::Infected2, Individual::Infected2)]
+    #[test_case(Individual::Immune, Individual::Immune)]
+    fn infected(i: Individual, expected: Individual) {
+        assert_eq!(i.infected(), expected);
+    }
+
+    #[test_case(Individual::Healthy, Individual::Healthy)]
+    #[test_case(Individual::Infected3, Individual::Sick)]
+    #[test_case(Individual::Superspreader1, Individual::Superspreader2)]
+    #[test_case(Individual::Superspreader3, Individual::Sick)]
+    fn progressed(i: Individual, expected: Individual) {
+        assert_eq!(i.progressed(), expected);
+    }
+
     
 }
@@ -0,0 +1,410 @@
+//! Widgets for egui applications, so that desktop tools built with eframe can embed simulations with little glue.
+//!
+//! - `BoardBuilderEditor` is a form to edit a `BoardBuilder`.
+//! - `ReportChart` plots the average number of individuals of each variant per day of a `Report`.
+//! - `CountingChart` plots a `CountingTable`, for example the one of a board being played.
+//!
+//! # Examples
+//!
+//! ```
+//! # use virus_alarm::prelude::*;
+//! use virus_alarm::inspector::{BoardBuilderEditor, CountingChart};
+//! fn inspect(ui: &mut egui::Ui, board_builder: &mut BoardBuilder, board: &Board) {
+//!     if ui.add(BoardBuilderEditor::new(board_builder)).changed() {
+//!         println!("The board changed");
+//!     }
+//!     ui.add(CountingChart::new(board.counting_table()));
+//! }
+//! ```
+
+use crate::board::{BoardBuilder, Convalescence, Vaccine, VaccinationStrategy, Visiting};
+use crate::recording::{CountingTable, DayZero};
+use crate::simulation::Report;
+use crate::building::Spreading;
+use crate::Individual;
+use egui::{Color32, ComboBox, DragValue, Response, Ui, Widget};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+use strum::IntoEnumIterator;
+
+/// Largest number of columns or rows of a building in the editor
+const MAX_SIDE: usize = 20;
+
+/// Returns the color used to draw `individual`, see `render::color`.
+fn color(individual: &Individual) -> Color32 {
+    Color32::from_hex(crate::render::color(individual)).expect("colors are hexadecimal")
+}
+
+/// Shows a checkbox for `value` being `Some`, and its value if so.
+///
+/// Returns `true` if `value` changed.
+fn optional(ui: &mut Ui, label: &str, value: &mut Option<usize>) -> bool {
+    let mut enabled = value.is_some();
+    let mut changed = ui.checkbox(&mut enabled, label).changed();
+    match (enabled, value.as_mut()) {
+        (true, Some(inner)) => changed |= ui.add(DragValue::new(inner)).changed(),
+        (true, None) => *value = Some(1),
+        (false, _) => *value = None,
+    }
+    changed
+}
+
+/// Shows a slider for a percentage.
+fn percentage(ui: &mut Ui, value: &mut u8) -> bool {
+    ui.add(egui::Slider::new(value, 0..=100).suffix("%")).changed()
+}
+
+/// Shows a combo box to choose the variant of `value`, among `variants` with their names, with id `id`.
+///
+/// Choosing the variant named `selected` keeps `value`, so its parameters are kept.
+/// Returns `true` if `value` changed.
+fn choose<T>(ui: &mut Ui, id: &str, label: &str, value: &mut T, variants: Vec<(T, &str)>, selected: &str) -> bool {
+    let mut changed = false;
+    ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (variant, name) in variants {
+                if ui.selectable_label(name == selected, name).clicked() && name != selected {
+                    *value = variant;
+                    changed = true;
+                }
+            }
+        });
+    ui.label(label);
+    changed
+}
+
+fn spreading_name(spreading: &Spreading) -> &'static str {
+    match spreading {
+        Spreading::Everyone => "Everyone",
+        Spreading::One => "One",
+        Spreading::OneNear => "One near",
+        Spreading::OneVeryNear => "One very near",
+        Spreading::OneAdjacent => "One adjacent",
+        Spreading::Airborne(_) => "Airborne",
+        Spreading::OneWithin(_) => "One within",
+        Spreading::DistanceDecay(_) => "Distance decay",
+    }
+}
+
+fn spreading(ui: &mut Ui, value: &mut Spreading) -> bool {
+    let variants = [
+        Spreading::Everyone, Spreading::One, Spreading::OneNear, Spreading::OneVeryNear, Spreading::OneAdjacent,
+        Spreading::Airborne(50), Spreading::OneWithin(2), Spreading::DistanceDecay(50),
+    ];
+    let selected = spreading_name(value);
+    let variants = variants.iter().map(|variant| (*variant, spreading_name(variant))).collect();
+    let mut changed = choose(ui, "spreading", "Spreading", value, variants, selected);
+    match value {
+        Spreading::Airborne(probability) | Spreading::DistanceDecay(probability) => changed |= percentage(ui, probability),
+        Spreading::OneWithin(distance) => changed |= ui.add(DragValue::new(distance).range(1..=MAX_SIDE as u8)).changed(),
+        _ => (),
+    }
+    changed
+}
+
+fn visiting(ui: &mut Ui, value: &mut Visiting, buildings: usize) -> bool {
+    let selected = match value {
+        Visiting::InOrder => "In order",
+        Visiting::Capacity { .. } => "Capacity",
+        Visiting::Weighted { .. } => "Weighted",
+    };
+    let variants = vec![
+        (Visiting::InOrder, "In order"),
+        (Visiting::Capacity { stay_home: 0 }, "Capacity"),
+        (Visiting::Weighted { weights: vec![1; buildings], stay_home: 0 }, "Weighted"),
+    ];
+    let mut changed = choose(ui, "visiting", "Visiting", value, variants, selected);
+    match value {
+        Visiting::InOrder => (),
+        Visiting::Capacity { stay_home } => changed |= percentage(ui, stay_home),
+        Visiting::Weighted { weights, stay_home } => {
+            changed |= percentage(ui, stay_home);
+            for weight in weights.iter_mut() {
+                changed |= ui.add(DragValue::new(weight)).changed();
+            }
+        },
+    }
+    changed
+}
+
+fn vaccine(ui: &mut Ui, value: &mut Vaccine) -> bool {
+    let selected = match value {
+        Vaccine::Perfect => "Perfect",
+        Vaccine::Imperfect { .. } => "Imperfect",
+        Vaccine::TwoDoses { .. } => "Two doses",
+    };
+    let variants = vec![
+        (Vaccine::Perfect, "Perfect"),
+        (Vaccine::Imperfect { efficacy: 90 }, "Imperfect"),
+        (Vaccine::TwoDoses { protection: 50, delay: 3 }, "Two doses"),
+    ];
+    let mut changed = choose(ui, "vaccine", "Vaccine", value, variants, selected);
+    match value {
+        Vaccine::Perfect => (),
+        Vaccine::Imperfect { efficacy } => changed |= percentage(ui, efficacy),
+        Vaccine::TwoDoses { protection, delay } => {
+            changed |= percentage(ui, protection);
+            changed |= ui.add(DragValue::new(delay).suffix(" days")).changed();
+        },
+    }
+    changed
+}
+
+fn vaccination_strategy(ui: &mut Ui, value: &mut VaccinationStrategy) -> bool {
+    let selected = match value {
+        VaccinationStrategy::FirstHealthy => "First healthy",
+        VaccinationStrategy::Random => "Random",
+        VaccinationStrategy::ByIdentity(_) => "By identity",
+    };
+    // Identities are not known before the board is built, so they can not be chosen here
+    let variants = vec![(VaccinationStrategy::FirstHealthy, "First healthy"), (VaccinationStrategy::Random, "Random")];
+    choose(ui, "vaccination_strategy", "Vaccination strategy", value, variants, selected)
+}
+
+fn convalescence(ui: &mut Ui, value: &mut Convalescence) -> bool {
+    let selected = match value {
+        Convalescence::Forever => "Forever",
+        Convalescence::Immune { .. } => "Immune",
+        Convalescence::Healthy { .. } => "Healthy",
+    };
+    let variants = vec![
+        (Convalescence::Forever, "Forever"),
+        (Convalescence::Immune { days: 7 }, "Immune"),
+        (Convalescence::Healthy { days: 7 }, "Healthy"),
+    ];
+    let mut changed = choose(ui, "convalescence", "Convalescence", value, variants, selected);
+    if let Convalescence::Immune { days } | Convalescence::Healthy { days } = value {
+        changed |= ui.add(DragValue::new(days).suffix(" days")).changed();
+    }
+    changed
+}
+
+fn day_zero(ui: &mut Ui, value: &mut DayZero) -> bool {
+    let names = [(DayZero::AfterActions, "After actions"), (DayZero::BeforeActions, "Before actions"), (DayZero::Excluded, "Excluded")];
+    let selected = names.iter().find(|(day_zero, _)| day_zero == value).map(|(_, name)| *name).unwrap_or_default();
+    choose(ui, "day_zero", "Day zero", value, names.to_vec(), selected)
+}
+
+/// Form to edit a `BoardBuilder`, with one input per field.
+///
+/// The response is marked as changed if any field changed.
+pub struct BoardBuilderEditor<'a> {
+    board_builder: &'a mut BoardBuilder,
+}
+
+impl<'a> BoardBuilderEditor<'a> {
+    /// Editor of `board_builder`.
+    pub fn new(board_builder: &'a mut BoardBuilder) -> Self {
+        BoardBuilderEditor { board_builder }
+    }
+}
+
+impl Widget for BoardBuilderEditor<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let board_builder = self.board_builder;
+        let mut changed = false;
+        let mut response = ui.vertical(|ui| {
+            ui.heading("Population");
+            egui::Grid::new("population").num_columns(2).show(ui, |ui| {
+                let counts = [
+                    ("Healthy", &mut board_builder.healthy),
+                    ("Infected1", &mut board_builder.infected1),
+                    ("Infected2", &mut board_builder.infected2),
+                    ("Infected3", &mut board_builder.infected3),
+                    ("Sick", &mut board_builder.sick),
+                    ("Immune", &mut board_builder.immune),
+                    ("Superspreaders", &mut board_builder.superspreaders),
+                ];
+                for (label, count) in counts {
+                    ui.label(label);
+                    changed |= ui.add(DragValue::new(count)).changed();
+                    ui.end_row();
+                }
+            });
+
+            ui.heading("Buildings");
+            let mut removed = None;
+            egui::Grid::new("buildings").num_columns(3).show(ui, |ui| {
+                for (index, (columns, rows)) in board_builder.buildings.iter_mut().enumerate() {
+                    changed |= ui.add(DragValue::new(columns).range(1..=MAX_SIDE).suffix(" columns")).changed();
+                    changed |= ui.add(DragValue::new(rows).range(1..=MAX_SIDE).suffix(" rows")).changed();
+                    if ui.button("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(index) = removed {
+                board_builder.buildings.remove(index);
+                board_builder.visiting.building_removed(index);
+                changed = true;
+            }
+            if ui.button("Add building").clicked() {
+                board_builder.visiting.building_added(board_builder.buildings.len());
+                board_builder.buildings.push((2, 2));
+                changed = true;
+            }
+
+            ui.heading("Rules");
+            let buildings = board_builder.buildings.len();
+            changed |= ui.horizontal(|ui| spreading(ui, &mut board_builder.spreading)).inner;
+            changed |= ui.horizontal(|ui| visiting(ui, &mut board_builder.visiting, buildings)).inner;
+            changed |= ui.horizontal(|ui| vaccine(ui, &mut board_builder.vaccine)).inner;
+            changed |= ui.horizontal(|ui| vaccination_strategy(ui, &mut board_builder.vaccination_strategy)).inner;
+            changed |= ui.horizontal(|ui| convalescence(ui, &mut board_builder.convalescence)).inner;
+            changed |= ui.horizontal(|ui| day_zero(ui, &mut board_builder.day_zero)).inner;
+            changed |= ui.horizontal(|ui| {
+                ui.label("Asymptomatic");
+                percentage(ui, &mut board_builder.asymptomatic)
+            }).inner;
+            changed |= ui.horizontal(|ui| optional(ui, "Immunity duration", &mut board_builder.immunity_duration)).inner;
+            changed |= ui.horizontal(|ui| optional(ui, "Latency", &mut board_builder.latency)).inner;
+            changed |= ui.horizontal(|ui| optional(ui, "Vaccines", &mut board_builder.vaccines)).inner;
+            changed |= ui.horizontal(|ui| {
+                ui.label("Vaccination rate");
+                ui.add(DragValue::new(&mut board_builder.vaccination_rate).suffix(" per day")).changed()
+            }).inner;
+            changed |= ui.checkbox(&mut board_builder.collect_events, "Collect events").changed();
+            changed |= ui.checkbox(&mut board_builder.track_identities, "Track identities").changed();
+        }).response;
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+/// Plots `series` of each individual variant per day, skipping variants that are always zero.
+fn plot<F>(ui: &mut Ui, id: &str, individuals: &[Individual], mut series: F) -> Response
+where
+    F: FnMut(&Individual) -> Vec<f64>,
+{
+    Plot::new(id)
+        .legend(Legend::default())
+        .x_axis_label("Day")
+        .show(ui, |plot_ui| {
+            for individual in individuals {
+                let values = series(individual);
+                if values.iter().all(|&value| value == 0.) {
+                    continue;
+                }
+                let points: Vec<[f64; 2]> = values.into_iter().enumerate().map(|(day, value)| [day as f64, value]).collect();
+                plot_ui.line(Line::new(PlotPoints::new(points)).name(individual.to_string()).color(color(individual)));
+            }
+        })
+        .response
+}
+
+/// Chart of the average number of individuals of each variant per day of a report,
+/// see `Report::individual_average`.
+pub struct ReportChart<'a> {
+    report: &'a Report,
+    individuals: Vec<Individual>,
+    id: String,
+}
+
+impl<'a> ReportChart<'a> {
+    /// Chart of `report`, with all individual variants.
+    pub fn new(report: &'a Report) -> Self {
+        ReportChart { report, individuals: Individual::iter().collect(), id: String::from("report_chart") }
+    }
+
+    /// Shows only `individuals`.
+    pub fn individuals(mut self, individuals: &[Individual]) -> Self {
+        self.individuals = individuals.to_vec();
+        self
+    }
+
+    /// Changes the id of the chart, needed if there are many charts in the same window.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+}
+
+impl Widget for ReportChart<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let report = self.report;
+        plot(ui, &self.id, &self.individuals, |individual| {
+            report.individual_average(individual).iter().map(|variance| variance.mean()).collect()
+        })
+    }
+}
+
+/// Chart of the number of individuals of each variant per day of a counting table,
+/// for example `Board::counting_table` while the board is played.
+pub struct CountingChart<'a> {
+    counting_table: &'a CountingTable,
+    individuals: Vec<Individual>,
+    id: String,
+}
+
+impl<'a> CountingChart<'a> {
+    /// Chart of `counting_table`, with all individual variants.
+    pub fn new(counting_table: &'a CountingTable) -> Self {
+        CountingChart { counting_table, individuals: Individual::iter().collect(), id: String::from("counting_chart") }
+    }
+
+    /// Shows only `individuals`.
+    pub fn individuals(mut self, individuals: &[Individual]) -> Self {
+        self.individuals = individuals.to_vec();
+        self
+    }
+
+    /// Changes the id of the chart, needed if there are many charts in the same window.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+}
+
+impl Widget for CountingChart<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let counting_table = self.counting_table;
+        plot(ui, &self.id, &self.individuals, |individual| {
+            counting_table.inner().get(individual)
+                .map(|counts| counts.iter().map(|&count| count as f64).collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Board;
+
+    /// Runs `add_contents` in a frame of a headless egui context.
+    fn run(mut add_contents: impl FnMut(&mut Ui)) {
+        let context = egui::Context::default();
+        let _ = context.run(egui::RawInput::default(), |context| {
+            egui::CentralPanel::default().show(context, |ui| add_contents(ui));
+        });
+    }
+
+    #[test]
+    fn board_builder_editor() {
+        let mut board_builder = BoardBuilder { healthy: 3, buildings: vec![(2, 2)], vaccines: Some(4), ..Default::default() };
+        let expected = board_builder.clone();
+        let mut changed = true;
+        run(|ui| changed = ui.add(BoardBuilderEditor::new(&mut board_builder)).changed());
+        assert!(!changed);
+        assert_eq!(board_builder, expected);
+    }
+
+    #[test]
+    fn charts() {
+        let mut board = Board::default();
+        board.advance_many(3);
+        let report = crate::SimulationBuilder {
+            report_plan: crate::simulation::ReportPlan { num_simulations: 2, days: 3, ..Default::default() },
+            board_builder: BoardBuilder { healthy: 3, infected1: 1, buildings: vec![(2, 2)], ..Default::default() },
+            ..Default::default()
+        }.build().run();
+        run(|ui| {
+            ui.add(CountingChart::new(board.counting_table()).individuals(&[Individual::Healthy]));
+            ui.add(ReportChart::new(&report).id("other"));
+        });
+        assert_eq!(color(&Individual::Healthy), Color32::from_rgb(0x2c, 0xa0, 0x2c));
+    }
+}
@@ -3,13 +3,42 @@
 //! This crate allows to simulate and study the dynamics defined in the
 //! [Virus Alert](https://ist.ac.at/en/education/ist-for-kids/virus-alert/) educational board game.
 //!
+//! # Stability
+//!
+//! The core types `Individual`, `Building`, `Population`, `Board`, `Recording`, 
+//! `Simulation` and `Report`, together with their builders, are stable since version 0.1 
+//! and follow semantic versioning. So does `Action`, the common representation of actions on a board.
+//!
+//! Newer experimental subsystems are behind the `unstable` feature and 
+//! may change in any release. Currently, these are:
+//! - Scheduled interventions (`simulation::policy`).
+//! - Custom spreading rules (`building::SpreadingRule`).
+//! - Failure injection (`simulation::Failure`).
+//! - Scheduled runs for long-running processes (`simulation::Schedule`).
+//! - Alternative simulation engines (`simulation::Engine`).
+//! - Analyses built on simulations: strategy experiments (`simulation::StrategyExperiment`), 
+//!   vaccination searches (`simulation::VaccinationSearch`), sensitivity analyses (`simulation::SensitivityAnalysis`), 
+//!   calibration (`simulation::Calibration`), deterministic bounds (`simulation::Bounds`) 
+//!   and comparison of reports (`Report::diff` and `Simulation::run_paired`).
+//!
+//! The rest of the public API, such as the `game`, `render`, `score`, `presets` and `random` modules, 
+//! follows semantic versioning as the core types do.
+//!
+//! # Features
+//!
+//! The `analysis` feature, enabled by default, gives the `simulation` module and 
+//! the conversions of counting tables to arrays. The `serde` feature, also enabled by default,
+//! gives serialization of the types of the crate and csv output. 
+//! Frontends that only play the game can disable both for a smaller binary.
 
 pub use building::{Building, BuildingBuilder};
 pub use individual::Individual;
 pub use population::Population;
 pub use board::Board;
 pub use recording::Recording;
+#[cfg(feature = "analysis")]
 pub use simulation::{Simulation, SimulationBuilder};
+pub use action::Action;
 
 /// Individuals that can be in different states of health.
 pub mod individual;
@@ -22,21 +51,52 @@ pub mod board;
 /// Resources used to keep track of the state of the game.
 pub mod recording;
 /// Simulation setup and results.
+#[cfg(feature = "analysis")]
 pub mod simulation;
+/// Actions that can be taken on a board.
+pub mod action;
+/// Sources of randomness that give the same games on every platform.
+pub mod random;
+/// Text and SVG rendering of buildings and boards.
+pub mod render;
+/// Scoring of games.
+pub mod score;
+/// Ready-made boards, retrievable by name.
+pub mod presets;
+/// Turn-based game over a board.
+pub mod game;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "egui")]
+pub mod inspector;
 
 /// All you should need to play the game. 
 pub mod prelude {
 	pub use crate::{
-        simulation::Report,
-        simulation::report::ReportPlan, 
         Board, 
         Individual, 
         Population, 
         board::BoardBuilder, 
+        board::Visiting,
+        board::Vaccine,
+        board::VaccinationStrategy,
+        board::Convalescence,
+        building::Spreading,
+        Action,
+    };
+    #[cfg(feature = "analysis")]
+    pub use crate::{
+        simulation::Report,
+        simulation::report::ReportPlan, 
         Simulation, 
         SimulationBuilder,
-        building::Spreading,
     };
+    #[cfg(all(feature = "unstable", feature = "analysis"))]
+    pub use crate::simulation::policy::Intervention;
 }
 
 /// All errors in this crate.
@@ -49,6 +109,8 @@ pub mod errors {
         Full,
         #[error("Sick individuals are not allowed in the buildings")]
         Sick,
+        #[error("capacity fractions must be between zero and one")]
+        InvalidFraction,
     }
 
     #[derive(Error, Debug, PartialEq, Eq)]
@@ -57,6 +119,134 @@ pub mod errors {
         NoHealthyLeft,
         #[error("There are no more immune individuals in the population")]
         NoImmuneLeft,
+        #[error("There are no more vaccines available")]
+        NoVaccinesLeft,
+        #[error("There are no {0} individuals in the population")]
+        NotPresent(crate::Individual),
+        #[error("The individual with index {0} is not healthy")]
+        NotHealthy(usize),
+        #[error("There is no building named {0}")]
+        UnknownBuilding(String),
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum BoardError {
+        #[error("Individuals are not conserved: {expected} recorded but {found} found in the board")]
+        NotConserved { expected: usize, found: usize },
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum GameError {
+        #[error("The game is over")]
+        Over,
+        #[error("There are no action points left in this turn")]
+        NoActionPoints,
+        #[error("The action {0:?} is not legal now")]
+        Illegal(crate::Action),
+        #[error(transparent)]
+        Action(#[from] ActionError),
+        #[error(transparent)]
+        Board(#[from] BoardError),
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum SimulationError {
+        #[error("There are {vaccines} vaccines but only {healthy} healthy individuals")]
+        TooManyVaccines { vaccines: usize, healthy: usize },
+        #[error("{scheduled} vaccines are scheduled but only {vaccines} are available")]
+        VaccinesExceeded { scheduled: usize, vaccines: usize },
+        #[error("The intervention of day {day} names {name}, which is not a building of the board")]
+        UnknownBuilding { day: usize, name: String },
+        #[error("Interventions are only applied by the MonteCarlo engine")]
+        InterventionsWithEngine,
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum AnimationError {
+        #[error("The animation could not be written: {0}")]
+        Encoding(String),
+        #[error("The frames of the animation are {width}x{height} pixels but the board needs {found_width}x{found_height}")]
+        SizeChanged { width: u16, height: u16, found_width: u16, found_height: u16 },
+        #[error("The board needs frames of {width}x{height} pixels, which is too large for an animation")]
+        TooLarge { width: usize, height: usize },
+        #[error(transparent)]
+        Simulation(#[from] SimulationError),
+        #[error(transparent)]
+        Board(#[from] BoardError),
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum ReportError {
+        #[error("Reports have {expected} and {found} realizations")]
+        RealizationsMismatch { expected: usize, found: usize },
+        #[error("Realization {realization} does not continue from the last day of the report")]
+        Discontinuous { realization: usize },
+        #[error("Streamed reports have no counting tables to extend")]
+        Streaming,
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum InvariantError {
+        #[error("Individuals are not conserved: {expected} on day zero but {found} on day {day}")]
+        NotConserved { day: usize, expected: usize, found: usize },
+        #[error("Immune individuals decreased from {before} to {after} on day {day}")]
+        ImmuneDecreased { day: usize, before: usize, after: usize },
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum CountingTableError {
+        #[error("The counting table does not continue from the last day")]
+        Discontinuous,
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum PresetError {
+        #[error("There is no preset named {0:?}, the presets are official, classroom and city")]
+        Unknown(String),
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum RulesetError {
+        #[error("There is no official rule variant named {0:?}")]
+        Unknown(String),
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum StoreError {
+        #[error("The experiment store failed: {0}")]
+        Sqlite(String),
+        #[error("The configuration could not be serialized: {0}")]
+        Serialization(String),
+    }
+
+    #[derive(Error, Debug, PartialEq, Eq)]
+    pub enum ConfigError {
+        #[error("The configuration could not be parsed: {0}")]
+        Parse(String),
+        #[error("The configuration has {} invalid fields", .0.len())]
+        Invalid(Vec<Diagnostic>),
+        #[error("The configuration file could not be read: {0}")]
+        Io(String),
+        #[error("There is no supported format for configuration files with extension {0:?}")]
+        UnsupportedFormat(String),
+    }
+
+    /// Invalid field of a configuration, see `SimulationBuilder::diagnostics`.
+    #[derive(Error, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[error("{path} {message}")]
+    pub struct Diagnostic {
+        /// Path of the field, for example `board_builder.buildings[2].rows`
+        pub path: String,
+        /// What is wrong with the field, for example `must be at least 1`
+        pub message: String,
+    }
+
+    impl Diagnostic {
+        /// Constructor
+        pub fn new<P: Into<String>, M: Into<String>>(path: P, message: M) -> Self {
+            Diagnostic { path: path.into(), message: message.into() }
+        }
     }
 }
 
@@ -65,8 +255,7 @@ mod tests {
     /// Construct a deterministic RNG with the given seed
     pub fn rng(seed: u64) -> impl rand::RngCore {
         // For tests, we want a statistically good, fast, reproducible RNG.
-        // PCG32 will do fine, and will be easy to embed if we ever need to.
-        const INC: u64 = 11634580027462260723;
-        rand_pcg::Pcg32::new(seed, INC)
+        // PCG32 does fine, and the embedded one does not depend on the version of `rand`.
+        crate::random::Pcg32::new(seed)
     }
 }
@@ -1,16 +1,148 @@
 use std::collections::HashMap;
-use crate::Individual;
-use rand::seq::SliceRandom;
-use strum::IntoEnumIterator;
+use crate::{Individual, individual::Id};
+use strum::{EnumCount, IntoEnumIterator};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 
 /// Population of the game
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Two populations are equal if they have the same individuals in the same order, 
+/// regardless of how many individuals have been drawn from them as an iterator.
+#[derive(Debug, Eq)]
 pub struct Population {
     population: Vec<Individual>,
-    counter: usize
+    counter: usize,
+    /// Number of individuals of each variant, indexed by the variant
+    counts: [usize; Individual::COUNT],
+}
+
+/// Row of a population file, see `Population::from_csv`.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PopulationRow {
+	/// State of the individual, for example `Healthy` or `Infected1`
+	pub state: Individual,
+	/// Tags of the individual separated by semicolons, for example `teacher;front row`
+	///
+	/// Tags are only labels for whoever prepares the file, the game does not use them.
+	#[serde(default)]
+	pub tags: String,
+}
+
+/// Share of each individual variant in a population of a given size, see `BoardBuilder::composition`.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::population::Composition;
+/// let composition = Composition::new(200)
+///     .with(Individual::Healthy, 95)
+///     .with(Individual::Infected1, 3)
+///     .with(Individual::Immune, 2);
+/// assert_eq!(composition.counts(), vec![(Individual::Healthy, 190), (Individual::Infected1, 6), (Individual::Immune, 4)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Composition {
+	/// Number of individuals
+	pub size: usize,
+	/// Percentage of each individual variant, which should add up to 100
+	pub percentages: Vec<(Individual, u8)>,
+}
+
+impl Composition {
+	/// Returns a composition of `size` individuals without any variant.
+	pub fn new(size: usize) -> Self {
+		Composition { size, percentages: Vec::new() }
+	}
+
+	/// Adds a variant with the given percentage.
+	pub fn with(mut self, individual: Individual, percentage: u8) -> Self {
+		self.percentages.push((individual, percentage));
+		self
+	}
+
+	/// Returns the number of individuals of each variant, in the order of `percentages`.
+	///
+	/// The numbers add up to `size`, and are the closest to the percentages:
+	/// the individuals left after rounding down are given to the variants with the largest remainders,
+	/// the first ones in case of ties. Percentages are normalized if they do not add up to 100.
+	///
+	/// # Panics
+	///
+	/// If `size` is positive and no percentage is.
+	pub fn counts(&self) -> Vec<(Individual, usize)> {
+		let total: usize = self.percentages.iter().map(|&(_, percentage)| usize::from(percentage)).sum();
+		if self.size == 0 {
+			return self.percentages.iter().map(|&(individual, _)| (individual, 0)).collect();
+		}
+		assert!(total > 0, "there is no positive percentage in the composition");
+		let mut counts: Vec<(Individual, usize)> = Vec::with_capacity(self.percentages.len());
+		let mut remainders: Vec<(usize, usize)> = Vec::with_capacity(self.percentages.len());
+		for (index, &(individual, percentage)) in self.percentages.iter().enumerate() {
+			let exact = self.size * usize::from(percentage);
+			counts.push((individual, exact / total));
+			remainders.push((exact % total, index));
+		}
+		let left = self.size - counts.iter().map(|&(_, count)| count).sum::<usize>();
+		remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+		for &(_, index) in remainders.iter().take(left) {
+			counts[index].1 += 1;
+		}
+		counts
+	}
+
+	/// Returns the population with the numbers of individuals given by `counts`, 
+	/// with variants in the order of `percentages`.
+	pub fn population(&self) -> Population {
+		let population: Vec<Individual> = self.counts().into_iter()
+			.flat_map(|(individual, count)| vec![individual; count])
+			.collect();
+		Population::from(population)
+	}
+}
+
+/// Returns the number of individuals of each variant in `population`.
+fn count(population: &[Individual]) -> [usize; Individual::COUNT] {
+	let mut counts = [0; Individual::COUNT];
+	for &individual in population {
+		counts[individual as usize] += 1;
+	}
+	counts
 }
 
 impl Population {
+	/// Returns a population of `size` individuals, each of them of a variant chosen independently at random
+	/// with probability proportional to its weight in `distribution`.
+	///
+	/// The numbers of individuals of each variant are random, see `Composition` for fixed numbers.
+	///
+	/// # Panics
+	///
+	/// If `size` is positive and no weight is.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let distribution = [(Individual::Healthy, 0.95), (Individual::Infected1, 0.03), (Individual::Immune, 0.02)];
+	/// let population = Population::random(1000, &distribution, &mut rand::thread_rng());
+	/// assert_eq!(population.len(), 1000);
+	/// assert_eq!(population.counting(Individual::Sick), 0);
+	/// ```
+	pub fn random<R: rand::RngCore + ?Sized>(size: usize, distribution: &[(Individual, f64)], rng: &mut R) -> Population {
+		if size == 0 {
+			return Population::from(Vec::new());
+		}
+		let weights: Vec<f64> = distribution.iter().map(|&(_, weight)| weight).collect();
+		let population: Vec<Individual> = (0..size)
+			.map(|_| distribution[crate::random::weighted(&weights, rng)].0)
+			.collect();
+		Population::from(population)
+	}
+
 	/// Immunize one person in the population. 
 	/// 
 	/// # Errors
@@ -30,12 +162,91 @@ impl Population {
 		for i in self.population.iter_mut() {
 		    if i == &mut Individual::Healthy {
 		    	*i = Individual::Immune;
+		    	self.counts[Individual::Healthy as usize] -= 1;
+		    	self.counts[Individual::Immune as usize] += 1;
 		    	return Ok(self)
 		    }
 		}
 	    Err(crate::errors::ActionError::NoHealthyLeft)
 	}
 
+	/// Vaccinate one person in the population, without protecting them. 
+	///
+	/// The person becomes `Vaccinated`, which is as vulnerable as a healthy person.
+	/// 
+	/// # Errors
+	///
+	/// If there is no healthy individual to vaccinate.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut population = Population::default();
+	/// population.vaccinate();
+	/// assert_eq!(population.counting(Individual::Vaccinated), 1);
+	/// ```
+	pub fn vaccinate(&mut self) -> Result<&mut Self, crate::errors::ActionError> {
+		for i in self.population.iter_mut() {
+		    if i == &mut Individual::Healthy {
+		    	*i = Individual::Vaccinated;
+		    	self.counts[Individual::Healthy as usize] -= 1;
+		    	self.counts[Individual::Vaccinated as usize] += 1;
+		    	return Ok(self)
+		    }
+		}
+	    Err(crate::errors::ActionError::NoHealthyLeft)
+	}
+
+	/// Infects the individual with index `index`, which becomes `stage`.
+	///
+	/// # Errors
+	///
+	/// If the individual is not healthy.
+	///
+	/// # Panics
+	///
+	/// If `index` is out of bounds or `stage` is not an infected variant.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut population = Population::default();
+	/// population.infect_at(0, Individual::Infected2).unwrap();
+	/// assert_eq!(population.counting(Individual::Infected2), 1);
+	/// assert!(population.infect_at(0, Individual::Infected1).is_err());
+	/// ```
+	pub fn infect_at(&mut self, index: usize, stage: Individual) -> Result<&mut Self, crate::errors::ActionError> {
+		assert!(stage.is_infected(), "{} is not an infected variant", stage);
+		if !self.population[index].is_healthy() {
+			return Err(crate::errors::ActionError::NotHealthy(index));
+		}
+		self.set(index, stage);
+		Ok(self)
+	}
+
+	/// Changes the variant of the individual with index `index`.
+	pub(crate) fn set(&mut self, index: usize, individual: Individual) {
+		self.counts[self.population[index] as usize] -= 1;
+		self.counts[individual as usize] += 1;
+		self.population[index] = individual;
+	}
+
+	/// Changes the variant of up to `num` individuals, from `from` to `to`.
+	///
+	/// Returns the number of individuals changed.
+	pub(crate) fn replace(&mut self, from: Individual, to: Individual, num: usize) -> usize {
+		let mut changed = 0;
+		for i in self.population.iter_mut().filter(|i| **i == from).take(num) {
+			*i = to;
+			changed += 1;
+		}
+		self.counts[from as usize] -= changed;
+		self.counts[to as usize] += changed;
+		changed
+	}
+
 	/// Reverse one person from immune to healthy in the population. 
 	/// 
 	/// # Errors
@@ -57,22 +268,162 @@ impl Population {
 		for i in self.population.iter_mut() {
 		    if i == &mut Individual::Immune {
 		    	*i = Individual::Healthy;
+		    	self.counts[Individual::Immune as usize] -= 1;
+		    	self.counts[Individual::Healthy as usize] += 1;
 		    	return Ok(self)
 		    }
 		}
 	    Err(crate::errors::ActionError::NoImmuneLeft)
 	}
 
+	/// Adds `individual` at the end of the population.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut population = Population::default();
+	/// population.push(Individual::Infected1);
+	/// assert_eq!(population.len(), 101);
+	/// assert_eq!(population.counting(Individual::Infected1), 3);
+	/// ```
+	pub fn push(&mut self, individual: Individual) -> &mut Self {
+		self.population.push(individual);
+		self.counts[individual as usize] += 1;
+		self
+	}
+
+	/// Removes one individual of variant `individual` from the population.
+	///
+	/// # Errors
+	///
+	/// If there is no individual of that variant.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut population = Population::default();
+	/// population.remove(Individual::Infected1).unwrap();
+	/// assert_eq!(population.len(), 99);
+	/// assert!(population.remove(Individual::Sick).is_err());
+	/// ```
+	pub fn remove(&mut self, individual: Individual) -> Result<&mut Self, crate::errors::ActionError> {
+		self.remove_index(individual)?;
+		Ok(self)
+	}
+
+	/// Removes one individual of variant `individual` from the population,
+	/// returning the index it had.
+	pub(crate) fn remove_index(&mut self, individual: Individual) -> Result<usize, crate::errors::ActionError> {
+		let index = self.population.iter()
+			.position(|i| *i == individual)
+			.ok_or(crate::errors::ActionError::NotPresent(individual))?;
+		self.population.remove(index);
+		self.counts[individual as usize] -= 1;
+		Ok(index)
+	}
+
 	/// change current population for `new_population`.
 	///
+	/// To change the size of the population, use `push` and `remove`.
+	///
 	/// # Panics
 	///
 	/// If the size of the new population does not coincide with the original one.
 	pub fn update(&mut self, new_population: Vec<Individual>) {
 		assert_eq!(self.len(), new_population.len());
+		self.counts = count(&new_population);
 		self.population = new_population;
 	}
 
+	/// Reads the rows of a population file in CSV format.
+	///
+	/// The header is `state,tags`, followed by one row per individual, see `PopulationRow`. 
+	/// The `tags` column is optional, and so are the tags of each individual.
+	///
+	/// # Errors
+	///
+	/// If reading fails, or a row does not have a valid state.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let file = "state,tags\nHealthy,teacher\nInfected1,\nHealthy,front row;glasses\n";
+	/// let rows = Population::read_csv(file.as_bytes()).unwrap();
+	/// assert_eq!(rows[1].state, Individual::Infected1);
+	/// assert_eq!(rows[2].tags, "front row;glasses");
+	/// ```
+	#[cfg(feature = "serde")]
+	pub fn read_csv<R: std::io::Read>(reader: R) -> csv::Result<Vec<PopulationRow>> {
+		csv::ReaderBuilder::new()
+			.flexible(true)
+			.trim(csv::Trim::All)
+			.from_reader(reader)
+			.deserialize()
+			.collect()
+	}
+
+	/// Returns the population of a population file in CSV format, with individuals in the order of the rows.
+	///
+	/// See `read_csv` for the format. Tags are ignored.
+	///
+	/// # Errors
+	///
+	/// If reading fails, or a row does not have a valid state.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let file = "state\nHealthy\nHealthy\nSick\n";
+	/// let population = Population::from_csv(file.as_bytes()).unwrap();
+	/// assert_eq!(population.len(), 3);
+	/// assert_eq!(population.counting(Individual::Sick), 1);
+	/// ```
+	#[cfg(feature = "serde")]
+	pub fn from_csv<R: std::io::Read>(reader: R) -> csv::Result<Population> {
+		let rows = Population::read_csv(reader)?;
+		Ok(Population::from(rows.into_iter().map(|row| row.state).collect::<Vec<Individual>>()))
+	}
+
+	/// Returns the population of the population file at `path`, see `from_csv`.
+	///
+	/// # Errors
+	///
+	/// If the file can not be read, or a row does not have a valid state.
+	#[cfg(feature = "serde")]
+	pub fn from_csv_path<P: AsRef<std::path::Path>>(path: P) -> csv::Result<Population> {
+		Population::from_csv(std::fs::File::open(path)?)
+	}
+
+	/// Writes the population on the writer as a population file, with one row per individual and no tags.
+	///
+	/// See `read_csv` for the format.
+	///
+	/// # Remarks
+	///
+	/// Recall that a writer needs to be flushed to show in the output stream.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let population = Population::from(vec![Individual::Healthy, Individual::Immune]);
+	/// let bytes = population.to_csv(Vec::new()).unwrap().into_inner().unwrap();
+	/// assert_eq!(String::from_utf8(bytes).unwrap(), "state,tags\nHealthy,\nImmune,\n");
+	/// assert_eq!(Population::from_csv(population.to_csv(Vec::new()).unwrap().into_inner().unwrap().as_slice()).unwrap(), population);
+	/// ```
+	#[cfg(feature = "serde")]
+	pub fn to_csv<W: std::io::Write>(&self, writer: W) -> csv::Result<csv::Writer<W>> {
+		let mut writer = csv::Writer::from_writer(writer);
+		for &state in self.population.iter() {
+			writer.serialize(PopulationRow { state, tags: String::new() })?;
+		}
+		Ok(writer)
+	}
+
 	/// Shuffles (ie reorders in a random way) the population and restarts the iterator.
 	///
 	/// # Examples
@@ -88,8 +439,80 @@ impl Population {
 	/// To draw a random individual from the population, 
 	pub fn shuffle<R: ?Sized + rand::Rng>(&mut self, rng: &mut R) {
 		let slice = self.population.as_mut_slice();
-		slice.shuffle(rng);
+		crate::random::shuffle(slice, rng);
+		self.counter = 0;
+	}
+
+	/// Shuffles the population as in `shuffle`, and `ids` with the same permutation.
+	///
+	/// The population ends up exactly as with `shuffle` with the same source of randomness.
+	///
+	/// # Panics
+	///
+	/// If the lengths of the population and `ids` differ.
+	pub(crate) fn shuffle_with_ids<R: ?Sized + rand::Rng>(&mut self, rng: &mut R, ids: &mut Vec<Id>) {
+		assert_eq!(self.len(), ids.len());
+		let mut order: Vec<usize> = (0..self.len()).collect();
+		crate::random::shuffle(&mut order, rng);
+		self.population = order.iter().map(|&k| self.population[k]).collect();
+		*ids = order.iter().map(|&k| ids[k]).collect();
+		self.counter = 0;
+	}
+
+	/// Takes the individuals out of the population, which becomes empty, 
+	/// and returns the emptied vector that held them, to be reused as a buffer.
+	pub(crate) fn take_buffer(&mut self) -> Vec<Individual> {
+		let mut buffer = std::mem::take(&mut self.population);
+		buffer.clear();
+		self.counter = 0;
+		self.counts = [0; Individual::COUNT];
+		buffer
+	}
+
+	/// Returns all individuals, including the ones already drawn from the population as an iterator.
+	pub(crate) fn as_slice(&self) -> &[Individual] {
+		&self.population
+	}
+
+	/// Returns `true` if both populations have the same individuals, regardless of their order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let population = Population::default();
+	/// let mut shuffled = population.clone();
+	/// shuffled.shuffle(&mut rand::thread_rng());
+	/// assert!(population.equivalent(&shuffled));
+	/// ```
+	pub fn equivalent(&self, other: &Population) -> bool {
+		self.len() == other.len() && self.counting_all() == other.counting_all()
+	}
+
+	/// Sorts the population in the order of `Individual` variants and restarts the iterator.
+	///
+	/// Two populations are equivalent if and only if they are equal after being normalized,
+	/// see `equivalent`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut population = Population::from(vec![Individual::Sick, Individual::Healthy]);
+	/// population.normalize();
+	/// assert_eq!(population, Population::from(vec![Individual::Healthy, Individual::Sick]));
+	/// ```
+	pub fn normalize(&mut self) -> &mut Self {
+		self.population.sort_unstable();
 		self.counter = 0;
+		self
+	}
+
+	/// Returns the population in canonical order, see `normalize`.
+	pub fn normalized(&self) -> Population {
+		let mut population = self.clone();
+		population.normalize();
+		population
 	}
 
 	/// Returns the size of the population
@@ -97,6 +520,11 @@ impl Population {
 		self.population.len()
 	}
 
+	/// Returns the number of individuals not yet drawn from the population as an iterator.
+	pub fn remaining(&self) -> usize {
+		self.len() - self.counter
+	}
+
 	/// Returns true if there are no individuals.
 	pub fn is_empty(&self) -> bool {
 		self.population.is_empty()
@@ -114,15 +542,15 @@ impl Population {
 	/// assert_eq!(population.counting(Individual::Infected1), 2);
 	/// ```
 	pub fn counting(&self, query: Individual) -> usize {
-		self.population.iter().filter(|&&i| i == query).count()
+		self.counts[query as usize]
 	}
 
 	/// Returns the number of individuals of each type.
 	///
 	/// # Remarks
 	///
-	/// For individual queries, see `counting`, which is faster. 
-	/// This method is prefered is you need many queries.
+	/// Counts are kept up to date as the population changes, so neither this method
+	/// nor `counting` goes through the individuals.
 	///
 	/// # Examples
 	///
@@ -139,15 +567,30 @@ impl Population {
 	/// assert_eq!(hm[&Individual::Immune], 0);
 	/// ```
 	pub fn counting_all(&self) -> HashMap<Individual, usize> {
-		let mut hm: HashMap<Individual, usize> = Individual::iter().map(|i| (i, 0)).collect();
-		for individual in &self.population {
-			*hm.entry(*individual).or_insert(0) += 1;
-		}
-		hm
+		Individual::iter().map(|i| (i, self.counts[i as usize])).collect()
 	}
 
 }
 
+impl Clone for Population {
+	fn clone(&self) -> Self {
+		Population { population: self.population.clone(), counter: self.counter, counts: self.counts }
+	}
+
+	/// Reuses the vector of individuals of `self`.
+	fn clone_from(&mut self, source: &Self) {
+		self.population.clone_from(&source.population);
+		self.counter = source.counter;
+		self.counts = source.counts;
+	}
+}
+
+impl PartialEq for Population {
+	fn eq(&self, other: &Self) -> bool {
+		self.population == other.population
+	}
+}
+
 impl Default for Population {
 	// add code here
 	fn default() -> Self { 
@@ -155,12 +598,12 @@ impl Default for Population {
 		population.push(Individual::Infected1);
 		population.push(Individual::Infected1);
 
-		Population{ population, counter: 0 }
+		Population::from(population)
 	}
 }
 
 impl From<Vec<Individual>> for Population {
-	fn from(vec: Vec<Individual>) -> Self { Population{ population: vec, counter: 0 } }
+	fn from(vec: Vec<Individual>) -> Self { Population{ counts: count(&vec), population: vec, counter: 0 } }
 }
 
 impl Iterator for Population {
@@ -179,23 +622,92 @@ impl Iterator for Population {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn random() {
+		let distribution = [(Individual::Healthy, 3.), (Individual::Sick, 1.), (Individual::Immune, 0.)];
+		let population = Population::random(10_000, &distribution, &mut crate::tests::rng(1));
+		assert_eq!(population.len(), 10_000);
+		assert!((7_300..7_700).contains(&population.counting(Individual::Healthy)));
+		assert_eq!(population.counting(Individual::Immune), 0);
+		assert_eq!(Population::random(0, &[], &mut crate::tests::rng(1)).len(), 0);
+	}
+
+	#[test]
+	fn composition() {
+		let composition = Composition::new(10)
+			.with(Individual::Healthy, 33)
+			.with(Individual::Infected1, 33)
+			.with(Individual::Immune, 34);
+		assert_eq!(composition.counts(), vec![(Individual::Healthy, 3), (Individual::Infected1, 3), (Individual::Immune, 4)]);
+		let composition = Composition::new(7).with(Individual::Healthy, 1).with(Individual::Sick, 1);
+		assert_eq!(composition.population().counting(Individual::Healthy), 4);
+		assert_eq!(Composition::new(0).counts(), vec![]);
+	}
+
 	#[test]
 	fn shuffle() {
 		let mut population = Population::default();
 		population.shuffle(&mut crate::tests::rng(1));
 		let shuffled = {
 			let mut vec = vec![Individual::Healthy; 100];
-			vec[4] = Individual::Infected1;
+			vec[11] = Individual::Infected1;
 			vec[96] = Individual::Infected1;
 			Population::from(vec)
 		};
 		assert_eq!(shuffled, population);
 	}
 
+	#[test]
+	fn shuffle_with_ids() {
+		let mut population = Population::default();
+		let mut ids: Vec<Id> = (0..100).map(Id).collect();
+		population.shuffle_with_ids(&mut crate::tests::rng(1), &mut ids);
+		let mut expected = Population::default();
+		expected.shuffle(&mut crate::tests::rng(1));
+		assert_eq!(population, expected);
+		assert_eq!(ids[11], Id(98));
+		assert_eq!(ids[96], Id(99));
+	}
+
+	#[test]
+	fn equivalent() {
+		let mut population = Population::default();
+		population.shuffle(&mut crate::tests::rng(1));
+		assert_ne!(population, Population::default());
+		assert!(population.equivalent(&Population::default()));
+		assert_eq!(population.normalized(), Population::default().normalized());
+		assert!(!population.equivalent(&Population::from(vec![Individual::Healthy; 100])));
+	}
+
+	#[test]
+	fn remaining() {
+		let mut population = Population::default();
+		assert_eq!(population.remaining(), 100);
+		population.next();
+		assert_eq!(population.remaining(), 99);
+		assert_eq!(population, Population::default());
+	}
+
 	#[test]
 	fn counting1() {
 		let population = Population::default();
 		assert_eq!(population.counting(Individual::Healthy), 98);
 		assert_eq!(population.counting(Individual::Infected1), 2);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn cached_counts() {
+		let mut population = Population::default();
+		population.immunize().unwrap().vaccinate().unwrap().push(Individual::Sick);
+		population.infect_at(3, Individual::Exposed).unwrap();
+		population.set(4, Individual::HealthySuperspreader);
+		population.replace(Individual::Healthy, Individual::Recovered, 5);
+		population.remove(Individual::Infected1).unwrap();
+		population.reverse_immunize().unwrap();
+		population.shuffle(&mut crate::tests::rng(1));
+		assert_eq!(population.counts, count(population.as_slice()));
+		assert_eq!(population.counting(Individual::Recovered), 5);
+		let buffer = population.take_buffer();
+		assert_eq!(population.counting_all(), Population::from(buffer).counting_all());
+	}
+}
@@ -0,0 +1,181 @@
+//! Ready-made boards, retrievable by name with `Board::preset` or `BoardBuilder::preset`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use virus_alarm::prelude::*;
+//! let board = Board::preset("classroom").unwrap();
+//! assert_eq!(board.population().len(), 30);
+//! assert!(Board::preset("village").is_err());
+//! ```
+
+use crate::board::BoardBuilder;
+use crate::building::Tag;
+use crate::errors::PresetError;
+use crate::{Board, BuildingBuilder, Individual, Population};
+use strum::IntoEnumIterator;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Named configuration of a board.
+#[derive(strum_macros::EnumIter, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Preset {
+    /// Board of the official game, the same as `Board::default`:
+    /// 98 healthy and 2 infected individuals, and 8 buildings.
+    Official,
+    /// Small classroom: 29 healthy and 1 infected individuals, and 4 buildings of a school.
+    Classroom,
+    /// Big city: 495 healthy and 5 infected individuals, and 12 buildings, some of them large.
+    City,
+}
+
+impl Preset {
+    /// Returns the name of the preset, as accepted by `Board::preset`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Official => "official",
+            Preset::Classroom => "classroom",
+            Preset::City => "city",
+        }
+    }
+
+    /// Returns the number of healthy and infected individuals.
+    fn population(&self) -> (usize, usize) {
+        match self {
+            Preset::Official => (98, 2),
+            Preset::Classroom => (29, 1),
+            Preset::City => (495, 5),
+        }
+    }
+
+    /// Returns the name, columns, rows and tag of each building.
+    fn buildings(&self) -> &'static [(&'static str, usize, usize, Tag)] {
+        match self {
+            Preset::Official => &[
+                ("Concert Hall", 5, 4, Tag::Leisure),
+                ("Bakery", 2, 2, Tag::Essential),
+                ("School", 4, 4, Tag::Education),
+                ("Pharmacy", 2, 2, Tag::Essential),
+                ("Restaurant", 4, 3, Tag::Leisure),
+                ("Gym", 4, 2, Tag::Leisure),
+                ("Supermarket", 2, 2, Tag::Essential),
+                ("Shopping Center", 4, 2, Tag::Leisure),
+            ],
+            Preset::Classroom => &[
+                ("Classroom", 6, 5, Tag::Education),
+                ("Cafeteria", 4, 3, Tag::Essential),
+                ("Playground", 4, 4, Tag::Leisure),
+                ("Library", 3, 2, Tag::Education),
+            ],
+            Preset::City => &[
+                ("Stadium", 10, 8, Tag::Leisure),
+                ("Concert Hall", 6, 5, Tag::Leisure),
+                ("University", 8, 6, Tag::Education),
+                ("School", 5, 4, Tag::Education),
+                ("Hospital", 4, 4, Tag::Essential),
+                ("Supermarket", 4, 3, Tag::Essential),
+                ("Bakery", 2, 2, Tag::Essential),
+                ("Pharmacy", 2, 2, Tag::Essential),
+                ("Restaurant", 5, 3, Tag::Leisure),
+                ("Gym", 4, 3, Tag::Leisure),
+                ("Shopping Center", 8, 4, Tag::Leisure),
+                ("Train Station", 6, 4, Tag::Essential),
+            ],
+        }
+    }
+
+    /// Returns the board of the preset, with named and tagged buildings.
+    pub fn board(&self) -> Board {
+        let (healthy, infected) = self.population();
+        let mut population = vec![Individual::Healthy; healthy];
+        population.append(&mut vec![Individual::Infected1; infected]);
+        let buildings = self.buildings().iter()
+            .map(|&(name, columns, rows, tag)| BuildingBuilder::new(name).with_size(columns, rows).with_tag(tag).build())
+            .collect();
+        Board::new(Population::from(population), buildings)
+    }
+
+    /// Returns the configuration of the preset, with the same population and sizes of buildings as `board`.
+    ///
+    /// Configurations do not have names nor tags of buildings.
+    pub fn board_builder(&self) -> BoardBuilder {
+        let (healthy, infected1) = self.population();
+        BoardBuilder {
+            healthy,
+            infected1,
+            buildings: self.buildings().iter().map(|&(_, columns, rows, _)| (columns, rows)).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl std::str::FromStr for Preset {
+    type Err = PresetError;
+
+    /// Returns the preset with the given name, ignoring case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Preset::iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| PresetError::Unknown(name.to_string()))
+    }
+}
+
+impl Board {
+    /// Returns the board of the preset named `name`, see `Preset`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no preset with that name.
+    pub fn preset(name: &str) -> Result<Board, PresetError> {
+        Ok(name.parse::<Preset>()?.board())
+    }
+}
+
+impl BoardBuilder {
+    /// Returns the configuration of the preset named `name`, see `Preset::board_builder`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no preset with that name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let board_builder = BoardBuilder::preset("City").unwrap();
+    /// assert_eq!(board_builder.healthy, 495);
+    /// ```
+    pub fn preset(name: &str) -> Result<BoardBuilder, PresetError> {
+        Ok(name.parse::<Preset>()?.board_builder())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn official() {
+        assert_eq!(Board::preset("official").unwrap(), Board::default());
+    }
+
+    #[test]
+    fn consistent() {
+        for preset in Preset::iter() {
+            let board = preset.board();
+            let board_builder = preset.board_builder();
+            assert_eq!(board_builder.diagnostics(), vec![]);
+            let built = board_builder.build();
+            assert_eq!(board.population(), built.population());
+            let sizes = |board: &Board| board.buildings().iter().map(|building| building.people().dim()).collect::<Vec<_>>();
+            assert_eq!(sizes(&board), sizes(&built));
+            assert_eq!(preset.name().parse::<Preset>(), Ok(preset));
+        }
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(Board::preset("village").unwrap_err().to_string(), "There is no preset named \"village\", the presets are official, classroom and city");
+    }
+}
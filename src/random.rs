@@ -0,0 +1,244 @@
+//! Sources of randomness whose results do not depend on the platform nor on the version of `rand`.
+//!
+//! The algorithms that `rand` uses to shuffle, sample ranges and flip coins may change between its versions,
+//! and `StdRng` is not even the same generator on every version.
+//! The board only uses the functions of this module on the raw output of the given source of randomness,
+//! so a given seed of `Pcg32` produces identical games on every platform and with every version of this crate
+//! that does not announce a change in its changelog.
+//!
+//! # Examples
+//!
+//! ```
+//! # use virus_alarm::prelude::*;
+//! use virus_alarm::random::Pcg32;
+//! let (mut board, mut other) = (Board::default(), Board::default());
+//! board.try_advance_with(&mut Pcg32::new(7)).unwrap();
+//! other.try_advance_with(&mut Pcg32::new(7)).unwrap();
+//! assert_eq!(board.state_hash(), other.state_hash());
+//! ```
+
+use rand::RngCore;
+
+/// Multiplier of the linear congruential generator of PCG32
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+/// Stream of `Pcg32::new`
+const STREAM: u64 = 11_634_580_027_462_260_723;
+
+/// Generator PCG32, also known as PCG-XSH-RR 64/32, as implemented by the `rand_pcg` crate.
+///
+/// It is fast and statistically good, but not cryptographically secure.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    /// Returns the generator with the given seed, in a fixed stream.
+    pub fn new(seed: u64) -> Self {
+        Pcg32::with_stream(seed, STREAM)
+    }
+
+    /// Returns the generator with the given seed and stream.
+    ///
+    /// This is the same generator as `rand_pcg::Pcg32::new(seed, stream)`.
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut pcg = Pcg32 { state: seed, increment: (stream << 1) | 1 };
+        pcg.state = pcg.state.wrapping_add(pcg.increment);
+        pcg.step();
+        pcg
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(self.increment);
+    }
+}
+
+impl RngCore for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let state = self.state;
+        self.step();
+        let rotation = (state >> 59) as u32;
+        let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = u64::from(self.next_u32());
+        let high = u64::from(self.next_u32());
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl rand::SeedableRng for Pcg32 {
+    /// Seed and stream, in little-endian order
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_bytes = [0; 8];
+        let mut stream_bytes = [0; 8];
+        seed_bytes.copy_from_slice(&seed[..8]);
+        stream_bytes.copy_from_slice(&seed[8..]);
+        Pcg32::with_stream(u64::from_le_bytes(seed_bytes), u64::from_le_bytes(stream_bytes))
+    }
+
+    /// Same as `Pcg32::new`, which does not depend on the version of `rand`.
+    fn seed_from_u64(seed: u64) -> Self {
+        Pcg32::new(seed)
+    }
+}
+
+/// Returns a number chosen uniformly at random in `0..n`.
+///
+/// # Panics
+///
+/// If `n` is zero.
+pub fn below<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> usize {
+    assert!(n > 0, "there is no number below zero to choose from");
+    let n = n as u64;
+    // Widening multiplication, rejecting the low halves above the largest multiple of `n`,
+    // so that the result is unbiased and sources that always draw zero still terminate
+    let zone = u64::MAX - n.wrapping_neg() % n;
+    loop {
+        let product = u128::from(rng.next_u64()) * u128::from(n);
+        if product as u64 <= zone {
+            return (product >> 64) as usize;
+        }
+    }
+}
+
+/// Returns `true` with the given probability.
+///
+/// Probabilities below zero are treated as zero, and above one as one.
+pub fn chance<R: RngCore + ?Sized>(rng: &mut R, probability: f64) -> bool {
+    // The 53 highest bits give a uniform number in [0, 1) with every representable value
+    let unit = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    unit < probability
+}
+
+/// Returns an index of `weights` chosen at random, with probability proportional to its weight.
+///
+/// Negative weights are treated as zero.
+///
+/// # Panics
+///
+/// If no weight is positive.
+pub fn weighted<R: RngCore + ?Sized>(weights: &[f64], rng: &mut R) -> usize {
+    let total: f64 = weights.iter().map(|weight| weight.max(0.)).sum();
+    assert!(total > 0., "there is no positive weight to choose from");
+    let unit = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    let mut target = unit * total;
+    for (index, weight) in weights.iter().enumerate() {
+        let weight = weight.max(0.);
+        if target < weight {
+            return index;
+        }
+        target -= weight;
+    }
+    // Rounding errors can only leave the target past the last positive weight
+    weights.iter().rposition(|&weight| weight > 0.).unwrap()
+}
+
+/// Shuffles `slice` uniformly at random, with the Fisher-Yates algorithm.
+pub fn shuffle<T, R: RngCore + ?Sized>(slice: &mut [T], rng: &mut R) {
+    for index in (1..slice.len()).rev() {
+        slice.swap(index, below(rng, index + 1));
+    }
+}
+
+/// Returns an element of `slice` chosen uniformly at random, or `None` if it is empty.
+pub fn choose<'a, T, R: RngCore + ?Sized>(slice: &'a [T], rng: &mut R) -> Option<&'a T> {
+    if slice.is_empty() {
+        None
+    } else {
+        slice.get(below(rng, slice.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn pcg32() {
+        let mut rng = Pcg32::with_stream(42, 54);
+        // Reference values of the PCG paper for seed 42 and stream 54
+        let expected: [u32; 6] = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+        for value in expected.iter() {
+            assert_eq!(rng.next_u32(), *value);
+        }
+    }
+
+    #[test]
+    fn rand_pcg() {
+        let (mut rng, mut other) = (Pcg32::new(9), rand_pcg::Pcg32::new(9, STREAM));
+        for _ in 0..100 {
+            assert_eq!(rng.next_u64(), other.next_u64());
+        }
+    }
+
+    #[test]
+    fn fill_bytes() {
+        let (mut rng, mut other) = (Pcg32::new(3), Pcg32::new(3));
+        let mut bytes = [0; 6];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..4], other.next_u32().to_le_bytes());
+        assert_eq!(bytes[4..], other.next_u32().to_le_bytes()[..2]);
+    }
+
+    #[test_case(1; "one")]
+    #[test_case(3; "three")]
+    #[test_case(10; "ten")]
+    fn below(n: usize) {
+        let mut rng = Pcg32::new(1);
+        let mut seen = vec![false; n];
+        for _ in 0..100 * n {
+            seen[super::below(&mut rng, n)] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn chance() {
+        let mut rng = Pcg32::new(2);
+        assert!((0..100).all(|_| super::chance(&mut rng, 1.)));
+        assert!((0..100).all(|_| !super::chance(&mut rng, 0.)));
+        let heads = (0..10_000).filter(|_| super::chance(&mut rng, 0.25)).count();
+        assert!((2_300..2_700).contains(&heads));
+    }
+
+    #[test]
+    fn weighted() {
+        let mut rng = Pcg32::new(4);
+        let mut counts = [0; 4];
+        for _ in 0..10_000 {
+            counts[super::weighted(&[1., 0., -2., 3.], &mut rng)] += 1;
+        }
+        assert_eq!(counts[1] + counts[2], 0);
+        assert!((2_300..2_700).contains(&counts[0]));
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut values: Vec<usize> = (0..10).collect();
+        super::shuffle(&mut values, &mut Pcg32::new(5));
+        let mut sorted = values.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<usize>>());
+        assert_ne!(values, sorted);
+        assert_eq!(super::choose(&[] as &[usize], &mut Pcg32::new(5)), None);
+    }
+}
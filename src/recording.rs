@@ -6,9 +6,19 @@ use strum::IntoEnumIterator;
 
 mod counting_table;
 pub use counting_table::*;
+mod metadata;
+pub use metadata::*;
+mod events;
+pub use events::*;
+mod history;
+pub use history::*;
+mod replay;
+pub use replay::*;
+mod recorder;
+pub use recorder::*;
 
 /// Represents the state of the game and have high level commands.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, MutGetters)]
+#[derive(Debug, PartialEq, Eq, Getters, MutGetters)]
 pub struct Recording {
 	/// Returns a "table" with the counting of individual types per day.
 	///
@@ -20,11 +30,59 @@ pub struct Recording {
     #[getset(get = "pub", get_mut)]
     timeline: usize, 
     /// Returns a table with the counting of penalty for each building per day.  
+    ///
+    /// A building adds its penalty in each stage it is closed, see `Building::penalty`.
     #[getset(get = "pub", get_mut)]
     penalty: Vec<(Building, Vec<usize>)>,
-    /// Returns the score obtained per day.  
+    /// Returns the score obtained per day, which is minus the penalty of all buildings.  
     #[getset(get = "pub", get_mut)]
     daily_score: Vec<isize>,
+    /// Returns the number of vaccines given so far, without the ones given back.  
+    #[getset(get = "pub")]
+    vaccines_used: usize,
+    /// Returns the number of newly infected individuals per day that had been vaccinated.  
+    #[getset(get = "pub", get_mut)]
+    vaccinated_infected: Vec<usize>,
+    /// Returns the number of newly infected individuals per day during the stages of the game. 
+    ///
+    /// Day zero is always zero. Infections outside buildings are in `imported_infections`.  
+    #[getset(get = "pub")]
+    incidence: Vec<usize>,
+    /// Returns the number of individuals infected per day outside buildings, see `Board::infect`.  
+    #[getset(get = "pub")]
+    imported_infections: Vec<usize>,
+    /// Returns the counting of the initial population, before any action is taken.  
+    #[getset(get = "pub")]
+    initial: HashMap<Individual, usize>,
+    /// Returns the meaning of the first day in `reported_counting_table`.  
+    #[getset(get = "pub")]
+    day_zero: DayZero,
+    /// Events collected so far, if collection is enabled.
+    events: Option<Vec<Event>>,
+    /// Newly infected individuals per building per day, see `infections_by_building`.
+    building_infections: Vec<(String, Vec<usize>)>,
+    /// Occupied seats per building per stage, see `occupancy`.
+    occupancy: Vec<(String, CountingTable)>,
+    /// Trajectory of each individual, if identities are tracked.
+    histories: Option<Vec<History>>,
+}
+
+/// Meaning of the first day (day zero) of a reported counting table.
+///
+/// Actions can be taken before the first stage is played, for example immunizations.
+/// See `Recording::reported_counting_table`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DayZero {
+	/// Day zero is the state when the first stage starts, after the actions taken before play.
+	#[default]
+	AfterActions,
+	/// Day zero is the initial population, before the actions taken before play, 
+	/// which are therefore reflected from day one on.
+	BeforeActions,
+	/// Day zero is omitted, so the first day is the state after the first stage.
+	Excluded,
 }
 
 impl Recording {
@@ -35,13 +93,244 @@ impl Recording {
 	pub fn new(population: Population, buildings: Vec<Building>) -> Self { 
 		let mut recording = Recording::default();
 		for building in buildings {
+			recording.building_infections.push((building.name().to_string(), vec![0]));
+			recording.occupancy.push((building.name().to_string(), Individual::iter().map(|i| (i, Vec::new())).collect()));
 			recording.penalty.push((building, vec![0]));
 		}
-		recording.counting_table = population.counting_all().iter().map(|(&i, &val)| (i, vec![val])).collect();
+		recording.initial = population.counting_all();
+		recording.counting_table = recording.initial.iter().map(|(&i, &val)| (i, vec![val])).collect();
 
 		recording
 	}
 
+	/// Changes the meaning of the first day in `reported_counting_table`.
+	pub fn set_day_zero(&mut self, new_day_zero: DayZero) -> &mut Self {
+		self.day_zero = new_day_zero;
+		self
+	}
+
+	/// Enables or disables the collection of events, see `events`.
+	///
+	/// Disabling the collection discards the events collected so far.
+	pub fn set_collect_events(&mut self, collect: bool) -> &mut Self {
+		match (collect, self.events.is_some()) {
+			(true, false) => self.events = Some(Vec::new()),
+			(false, true) => self.events = None,
+			_ => (),
+		}
+		self
+	}
+
+	/// Returns `true` if events are being collected.
+	pub fn is_collecting_events(&self) -> bool {
+		self.events.is_some()
+	}
+
+	/// Returns the events collected so far, in the order they happened.
+	///
+	/// Events are collected only if enabled, see `set_collect_events`. Otherwise, it is empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// # use virus_alarm::recording::Event;
+	/// let mut board = Board::default();
+	/// board.set_collect_events(true);
+	/// board.immunize().unwrap();
+	/// board.close("School").unwrap();
+	/// assert_eq!(board.recording().events(), &[
+	///     Event::Immunization { day: 0 },
+	///     Event::BuildingClosed { day: 0, building_name: "School".to_string() },
+	/// ]);
+	/// ```
+	pub fn events(&self) -> &[Event] {
+		self.events.as_deref().unwrap_or(&[])
+	}
+
+	/// Adds an event, if events are being collected.
+	pub(crate) fn push_event(&mut self, event: Event) -> &mut Self {
+		if let Some(events) = self.events.as_mut() {
+			events.push(event);
+		}
+		self
+	}
+
+	/// Returns the number of newly infected individuals per building per day, 
+	/// in the order of the buildings of the board.
+	///
+	/// Newly infected individuals include superspreaders and individuals that had been vaccinated. 
+	/// The first day is always zero, since no one has been infected yet.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = BoardBuilder { 
+	///     healthy: 3, 
+	///     infected1: 1, 
+	///     buildings: vec![(4, 1)], 
+	///     spreading: Spreading::Everyone, 
+	///     ..Default::default() 
+	/// }.build();
+	/// board.advance();
+	/// let (_, infections) = &board.recording().infections_by_building()[0];
+	/// assert_eq!(infections, &vec![0, 3]);
+	/// ```
+	pub fn infections_by_building(&self) -> &[(String, Vec<usize>)] {
+		&self.building_infections
+	}
+
+	/// Returns the total number of newly infected individuals per building, 
+	/// from the building with most infections to the one with least.
+	///
+	/// Buildings with the same number of infections are kept in the order of the board.
+	pub fn infections_ranking(&self) -> Vec<(&str, usize)> {
+		let mut ranking: Vec<(&str, usize)> = self.building_infections.iter()
+			.map(|(name, infections)| (name.as_str(), infections.iter().sum()))
+			.collect();
+		ranking.sort_by_key(|&(_, total)| std::cmp::Reverse(total));
+		ranking
+	}
+
+	/// Adds newly infected individuals in the building with index `index` for the stage being played.
+	pub(crate) fn register_building_infections(&mut self, index: usize, newly_infected: usize) -> &mut Self {
+		let day = self.timeline + 1;
+		if let Some((_, infections)) = self.building_infections.get_mut(index) {
+			infections.resize(infections.len().max(day + 1), 0);
+			infections[day] += newly_infected;
+		}
+		self
+	}
+
+	/// Returns the occupied seats of each building per stage, by individual variant, 
+	/// in the order of the buildings of the board.
+	///
+	/// Day `k` of each counting table is the occupancy when individuals visit buildings 
+	/// in the stage that starts on day `k`, before the infection propagates. 
+	/// Therefore, there is one day less than in the counting table of the recording.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let mut board = BoardBuilder { healthy: 3, buildings: vec![(2, 1)], ..Default::default() }.build();
+	/// board.advance();
+	/// let (_, occupancy) = &board.recording().occupancy()[0];
+	/// assert_eq!(occupancy.inner()[&Individual::Healthy], vec![2]);
+	/// ```
+	pub fn occupancy(&self) -> &[(String, CountingTable)] {
+		&self.occupancy
+	}
+
+	/// Writes the occupancy of buildings on the writer, in long format.
+	///
+	/// The first row is the header `building,day,individual,count`, followed by one row per 
+	/// building, day and individual variant, in this order of priority. 
+	/// Individual variants are in the same order as in `CountingTable::write_on`.
+	///
+	/// See `occupancy` for the meaning of days.
+	///
+	/// # Remarks
+	///
+	/// Recall that a writer needs to be flushed to show in the output stream.
+	#[cfg(feature = "serde")]
+	pub fn write_occupancy_on<W: std::io::Write>(&self, writer: W) -> csv::Result<csv::Writer<W>> {
+		let mut writer = csv::Writer::from_writer(writer);
+		writer.write_record(["building", "day", "individual", "count"])?;
+		for (name, occupancy) in self.occupancy.iter() {
+			for day in 0..occupancy.days() {
+				for individual in Individual::iter() {
+					writer.serialize((name, day, individual.to_string(), occupancy.inner()[&individual][day]))?;
+				}
+			}
+		}
+		Ok(writer)
+	}
+
+	/// Sets the occupancy of the building with index `index` for the stage being played, 
+	/// from the individuals in `building`.
+	pub(crate) fn register_occupancy(&mut self, index: usize, building: &Building) -> &mut Self {
+		let day = self.timeline;
+		if let Some((_, occupancy)) = self.occupancy.get_mut(index) {
+			let inner = occupancy.inner_mut();
+			for v in inner.values_mut() {
+				v.resize(v.len().max(day + 1), 0);
+				v[day] = 0;
+			}
+			for individual in building.people().iter().flatten() {
+				if let Some(v) = inner.get_mut(individual) {
+					v[day] += 1;
+				}
+			}
+		}
+		self
+	}
+
+	/// Returns the trajectory of each individual, indexed by their `Id`.
+	///
+	/// Histories are collected only if identities are tracked, see `Board::set_track_identities`. 
+	/// Otherwise, it is empty.
+	pub fn histories(&self) -> &[History] {
+		self.histories.as_deref().unwrap_or(&[])
+	}
+
+	/// Returns the number of distinct individuals that got infected while visiting the building named `building_name`.
+	///
+	/// It is always zero if identities are not tracked, see `histories`.
+	pub fn people_infected_at(&self, building_name: &str) -> usize {
+		self.histories().iter()
+			.filter(|history| history.infection_building() == Some(building_name))
+			.count()
+	}
+
+	/// Starts collecting the histories of `people` individuals, or stops if `None`.
+	pub(crate) fn set_histories(&mut self, people: Option<usize>) -> &mut Self {
+		self.histories = people.map(|people| vec![History::default(); people]);
+		self
+	}
+
+	/// Starts the history of a new individual, returning its identifier, if histories are collected.
+	pub(crate) fn push_history(&mut self) -> Option<crate::individual::Id> {
+		self.histories.as_mut().map(|histories| {
+			histories.push(History::default());
+			crate::individual::Id(histories.len() - 1)
+		})
+	}
+
+	/// Returns the history of the individual with identifier `id`, if histories are collected.
+	pub(crate) fn history_mut(&mut self, id: crate::individual::Id) -> Option<&mut History> {
+		self.histories.as_mut().and_then(|histories| histories.get_mut(id.0))
+	}
+
+	/// Returns the counting table, where the first day is as specified by `day_zero`.
+	///
+	/// # Examples
+	///
+	/// Immunizations before play are reflected on day one.
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// # use virus_alarm::recording::DayZero;
+	/// let mut board = Board::default();
+	/// board.set_day_zero(DayZero::BeforeActions);
+	/// board.immunize();
+	/// board.advance();
+	/// assert_eq!(board.recording().reported_counting_table().inner()[&Individual::Immune], vec![0, 1]);
+	/// ```
+	pub fn reported_counting_table(&self) -> CountingTable {
+		match self.day_zero {
+			DayZero::AfterActions => self.counting_table.clone(),
+			DayZero::BeforeActions => {
+				let mut counting_table = self.counting_table.clone();
+				for (individual, v) in counting_table.inner_mut().iter_mut() {
+					v[0] = self.initial.get(individual).copied().unwrap_or(0);
+				}
+				counting_table
+			},
+			DayZero::Excluded => self.counting_table.without_first_day(),
+		}
+	}
+
 	/// Returns `true` if the outbreak of the virus is contained in the last day.
 	///
 	/// An outbreak  is contained if two conditions hold:
@@ -93,6 +382,16 @@ impl Recording {
 		self
 	}
 
+	/// Changes the spreading mode of the buildings named `name`. 
+	pub(crate) fn set_building_spreading<S: core::fmt::Display>(&mut self, name: S, new_spreading: Spreading) -> &mut Self {
+		for (building, _) in self.penalty_mut().iter_mut() {
+			if building.name() == name.to_string() {
+				building.set_spreading(new_spreading);
+			}
+		}
+		self
+	}
+
 	/// Immunize one person in the population. 
 	/// 
 	/// # Errors
@@ -105,6 +404,7 @@ impl Recording {
 			*healthy_last -= 1;
 			let immune_last = hm.get_mut(&Individual::Immune).unwrap().last_mut().unwrap();
 			*immune_last += 1;
+			self.vaccines_used += 1;
 			Ok(self)
 		} else {
 			Err(crate::errors::ActionError::NoHealthyLeft)
@@ -112,6 +412,51 @@ impl Recording {
 		
 	}
 
+	/// Vaccinate one person in the population, without protecting them. 
+	/// 
+	/// # Errors
+	///
+	/// If there is no healthy individual to vaccinate.
+	pub(crate) fn vaccinate(&mut self) -> Result<&mut Self, crate::errors::ActionError> {
+		let hm = self.counting_table_mut().inner_mut();
+		let healthy_last = hm.get_mut(&Individual::Healthy).unwrap().last_mut().unwrap();
+		if healthy_last > &mut 0 {
+			*healthy_last -= 1;
+			let vaccinated_last = hm.get_mut(&Individual::Vaccinated).unwrap().last_mut().unwrap();
+			*vaccinated_last += 1;
+			self.vaccines_used += 1;
+			Ok(self)
+		} else {
+			Err(crate::errors::ActionError::NoHealthyLeft)
+		}
+	}
+
+	/// Gives a first dose to one person in the population, who becomes partially vaccinated. 
+	/// 
+	/// # Errors
+	///
+	/// If there is no healthy individual to vaccinate.
+	pub(crate) fn first_dose(&mut self) -> Result<&mut Self, crate::errors::ActionError> {
+		if self.last_day_individuals()[&Individual::Healthy] > 0 {
+			self.replace(Individual::Healthy, Individual::PartiallyVaccinated, 1);
+			self.vaccines_used += 1;
+			Ok(self)
+		} else {
+			Err(crate::errors::ActionError::NoHealthyLeft)
+		}
+	}
+
+	/// Gives a second dose to `num` partially vaccinated individuals in the last day, who become immune.
+	///
+	/// # Panics
+	///
+	/// If there are less than `num` partially vaccinated individuals in the last day.
+	pub(crate) fn second_doses(&mut self, num: usize) -> &mut Self {
+		self.replace(Individual::PartiallyVaccinated, Individual::Immune, num);
+		self.vaccines_used += num;
+		self
+	}
+
 	/// Reverse one individual from immune to healthy in the population. 
 	/// 
 	/// # Errors
@@ -124,6 +469,7 @@ impl Recording {
 			*immune_last -= 1;
 			let healthy_last = hm.get_mut(&Individual::Healthy).unwrap().last_mut().unwrap();
 			*healthy_last += 1;
+			self.vaccines_used = self.vaccines_used.saturating_sub(1);
 			Ok(self)
 		} else {
 			Err(crate::errors::ActionError::NoImmuneLeft)
@@ -131,6 +477,60 @@ impl Recording {
 		
 	}
 
+	/// Infects `num` healthy individuals in the last day, outside buildings.
+	///
+	/// # Panics
+	///
+	/// If there are less than `num` healthy individuals in the last day.
+	pub(crate) fn register_imported(&mut self, num: usize) -> &mut Self {
+		self.replace(Individual::Healthy, Individual::Infected1, num);
+		*self.imported_infections.last_mut().unwrap() += num;
+		self
+	}
+
+	/// Starts tracking a building added to the board, after the buildings already tracked.
+	///
+	/// The building has no penalty nor infections in past days.
+	pub(crate) fn add_building(&mut self, building: Building) -> &mut Self {
+		let days = self.timeline + 1;
+		self.building_infections.push((building.name().to_string(), vec![0; days]));
+		self.occupancy.push((building.name().to_string(), Individual::iter().map(|i| (i, Vec::new())).collect()));
+		self.penalty.push((building, vec![0; days]));
+		self
+	}
+
+	/// Stops tracking the building with index `index`, discarding its penalty, infections and occupancy.
+	///
+	/// The daily score of past days is kept, see `daily_score`.
+	pub(crate) fn remove_building(&mut self, index: usize) -> &mut Self {
+		self.building_infections.remove(index);
+		self.occupancy.remove(index);
+		self.penalty.remove(index);
+		self
+	}
+
+	/// Adds one individual of variant `individual` in the last day.
+	pub(crate) fn arrive(&mut self, individual: Individual) -> &mut Self {
+		*self.counting_table_mut().inner_mut().get_mut(&individual).unwrap().last_mut().unwrap() += 1;
+		self
+	}
+
+	/// Removes one individual of variant `individual` in the last day.
+	///
+	/// # Errors
+	///
+	/// If there is no individual of that variant in the last day.
+	pub(crate) fn depart(&mut self, individual: Individual) -> Result<&mut Self, crate::errors::ActionError> {
+		let last = self.counting_table_mut().inner_mut().get_mut(&individual).and_then(|v| v.last_mut());
+		match last {
+			Some(last) if *last > 0 => {
+				*last -= 1;
+				Ok(self)
+			},
+			_ => Err(crate::errors::ActionError::NotPresent(individual)),
+		}
+	}
+
 	/// Returns a "table" with the following information per day: Total healthy, total sick and total infected.  
 	///
 	/// The information provided in this table is the total number of 
@@ -141,17 +541,42 @@ impl Recording {
 
 	/// Main functions that registers newly infected individuals
 	///
+	/// Newly infected individuals include the ones that had been vaccinated, fully or partially.
+	///
 	/// # Panics
 	///
 	/// If the number of newly infected is larger than the number of healthy individuals available
-	pub(crate) fn register(&mut self, newly_infected: usize, _buildings: &[Building]) -> &mut Self {
-		self.register_counting_table(newly_infected);
-		// self.register_penalty(buildings);
-		// self.register_daily_score(buildings);
+	pub(crate) fn register(&mut self, newly_infected: usize, newly_superspreaders: usize, newly_vaccinated_infected: usize, newly_partially_vaccinated_infected: usize, buildings: &[Building]) -> &mut Self {
+		let last_values = self.last_day_individuals();
+		self.register_counting_table(newly_infected - newly_vaccinated_infected - newly_partially_vaccinated_infected);
+		self.register_superspreaders(&last_values, newly_superspreaders);
+		self.register_vaccinated(&last_values, newly_vaccinated_infected, newly_partially_vaccinated_infected);
+		self.register_penalty(buildings);
+		self.register_daily_score();
 		self.increment_time();
+		self.incidence.push(newly_infected + newly_superspreaders);
+		self.imported_infections.push(0);
+		let days = self.timeline + 1;
+		for (_, infections) in self.building_infections.iter_mut() {
+			infections.resize(days.max(infections.len()), 0);
+		}
 		self
 	}
 
+	/// Registers the penalty of each building for the stage, which is non-zero if it was closed.
+	fn register_penalty(&mut self, buildings: &[Building]) {
+		for ((_, penalties), building) in self.penalty.iter_mut().zip(buildings) {
+			penalties.push(if building.is_open() { 0 } else { *building.penalty() });
+		}
+	}
+
+	/// Registers the score of the stage, see `daily_score`.
+	fn register_daily_score(&mut self) {
+		let day = self.timeline + 1;
+		let penalty: usize = self.penalty.iter().filter_map(|(_, penalties)| penalties.get(day)).sum();
+		self.daily_score.push(-(penalty as isize));
+	}
+
 	fn register_counting_table(&mut self, newly_infected: usize) {
 	 	let last_values = self.last_day_individuals();
 
@@ -162,6 +587,49 @@ impl Recording {
 	 	counting_table.inner_mut().entry(Individual::Infected3).and_modify(|v| v.push(last_values[&Individual::Infected2]));
 	 	counting_table.inner_mut().entry(Individual::Sick).and_modify(|v| v.push(last_values[&Individual::Infected3] + last_values[&Individual::Sick]));
 	 	counting_table.inner_mut().entry(Individual::Immune).and_modify(|v| v.push(last_values[&Individual::Immune]));
+	 	counting_table.inner_mut().entry(Individual::Exposed).and_modify(|v| v.push(last_values[&Individual::Exposed]));
+	 	counting_table.inner_mut().entry(Individual::Recovered).and_modify(|v| v.push(last_values[&Individual::Recovered]));
+	}
+
+	/// Changes the variant of `num` individuals in the last day, from `from` to `to`.
+	///
+	/// # Panics
+	///
+	/// If there are less than `num` individuals of variant `from` in the last day.
+	pub(crate) fn replace(&mut self, from: Individual, to: Individual, num: usize) -> &mut Self {
+		let hm = self.counting_table_mut().inner_mut();
+		*hm.get_mut(&from).unwrap().last_mut().unwrap() -= num;
+		*hm.get_mut(&to).unwrap().last_mut().unwrap() += num;
+		self
+	}
+
+	/// Registers the evolution of superspreaders, given the values of the previous day.
+	///
+	/// # Panics
+	///
+	/// If the number of newly infected superspreaders is larger than the number of healthy superspreaders available
+	fn register_superspreaders(&mut self, last_values: &HashMap<Individual, usize>, newly_infected: usize) {
+		let counting_table = self.counting_table_mut();
+	 	counting_table.inner_mut().entry(Individual::HealthySuperspreader).and_modify(|v| v.push(last_values[&Individual::HealthySuperspreader] - newly_infected));
+	 	counting_table.inner_mut().entry(Individual::Superspreader1).and_modify(|v| v.push(newly_infected));
+	 	counting_table.inner_mut().entry(Individual::Superspreader2).and_modify(|v| v.push(last_values[&Individual::Superspreader1]));
+	 	counting_table.inner_mut().entry(Individual::Superspreader3).and_modify(|v| v.push(last_values[&Individual::Superspreader2]));
+	 	counting_table.inner_mut().entry(Individual::Sick).and_modify(|v| *v.last_mut().unwrap() += last_values[&Individual::Superspreader3]);
+	}
+
+	/// Registers the evolution of vaccinated individuals, given the values of the previous day.
+	///
+	/// Newly infected vaccinated individuals, fully or partially, are added to `Infected1`.
+	///
+	/// # Panics
+	///
+	/// If the number of newly infected vaccinated individuals is larger than the number of vaccinated individuals available
+	fn register_vaccinated(&mut self, last_values: &HashMap<Individual, usize>, newly_infected: usize, newly_partially_infected: usize) {
+		let counting_table = self.counting_table_mut();
+	 	counting_table.inner_mut().entry(Individual::Vaccinated).and_modify(|v| v.push(last_values[&Individual::Vaccinated] - newly_infected));
+	 	counting_table.inner_mut().entry(Individual::PartiallyVaccinated).and_modify(|v| v.push(last_values[&Individual::PartiallyVaccinated] - newly_partially_infected));
+	 	counting_table.inner_mut().entry(Individual::Infected1).and_modify(|v| *v.last_mut().unwrap() += newly_infected + newly_partially_infected);
+	 	self.vaccinated_infected.push(newly_infected + newly_partially_infected);
 	}
 
 	/// # Panics
@@ -174,6 +642,57 @@ impl Recording {
 
 
 
+impl Clone for Recording {
+	fn clone(&self) -> Self {
+		Recording {
+			counting_table: self.counting_table.clone(),
+			timeline: self.timeline,
+			penalty: self.penalty.clone(),
+			daily_score: self.daily_score.clone(),
+			vaccines_used: self.vaccines_used,
+			vaccinated_infected: self.vaccinated_infected.clone(),
+			incidence: self.incidence.clone(),
+			imported_infections: self.imported_infections.clone(),
+			initial: self.initial.clone(),
+			day_zero: self.day_zero,
+			events: self.events.clone(),
+			building_infections: self.building_infections.clone(),
+			occupancy: self.occupancy.clone(),
+			histories: self.histories.clone(),
+		}
+	}
+
+	/// Reuses the tables of `self`.
+	fn clone_from(&mut self, source: &Self) {
+		let Recording { counting_table, timeline, penalty, daily_score, vaccines_used, vaccinated_infected, incidence, imported_infections, initial, day_zero, events, building_infections, occupancy, histories } = self;
+		counting_table.clone_from(&source.counting_table);
+		*timeline = source.timeline;
+		clone_pairs_from(penalty, &source.penalty);
+		daily_score.clone_from(&source.daily_score);
+		*vaccines_used = source.vaccines_used;
+		vaccinated_infected.clone_from(&source.vaccinated_infected);
+		incidence.clone_from(&source.incidence);
+		imported_infections.clone_from(&source.imported_infections);
+		initial.clone_from(&source.initial);
+		*day_zero = source.day_zero;
+		events.clone_from(&source.events);
+		clone_pairs_from(building_infections, &source.building_infections);
+		clone_pairs_from(occupancy, &source.occupancy);
+		histories.clone_from(&source.histories);
+	}
+}
+
+/// Clones `source` into `target` reusing the elements of `target`, 
+/// which tuples do not do by themselves.
+fn clone_pairs_from<A: Clone, B: Clone>(target: &mut Vec<(A, B)>, source: &[(A, B)]) {
+	target.truncate(source.len());
+	for ((a, b), (source_a, source_b)) in target.iter_mut().zip(source) {
+		a.clone_from(source_a);
+		b.clone_from(source_b);
+	}
+	target.extend_from_slice(&source[target.len()..]);
+}
+
 impl Default for Recording {
 	// add code here
 	fn default() -> Self { 
@@ -182,8 +701,18 @@ impl Default for Recording {
 		let timeline = 0;
 		let penalty = Vec::new();
 		let daily_score = vec![0];
+		let vaccines_used = 0;
+		let vaccinated_infected = vec![0];
+		let incidence = vec![0];
+		let imported_infections = vec![0];
+		let initial = Individual::iter().map(|i| (i, 0)).collect();
+		let day_zero = DayZero::default();
+		let events = None;
+		let building_infections = Vec::new();
+		let occupancy = Vec::new();
+		let histories = None;
 
-		Recording { counting_table, timeline, penalty, daily_score }
+		Recording { counting_table, timeline, penalty, daily_score, vaccines_used, vaccinated_infected, incidence, imported_infections, initial, day_zero, events, building_infections, occupancy, histories }
 	}
 }
 
@@ -191,6 +720,7 @@ impl Default for Recording {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use test_case::test_case;
 	
 
 	#[test]
@@ -205,6 +735,86 @@ mod tests {
 		assert_eq!(recording.counting_table().inner()[&Individual::Immune], vec![2]);
 	}
 
+	#[test]
+	fn vaccinate() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Sick]);
+		let buildings = Vec::new();
+		let mut recording = Recording::new(population, buildings);
+		recording.vaccinate().unwrap();
+		assert_eq!(recording.counting_table().inner()[&Individual::Healthy], vec![0]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Vaccinated], vec![1]);
+		assert!(recording.vaccinate().is_err());
+	}
+
+	#[test]
+	fn register_vaccinated() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Vaccinated, Individual::Vaccinated]);
+		let buildings = Vec::new();
+		let mut recording = Recording::new(population, buildings);
+		recording.register(2, 0, 1, 0, &[]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Healthy], vec![1, 0]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Vaccinated], vec![2, 1]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Infected1], vec![0, 2]);
+		assert_eq!(recording.vaccinated_infected(), &vec![0, 1]);
+		assert_eq!(recording.incidence(), &vec![0, 2]);
+	}
+
+	#[test]
+	fn register_partially_vaccinated() {
+		let population = Population::from(vec![Individual::Healthy, Individual::Healthy, Individual::Healthy]);
+		let mut recording = Recording::new(population, Vec::new());
+		recording.first_dose().unwrap();
+		recording.first_dose().unwrap();
+		recording.register(1, 0, 0, 1, &[]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Healthy], vec![1, 1]);
+		assert_eq!(recording.counting_table().inner()[&Individual::PartiallyVaccinated], vec![2, 1]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Infected1], vec![0, 1]);
+		assert_eq!(recording.vaccinated_infected(), &vec![0, 1]);
+		recording.second_doses(1);
+		assert_eq!(recording.counting_table().inner()[&Individual::Immune], vec![0, 1]);
+		assert_eq!(recording.vaccines_used(), &3);
+	}
+
+	#[test]
+	fn incidence() {
+		let mut board = crate::Board::default();
+		board.set_spreading(Spreading::Everyone);
+		for _ in 0..6 {
+			board.try_advance_with(&mut crate::tests::rng(7)).unwrap();
+		}
+		let incidence = board.recording().incidence();
+		assert!(incidence.iter().sum::<usize>() > 0);
+		assert_eq!(incidence, &board.counting_table().incidence());
+	}
+
+	#[test]
+	fn replace() {
+		let population = Population::from(vec![Individual::Infected1, Individual::Infected1]);
+		let buildings = Vec::new();
+		let mut recording = Recording::new(population, buildings);
+		recording.replace(Individual::Infected1, Individual::Exposed, 2);
+		assert_eq!(recording.counting_table().inner()[&Individual::Infected1], vec![0]);
+		assert_eq!(recording.counting_table().inner()[&Individual::Exposed], vec![2]);
+	}
+
+	#[test_case(DayZero::AfterActions, vec![0, 1], vec![1, 0])]
+	#[test_case(DayZero::BeforeActions, vec![1, 1], vec![0, 0])]
+	#[test_case(DayZero::Excluded, vec![1], vec![0])]
+	fn reported_counting_table(day_zero: DayZero, healthy: Vec<usize>, immune: Vec<usize>) {
+		let population = Population::from(vec![Individual::Healthy, Individual::Sick]);
+		let buildings = Vec::new();
+		let mut recording = Recording::new(population, buildings);
+		recording.set_day_zero(day_zero);
+		recording.immunize().unwrap();
+		recording.reverse_immunize().unwrap();
+		recording.immunize().unwrap();
+		recording.register(0, 0, 0, 0, &[]);
+		recording.reverse_immunize().unwrap();
+		let counting_table = recording.reported_counting_table();
+		assert_eq!(counting_table.inner()[&Individual::Healthy], healthy);
+		assert_eq!(counting_table.inner()[&Individual::Immune], immune);
+	}
+
 	#[test]
 	fn reverse_immunize() {
 		let population = Population::from(vec![Individual::Healthy, Individual::Sick, Individual::Immune]);
@@ -216,4 +826,43 @@ mod tests {
 		assert_eq!(recording.counting_table().inner()[&Individual::Healthy], vec![2]);
 		assert_eq!(recording.counting_table().inner()[&Individual::Immune], vec![0]);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn infections_by_building() {
+		let population = Population::from(vec![Individual::Healthy; 3]);
+		let buildings = vec![Building::new(1, 1, "A"), Building::new(1, 1, "B")];
+		let mut recording = Recording::new(population, buildings);
+		recording.register_building_infections(1, 1);
+		recording.register(1, 0, 0, 0, &[]);
+		recording.register(0, 0, 0, 0, &[]);
+		recording.register_building_infections(0, 2);
+		recording.register(2, 0, 0, 0, &[]);
+		let expected = [("A".to_string(), vec![0, 0, 0, 2]), ("B".to_string(), vec![0, 1, 0, 0])];
+		assert_eq!(recording.infections_by_building(), &expected);
+		assert_eq!(recording.infections_ranking(), vec![("A", 2), ("B", 1)]);
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn occupancy() -> csv::Result<()> {
+		let population = Population::from(vec![Individual::Healthy; 2]);
+		let mut building = Building::new(2, 1, "A");
+		let mut recording = Recording::new(population, vec![building.clone()]);
+		building.try_push(Individual::Healthy).unwrap();
+		building.try_push(Individual::Healthy).unwrap();
+		recording.register_occupancy(0, &building);
+		recording.register(0, 0, 0, 0, &[]);
+		building.empty();
+		building.try_push(Individual::Healthy).unwrap();
+		recording.register_occupancy(0, &building);
+		let (name, occupancy) = &recording.occupancy()[0];
+		assert_eq!(name, "A");
+		assert_eq!(occupancy.inner()[&Individual::Healthy], vec![2, 1]);
+		assert_eq!(occupancy.inner()[&Individual::Sick], vec![0, 0]);
+		let writer = recording.write_occupancy_on(vec![])?;
+		let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+		assert!(data.starts_with("building,day,individual,count\nA,0,Healthy,2\nA,0,Infected1,0\n"));
+		assert!(data.contains("A,1,Healthy,1\n"));
+		Ok(())
+	}
+}
@@ -2,7 +2,12 @@ use std::collections::HashMap;
 use crate::{Individual};
 use getset::{Getters, MutGetters};
 use strum::IntoEnumIterator;
+#[cfg(feature = "analysis")]
 use ndarray::Array2;
+
+#[cfg(feature = "polars")]
+mod dataframe;
+
 /// Represents the state of the game and have high level commands.
 ///
 /// # Examples
@@ -29,7 +34,7 @@ use ndarray::Array2;
 ///     Immune         0  0  \n\
 /// "));
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Getters, MutGetters, Default)]
+#[derive(Debug, PartialEq, Eq, Getters, MutGetters, Default)]
 pub struct CountingTable {
     /// Returns a "table" with the counting of individual types per day.
     ///
@@ -39,6 +44,20 @@ pub struct CountingTable {
     inner: HashMap<Individual, Vec<usize>>,
 }
 
+impl Clone for CountingTable {
+    fn clone(&self) -> Self {
+        CountingTable { inner: self.inner.clone() }
+    }
+
+    /// Reuses the vectors of `self` for the variants counted in both tables.
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.retain(|individual, _| source.inner.contains_key(individual));
+        for (individual, counts) in source.inner.iter() {
+            self.inner.entry(*individual).or_default().clone_from(counts);
+        }
+    }
+}
+
 impl CountingTable {
     /// Constructor
     ///
@@ -93,8 +112,15 @@ impl CountingTable {
     /// ```
     pub fn is_contained(&self) -> bool {
         let last_day = self.last_day();
-        (last_day[&Individual::Healthy] + last_day[&Individual::Immune] > 0) 
-            && (last_day[&Individual::Infected1] + last_day[&Individual::Infected2] + last_day[&Individual::Infected3] == 0)
+        let non_sick: usize = last_day.iter()
+            .filter(|(i, _)| i.is_healthy() || matches!(i, Individual::Immune | Individual::Recovered))
+            .map(|(_, value)| value)
+            .sum();
+        let infectious: usize = last_day.iter()
+            .filter(|(i, _)| i.is_infected())
+            .map(|(_, value)| value)
+            .sum();
+        non_sick > 0 && infectious == 0
     }
 
 
@@ -106,6 +132,62 @@ impl CountingTable {
         }
     }
 
+    /// Returns the information about the first day in the counting table.
+    ///
+    /// # Panics
+    ///
+    /// If the counting table is empty.
+    pub fn first_day(&self) -> HashMap<Individual, usize> {
+        self.inner().iter().map(|(i, v)| {
+            (*i, v[0])
+        }).collect()
+    }
+
+    /// Returns the counting table without its first day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![(Individual::Healthy, vec![98, 97])]);
+    /// assert_eq!(counting_table.without_first_day(), CountingTable::from(vec![(Individual::Healthy, vec![97])]));
+    /// ```
+    pub fn without_first_day(&self) -> CountingTable {
+        self.inner().iter().map(|(i, v)| {
+            (*i, v.iter().skip(1).copied().collect())
+        }).collect()
+    }
+
+    /// Returns the trajectory of `individual` as a Unicode sparkline, one character per day.
+    ///
+    /// Each day is drawn with one of the blocks `▁▂▃▄▅▆▇█`, scaled between the minimum and 
+    /// the maximum of the trajectory. A constant trajectory is drawn with the lowest block.
+    /// If there is no trajectory for `individual`, it returns an empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![(Individual::Sick, vec![0, 1, 3, 7, 7])]);
+    /// assert_eq!(counting_table.sparkline(Individual::Sick), "▁▂▄██");
+    /// ```
+    pub fn sparkline(&self, individual: Individual) -> String {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let trajectory = match self.inner().get(&individual) {
+            Some(v) => v,
+            None => return String::new(),
+        };
+        let min = trajectory.iter().copied().min().unwrap_or(0);
+        let max = trajectory.iter().copied().max().unwrap_or(0);
+        trajectory.iter().map(|&value| {
+            if max == min {
+                BLOCKS[0]
+            } else {
+                BLOCKS[(value - min) * (BLOCKS.len() - 1) / (max - min)]
+            }
+        }).collect()
+    }
+
     /// Returns the information about the last day in the counting table.
     ///
     /// # Panics
@@ -117,11 +199,247 @@ impl CountingTable {
         }).collect()
     }
 
+    /// Returns the number of individuals of variant `individual` in `day`, if counted.
+    ///
+    /// See also the indexing by `(individual, day)`, which panics instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![(Individual::Sick, vec![0, 1, 3])]);
+    /// assert_eq!(counting_table.get(Individual::Sick, 2), Some(3));
+    /// assert_eq!(counting_table[(Individual::Sick, 2)], 3);
+    /// assert_eq!(counting_table.get(Individual::Sick, 3), None);
+    /// assert_eq!(counting_table.get(Individual::Immune, 0), None);
+    /// ```
+    pub fn get(&self, individual: Individual, day: usize) -> Option<usize> {
+        self.inner().get(&individual).and_then(|v| v.get(day)).copied()
+    }
+
+    /// Returns the total number of individuals per day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![98, 97]),
+    ///     (Individual::Infected1, vec![2, 1]),
+    ///     (Individual::Infected2, vec![0, 2]),
+    /// ]);
+    /// assert_eq!(counting_table.totals(), vec![100, 100]);
+    /// ```
+    pub fn totals(&self) -> Vec<usize> {
+        (0..self.days())
+            .map(|day| self.inner().values().map(|v| v[day]).sum())
+            .collect()
+    }
+
+    /// Returns the number of individuals, checking that it is the same every day.
+    ///
+    /// # Errors
+    ///
+    /// If the number of individuals on a day differs from day zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![98, 97]),
+    ///     (Individual::Infected1, vec![2, 2]),
+    /// ]);
+    /// assert!(counting_table.check_conserved().is_err());
+    /// ```
+    pub fn check_conserved(&self) -> Result<usize, crate::errors::InvariantError> {
+        let totals = self.totals();
+        let expected = totals.first().copied().unwrap_or(0);
+        match totals.iter().position(|&total| total != expected) {
+            Some(day) => Err(crate::errors::InvariantError::NotConserved { day, expected, found: totals[day] }),
+            None => Ok(expected),
+        }
+    }
+
+    /// Checks that the number of immune individuals never decreases.
+    ///
+    /// This holds if immunity does not wane and no immunization is reversed,
+    /// see `Board::set_immunity_duration` and `Board::reverse_immunize`.
+    ///
+    /// # Errors
+    ///
+    /// If there are less immune individuals on a day than on the day before.
+    pub fn check_immune_monotone(&self) -> Result<(), crate::errors::InvariantError> {
+        let immune = match self.inner().get(&Individual::Immune) {
+            Some(immune) => immune,
+            None => return Ok(()),
+        };
+        match immune.windows(2).position(|pair| pair[1] < pair[0]) {
+            Some(day) => Err(crate::errors::InvariantError::ImmuneDecreased { day: day + 1, before: immune[day], after: immune[day + 1] }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the cumulative number of newly infected individuals per day.
+    ///
+    /// Day zero is always zero, since individuals infected at the start are not counted.
+    /// Newly infected individuals are the ones in their first day of infection, 
+    /// `Infected1` and `Superspreader1`, plus the increase of `Exposed` ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![98, 97, 95]),
+    ///     (Individual::Infected1, vec![2, 1, 2]),
+    ///     (Individual::Infected2, vec![0, 2, 1]),
+    ///     (Individual::Infected3, vec![0, 0, 2]),
+    /// ]);
+    /// assert_eq!(counting_table.cumulative_infections(), vec![0, 1, 3]);
+    /// ```
+    pub fn cumulative_infections(&self) -> Vec<usize> {
+        self.incidence().into_iter()
+            .scan(0, |total, newly_infected| {
+                *total += newly_infected;
+                Some(*total)
+            })
+            .collect()
+    }
+
+    /// Returns the number of newly infected individuals per day, see `cumulative_infections`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![98, 97, 95]),
+    ///     (Individual::Infected1, vec![2, 1, 2]),
+    /// ]);
+    /// assert_eq!(counting_table.incidence(), vec![0, 1, 2]);
+    /// ```
+    pub fn incidence(&self) -> Vec<usize> {
+        let count = |individual: Individual, day: usize| self.get(individual, day).unwrap_or(0);
+        (0..self.days())
+            .map(|day| match day {
+                0 => 0,
+                _ => {
+                    let first_day = count(Individual::Infected1, day) + count(Individual::Superspreader1, day);
+                    (first_day + count(Individual::Exposed, day)).saturating_sub(count(Individual::Exposed, day - 1))
+                },
+            })
+            .collect()
+    }
+
+    /// Returns the effective reproduction number per day, 
+    /// ie the newly infected individuals of the day divided by the infectious individuals the day before.
+    ///
+    /// Newly infected individuals are as in `incidence`.
+    /// The reproduction number is `None` on day zero and on days after a day without infectious individuals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![98, 96, 95, 95]),
+    ///     (Individual::Infected1, vec![2, 2, 1, 0]),
+    ///     (Individual::Infected2, vec![0, 2, 2, 1]),
+    ///     (Individual::Infected3, vec![0, 0, 2, 2]),
+    /// ]);
+    /// assert_eq!(counting_table.reproduction_numbers(), vec![None, Some(1.), Some(0.25), Some(0.)]);
+    /// ```
+    pub fn reproduction_numbers(&self) -> Vec<Option<f64>> {
+        let incidence = self.incidence();
+        let infectious: Vec<usize> = (0..self.days())
+            .map(|day| {
+                self.inner().iter()
+                    .filter(|(individual, _)| individual.is_infectious())
+                    .map(|(_, v)| v[day])
+                    .sum()
+            })
+            .collect();
+        (0..self.days())
+            .map(|day| match day {
+                0 => None,
+                _ if infectious[day - 1] == 0 => None,
+                _ => Some(incidence[day] as f64 / infectious[day - 1] as f64),
+            })
+            .collect()
+    }
+
+    /// Returns the first day when no individual is infected, if any, 
+    /// which is when the epidemic ends.
+    ///
+    /// All infected variants are considered, see `Individual::is_infected`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![8, 6, 6, 6]),
+    ///     (Individual::Infected1, vec![2, 1, 0, 0]),
+    ///     (Individual::Infected2, vec![0, 2, 1, 0]),
+    ///     (Individual::Sick, vec![0, 0, 2, 3]),
+    /// ]);
+    /// assert_eq!(counting_table.extinction_day(), Some(3));
+    /// ```
+    pub fn extinction_day(&self) -> Option<usize> {
+        (0..self.days()).find(|&day| {
+            self.inner().iter()
+                .filter(|(individual, _)| individual.is_infected())
+                .all(|(_, v)| v[day] == 0)
+        })
+    }
+
+    /// Returns `true` if `next` starts in the last day of this counting table, 
+    /// as when a game is resumed.
+    pub fn is_continued_by(&self, next: &CountingTable) -> bool {
+        self.days() > 0 
+            && next.days() > 0 
+            && Individual::iter().all(|i| self.inner().get(&i).and_then(|v| v.last()) == next.inner().get(&i).map(|v| &v[0]))
+    }
+
+    /// Appends the days of `next`, which must start in the last day of this counting table, 
+    /// so that it is not repeated.
+    ///
+    /// This allows to join the counting tables of a resumed game.
+    ///
+    /// # Errors
+    ///
+    /// If `next` does not start in the last day of this counting table, see `is_continued_by`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::{prelude::*, recording::CountingTable};
+    /// let mut counting_table = CountingTable::from(vec![(Individual::Healthy, vec![98, 97])]);
+    /// let next = CountingTable::from(vec![(Individual::Healthy, vec![97, 95, 94])]);
+    /// counting_table.extend(&next).unwrap();
+    /// assert_eq!(counting_table.inner()[&Individual::Healthy], vec![98, 97, 95, 94]);
+    /// assert!(counting_table.extend(&next).is_err());
+    /// ```
+    pub fn extend(&mut self, next: &CountingTable) -> Result<&mut Self, crate::errors::CountingTableError> {
+        if !self.is_continued_by(next) {
+            return Err(crate::errors::CountingTableError::Discontinuous);
+        }
+        for (individual, v) in self.inner_mut().iter_mut() {
+            v.extend(&next.inner()[individual][1..]);
+        }
+        Ok(self)
+    }
+
     /// Writes the contents of the counting table on the writer.
     ///
+    /// The first row is the header `Individual\Day, 0, 1, ...`, followed by one row per 
+    /// individual variant, always in the order `Healthy`, `Infected1`, `Infected2`, `Infected3`, `Sick`, `Immune`, `HealthySuperspreader`, `Superspreader1`, `Superspreader2`, `Superspreader3`, `Vaccinated`, `Exposed`, `Recovered` and `PartiallyVaccinated`.
+    ///
     /// # Remarks
     ///
     /// Recall that a writer needs to be flushed to show in the output stream.
+    #[cfg(feature = "serde")]
     pub fn write_on<W: std::io::Write>(&self, writer: W) -> csv::Result<csv::Writer<W>> {
         let mut writer = csv::Writer::from_writer(writer);
         let table: Vec<Vec<String>> = self.clone().into();
@@ -131,24 +449,48 @@ impl CountingTable {
         Ok(writer)
     }
 
+    /// Writes the metadata header block followed by the contents of the counting table on the writer.
+    ///
+    /// See `Metadata` for the header block and `write_on` for the column order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::recording::Metadata;
+    /// let board = Board::default();
+    /// let writer = board.counting_table().write_with_metadata(vec![], &Metadata::new()).unwrap();
+    /// let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+    /// assert!(data.starts_with("# crate: virus_alarm"));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn write_with_metadata<W: std::io::Write>(&self, mut writer: W, metadata: &super::Metadata) -> csv::Result<csv::Writer<W>> {
+        metadata.write_on(&mut writer)?;
+        self.write_on(writer)
+    }
+
     /// Returns a "table" with the following information per day: Total healthy, total sick and total infected.  
     ///
     /// The information provided in this table is the total number of 
     /// infected, sick and healthy individuals respectively for each day that has been recorded.
     pub fn diagram(&self) -> [Vec<usize>; 3] {
-        let healthy = &self.inner()[&Individual::Healthy];
-        let infected = self.inner()[&Individual::Infected1].iter()
-            .zip(
-            self.inner()[&Individual::Infected2].iter()
-            ).zip(
-            self.inner()[&Individual::Infected3].iter()
-            ).map(|((inf1, inf2), inf3)| inf1 + inf2 + inf3)
-            .collect();
-        let sick = &self.inner()[&Individual::Sick];
-        [healthy.to_vec(), infected, sick.to_vec()]
+        let total = |query: fn(&Individual) -> bool| -> Vec<usize> {
+            (0..self.days()).map(|day| {
+                self.inner().iter()
+                    .filter(|(i, _)| query(i))
+                    .map(|(_, v)| v[day])
+                    .sum()
+            }).collect()
+        };
+        [
+            total(Individual::is_healthy), 
+            total(Individual::is_infected), 
+            total(|i| i == &Individual::Sick),
+        ]
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<Vec<Vec<String>>> for CountingTable {
     fn into(self) -> Vec<Vec<String>> {
         let mut table = Vec::new();
@@ -157,7 +499,7 @@ impl Into<Vec<Vec<String>>> for CountingTable {
             row.extend((0..self.days()).map(|day| day.to_string()));
             row
             });
-        for i in Individual::iter() {
+        for i in Individual::iter().filter(|i| self.inner().contains_key(i)) {
             table.push({
                 let mut row = vec![i.to_string()];
                 row.extend((0..self.days()).map(|day| self.inner()[&i][day].to_string()));
@@ -184,6 +526,19 @@ impl core::fmt::Display for CountingTable {
     }
 }
 
+impl core::ops::Index<(Individual, usize)> for CountingTable {
+    type Output = usize;
+
+    /// Returns the number of individuals of a variant in a day.
+    ///
+    /// # Panics
+    ///
+    /// If the variant or the day is not counted, see `get`.
+    fn index(&self, (individual, day): (Individual, usize)) -> &usize {
+        &self.inner()[&individual][day]
+    }
+}
+
 impl<T> From<T> for CountingTable 
 where
     T: IntoIterator<Item = (Individual, Vec<usize>)>,
@@ -202,20 +557,23 @@ impl core::iter::FromIterator<(Individual, Vec<usize>)> for CountingTable {
     }
 }
 
-
+#[cfg(feature = "analysis")]
 impl From<CountingTable> for Array2<usize> {
     fn from(counting_table: CountingTable) -> Array2<usize> {
         Array2::from(&counting_table)
     }
 }
 
+#[cfg(feature = "analysis")]
 impl From<&CountingTable> for Array2<usize> {
     fn from(counting_table: &CountingTable) -> Array2<usize> {
-        let mut array = Array2::from_elem((6, counting_table.days()), 0);
         let individual_variants: Vec<Individual> = Individual::iter().collect();
-        for counter in 0..individual_variants.len() {
-            for day in 0..counting_table.days() {
-                array[[counter, day]] = counting_table.inner()[&individual_variants[counter]][day];
+        let mut array = Array2::from_elem((individual_variants.len(), counting_table.days()), 0);
+        for (counter, individual) in individual_variants.iter().enumerate() {
+            if let Some(v) = counting_table.inner().get(individual) {
+                for day in 0..counting_table.days() {
+                    array[[counter, day]] = v[day];
+                }
             }
         }
         array
@@ -224,7 +582,9 @@ impl From<&CountingTable> for Array2<usize> {
 
 impl From<&CountingTable> for Vec<(String, Vec<usize>)> {
     fn from(counting_table: &CountingTable) -> Vec<(String, Vec<usize>)> {
-        Individual::iter().map(|i| (i.to_string(), counting_table.inner()[&i].clone())).collect()
+        Individual::iter()
+            .filter_map(|i| counting_table.inner().get(&i).map(|v| (i.to_string(), v.clone())))
+            .collect()
     }
 }
 
@@ -233,8 +593,50 @@ impl From<&CountingTable> for Vec<(String, Vec<usize>)> {
 mod tests {
     use crate::{Recording, Population};
     use super::*;
+    #[cfg(feature = "analysis")]
     use ndarray::array;
 
+    #[test]
+    fn sparkline() {
+        let counting_table = CountingTable::from(vec![(Individual::Healthy, vec![5, 5]), (Individual::Sick, vec![14, 7, 0])]);
+        assert_eq!(counting_table.sparkline(Individual::Healthy), "▁▁");
+        assert_eq!(counting_table.sparkline(Individual::Sick), "█▄▁");
+        assert_eq!(counting_table.sparkline(Individual::Immune), "");
+    }
+
+    #[test]
+    fn cumulative_infections() {
+        let counting_table = CountingTable::from(vec![
+            (Individual::Healthy, vec![9, 5, 5, 3]),
+            (Individual::Infected1, vec![1, 0, 2, 1]),
+            (Individual::Superspreader1, vec![0, 1, 0, 0]),
+            (Individual::Exposed, vec![0, 3, 1, 2]),
+        ]);
+        assert_eq!(counting_table.cumulative_infections(), vec![0, 4, 4, 6]);
+        assert_eq!(counting_table.totals(), vec![10, 9, 8, 6]);
+    }
+
+    #[test]
+    fn reproduction_numbers() {
+        let counting_table = CountingTable::from(vec![
+            (Individual::Healthy, vec![5, 5, 3]),
+            (Individual::Infected3, vec![1, 0, 0]),
+            (Individual::Exposed, vec![0, 0, 2]),
+        ]);
+        assert_eq!(counting_table.reproduction_numbers(), vec![None, Some(0.), None]);
+    }
+
+    #[test]
+    fn extend() {
+        let mut counting_table = CountingTable::from(vec![(Individual::Healthy, vec![3]), (Individual::Sick, vec![1])]);
+        let next = CountingTable::from(vec![(Individual::Healthy, vec![3, 2]), (Individual::Sick, vec![1, 2])]);
+        assert!(counting_table.is_continued_by(&next));
+        counting_table.extend(&next).unwrap();
+        assert_eq!(counting_table[(Individual::Sick, 1)], 2);
+        let partial = CountingTable::from(vec![(Individual::Healthy, vec![2, 2])]);
+        assert_eq!(counting_table.extend(&partial).err(), Some(crate::errors::CountingTableError::Discontinuous));
+    }
+
     #[test]
     fn register_counting_table1() {
         let population = Population::from(vec![Individual::Healthy, Individual::Infected1, Individual::Infected3]);
@@ -276,26 +678,46 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn write_on() -> csv::Result<()> {
         let writer = vec![];
         let counting_table: CountingTable = Individual::iter().map(|i| (i, vec![0])).collect();
         let writer = counting_table.write_on(writer)?;
         let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
-        assert_eq!(data, String::from("Individual\\Day,0\nHealthy,0\nInfected1,0\nInfected2,0\nInfected3,0\nSick,0\nImmune,0\n"));
+        assert_eq!(data, String::from("Individual\\Day,0\nHealthy,0\nInfected1,0\nInfected2,0\nInfected3,0\nSick,0\nImmune,0\nHealthySuperspreader,0\nSuperspreader1,0\nSuperspreader2,0\nSuperspreader3,0\nVaccinated,0\nExposed,0\nRecovered,0\nPartiallyVaccinated,0\n"));
         Ok(())
     }
 
     #[test]
+    #[cfg(feature = "serde")]
+    fn write_with_metadata() -> csv::Result<()> {
+        let counting_table: CountingTable = Individual::iter().map(|i| (i, vec![0])).collect();
+        let metadata = crate::recording::Metadata::new().with_seed(3);
+        let writer = counting_table.write_with_metadata(vec![], &metadata)?;
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(data, format!("{}Individual\\Day,0\nHealthy,0\nInfected1,0\nInfected2,0\nInfected3,0\nSick,0\nImmune,0\nHealthySuperspreader,0\nSuperspreader1,0\nSuperspreader2,0\nSuperspreader3,0\nVaccinated,0\nExposed,0\nRecovered,0\nPartiallyVaccinated,0\n", metadata));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "analysis")]
     fn array2() {
         let counting_table: CountingTable = Individual::iter().map(|i| (i, vec![0])).collect();
-        let expected = array![[0], [0], [0], [0], [0], [0]];
+        let expected = array![[0], [0], [0], [0], [0], [0], [0], [0], [0], [0], [0], [0], [0], [0]];
         assert_eq!(Array2::from(&counting_table), expected);
     }
 
+    #[test]
+    fn first_day() {
+        let counting_table = CountingTable::from(vec![(Individual::Healthy, vec![98, 97]), (Individual::Sick, vec![0, 1])]);
+        let expected: HashMap<Individual, usize> = vec![(Individual::Healthy, 98), (Individual::Sick, 0)].into_iter().collect();
+        assert_eq!(counting_table.first_day(), expected);
+    }
+
     #[test]
     fn diagram() {
         let counting_table: CountingTable = Individual::iter().map(|i| (i, vec![1, 2])).collect();
-        let expected = [vec![1, 2], vec![3, 6], vec![1, 2]];
+        let expected = [vec![4, 8], vec![7, 14], vec![1, 2]];
         assert_eq!(counting_table.diagram(), expected);
     }
 
@@ -309,7 +731,15 @@ mod tests {
             Infected2      0  \n\
             Infected3      0  \n\
             Sick           0  \n\
-            Immune         0  \n");
+            Immune         0  \n\
+            HealthySuperspreader0  \n\
+            Superspreader1 0  \n\
+            Superspreader2 0  \n\
+            Superspreader3 0  \n\
+            Vaccinated     0  \n\
+            Exposed        0  \n\
+            Recovered      0  \n\
+            PartiallyVaccinated0  \n");
         println!("{}", counting_table);
         assert_eq!(format!("{}", counting_table), expected);
     }
@@ -0,0 +1,50 @@
+use super::CountingTable;
+use crate::Individual;
+use polars::prelude::{DataFrame, NamedFrom, PolarsResult, Series};
+use strum::IntoEnumIterator;
+
+impl CountingTable {
+    /// Returns the counting table as a polars `DataFrame`, in long format.
+    ///
+    /// The columns are `day`, `state` and `count`, with one row per individual variant and day,
+    /// in this order of priority. Variants follow the order of `Individual::iter`.
+    ///
+    /// Requires the `polars` feature.
+    ///
+    /// # Errors
+    ///
+    /// If polars fails to build the data frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::recording::CountingTable;
+    /// let counting_table = CountingTable::from(vec![
+    ///     (Individual::Healthy, vec![98, 97]),
+    ///     (Individual::Infected1, vec![2, 3]),
+    /// ]);
+    /// let dataframe = counting_table.to_dataframe().unwrap();
+    /// assert_eq!(dataframe.shape(), (4, 3));
+    /// assert_eq!(dataframe.column("state").unwrap().str().unwrap().get(2), Some("Infected1"));
+    /// ```
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let mut days = Vec::new();
+        let mut states = Vec::new();
+        let mut counts = Vec::new();
+        for individual in Individual::iter() {
+            if let Some(v) = self.inner.get(&individual) {
+                for (day, &count) in v.iter().enumerate() {
+                    days.push(day as u64);
+                    states.push(individual.to_string());
+                    counts.push(count as u64);
+                }
+            }
+        }
+        DataFrame::new(vec![
+            Series::new("day".into(), days),
+            Series::new("state".into(), states),
+            Series::new("count".into(), counts),
+        ])
+    }
+}
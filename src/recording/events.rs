@@ -0,0 +1,108 @@
+use crate::Individual;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Something that happened during the game, as collected by `Recording::events`.
+///
+/// Days refer to the columns of the counting table where the event is reflected.
+/// For example, an infection during the first stage happens on day one,
+/// while an immunization before play happens on day zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Event {
+    /// A healthy individual got infected in a building.
+    Infection {
+        /// Day of the infection
+        day: usize,
+        /// Name of the building where the infection happened
+        building_name: String,
+        /// Variant of the infector, if all infectious individuals in the building were of the same variant
+        infector_stage: Option<Individual>,
+    },
+    /// A healthy individual got infected outside buildings, see `Board::infect`.
+    ImportedInfection {
+        /// Day of the infection
+        day: usize,
+    },
+    /// A healthy individual was immunized.
+    Immunization {
+        /// Day of the immunization
+        day: usize,
+    },
+    /// An immunization was reversed.
+    ReverseImmunization {
+        /// Day of the reversal
+        day: usize,
+    },
+    /// A building was closed.
+    BuildingClosed {
+        /// Day of the closure
+        day: usize,
+        /// Name of the building
+        building_name: String,
+    },
+    /// A building was opened.
+    BuildingOpened {
+        /// Day of the opening
+        day: usize,
+        /// Name of the building
+        building_name: String,
+    },
+    /// A building was added to the board, see `Board::add_building`.
+    BuildingAdded {
+        /// Day of the addition
+        day: usize,
+        /// Name of the building
+        building_name: String,
+    },
+    /// A building was removed from the board, see `Board::remove_building`.
+    BuildingRemoved {
+        /// Day of the removal
+        day: usize,
+        /// Name of the building
+        building_name: String,
+    },
+    /// An individual joined the board.
+    Arrival {
+        /// Day of the arrival
+        day: usize,
+        /// Variant of the individual
+        individual: Individual,
+    },
+    /// An individual left the board.
+    Departure {
+        /// Day of the departure
+        day: usize,
+        /// Variant of the individual
+        individual: Individual,
+    },
+}
+
+impl Event {
+    /// Returns the day of the event.
+    pub fn day(&self) -> usize {
+        match self {
+            Event::Infection { day, .. }
+            | Event::ImportedInfection { day }
+            | Event::Immunization { day }
+            | Event::ReverseImmunization { day }
+            | Event::BuildingClosed { day, .. }
+            | Event::BuildingOpened { day, .. }
+            | Event::BuildingAdded { day, .. }
+            | Event::BuildingRemoved { day, .. }
+            | Event::Arrival { day, .. }
+            | Event::Departure { day, .. } => *day,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day() {
+        let event = Event::BuildingClosed { day: 3, building_name: "School".to_string() };
+        assert_eq!(event.day(), 3);
+    }
+}
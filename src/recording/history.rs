@@ -0,0 +1,90 @@
+use crate::Individual;
+use getset::Getters;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Trajectory of one individual during the game, as collected by `Recording::histories`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct History {
+    /// Variant of the individual at the start of each day
+    #[getset(get = "pub")]
+    pub(crate) states: Vec<Individual>,
+    /// Name of the building visited in each stage, `None` if the individual stayed home
+    #[getset(get = "pub")]
+    pub(crate) visits: Vec<Option<String>>,
+    /// Whether the individual was infected outside buildings, see `Board::infect`
+    #[getset(get = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) imported: bool,
+}
+
+impl History {
+    /// Returns the stage in which the individual got infected, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let mut board = BoardBuilder {
+    ///     healthy: 1,
+    ///     infected1: 1,
+    ///     buildings: vec![(2, 1)],
+    ///     spreading: Spreading::Everyone,
+    ///     track_identities: true,
+    ///     ..Default::default()
+    /// }.build();
+    /// board.advance();
+    /// let histories = board.recording().histories();
+    /// assert_eq!(histories[0].infection_stage(), Some(0));
+    /// assert_eq!(histories[1].infection_stage(), None);
+    /// ```
+    pub fn infection_stage(&self) -> Option<usize> {
+        self.states.windows(2)
+            .position(|states| states[0].is_healthy() && states[1].is_infected())
+    }
+
+    /// Returns the building where the individual got infected,
+    /// or `None` if it was not infected while visiting a building.
+    pub fn infection_building(&self) -> Option<&str> {
+        if self.imported {
+            return None;
+        }
+        self.infection_stage()
+            .and_then(|stage| self.visits.get(stage))
+            .and_then(|visit| visit.as_deref())
+    }
+
+    /// Sets the state at the start of `day`.
+    pub(crate) fn set_state(&mut self, day: usize, individual: Individual) {
+        if self.states.len() <= day {
+            self.states.resize(day + 1, individual);
+        }
+        self.states[day] = individual;
+    }
+
+    /// Sets the building visited in `stage`.
+    pub(crate) fn set_visit(&mut self, stage: usize, building_name: Option<String>) {
+        if self.visits.len() <= stage {
+            self.visits.resize(stage + 1, None);
+        }
+        self.visits[stage] = building_name;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infection_building() {
+        let mut history = History::default();
+        history.set_state(0, Individual::Healthy);
+        history.set_visit(0, None);
+        history.set_state(1, Individual::Healthy);
+        history.set_visit(1, Some("School".to_string()));
+        history.set_state(2, Individual::Exposed);
+        assert_eq!(history.infection_stage(), Some(1));
+        assert_eq!(history.infection_building(), Some("School"));
+    }
+}
@@ -0,0 +1,220 @@
+use getset::{Getters, Setters};
+use std::hash::{Hash, Hasher};
+
+/// Version of the layout of CSV exports.
+///
+/// It is incremented every time the column order or the meaning of a column changes.
+///
+/// - Version 1: individual variants `Healthy`, `Infected1`, `Infected2`, `Infected3`, `Sick` and `Immune`.
+/// - Version 2: superspreader variants `HealthySuperspreader`, `Superspreader1`, `Superspreader2` and `Superspreader3` are appended.
+/// - Version 3: variant `Vaccinated` is appended.
+/// - Version 4: variant `Exposed` is appended.
+/// - Version 5: variant `Recovered` is appended.
+/// - Version 6: variant `PartiallyVaccinated` is appended.
+pub const CSV_SCHEMA_VERSION: usize = 6;
+
+/// Metadata written as a header block in CSV exports.
+///
+/// The block consists of lines starting with `#`, one per field, before any CSV record:
+/// ```text
+/// # crate: virus_alarm 0.1.1
+/// # schema_version: 6
+/// # seed: none
+/// # config_hash: none
+/// ```
+/// Most CSV parsers can skip these lines by setting `#` as comment character.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::recording::Metadata;
+/// let metadata = Metadata::new().with_seed(42);
+/// assert_eq!(metadata.seed(), &Some(42));
+/// assert!(metadata.to_string().contains("# seed: 42\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters)]
+pub struct Metadata {
+    /// Version of this crate
+    #[getset(get = "pub")]
+    crate_version: String,
+    /// Version of the layout of the CSV export, see `CSV_SCHEMA_VERSION`
+    #[getset(get = "pub")]
+    schema_version: usize,
+    /// Seed of the random number generator, if known
+    #[getset(get = "pub", set = "pub")]
+    seed: Option<u64>,
+    /// Hash of the configuration, if known
+    #[getset(get = "pub", set = "pub")]
+    config_hash: Option<u64>,
+}
+
+impl Metadata {
+    /// Creates metadata for the current version of the crate, without seed nor configuration.
+    pub fn new() -> Self {
+        Metadata {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version: CSV_SCHEMA_VERSION,
+            seed: None,
+            config_hash: None,
+        }
+    }
+
+    /// Changes the seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Changes the configuration hash to the one of `config`, see `config_hash`.
+    pub fn with_config<T: Hash>(mut self, config: &T) -> Self {
+        self.config_hash = Some(config_hash(config));
+        self
+    }
+
+    /// Writes the header block on the writer.
+    pub fn write_on<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Metadata::new()
+    }
+}
+
+impl core::fmt::Display for Metadata {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let show = |value: &Option<u64>| match value {
+            Some(value) => value.to_string(),
+            None => "none".to_string(),
+        };
+        writeln!(f, "# crate: {} {}", env!("CARGO_PKG_NAME"), self.crate_version)?;
+        writeln!(f, "# schema_version: {}", self.schema_version)?;
+        writeln!(f, "# seed: {}", show(&self.seed))?;
+        writeln!(f, "# config_hash: {}", show(&self.config_hash))
+    }
+}
+
+/// Returns a hash of the configuration which does not depend on the platform.
+///
+/// It uses the 64-bit FNV-1a hash function, where every integer is hashed as its 
+/// little-endian bytes, and `usize` and `isize` as 64-bit integers.
+///
+/// # Remarks
+///
+/// The hash relies on the `Hash` implementations of the configuration, 
+/// which are derived and might change with the version of Rust, 
+/// so hashes are only comparable between builds with the same version of Rust.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// # use virus_alarm::recording::config_hash;
+/// let config = SimulationBuilder::default();
+/// assert_eq!(config_hash(&config), config_hash(&config.clone()));
+/// ```
+pub fn config_hash<T: Hash>(config: &T) -> u64 {
+    let mut hasher = FnvHasher::default();
+    config.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 64-bit FNV-1a hasher, see `config_hash`.
+pub(crate) struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i])
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8)
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16)
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32)
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let metadata = Metadata::new();
+        let expected = format!("# crate: virus_alarm {}\n# schema_version: 6\n# seed: none\n# config_hash: none\n", env!("CARGO_PKG_VERSION"));
+        assert_eq!(metadata.to_string(), expected);
+    }
+
+    #[test]
+    fn stable() {
+        // Changing this value breaks the comparison of configuration hashes between versions of the crate
+        let config = (crate::Individual::Immune, 7_u32, -1_i64, 3_usize, "virus", vec![1_u16, 2]);
+        assert_eq!(super::config_hash(&config), 11090675615282069411);
+    }
+
+    #[test]
+    #[cfg(feature = "analysis")]
+    fn config_hash() {
+        let mut config = crate::SimulationBuilder::default();
+        let hash = super::config_hash(&config);
+        config.report_plan.days = 1;
+        assert_ne!(hash, super::config_hash(&config));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::Board;
+use crate::errors::BoardError;
+use super::{CountingTable, Event};
+
+/// Observer of the stages of a game, see `Board::try_advance_recorded_with`.
+///
+/// The board always keeps its own `Recording`, which the rules of the game rely on.
+/// Recorders keep what a particular use needs, for example only the final state in large ensembles.
+///
+/// # Examples
+///
+/// A recorder of the number of sick individuals.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::recording::{Event, Recorder};
+/// struct Sick(Vec<usize>);
+/// impl Recorder for Sick {
+///     fn record_stage(&mut self, board: &Board, _events: &[Event]) {
+///         self.0.push(board.population().counting(Individual::Sick));
+///     }
+/// }
+/// let mut board = Board::default();
+/// let mut recorder = Sick(Vec::new());
+/// for _ in 0..3 {
+///     board.try_advance_recorded_with(&mut rand::thread_rng(), &mut recorder).unwrap();
+/// }
+/// assert_eq!(recorder.0.len(), 3);
+/// ```
+pub trait Recorder {
+    /// Records the state of `board` at the end of a stage, with the events of the stage.
+    ///
+    /// Events are empty unless the recorder needs them, see `needs_events`.
+    fn record_stage(&mut self, board: &Board, events: &[Event]);
+
+    /// Returns `true` if the recorder needs the events of each stage.
+    ///
+    /// If so, events are collected during the stage even if the board does not collect them,
+    /// see `Board::set_collect_events`.
+    fn needs_events(&self) -> bool {
+        false
+    }
+}
+
+/// Recorder that keeps nothing, for ensembles where only the final state matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NullRecorder;
+
+impl Recorder for NullRecorder {
+    fn record_stage(&mut self, _board: &Board, _events: &[Event]) {}
+}
+
+/// Recorder of the counting of individuals at the end of each stage.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CountingRecorder {
+    counting_table: CountingTable,
+}
+
+impl CountingRecorder {
+    /// Returns the counting of individuals per recorded stage.
+    pub fn counting_table(&self) -> &CountingTable {
+        &self.counting_table
+    }
+}
+
+impl Recorder for CountingRecorder {
+    fn record_stage(&mut self, board: &Board, _events: &[Event]) {
+        for (individual, count) in board.counting_table().last_day() {
+            self.counting_table.inner_mut().entry(individual).or_default().push(count);
+        }
+    }
+}
+
+/// Recorder of the events of each stage.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct EventLogRecorder {
+    events: Vec<Event>,
+}
+
+impl EventLogRecorder {
+    /// Returns the events recorded so far, in the order they happened.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+}
+
+impl Recorder for EventLogRecorder {
+    fn record_stage(&mut self, _board: &Board, events: &[Event]) {
+        self.events.extend_from_slice(events);
+    }
+
+    fn needs_events(&self) -> bool {
+        true
+    }
+}
+
+impl Board {
+    /// Advances a stage in the game, like `try_advance_with`, and records it in `recorder`.
+    ///
+    /// # Errors
+    ///
+    /// If individuals are not conserved at the end of the stage, see `try_advance`.
+    pub fn try_advance_recorded_with<R, Rec>(&mut self, rng: &mut R, recorder: &mut Rec) -> Result<&mut Self, BoardError>
+    where
+        R: rand::Rng + ?Sized,
+        Rec: Recorder + ?Sized,
+    {
+        let collecting = self.recording().is_collecting_events();
+        let events_before = self.recording().events().len();
+        if recorder.needs_events() {
+            self.set_collect_events(true);
+        }
+        let result = self.try_advance_with(rng).map(|_| ());
+        recorder.record_stage(self, &self.recording().events()[events_before..]);
+        if !collecting {
+            self.set_collect_events(false);
+        }
+        result.map(move |_| self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Individual;
+
+    #[test]
+    fn recorders() {
+        let mut board = Board::default();
+        let mut other = board.clone();
+        let (mut rng, mut other_rng) = (crate::tests::rng(8), crate::tests::rng(8));
+        let (mut counting, mut log) = (CountingRecorder::default(), EventLogRecorder::default());
+        for _ in 0..3 {
+            board.try_advance_recorded_with(&mut rng, &mut counting).unwrap();
+            board.try_advance_recorded_with(&mut rng, &mut log).unwrap();
+            board.try_advance_recorded_with(&mut rng, &mut NullRecorder).unwrap();
+            for _ in 0..3 {
+                other.try_advance_with(&mut other_rng).unwrap();
+            }
+        }
+        assert_eq!(board, other);
+        assert!(!board.recording().is_collecting_events());
+        assert_eq!(counting.counting_table().days(), 3);
+        let infections = log.events().iter().filter(|event| matches!(event, Event::Infection { .. })).count();
+        let days = [2, 5, 8];
+        let expected: usize = days.iter().map(|&day| board.recording().incidence()[day]).sum();
+        assert_eq!(infections, expected);
+        assert_eq!(counting.counting_table().inner()[&Individual::Healthy][2], board.counting_table().inner()[&Individual::Healthy][7]);
+    }
+}
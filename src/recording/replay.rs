@@ -0,0 +1,167 @@
+use crate::{Action, Board};
+use crate::errors::{ActionError, BoardError};
+use crate::random::Pcg32;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Step of a recorded game, see `Replay`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Step {
+    /// An action taken on the board
+    Act(Action),
+    /// A stage of the game, see `Board::try_advance`
+    Advance,
+}
+
+/// Compact record of a game: the seed of the randomness and the sequence of steps.
+///
+/// Events are not stored, since replaying the steps produces them again, see `Board::replay`.
+///
+/// # Remarks
+///
+/// The randomness is given by `random::Pcg32`, which does not depend on the version of `rand`,
+/// so replays are exact on every platform and with every version of the crate, see `random`.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::recording::ReplaySession;
+/// let mut session = ReplaySession::new(Board::default(), 7);
+/// session.apply(Action::Close("School".to_string())).unwrap();
+/// session.advance().unwrap().advance().unwrap();
+/// let replay = session.replay().clone();
+///
+/// let text = ron::to_string(&replay).unwrap();
+/// let replay = ron::from_str(&text).unwrap();
+/// assert_eq!(&Board::default().replay(&replay).unwrap(), session.board());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Replay {
+    /// Seed of the source of randomness
+    pub seed: u64,
+    /// Steps of the game, in order
+    pub steps: Vec<Step>,
+}
+
+impl Replay {
+    /// Returns a replay with no steps.
+    pub fn new(seed: u64) -> Self {
+        Replay { seed, steps: Vec::new() }
+    }
+
+    /// Returns the source of randomness of the game, at its start.
+    pub fn rng(&self) -> Pcg32 {
+        Pcg32::new(self.seed)
+    }
+}
+
+/// Game played on a board while recording a `Replay`.
+#[derive(Debug, Clone)]
+pub struct ReplaySession {
+    board: Board,
+    rng: Pcg32,
+    replay: Replay,
+}
+
+impl ReplaySession {
+    /// Starts recording a game on `board`, with randomness given by `seed`.
+    pub fn new(board: Board, seed: u64) -> Self {
+        let replay = Replay::new(seed);
+        ReplaySession { board, rng: replay.rng(), replay }
+    }
+
+    /// Applies an action to the board, see `Action::apply`.
+    ///
+    /// The action is recorded even if it fails, since it may have been partially applied.
+    ///
+    /// # Errors
+    ///
+    /// If the action fails.
+    pub fn apply(&mut self, action: Action) -> Result<&mut Self, ActionError> {
+        let result = action.apply_with(&mut self.board, &mut self.rng).map(|_| ());
+        self.replay.steps.push(Step::Act(action));
+        result.map(move |_| self)
+    }
+
+    /// Plays a stage of the game, see `Board::try_advance`.
+    ///
+    /// # Errors
+    ///
+    /// If the board is corrupted.
+    pub fn advance(&mut self) -> Result<&mut Self, BoardError> {
+        self.board.try_advance_with(&mut self.rng)?;
+        self.replay.steps.push(Step::Advance);
+        Ok(self)
+    }
+
+    /// Returns the board.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the replay recorded so far.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+impl Board {
+    /// Returns the board after replaying `replay` from this board.
+    ///
+    /// This board must be as the board where the replay was recorded at the start.
+    /// See `Replay` for an example.
+    ///
+    /// Actions that failed while recording fail again in the same way, so they are not errors.
+    ///
+    /// # Errors
+    ///
+    /// If a stage fails, for example because this board is not the one where the replay started.
+    pub fn replay(&self, replay: &Replay) -> Result<Board, BoardError> {
+        let mut board = self.clone();
+        let mut rng = replay.rng();
+        for step in replay.steps.iter() {
+            match step {
+                Step::Act(action) => { action.apply_with(&mut board, &mut rng).ok(); },
+                Step::Advance => { board.try_advance_with(&mut rng)?; },
+            }
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay() {
+        let mut board = Board::default();
+        board.set_collect_events(true);
+        let mut session = ReplaySession::new(board.clone(), 3);
+        session.apply(Action::Immunize(5)).unwrap();
+        for day in 0..6 {
+            if day == 2 {
+                session.apply(Action::Close("Concert Hall".to_string())).unwrap();
+            }
+            session.advance().unwrap();
+        }
+        assert_eq!(session.replay().steps.len(), 8);
+        let replayed = board.replay(session.replay()).unwrap();
+        assert_eq!(&replayed, session.board());
+        assert_eq!(replayed.recording().events(), session.board().recording().events());
+    }
+
+    #[test]
+    fn failed_action() {
+        let mut board = Board::default();
+        board.set_vaccines(Some(1));
+        let mut session = ReplaySession::new(board.clone(), 0);
+        assert_eq!(session.apply(Action::Immunize(2)).unwrap_err(), ActionError::NoVaccinesLeft);
+        assert_eq!(session.board().population().counting(crate::Individual::Immune), 1);
+        session.advance().unwrap();
+        assert_eq!(&board.replay(session.replay()).unwrap(), session.board());
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{Board, Building, Individual};
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::fmt::Write;
+use strum::IntoEnumIterator;
+
+mod svg;
+pub use svg::*;
+#[cfg(feature = "animation")]
+mod animation;
+#[cfg(feature = "animation")]
+pub use animation::*;
+
+/// Symbols used to represent each individual variant in text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Legend {
+    /// One ASCII character per variant, safe for any terminal and log file.
+    #[default]
+    Letters,
+    /// One emoji per variant.
+    Emoji,
+}
+
+impl Legend {
+    /// Returns the symbol of `individual`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::render::Legend;
+    /// assert_eq!(Legend::Letters.symbol(&Individual::Sick), "S");
+    /// assert_eq!(Legend::Emoji.symbol(&Individual::Sick), "🤢");
+    /// ```
+    pub fn symbol(&self, individual: &Individual) -> &'static str {
+        match self {
+            Legend::Letters => match individual {
+                Individual::Healthy => "H",
+                Individual::Infected1 => "1",
+                Individual::Infected2 => "2",
+                Individual::Infected3 => "3",
+                Individual::Sick => "S",
+                Individual::Immune => "I",
+                Individual::HealthySuperspreader => "h",
+                Individual::Superspreader1 => "a",
+                Individual::Superspreader2 => "b",
+                Individual::Superspreader3 => "c",
+                Individual::Vaccinated => "V",
+                Individual::Exposed => "E",
+                Individual::Recovered => "R",
+                Individual::PartiallyVaccinated => "v",
+            },
+            Legend::Emoji => match individual {
+                Individual::Healthy => "🙂",
+                Individual::Infected1 => "😐",
+                Individual::Infected2 => "😟",
+                Individual::Infected3 => "😷",
+                Individual::Sick => "🤢",
+                Individual::Immune => "💪",
+                Individual::HealthySuperspreader => "😎",
+                Individual::Superspreader1 => "😬",
+                Individual::Superspreader2 => "😨",
+                Individual::Superspreader3 => "🤧",
+                Individual::Vaccinated => "💉",
+                Individual::Exposed => "😶",
+                Individual::Recovered => "😌",
+                Individual::PartiallyVaccinated => "🩹",
+            },
+        }
+    }
+
+    /// Returns the symbol of an empty seat.
+    pub fn empty(&self) -> &'static str {
+        match self {
+            Legend::Letters => ".",
+            Legend::Emoji => "⬜",
+        }
+    }
+
+    /// Returns the symbol of a seat that must stay empty, see `Building::set_limit`.
+    pub fn blocked(&self) -> &'static str {
+        match self {
+            Legend::Letters => "x",
+            Legend::Emoji => "⬛",
+        }
+    }
+}
+
+/// Options for rendering buildings and boards as text.
+///
+/// The same options are meant to be shared by every text frontend,
+/// for example command line output, terminal interfaces and logs.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// # use virus_alarm::render::{Legend, RenderOptions};
+/// let options = RenderOptions { legend: Legend::Emoji, ..Default::default() };
+/// let text = Board::default().render(&options);
+/// assert!(text.starts_with("Concert Hall (0/20)\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RenderOptions {
+    /// Symbols for each individual variant
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub legend: Legend,
+    /// Whether each building is preceded by a header with its name and occupancy fraction
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub headers: bool,
+    /// Whether the output ends with the meaning of each symbol
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub key: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions { legend: Legend::default(), headers: true, key: false }
+    }
+}
+
+impl RenderOptions {
+    /// Returns the meaning of each symbol, one variant per line.
+    pub fn key_text(&self) -> String {
+        let mut text = String::new();
+        for individual in Individual::iter() {
+            writeln!(text, "{} {}", self.legend.symbol(&individual), individual).expect("writing on a string can not fail");
+        }
+        writeln!(text, "{} Empty", self.legend.empty()).expect("writing on a string can not fail");
+        writeln!(text, "{} Blocked", self.legend.blocked()).expect("writing on a string can not fail");
+        text
+    }
+}
+
+impl Building {
+    /// Returns the seats of the building as text, one row of seats per line.
+    ///
+    /// Seats that must stay empty are shown as blocked, see `Legend::blocked`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::Building;
+    /// # use virus_alarm::render::RenderOptions;
+    /// let mut building = Building::new(3, 1, "Shop");
+    /// building.try_push(Individual::Healthy).unwrap();
+    /// assert_eq!(building.render(&RenderOptions::default()), "Shop (1/3)\nH..\n");
+    /// ```
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut text = String::new();
+        if options.headers {
+            let status = if self.is_open() { "" } else { ", closed" };
+            writeln!(text, "{} ({}/{}{})", self.name(), self.occupancy(), self.capacity(), status).expect("writing on a string can not fail");
+        }
+        let columns = self.people().ncols();
+        for (row, seats) in self.people().outer_iter().enumerate() {
+            for (column, seat) in seats.iter().enumerate() {
+                text.push_str(match seat {
+                    Some(individual) => options.legend.symbol(individual),
+                    None if self.blocked_seats().binary_search(&(row * columns + column)).is_ok() => options.legend.blocked(),
+                    None => options.legend.empty(),
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Renders the building with the default options, see `Building::render`.
+impl std::fmt::Display for Building {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&RenderOptions::default()))
+    }
+}
+
+impl Board {
+    /// Returns all buildings of the board as text, separated by empty lines.
+    ///
+    /// See `RenderOptions` for the available options.
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut text = self.buildings().iter()
+            .map(|building| building.render(options))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if options.key {
+            text.push('\n');
+            text.push_str(&options.key_text());
+        }
+        text
+    }
+
+    /// Returns all buildings of the board as text, followed by the day and 
+    /// the number of individuals of each variant present in the board.
+    ///
+    /// Buildings are rendered with the default options, see `render`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let text = Board::default().render_text();
+    /// assert!(text.ends_with("\nDay 0\nH Healthy: 98\n1 Infected1: 2\n"));
+    /// ```
+    pub fn render_text(&self) -> String {
+        let options = RenderOptions::default();
+        let mut text = self.render(&options);
+        let counting = self.counting_table().last_day();
+        writeln!(text, "\nDay {}", self.recording().timeline()).expect("writing on a string can not fail");
+        for individual in Individual::iter() {
+            match counting.get(&individual) {
+                Some(&count) if count > 0 => {
+                    writeln!(text, "{} {}: {}", options.legend.symbol(&individual), individual, count).expect("writing on a string can not fail");
+                },
+                _ => (),
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Legend::Letters)]
+    #[test_case(Legend::Emoji)]
+    fn distinct_symbols(legend: Legend) {
+        let symbols: std::collections::HashSet<&str> = Individual::iter().map(|i| legend.symbol(&i)).collect();
+        assert_eq!(symbols.len(), Individual::iter().len());
+        assert!(!symbols.contains(legend.empty()));
+        assert!(!symbols.contains(legend.blocked()));
+    }
+
+    #[test]
+    fn render_building() {
+        let mut building = Building::new(2, 2, "Home");
+        building.try_push(Individual::Immune).unwrap();
+        let options = RenderOptions { legend: Legend::Letters, headers: false, key: false };
+        assert_eq!(building.render(&options), "I.\n..\n");
+        building.empty();
+        building.close();
+        assert_eq!(building.render(&RenderOptions::default()), "Home (0/4, closed)\n..\n..\n");
+    }
+
+    #[test]
+    fn render_board() {
+        let board = Board::new(crate::Population::from(vec![]), vec![Building::new(1, 1, "A"), Building::new(1, 1, "B")]);
+        let options = RenderOptions { key: true, ..Default::default() };
+        let text = board.render(&options);
+        assert!(text.starts_with("A (0/1)\n.\n\nB (0/1)\n.\n\n"));
+        assert!(text.ends_with("R Recovered\nv PartiallyVaccinated\n. Empty\nx Blocked\n"));
+    }
+
+    #[test]
+    fn render_blocked() {
+        let mut building = Building::new(3, 2, "Cinema");
+        building.set_capacity_fraction(0.5).unwrap();
+        building.try_push(Individual::Sick).ok();
+        building.try_push(Individual::Infected2).unwrap();
+        assert_eq!(building.to_string(), "Cinema (1/6)\n2x.\nx.x\n");
+    }
+
+    #[test]
+    fn render_text() {
+        let mut board = crate::board::BoardBuilder { healthy: 1, sick: 1, buildings: vec![(1, 2)], ..Default::default() }.build();
+        board.try_advance_with(&mut crate::tests::rng(1)).unwrap();
+        assert_eq!(board.render_text(), "Building 1 (0/2)\n.\n.\n\nDay 1\nH Healthy: 1\nS Sick: 1\n");
+    }
+}
@@ -0,0 +1,318 @@
+use crate::errors::AnimationError;
+use crate::{Board, Building, Individual, SimulationBuilder};
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+/// Space around and between buildings, in pixels
+const PADDING: usize = 8;
+/// Palette index of the background
+const WHITE: u8 = 0;
+/// Palette index of the walls of open buildings
+const BLACK: u8 = 1;
+/// Palette index of blocked seats and of the walls of closed buildings
+const GRAY: u8 = 2;
+/// Palette index of the first individual variant, the others follow in order
+const FIRST_VARIANT: u8 = 3;
+
+/// Options for rendering animations, see `Animation`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnimationOptions {
+    /// Side of each seat, in pixels
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub seat_size: u16,
+    /// Number of buildings in each row of the frames
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub buildings_per_row: usize,
+    /// Time each frame is shown, in hundredths of a second
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub delay: u16,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions { seat_size: 8, buildings_per_row: 4, delay: 50 }
+    }
+}
+
+/// Returns the colors of the frames, as RGB triples, in the order of the palette indices.
+fn palette() -> Vec<u8> {
+    let mut colors = vec!["#ffffff", "#000000", super::svg::BLOCKED];
+    colors.extend(Individual::iter().map(|individual| super::color(&individual)));
+    colors.iter()
+        .flat_map(|color| (0..3).map(move |channel| u8::from_str_radix(&color[1 + 2 * channel..3 + 2 * channel], 16).expect("colors are hexadecimal")))
+        .collect()
+}
+
+/// Returns the width and height of `building` in a frame, in pixels.
+fn size(building: &Building, options: &AnimationOptions) -> (usize, usize) {
+    let (rows, columns) = building.people().dim();
+    let pitch = options.seat_size as usize + 1;
+    (columns * pitch + 1, rows * pitch + 1)
+}
+
+/// Returns the width and height of the frames of `board`, in pixels.
+fn frame_size(board: &Board, options: &AnimationOptions) -> (usize, usize) {
+    let (mut width, mut height) = (0, PADDING);
+    for row in board.buildings().chunks(options.buildings_per_row.max(1)) {
+        let row_width: usize = row.iter().map(|building| size(building, options).0 + PADDING).sum();
+        width = width.max(PADDING + row_width);
+        height += row.iter().map(|building| size(building, options).1).max().unwrap_or(0) + PADDING;
+    }
+    (width.max(1), height)
+}
+
+/// Returns the palette indices of the pixels of a frame of `board` with width `width`, row after row.
+fn pixels(board: &Board, width: usize, height: usize, options: &AnimationOptions) -> Vec<u8> {
+    let mut pixels = vec![WHITE; width * height];
+    let pitch = options.seat_size as usize + 1;
+    let mut y = PADDING;
+    for row in board.buildings().chunks(options.buildings_per_row.max(1)) {
+        let mut x = PADDING;
+        for building in row {
+            let (building_width, building_height) = size(building, options);
+            let wall = if building.is_open() { BLACK } else { GRAY };
+            for line in y..y + building_height {
+                pixels[line * width + x..line * width + x + building_width].iter_mut().for_each(|pixel| *pixel = wall);
+            }
+            let columns = building.people().ncols();
+            for ((seat_row, seat_column), individual) in building.people().indexed_iter() {
+                let color = match individual {
+                    Some(individual) => FIRST_VARIANT + *individual as u8,
+                    None if building.blocked_seats().binary_search(&(seat_row * columns + seat_column)).is_ok() => GRAY,
+                    None => WHITE,
+                };
+                let (left, top) = (x + 1 + seat_column * pitch, y + 1 + seat_row * pitch);
+                for line in top..top + pitch - 1 {
+                    pixels[line * width + left..line * width + left + pitch - 1].iter_mut().for_each(|pixel| *pixel = color);
+                }
+            }
+            x += building_width + PADDING;
+        }
+        y += row.iter().map(|building| size(building, options).1).max().unwrap_or(0) + PADDING;
+    }
+    pixels
+}
+
+fn encoding_error<E: ToString>(error: E) -> AnimationError {
+    AnimationError::Encoding(error.to_string())
+}
+
+/// Animated GIF of a board, with one frame per pushed state.
+///
+/// Frames show the buildings in the order of the board, like `Board::render_svg`,
+/// with each seat colored by the variant of its occupant, see `color`.
+/// Closed buildings and seats that must stay empty are gray.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::render::{Animation, AnimationOptions};
+/// let mut board = Board::default();
+/// let mut animation = Animation::new(Vec::new(), &board, &AnimationOptions::default()).unwrap();
+/// for _ in 0..3 {
+///     board.try_advance_inspected_with(&mut rand::thread_rng(), |board| {
+///         animation.push(board).unwrap();
+///     }).unwrap();
+/// }
+/// let gif = animation.into_inner().unwrap();
+/// assert!(gif.starts_with(b"GIF89a"));
+/// ```
+pub struct Animation<W: Write> {
+    encoder: gif::Encoder<W>,
+    options: AnimationOptions,
+    width: u16,
+    height: u16,
+}
+
+impl<W: Write> Animation<W> {
+    /// Starts an animation written on `writer`, with frames the size of `board`.
+    ///
+    /// The animation loops forever.
+    ///
+    /// # Errors
+    ///
+    /// If `board` needs frames larger than 65535 pixels in any direction, or writing fails.
+    pub fn new(writer: W, board: &Board, options: &AnimationOptions) -> Result<Self, AnimationError> {
+        let (width, height) = frame_size(board, options);
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(AnimationError::TooLarge { width, height });
+        }
+        let (width, height) = (width as u16, height as u16);
+        let mut encoder = gif::Encoder::new(writer, width, height, &palette()).map_err(encoding_error)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(encoding_error)?;
+        Ok(Animation { encoder, options: options.clone(), width, height })
+    }
+
+    /// Adds a frame with the current state of `board`.
+    ///
+    /// Individuals are only in the buildings while visiting, see `Board::try_advance_inspected_with`.
+    ///
+    /// # Errors
+    ///
+    /// If `board` needs frames of another size than the first one, for example after `Board::add_building`,
+    /// or writing fails.
+    pub fn push(&mut self, board: &Board) -> Result<&mut Self, AnimationError> {
+        let (found_width, found_height) = frame_size(board, &self.options);
+        if (found_width, found_height) != (self.width as usize, self.height as usize) {
+            return Err(AnimationError::SizeChanged {
+                width: self.width,
+                height: self.height,
+                found_width: found_width.min(u16::MAX as usize) as u16,
+                found_height: found_height.min(u16::MAX as usize) as u16,
+            });
+        }
+        let frame = gif::Frame {
+            width: self.width,
+            height: self.height,
+            delay: self.options.delay,
+            buffer: Cow::Owned(pixels(board, found_width, found_height, &self.options)),
+            ..Default::default()
+        };
+        self.encoder.write_frame(&frame).map_err(encoding_error)?;
+        Ok(self)
+    }
+
+    /// Finishes the animation and returns the writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing the end of the animation fails.
+    pub fn into_inner(self) -> Result<W, AnimationError> {
+        self.encoder.into_inner().map_err(encoding_error)
+    }
+}
+
+/// Plays `days` days on `board` and writes an animation of them on `writer`,
+/// with one frame per day showing individuals while visiting, see `Animation`.
+///
+/// Stops early if the report plan of a simulation would, see `simulate_and_animate`.
+///
+/// # Errors
+///
+/// If the animation can not be written, see `Animation`,
+/// or individuals are not conserved, see `Board::try_advance`.
+pub fn animate_with<W, R>(board: &mut Board, days: usize, rng: &mut R, writer: W, options: &AnimationOptions) -> Result<W, AnimationError>
+where
+    W: Write,
+    R: rand::Rng + ?Sized,
+{
+    let mut animation = Animation::new(writer, board, options)?;
+    for _ in 0..days {
+        push_day(board, rng, &mut animation)?;
+    }
+    animation.into_inner()
+}
+
+/// Advances `board` a stage and pushes a frame of it, while individuals are visiting, on `animation`.
+fn push_day<W, R>(board: &mut Board, rng: &mut R, animation: &mut Animation<W>) -> Result<(), AnimationError>
+where
+    W: Write,
+    R: rand::Rng + ?Sized,
+{
+    let mut pushed = Ok(());
+    board.try_advance_inspected_with(rng, |board| pushed = animation.push(board).map(|_| ()))?;
+    pushed
+}
+
+/// Writes on `path` an animated GIF of the first realization of `simulation_builder`,
+/// with randomness given by `seed`, and returns the number of days played.
+///
+/// The realization is the first one of `Simulation::run_with` with `random::Pcg32::new(seed)`,
+/// so the animation shows one of the realizations summarized by the report of the simulation.
+///
+/// # Errors
+///
+/// If the simulation is not valid, see `SimulationBuilder::try_build`,
+/// or the animation can not be written, see `Animation`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::render::simulate_and_animate;
+/// let simulation_builder = SimulationBuilder::default();
+/// let days = simulate_and_animate(&simulation_builder, 7, "outbreak.gif").unwrap();
+/// println!("Animated {} days", days);
+/// ```
+pub fn simulate_and_animate<P: AsRef<Path>>(simulation_builder: &SimulationBuilder, seed: u64, path: P) -> Result<usize, AnimationError> {
+    let simulation = simulation_builder.clone().try_build()?;
+    let report_plan = simulation.report_plan();
+    let mut board = simulation.board().clone();
+    let mut rng = crate::random::Pcg32::new(seed);
+    let file = std::fs::File::create(path).map_err(encoding_error)?;
+    let mut animation = Animation::new(std::io::BufWriter::new(file), &board, &AnimationOptions::default())?;
+    let mut days = 0;
+    while days < *report_plan.days() && !report_plan.should_stop(&board) {
+        #[cfg(feature = "unstable")]
+        for intervention in simulation.interventions().iter() {
+            // Failing actions are applied as much as possible, as in `Simulation::replay`
+            let _ = intervention.apply_on_with(days, &mut board, &mut rng);
+        }
+        push_day(&mut board, &mut rng, &mut animation)?;
+        days += 1;
+    }
+    animation.into_inner()?.flush().map_err(encoding_error)?;
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, Spreading};
+
+    /// Returns the frames of `gif`, as palette indices.
+    fn frames(gif: &[u8]) -> Vec<Vec<u8>> {
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif).unwrap();
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push(frame.buffer.to_vec());
+        }
+        frames
+    }
+
+    #[test]
+    fn animate() {
+        let mut board = BoardBuilder { healthy: 2, infected1: 1, buildings: vec![(2, 2)], spreading: Spreading::Everyone, ..Default::default() }.build();
+        let options = AnimationOptions { seat_size: 2, ..Default::default() };
+        let gif = animate_with(&mut board, 3, &mut crate::tests::rng(1), Vec::new(), &options).unwrap();
+        let frames = frames(&gif);
+        assert_eq!(frames.len(), 3);
+        // Frames are 7 pixels wide plus padding, and the first seat starts after the wall
+        let width = 7 + 2 * PADDING;
+        assert_eq!(frames[0].len(), width * width);
+        assert_eq!(frames[0][PADDING * width + PADDING], BLACK);
+        let first_seat = frames[0][(PADDING + 1) * width + PADDING + 1];
+        assert!(first_seat >= FIRST_VARIANT);
+        assert_eq!(frames[0].iter().filter(|&&pixel| pixel >= FIRST_VARIANT).count(), 3 * 4);
+        assert_eq!(board.recording().timeline(), &3);
+    }
+
+    #[test]
+    fn size_changed() {
+        let mut board = Board::default();
+        let mut animation = Animation::new(Vec::new(), &board, &AnimationOptions::default()).unwrap();
+        board.add_building(crate::BuildingBuilder::new("Library").with_size(3, 2).build());
+        let error = animation.push(&board).err().unwrap();
+        assert!(matches!(error, AnimationError::SizeChanged { .. }));
+    }
+
+    #[test]
+    fn simulate_and_animate() {
+        let path = std::env::temp_dir().join(format!("virus_alarm_animation_{}.gif", std::process::id()));
+        let mut simulation_builder = SimulationBuilder::default();
+        simulation_builder.report_plan.days = 4;
+        assert_eq!(super::simulate_and_animate(&simulation_builder, 7, &path).unwrap(), 4);
+        let gif = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frames(&gif).len(), 4);
+    }
+}
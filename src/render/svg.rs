@@ -0,0 +1,213 @@
+use crate::{Board, Building, Individual};
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::fmt::Write;
+use strum::IntoEnumIterator;
+
+/// Space around and between buildings
+const PADDING: f64 = 10.;
+/// Height of the name of each building
+const HEADER: f64 = 20.;
+/// Height of each line of the key
+const KEY_LINE: f64 = 16.;
+
+/// Returns the color used to draw `individual` in images, as a hexadecimal RGB code.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// # use virus_alarm::render::color;
+/// assert_eq!(color(&Individual::Healthy), "#2ca02c");
+/// ```
+pub fn color(individual: &Individual) -> &'static str {
+    match individual {
+        Individual::Healthy => "#2ca02c",
+        Individual::Infected1 => "#ffbb78",
+        Individual::Infected2 => "#ff7f0e",
+        Individual::Infected3 => "#d62728",
+        Individual::Sick => "#7f7f7f",
+        Individual::Immune => "#1f77b4",
+        Individual::HealthySuperspreader => "#98df8a",
+        Individual::Superspreader1 => "#c5b0d5",
+        Individual::Superspreader2 => "#9467bd",
+        Individual::Superspreader3 => "#8c564b",
+        Individual::Vaccinated => "#aec7e8",
+        Individual::Exposed => "#dbdb8d",
+        Individual::Recovered => "#17becf",
+        Individual::PartiallyVaccinated => "#9edae5",
+    }
+}
+
+/// Color of seats that must stay empty, see `Building::set_limit`.
+pub(super) const BLOCKED: &str = "#d9d9d9";
+
+/// Options for rendering buildings and boards as SVG images.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// # use virus_alarm::render::SvgOptions;
+/// let options = SvgOptions { buildings_per_row: 2, ..Default::default() };
+/// let svg = Board::default().render_svg(&options);
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvgOptions {
+    /// Side of each seat, in pixels
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub seat_size: f64,
+    /// Number of buildings in each row of the image
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub buildings_per_row: usize,
+    /// Whether the image ends with the color of each individual variant
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub key: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions { seat_size: 20., buildings_per_row: 4, key: true }
+    }
+}
+
+/// Returns `text` with the characters that are special in XML escaped.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Returns the width and height of `building` in an image.
+fn size(building: &Building, options: &SvgOptions) -> (f64, f64) {
+    let (rows, columns) = building.people().dim();
+    (columns as f64 * options.seat_size, HEADER + rows as f64 * options.seat_size)
+}
+
+/// Writes `building` on `svg`, with its top left corner at `(x, y)`.
+fn write_building(svg: &mut String, building: &Building, x: f64, y: f64, options: &SvgOptions) {
+    let (width, height) = size(building, options);
+    let seat = options.seat_size;
+    let status = if building.is_open() { "" } else { ", closed" };
+    writeln!(svg, "<g{}>", if building.is_open() { "" } else { " opacity=\"0.5\"" }).expect("writing on a string can not fail");
+    writeln!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"14\">{} ({}/{}{})</text>",
+        x, y + HEADER - 6., escape(building.name()), building.occupancy(), building.capacity(), status).expect("writing on a string can not fail");
+    let columns = building.people().ncols();
+    for ((row, column), individual) in building.people().indexed_iter() {
+        let fill = match individual {
+            Some(individual) => color(individual),
+            None if building.blocked_seats().binary_search(&(row * columns + column)).is_ok() => BLOCKED,
+            None => "white",
+        };
+        write!(svg, "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" stroke=\"black\" stroke-width=\"0.5\">",
+            x + column as f64 * seat, y + HEADER + row as f64 * seat, seat, seat, fill).expect("writing on a string can not fail");
+        match individual {
+            Some(individual) => writeln!(svg, "<title>{}</title></rect>", individual),
+            None => writeln!(svg, "</rect>"),
+        }.expect("writing on a string can not fail");
+    }
+    let dash = if building.is_open() { "" } else { " stroke-dasharray=\"4\"" };
+    writeln!(svg, "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" stroke=\"black\" stroke-width=\"2\"{}/>",
+        x, y + HEADER, width, height - HEADER, dash).expect("writing on a string can not fail");
+    svg.push_str("</g>\n");
+}
+
+/// Writes the color of each individual variant on `svg`, starting at height `y`.
+fn write_key(svg: &mut String, y: f64) {
+    for (counter, individual) in Individual::iter().enumerate() {
+        let top = y + counter as f64 * KEY_LINE;
+        writeln!(svg, "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"12\" height=\"12\" fill=\"{}\" stroke=\"black\" stroke-width=\"0.5\"/>",
+            PADDING, top, color(&individual)).expect("writing on a string can not fail");
+        writeln!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\">{}</text>", PADDING + 18., top + 11., individual).expect("writing on a string can not fail");
+    }
+}
+
+/// Returns a self-contained SVG image of `buildings`, see `Board::render_svg`.
+fn render_buildings(buildings: &[Building], options: &SvgOptions) -> String {
+    let mut body = String::new();
+    let (mut width, mut y): (f64, f64) = (0., PADDING);
+    for row in buildings.chunks(options.buildings_per_row.max(1)) {
+        let mut x = PADDING;
+        let mut row_height: f64 = 0.;
+        for building in row {
+            let (building_width, building_height) = size(building, options);
+            write_building(&mut body, building, x, y, options);
+            x += building_width.max(HEADER) + PADDING;
+            row_height = row_height.max(building_height);
+        }
+        width = width.max(x);
+        y += row_height + PADDING;
+    }
+    if options.key {
+        write_key(&mut body, y);
+        y += Individual::iter().len() as f64 * KEY_LINE + PADDING;
+        width = width.max(200.);
+    }
+    format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\">\n{}</svg>\n", width, y, body)
+}
+
+impl Building {
+    /// Returns a self-contained SVG image of the building, see `Board::render_svg`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::Building;
+    /// # use virus_alarm::render::SvgOptions;
+    /// let mut building = Building::new(2, 2, "Shop");
+    /// building.try_push(Individual::Infected1).unwrap();
+    /// let svg = building.render_svg(&SvgOptions::default());
+    /// assert!(svg.contains("<title>Infected1</title>"));
+    /// ```
+    pub fn render_svg(&self, options: &SvgOptions) -> String {
+        render_buildings(std::slice::from_ref(self), options)
+    }
+}
+
+impl Board {
+    /// Returns a self-contained SVG image of the buildings of the board,
+    /// with each seat colored by the variant of its occupant, see `color`.
+    ///
+    /// Buildings are drawn in rows, closed buildings are faded and
+    /// seats that must stay empty are gray. Each occupied seat has the variant as tooltip.
+    /// Individuals are only in the buildings while visiting, see `visit`.
+    pub fn render_svg(&self, options: &SvgOptions) -> String {
+        render_buildings(self.buildings(), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_building() {
+        let mut building = Building::new(3, 1, "Fish & Chips");
+        building.set_capacity_fraction(0.5).unwrap();
+        assert!(building.try_push(Individual::Sick).is_err());
+        building.try_push(Individual::Immune).unwrap();
+        let options = SvgOptions { key: false, ..Default::default() };
+        let svg = building.render_svg(&options);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"80\" height=\"60\">"));
+        assert!(svg.contains("Fish &amp; Chips (1/3)"));
+        assert!(svg.contains(&format!("fill=\"{}\"", color(&Individual::Immune))));
+        assert_eq!(svg.matches(BLOCKED).count(), 1);
+        assert_eq!(svg.matches("<rect").count(), 4);
+    }
+
+    #[test]
+    fn render_board() {
+        let mut board = Board::default();
+        board.close("School").unwrap();
+        board.visit_with(&mut crate::tests::rng(3));
+        let svg = board.render_svg(&SvgOptions::default());
+        assert_eq!(svg.matches("<g").count(), 8);
+        assert_eq!(svg.matches("<g opacity=\"0.5\">").count(), 1);
+        let occupied = board.buildings().iter().map(|building| building.occupancy()).sum::<usize>();
+        assert_eq!(svg.matches("</title></rect>").count(), occupied);
+        assert!(svg.contains("Recovered</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
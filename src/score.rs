@@ -0,0 +1,131 @@
+use crate::{Board, Individual};
+use strum::IntoEnumIterator;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Points given for the outcome of a game, see `Score`.
+///
+/// The penalty of each building is always paid for every day it is closed, see `Building::penalty`.
+/// By default, as in the board game, players score one point per inhabitant kept healthy,
+/// and vaccines and sick individuals cost nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScoringRules {
+    /// Points for each individual that is neither infected nor sick at the end
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub per_healthy: isize,
+    /// Points for each sick individual at the end, usually non-positive
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub per_sick: isize,
+    /// Points for each vaccine used, usually non-positive
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub per_vaccine: isize,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules { per_healthy: 1, per_sick: 0, per_vaccine: 0 }
+    }
+}
+
+/// Score of a game, see `Board::score`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Score {
+    /// Number of individuals that are neither infected nor sick
+    pub healthy: usize,
+    /// Number of sick individuals
+    pub sick: usize,
+    /// Number of vaccines used, see `Recording::vaccines_used`
+    pub vaccines_used: usize,
+    /// Total penalty of closing buildings, see `Recording::daily_score`
+    pub closure_penalty: usize,
+    /// Final score
+    pub total: isize,
+}
+
+impl ScoringRules {
+    /// Returns the score of the game played in `board` so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use virus_alarm::score::ScoringRules;
+    /// let mut board = Board::default();
+    /// board.immunize_many(3);
+    /// let rules = ScoringRules { per_vaccine: -2, ..Default::default() };
+    /// let score = rules.score(&board);
+    /// assert_eq!((score.healthy, score.vaccines_used), (98, 3));
+    /// assert_eq!(score.total, 98 - 6);
+    /// ```
+    pub fn score(&self, board: &Board) -> Score {
+        let population = board.population();
+        let healthy = Individual::iter()
+            .filter(|individual| !individual.is_infected() && individual != &Individual::Sick)
+            .map(|individual| population.counting(individual))
+            .sum();
+        let sick = population.counting(Individual::Sick);
+        let recording = board.recording();
+        let vaccines_used = *recording.vaccines_used();
+        // The daily score keeps the penalty of buildings that were removed
+        let closure_penalty = -recording.daily_score().iter().sum::<isize>() as usize;
+        let total = self.per_healthy * healthy as isize
+            + self.per_sick * sick as isize
+            + self.per_vaccine * vaccines_used as isize
+            - closure_penalty as isize;
+        Score { healthy, sick, vaccines_used, closure_penalty, total }
+    }
+}
+
+impl Board {
+    /// Returns the score of the game played so far, with the default rules.
+    ///
+    /// See `ScoringRules` for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::BuildingBuilder;
+    /// let population = Population::from(vec![Individual::Healthy; 10]);
+    /// let school = BuildingBuilder::new("School").with_size(2, 2).with_penalty(3).build();
+    /// let mut board = Board::new(population, vec![school]);
+    /// board.close("School").unwrap();
+    /// board.try_advance().unwrap().try_advance().unwrap();
+    /// let score = board.score();
+    /// assert_eq!(score.closure_penalty, 6);
+    /// assert_eq!(score.total, 10 - 6);
+    /// ```
+    pub fn score(&self) -> Score {
+        ScoringRules::default().score(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildingBuilder, Population};
+
+    #[test]
+    fn score() {
+        let population = Population::from(vec![Individual::Healthy, Individual::Healthy, Individual::Healthy, Individual::Sick]);
+        let buildings = vec![
+            BuildingBuilder::new("A").with_size(2, 2).with_penalty(5).build(),
+            BuildingBuilder::new("B").with_size(2, 2).with_penalty(1).build(),
+        ];
+        let mut board = Board::new(population, buildings);
+        board.immunize_many(2);
+        board.reverse_immunize().unwrap();
+        board.close("A").unwrap();
+        board.try_advance().unwrap();
+        board.open("A").unwrap().close("B").unwrap();
+        board.try_advance().unwrap();
+        assert_eq!(board.recording().daily_score(), &vec![0, -5, -1]);
+
+        let rules = ScoringRules { per_healthy: 2, per_sick: -3, per_vaccine: -1 };
+        let score = rules.score(&board);
+        assert_eq!(score, Score { healthy: 3, sick: 1, vaccines_used: 1, closure_penalty: 6, total: 6 - 3 - 1 - 6 });
+    }
+}
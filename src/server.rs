@@ -0,0 +1,202 @@
+//! Small HTTP server, so that many clients can drive one shared simulation backend.
+//!
+//! Requests and responses are JSON. The routes are:
+//! - `POST /simulate`, with a `SimulationBuilder`, runs the simulation and returns its `id`.
+//! - `GET /simulate/{id}` returns the results of a simulation, see `SimulationResults`.
+//! - `POST /board`, with a `BoardBuilder`, replaces the shared board and returns its snapshot.
+//! - `GET /board` returns the snapshot of the shared board, see `DaySnapshot`.
+//! - `POST /board/action`, with an `Action`, applies it to the shared board and returns its snapshot.
+//! - `POST /board/advance` advances the shared board a stage and returns the `DayOutcome`.
+//!
+//! Errors are returned as `{"error": message}` with status 400 for invalid requests and 404 for unknown routes or ids.
+//!
+//! # Remarks
+//!
+//! Requests are handled one at a time, so a long simulation delays the other clients.
+
+use crate::board::{BoardBuilder, DaySnapshot};
+use crate::{Action, Board, Individual, SimulationBuilder};
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+
+/// Results of a simulation, as returned by `GET /simulate/{id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationResults {
+    /// Number of days simulated in each realization, see `Report::days_simulated`
+    pub days_simulated: Vec<usize>,
+    /// Average number of individuals of each variant per day, see `Report::individual_average`
+    pub average: Vec<(Individual, Vec<f64>)>,
+}
+
+/// Response to a request, see `Server::handle`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Response {
+    /// HTTP status code
+    pub status: u16,
+    /// JSON body
+    pub body: String,
+}
+
+impl Response {
+    fn ok<T: Serialize>(value: &T) -> Self {
+        Response { status: 200, body: serde_json::to_string(value).expect("responses can be serialized") }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Self {
+        let body = serde_json::json!({ "error": message.to_string() }).to_string();
+        Response { status, body }
+    }
+}
+
+/// State shared by all clients: the results of the simulations run so far and a board.
+#[derive(Debug, Clone, Default)]
+pub struct Server {
+    results: Vec<SimulationResults>,
+    board: Board,
+}
+
+impl Server {
+    /// Returns a server whose shared board is `board`.
+    pub fn new(board: Board) -> Self {
+        Server { results: Vec::new(), board }
+    }
+
+    /// Returns the shared board.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Answers a request with method `method`, for example `"POST"`, on `path`, with body `body`.
+    ///
+    /// Query strings in `path` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use virus_alarm::server::Server;
+    /// let mut server = Server::default();
+    /// let response = server.handle("POST", "/board/advance", "");
+    /// assert_eq!(response.status, 200);
+    /// assert_eq!(server.board().recording().timeline(), &1);
+    /// ```
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> Response {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("POST", ["simulate"]) => self.simulate(body),
+            ("GET", ["simulate", id]) => match id.parse::<usize>().ok().and_then(|id| self.results.get(id)) {
+                Some(results) => Response::ok(results),
+                None => Response::error(404, format!("There is no simulation with id {}", id)),
+            },
+            ("POST", ["board"]) => match serde_json::from_str::<BoardBuilder>(body)
+                .map_err(|error| error.to_string())
+                .and_then(|board_builder| board_builder.try_build().map_err(|error| error.to_string()))
+            {
+                Ok(board) => {
+                    self.board = board;
+                    Response::ok(&DaySnapshot::new(&self.board))
+                },
+                Err(error) => Response::error(400, error),
+            },
+            ("GET", ["board"]) => Response::ok(&DaySnapshot::new(&self.board)),
+            ("POST", ["board", "action"]) => match serde_json::from_str::<Action>(body) {
+                Ok(action) => match action.apply(&mut self.board) {
+                    Ok(_) => Response::ok(&DaySnapshot::new(&self.board)),
+                    Err(error) => Response::error(400, error),
+                },
+                Err(error) => Response::error(400, error),
+            },
+            ("POST", ["board", "advance"]) => match self.board.play_day() {
+                Ok(outcome) => Response::ok(&outcome),
+                Err(error) => Response::error(400, error),
+            },
+            _ => Response::error(404, format!("There is no route {} {}", method, path)),
+        }
+    }
+
+    fn simulate(&mut self, body: &str) -> Response {
+        let simulation = serde_json::from_str::<SimulationBuilder>(body)
+            .map_err(|error| error.to_string())
+            .and_then(|simulation_builder| simulation_builder.validated().map_err(|error| error.to_string()))
+            .and_then(|simulation_builder| simulation_builder.try_build().map_err(|error| error.to_string()));
+        match simulation {
+            Ok(simulation) => {
+                let report = simulation.run();
+                self.results.push(SimulationResults {
+                    days_simulated: report.days_simulated().clone(),
+                    average: Individual::iter()
+                        .map(|individual| (individual, report.individual_average(&individual).iter().map(|variance| variance.mean()).collect()))
+                        .collect(),
+                });
+                Response::ok(&serde_json::json!({ "id": self.results.len() - 1 }))
+            },
+            Err(error) => Response::error(400, error),
+        }
+    }
+
+    /// Listens on `address`, for example `"0.0.0.0:8000"`, and answers requests forever, see `handle`.
+    ///
+    /// # Errors
+    ///
+    /// If the server can not listen on `address`.
+    pub fn serve(mut self, address: &str) -> std::io::Result<()> {
+        let listener = tiny_http::Server::http(address)
+            .map_err(|error| std::io::Error::other(error.to_string()))?;
+        for mut request in listener.incoming_requests() {
+            let mut body = String::new();
+            let response = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => self.handle(request.method().as_str(), request.url(), &body),
+                Err(error) => Response::error(400, error),
+            };
+            let header = tiny_http::Header::from_bytes("Content-Type", "application/json").expect("the header is valid");
+            let http_response = tiny_http::Response::from_string(response.body)
+                .with_status_code(response.status)
+                .with_header(header);
+            request.respond(http_response).ok();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulate() {
+        let mut server = Server::default();
+        let config = r#"{
+            "board_builder": {
+                "healthy": 3, "infected1": 1, "infected2": 0, "infected3": 0, "sick": 0, "immune": 0,
+                "buildings": [[2, 2]], "spreading": "Everyone"
+            },
+            "report_plan": { "num_simulations": 2, "days": 3 }
+        }"#;
+        let response = server.handle("POST", "/simulate", config);
+        assert_eq!(response, Response { status: 200, body: r#"{"id":0}"#.to_string() });
+        let response = server.handle("GET", "/simulate/0", "");
+        let results: SimulationResults = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(results.days_simulated, vec![3, 3]);
+        assert_eq!(results.average[0], (Individual::Healthy, vec![3., 0., 0., 0.]));
+        assert_eq!(server.handle("GET", "/simulate/1", "").status, 404);
+        assert_eq!(server.handle("POST", "/simulate", &config.replace("\"num_simulations\": 2", "\"num_simulations\": 0")).status, 400);
+    }
+
+    #[test]
+    fn board() {
+        let mut server = Server::default();
+        let response = server.handle("POST", "/board", r#"{ "healthy": 3, "infected1": 1, "infected2": 0, "infected3": 0, "sick": 0, "immune": 0, "buildings": [[2, 2]], "spreading": "Everyone" }"#);
+        let snapshot: DaySnapshot = serde_json::from_str(&response.body).unwrap();
+        assert_eq!((snapshot.healthy, snapshot.infected), (3, 1));
+        let response = server.handle("POST", "/board/action", r#"{"Immunize": 1}"#);
+        assert_eq!(response.status, 200);
+        let response = server.handle("POST", "/board/advance", "");
+        let outcome: crate::board::DayOutcome = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(outcome.newly_infected, 2);
+        assert_eq!(server.handle("POST", "/board/action", r#"{"Close": "Gym"}"#).status, 400);
+        assert_eq!(server.handle("DELETE", "/board", "").status, 404);
+        let snapshot: DaySnapshot = serde_json::from_str(&server.handle("GET", "/board?format=json", "").body).unwrap();
+        assert_eq!(snapshot.day, 1);
+    }
+}
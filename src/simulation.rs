@@ -1,15 +1,71 @@
 use crate::recording::CountingTable;
 use crate::prelude::{Board, BoardBuilder};
 use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
 
 pub mod report;
+#[cfg(feature = "unstable")]
+pub mod engine;
+#[cfg(not(feature = "unstable"))]
+mod engine;
+#[cfg(feature = "unstable")]
+pub mod experiment;
+#[cfg(feature = "unstable")]
+pub mod optimize;
+#[cfg(feature = "unstable")]
+pub mod sensitivity;
+#[cfg(feature = "unstable")]
+pub mod calibrate;
+#[cfg(feature = "unstable")]
+pub mod bounds;
+#[cfg(feature = "unstable")]
+pub mod compare;
+pub mod handle;
+mod sheet;
+mod config;
+mod statistics;
+#[cfg(feature = "unstable")]
+pub mod policy;
+#[cfg(feature = "unstable")]
+pub mod failure;
+#[cfg(feature = "unstable")]
+pub mod daemon;
+#[cfg(feature = "sqlite")]
+pub mod store;
 
 pub use report::*;
+#[cfg(feature = "unstable")]
+pub use engine::*;
+#[cfg(not(feature = "unstable"))]
+use engine::MonteCarlo;
+#[cfg(feature = "unstable")]
+pub use experiment::*;
+#[cfg(feature = "unstable")]
+pub use optimize::*;
+#[cfg(feature = "unstable")]
+pub use sensitivity::*;
+#[cfg(feature = "unstable")]
+pub use calibrate::*;
+#[cfg(feature = "unstable")]
+pub use bounds::*;
+#[cfg(feature = "unstable")]
+pub use compare::*;
+pub use handle::*;
+#[cfg(feature = "unstable")]
+pub use policy::*;
+#[cfg(feature = "unstable")]
+pub use failure::*;
+#[cfg(feature = "unstable")]
+pub use daemon::*;
+#[cfg(feature = "sqlite")]
+pub use store::*;
 
 /// Builder for `Simulation`.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, Setters, MutGetters, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SimulationBuilder {
     /// Board setup
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
@@ -17,12 +73,114 @@ pub struct SimulationBuilder {
     /// Report setup
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
     pub report_plan: ReportPlan,
+    /// Scheduled interventions, applied in every realization
+    #[cfg(feature = "unstable")]
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interventions: Vec<Intervention>,
+    /// Engine used to run the simulation, which is not part of serialized configurations
+    #[cfg(feature = "unstable")]
+    #[getset(set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "schema", schemars(skip))]
+    pub engine: SelectedEngine,
 }
 
 impl SimulationBuilder {
+	/// Changes the engine used to run the simulation, see `Simulation::run_with`.
+	///
+	/// Engines other than `MonteCarlo` can not apply interventions, see `try_build`,
+	/// and seeded runs always use `MonteCarlo`, see `Simulation::run_seeded`.
+	///
+	/// Requires the `unstable` feature.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// use virus_alarm::simulation::MonteCarlo;
+	/// let simulation = SimulationBuilder::default()
+	///     .engine(MonteCarlo)
+	///     .build();
+	/// assert!(!simulation.engine().is_default());
+	/// ```
+	#[cfg(feature = "unstable")]
+	pub fn engine<E: Engine + Send + Sync + 'static>(mut self, engine: E) -> Self {
+		self.engine = SelectedEngine::new(engine);
+		self
+	}
+
+	/// Returns the simulation, after checking the vaccine supply of `BoardBuilder::vaccines`.
+	///
+	/// # Errors
+	///
+	/// If there are more vaccines than healthy individuals, 
+	/// more vaccines are scheduled in interventions than the ones available, 
+	/// an intervention names a building that is not in the board, or
+	/// there are interventions but the engine is not `MonteCarlo`, see `SimulationBuilder::engine`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use virus_alarm::prelude::*;
+	/// let simulation_builder = SimulationBuilder {
+	///     board_builder: BoardBuilder { healthy: 10, vaccines: Some(20), ..Default::default() },
+	///     ..Default::default()
+	/// };
+	/// assert!(simulation_builder.try_build().is_err());
+	/// ```
+	pub fn try_build(self) -> Result<Simulation, crate::errors::SimulationError> {
+		let simulation = self.build();
+		if let Some(vaccines) = *simulation.board.vaccines() {
+			let healthy = crate::board::vaccinatable(simulation.board.population());
+			if vaccines > healthy {
+				return Err(crate::errors::SimulationError::TooManyVaccines { vaccines, healthy });
+			}
+			#[cfg(feature = "unstable")]
+			{
+				let scheduled: usize = simulation.interventions.iter()
+					.map(|intervention| match intervention.action {
+						crate::Action::Immunize(num) => num,
+						_ => 0,
+					})
+					.sum();
+				if scheduled > vaccines {
+					return Err(crate::errors::SimulationError::VaccinesExceeded { scheduled, vaccines });
+				}
+			}
+		}
+		#[cfg(feature = "unstable")]
+		if !simulation.interventions.is_empty() && !simulation.engine.is_default() {
+			return Err(crate::errors::SimulationError::InterventionsWithEngine);
+		}
+		#[cfg(feature = "unstable")]
+		for intervention in simulation.interventions.iter() {
+			match &intervention.action {
+				crate::Action::Close(name) 
+				| crate::Action::Open(name) 
+				| crate::Action::Toggle(name) 
+				| crate::Action::ReduceCapacity(name, _) if simulation.board.building(name).is_none() => {
+					return Err(crate::errors::SimulationError::UnknownBuilding { day: intervention.day, name: name.clone() });
+				},
+				_ => (),
+			}
+		}
+		Ok(simulation)
+	}
+
+	/// Returns the simulation, without checking the vaccine supply.
+	///
+	/// Vaccines are still limited during the game, see `Board::set_vaccines`.
 	pub fn build(self) -> Simulation {
 		let board = self.board_builder.build();
-		Simulation { board, report_plan: self.report_plan }
+		Simulation { 
+			board, 
+			report_plan: self.report_plan, 
+			#[cfg(feature = "unstable")]
+			interventions: self.interventions,
+			#[cfg(feature = "unstable")]
+			engine: self.engine,
+		}
 	}
 }
 
@@ -37,6 +195,14 @@ pub struct Simulation {
     /// Report plan that determines the result announced after running the simulation.
     #[getset(get = "pub")]
     report_plan: ReportPlan,
+    /// Interventions applied during the simulation.
+    #[cfg(feature = "unstable")]
+    #[getset(get = "pub")]
+    interventions: Vec<Intervention>,
+    /// Engine used to run the simulation
+    #[cfg(feature = "unstable")]
+    #[getset(get = "pub")]
+    engine: SelectedEngine,
 }
 
 impl Simulation {
@@ -47,22 +213,174 @@ impl Simulation {
     /// ```
     /// # use virus_alarm::prelude::*;
     /// let board = Board::default();
-    /// let report_plan = ReportPlan { num_simulations: 10, days: 10 };
+    /// let report_plan = ReportPlan { num_simulations: 10, days: 10, ..Default::default() };
     /// Simulation::new(board, report_plan);
     /// ```
     pub fn new(board: Board, report_plan: ReportPlan) -> Self {
-        Self { board, report_plan }
+        Self { 
+            board, 
+            report_plan, 
+            #[cfg(feature = "unstable")]
+            interventions: Vec::new(),
+            #[cfg(feature = "unstable")]
+            engine: SelectedEngine::default(),
+        }
+    }
+
+    /// Returns the simulation of `num_simulations` games with the board and number of rounds of `ruleset`.
+    ///
+    /// Nobody takes actions during the simulation, so action points are not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use virus_alarm::game::Ruleset;
+    /// let simulation = Simulation::from_ruleset(&Ruleset::official("beginner").unwrap(), 5);
+    /// assert_eq!(simulation.report_plan().days, 10);
+    /// assert_eq!(simulation.board().vaccines(), &Some(30));
+    /// ```
+    pub fn from_ruleset(ruleset: &crate::game::Ruleset, num_simulations: usize) -> Self {
+        let report_plan = ReportPlan { num_simulations, days: ruleset.rules.days, ..Default::default() };
+        Simulation::new(ruleset.board(), report_plan)
+    }
+
+    /// Changes the interventions applied during the simulation.
+    ///
+    /// Requires the `unstable` feature.
+    ///
+    /// # Examples
+    ///
+    /// Close the school from the third day on.
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let board = Board::default();
+    /// let report_plan = ReportPlan { num_simulations: 10, days: 10, ..Default::default() };
+    /// let simulation = Simulation::new(board, report_plan)
+    ///     .with_interventions(vec![Intervention::new(3, Action::Close("School".to_string()))]);
+    /// simulation.run();
+    /// ```
+    #[cfg(feature = "unstable")]
+    pub fn with_interventions(mut self, interventions: Vec<Intervention>) -> Self {
+        self.interventions = interventions;
+        self
     }
 
     /// Returns the result of the simulation.
+    ///
+    /// # Remarks
+    ///
+    /// Interventions are applied in each realization right before the stage of their day.
     pub fn run(&self) -> Report {
-        let mut counting_tables = Vec::new();
-        for _ in 0..*self.report_plan.num_simulations() {
-            let mut board = self.board.clone();
-            board.advance_many(*self.report_plan.days());
-            counting_tables.push(board.counting_table().clone());
+        self.run_with(&mut rand::thread_rng())
+    }
+
+    /// Returns the result of the simulation, using `rng` as the source of randomness.
+    ///
+    /// The simulation is run by `MonteCarlo`, unless another engine was selected, see `SimulationBuilder::engine`.
+    /// Other engines run the whole simulation on their own, so interventions are not applied,
+    /// which `SimulationBuilder::try_build` rejects.
+    pub fn run_with(&self, rng: &mut dyn rand::RngCore) -> Report {
+        self.run_cancellable_with(rng, &SimulationHandle::new())
+    }
+
+    /// Returns the result of the simulation, stopping early if `handle` is cancelled.
+    ///
+    /// If the simulation is cancelled, the report has only the realizations that were completed.
+    /// See `SimulationHandle` for an example.
+    pub fn run_cancellable(&self, handle: &SimulationHandle) -> Report {
+        self.run_cancellable_with(&mut rand::thread_rng(), handle)
+    }
+
+    /// Returns the result of the simulation, using `rng` as the source of randomness
+    /// and stopping early if `handle` is cancelled, see `run_cancellable`.
+    ///
+    /// Selected engines are given `handle`, see `Engine::run_cancellable`.
+    pub fn run_cancellable_with(&self, rng: &mut dyn rand::RngCore, handle: &SimulationHandle) -> Report {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("simulation", num_simulations = self.report_plan.num_simulations, days = self.report_plan.days).entered();
+        #[cfg(feature = "unstable")]
+        if let Some(engine) = self.engine.custom() {
+            return engine.run_cancellable(&self.board, &self.report_plan, rng, handle);
+        }
+        MonteCarlo.run_with(&self.board, &self.report_plan, rng, handle, |day, board, rng| self.intervene(day, board, rng))
+    }
+
+    /// Returns the result of the simulation with one realization per seed, 
+    /// each one using `random::Pcg32::new(seed)` as its source of randomness.
+    ///
+    /// The number of simulations of the report plan is ignored. The seeds are kept in the report, see `Report::seeds`,
+    /// and a simulation that drew them, see `MonteCarlo`, gives the same report.
+    /// Seeds are those of `MonteCarlo` realizations, so it is always the engine used, 
+    /// even if another one was selected, see `SimulationBuilder::engine`. 
+    /// The same holds for `run_paired` and `replay`.
+    pub fn run_seeded(&self, seeds: &[u64]) -> Report {
+        MonteCarlo.run_seeded_with(&self.board, &self.report_plan, seeds, &SimulationHandle::new(), |day, board, rng| self.intervene(day, board, rng))
+    }
+
+    /// Returns the results of this simulation and `other` with common random numbers: 
+    /// both play the same number of realizations as this one, and the realizations with the same index
+    /// use the same seed, see `run_seeded`.
+    ///
+    /// Paired realizations of two scenarios, for example with and without an intervention, 
+    /// consume the same random numbers for as long as the scenarios behave the same,
+    /// so that the difference between them is mostly due to the intervention and not to chance.
+    /// `Report::diff` of paired reports uses the paired differences, which gives much tighter confidence intervals.
+    ///
+    /// Requires the `unstable` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report_plan = ReportPlan { num_simulations: 20, days: 10, ..Default::default() };
+    /// let baseline = Simulation::new(Board::default(), report_plan.clone());
+    /// let mut board = Board::default();
+    /// board.immunize_many(20);
+    /// let vaccination = Simulation::new(board, report_plan);
+    /// let (vaccination, baseline) = vaccination.run_paired(&baseline);
+    /// assert_eq!(vaccination.seeds(), baseline.seeds());
+    /// let diff = vaccination.diff(&baseline);
+    /// assert!(diff.paired);
+    /// ```
+    #[cfg(feature = "unstable")]
+    pub fn run_paired(&self, other: &Simulation) -> (Report, Report) {
+        self.run_paired_with(other, &mut rand::thread_rng())
+    }
+
+    /// Returns the results of this simulation and `other` with common random numbers,
+    /// drawing the seeds from `rng`, see `run_paired`.
+    #[cfg(feature = "unstable")]
+    pub fn run_paired_with(&self, other: &Simulation, rng: &mut dyn rand::RngCore) -> (Report, Report) {
+        let seeds: Vec<u64> = (0..self.report_plan.num_simulations).map(|_| rng.next_u64()).collect();
+        (self.run_seeded(&seeds), other.run_seeded(&seeds))
+    }
+
+    /// Returns the board at the end of the realization with seed `seed`, 
+    /// playing it again as a simulation does, see `Report::seeds`.
+    ///
+    /// The recording of the board has the whole realization, ready to be inspected or rendered,
+    /// for example to see how the best or worst realizations unfolded, see `Report::best_realizations`.
+    pub fn replay(&self, seed: u64) -> Board {
+        let mut board = self.board.clone();
+        let mut rng = crate::random::Pcg32::new(seed);
+        let mut days = 0;
+        while days < self.report_plan.days && !self.report_plan.should_stop(&board) {
+            self.intervene(days, &mut board, &mut rng);
+            board.try_advance_with(&mut rng).expect("the board is in a corrupted state");
+            days += 1;
+        }
+        board
+    }
+
+    /// Applies the interventions of `day` to `board`, if any.
+    fn intervene(&self, _day: usize, _board: &mut Board, _rng: &mut crate::random::Pcg32) {
+        #[cfg(feature = "unstable")]
+        for intervention in self.interventions.iter() {
+            // Buildings are checked in `SimulationBuilder::try_build`, so actions only fail 
+            // when the epidemic leaves no one to act on, and then they are applied as much as possible
+            let _ = intervention.apply_on_with(_day, _board, _rng);
         }
-        Report { counting_tables }
     }
 
     // /// Returns the result of the last day of the simulation, 
@@ -86,6 +404,8 @@ impl Simulation {
 }
 
 #[cfg(test)]
+// Struct updates fill the fields of the `unstable` feature
+#[cfg_attr(not(feature = "unstable"), allow(clippy::needless_update))]
 mod tests {
     use super::*;
     use crate::{Individual, building::Spreading};
@@ -102,11 +422,14 @@ mod tests {
                     immune: 20,
                     buildings: vec![(0, 0)],
                     spreading: Spreading::OneNear,
+                    ..Default::default()
             },
             report_plan: ReportPlan{
                     num_simulations: 1,
                     days: 0,
-            }
+                    ..Default::default()
+            },
+            ..Default::default()
         };
         let simulation = simulation_builder.build();
         let report = simulation.run();
@@ -116,7 +439,15 @@ mod tests {
             (Individual::Infected2, vec![0]), 
             (Individual::Infected3, vec![0]), 
             (Individual::Sick, vec![3]), 
-            (Individual::Immune, vec![20])]);
+            (Individual::Immune, vec![20]),
+            (Individual::HealthySuperspreader, vec![0]),
+            (Individual::Superspreader1, vec![0]),
+            (Individual::Superspreader2, vec![0]),
+            (Individual::Superspreader3, vec![0]),
+            (Individual::Vaccinated, vec![0]),
+            (Individual::Exposed, vec![0]),
+            (Individual::Recovered, vec![0]),
+            (Individual::PartiallyVaccinated, vec![0])]);
         assert_eq!(report.counting_tables(), &vec![expected]);
     }
 
@@ -132,11 +463,14 @@ mod tests {
                     immune: 20,
                     buildings: vec![(2, 2)],
                     spreading: Spreading::OneNear,
+                    ..Default::default()
             },
             report_plan: ReportPlan{
                     num_simulations: 1,
                     days: 1,
-            }
+                    ..Default::default()
+            },
+            ..Default::default()
         };
         let simulation = simulation_builder.build();
         let report = simulation.run();
@@ -146,7 +480,15 @@ mod tests {
             (Individual::Infected2, vec![0, 0]), 
             (Individual::Infected3, vec![0, 0]), 
             (Individual::Sick, vec![3, 3]), 
-            (Individual::Immune, vec![20, 20])]);
+            (Individual::Immune, vec![20, 20]),
+            (Individual::HealthySuperspreader, vec![0, 0]),
+            (Individual::Superspreader1, vec![0, 0]),
+            (Individual::Superspreader2, vec![0, 0]),
+            (Individual::Superspreader3, vec![0, 0]),
+            (Individual::Vaccinated, vec![0, 0]),
+            (Individual::Exposed, vec![0, 0]),
+            (Individual::Recovered, vec![0, 0]),
+            (Individual::PartiallyVaccinated, vec![0, 0])]);
         assert_eq!(report.counting_tables(), &vec![expected]);
     }
 
@@ -162,11 +504,14 @@ mod tests {
                     immune: 0,
                     buildings: vec![(200, 200)],
                     spreading: Spreading::OneNear,
+                    ..Default::default()
             },
             report_plan: ReportPlan{
                     num_simulations: 1,
                     days: 1,
-            }
+                    ..Default::default()
+            },
+            ..Default::default()
         };
         let simulation = simulation_builder.build();
         let report = simulation.run();
@@ -176,7 +521,15 @@ mod tests {
             (Individual::Infected2, vec![0, 1]), 
             (Individual::Infected3, vec![0, 0]), 
             (Individual::Sick, vec![3, 3]), 
-            (Individual::Immune, vec![0, 0])]);
+            (Individual::Immune, vec![0, 0]),
+            (Individual::HealthySuperspreader, vec![0, 0]),
+            (Individual::Superspreader1, vec![0, 0]),
+            (Individual::Superspreader2, vec![0, 0]),
+            (Individual::Superspreader3, vec![0, 0]),
+            (Individual::Vaccinated, vec![0, 0]),
+            (Individual::Exposed, vec![0, 0]),
+            (Individual::Recovered, vec![0, 0]),
+            (Individual::PartiallyVaccinated, vec![0, 0])]);
         assert_eq!(report.counting_tables(), &vec![expected]);
     }
 
@@ -192,11 +545,14 @@ mod tests {
                     immune: 0,
                     buildings: vec![(200, 200)],
                     spreading: Spreading::OneNear,
+                    ..Default::default()
             },
             report_plan: ReportPlan{
                     num_simulations: 1,
                     days: 1,
-            }
+                    ..Default::default()
+            },
+            ..Default::default()
         };
         let report = simulation_builder.build().run();
         let result = vec![
@@ -217,4 +573,125 @@ mod tests {
             ];
         assert_eq!(result, expected);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn replay() {
+        let report_plan = ReportPlan { num_simulations: 5, days: 6, ..Default::default() };
+        let simulation = Simulation::new(Board::default(), report_plan);
+        let report = simulation.run_with(&mut crate::tests::rng(1));
+        for realization in report.best_realizations(1).into_iter().chain(report.worst_realizations(1)) {
+            let board = simulation.replay(realization.seed.unwrap());
+            assert_eq!(&board.recording().reported_counting_table(), realization.counting_table);
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn run_with_interventions() {
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder{
+                    healthy: 100,
+                    infected1: 1,
+                    infected2: 0,
+                    infected3: 0,
+                    sick: 0,
+                    immune: 0,
+                    buildings: vec![(200, 200)],
+                    spreading: Spreading::OneNear,
+                    ..Default::default()
+            },
+            report_plan: ReportPlan{
+                    num_simulations: 1,
+                    days: 2,
+                    ..Default::default()
+            },
+            interventions: vec![Intervention::new(1, crate::Action::Immunize(99))],
+            ..Default::default()
+        };
+        let report = simulation_builder.build().run();
+        let expected = CountingTable::from(vec![
+            (Individual::Healthy, vec![100, 0, 0]), 
+            (Individual::Infected1, vec![1, 1, 0]), 
+            (Individual::Infected2, vec![0, 1, 1]), 
+            (Individual::Infected3, vec![0, 0, 1]), 
+            (Individual::Sick, vec![0, 0, 0]), 
+            (Individual::Immune, vec![0, 99, 99]),
+            (Individual::HealthySuperspreader, vec![0, 0, 0]),
+            (Individual::Superspreader1, vec![0, 0, 0]),
+            (Individual::Superspreader2, vec![0, 0, 0]),
+            (Individual::Superspreader3, vec![0, 0, 0]),
+            (Individual::Vaccinated, vec![0, 0, 0]),
+            (Individual::Exposed, vec![0, 0, 0]),
+            (Individual::Recovered, vec![0, 0, 0]),
+            (Individual::PartiallyVaccinated, vec![0, 0, 0])]);
+        assert_eq!(report.counting_tables(), &vec![expected]);
+    }
+
+    #[test]
+    fn try_build() {
+        let mut simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder { healthy: 10, superspreaders: 2, buildings: vec![(2, 2)], vaccines: Some(11), ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(simulation_builder.clone().try_build().err(), Some(crate::errors::SimulationError::TooManyVaccines { vaccines: 11, healthy: 10 }));
+        simulation_builder.board_builder.set_vaccines(Some(3));
+        let simulation = simulation_builder.try_build().unwrap();
+        assert_eq!(simulation.board().vaccines(), &Some(3));
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn engine() {
+        struct Initial;
+
+        impl Engine for Initial {
+            fn run(&self, board: &Board, report_plan: &ReportPlan, _rng: &mut dyn rand::RngCore) -> Report {
+                Report::from(vec![board.counting_table().clone(); report_plan.num_simulations])
+            }
+        }
+
+        let report_plan = ReportPlan { num_simulations: 2, days: 5, ..Default::default() };
+        let simulation_builder = SimulationBuilder { report_plan, ..Default::default() }.engine(Initial);
+        let simulation = simulation_builder.clone().build();
+        assert_eq!(simulation.run().days(), 1);
+        let handle = SimulationHandle::new();
+        handle.cancel();
+        assert!(simulation.run_cancellable(&handle).counting_tables().is_empty());
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder { healthy: 10, ..Default::default() },
+            interventions: vec![Intervention::new(0, crate::Action::Immunize(2))],
+            ..simulation_builder
+        };
+        assert_eq!(simulation_builder.try_build().err(), Some(crate::errors::SimulationError::InterventionsWithEngine));
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn try_build_scheduled() {
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder { healthy: 10, buildings: vec![(2, 2)], vaccines: Some(3), ..Default::default() },
+            report_plan: ReportPlan { num_simulations: 1, days: 2, ..Default::default() },
+            interventions: vec![Intervention::new(0, crate::Action::Immunize(2)), Intervention::new(1, crate::Action::Immunize(2))],
+            ..Default::default()
+        };
+        assert_eq!(simulation_builder.clone().try_build().err(), Some(crate::errors::SimulationError::VaccinesExceeded { scheduled: 4, vaccines: 3 }));
+        let report = simulation_builder.build().run();
+        assert_eq!(report.counting_tables()[0].inner()[&Individual::Immune], vec![2, 3, 3]);
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn try_build_unknown_building() {
+        let mut simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder { healthy: 10, buildings: vec![(2, 2)], ..Default::default() },
+            interventions: vec![Intervention::new(2, crate::Action::Close("Buildin 1".to_string()))],
+            ..Default::default()
+        };
+        assert_eq!(
+            simulation_builder.clone().try_build().err(), 
+            Some(crate::errors::SimulationError::UnknownBuilding { day: 2, name: "Buildin 1".to_string() })
+        );
+        simulation_builder.interventions[0].action = crate::Action::Close("Building 1".to_string());
+        assert!(simulation_builder.try_build().is_ok());
+    }
+}
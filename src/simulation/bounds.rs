@@ -0,0 +1,282 @@
+use crate::prelude::{Board, Individual, Simulation, Spreading};
+use getset::Getters;
+
+/// Deterministic bounds on the number of newly infected individuals per day.
+///
+/// The worst case places individuals adversarially every day, while the best case
+/// places them optimally. Comparing these bounds with Monte Carlo averages
+/// shows how much the outcome of the game depends on the luck of the placement.
+///
+/// # Remarks
+///
+/// Bounds are computed for a building-level contact model, where the position of seats is ignored:
+/// - `Everyone`, and `Airborne` and `DistanceDecay` with positive percentage,
+///   infect every healthy individual in a building with an infectious one.
+/// - `Airborne` and `DistanceDecay` with zero percentage infect no one.
+/// - The remaining spreading modes infect one healthy individual per infectious one in the same building.
+///
+/// If buildings have different spreading modes, the worst case assumes the most infectious one 
+/// in every building and the best case the least infectious one.
+///
+/// Buildings are filled as with `Visiting::InOrder`. Superspreaders are counted as regular individuals
+/// and the latent stage, waning immunity and vaccine efficacy are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 10, days: 5, ..Default::default() });
+/// let bounds = simulation.bounds();
+/// assert_eq!(bounds.worst().len(), 5);
+/// assert!(bounds.best().iter().zip(bounds.worst()).all(|(best, worst)| best <= worst));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters)]
+pub struct Bounds {
+    /// Newly infected individuals per day if individuals are placed adversarially every day
+    #[getset(get = "pub")]
+    worst: Vec<usize>,
+    /// Newly infected individuals per day if individuals are placed optimally every day
+    #[getset(get = "pub")]
+    best: Vec<usize>,
+}
+
+impl Bounds {
+    /// Computes the bounds for the given number of days, starting from `board`.
+    pub fn new(board: &Board, days: usize) -> Self {
+        let contacts = board.buildings().iter().map(|building| Contact::from(*building.spreading()));
+        let worst_contact = contacts.clone().max().unwrap_or(Contact::Nobody);
+        let best_contact = contacts.min().unwrap_or(Contact::Nobody);
+        let capacities: Vec<usize> = board.buildings().iter()
+            .filter(|building| building.is_open())
+            .map(|building| building.available_seats())
+            .collect();
+        let state = State::from(board);
+        Bounds {
+            worst: state.clone().evolve(days, |state| state.most_infections(worst_contact, &capacities)),
+            best: state.evolve(days, |state| state.least_infections(best_contact, &capacities)),
+        }
+    }
+
+    /// Returns the total number of newly infected individuals in the worst case.
+    pub fn worst_total(&self) -> usize {
+        self.worst.iter().sum()
+    }
+
+    /// Returns the total number of newly infected individuals in the best case.
+    pub fn best_total(&self) -> usize {
+        self.best.iter().sum()
+    }
+}
+
+impl Simulation {
+    /// Returns the deterministic bounds for the days of the report plan, see `Bounds`.
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(self.board(), *self.report_plan().days())
+    }
+}
+
+/// Building-level abstraction of a spreading mode, from the least to the most infectious.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Contact {
+    /// No one can be infected
+    Nobody,
+    /// One healthy individual per infectious one can be infected
+    One,
+    /// Every healthy individual in the building can be infected
+    Everyone,
+}
+
+impl From<Spreading> for Contact {
+    fn from(spreading: Spreading) -> Self {
+        match spreading {
+            Spreading::Everyone => Contact::Everyone,
+            Spreading::Airborne(0) | Spreading::DistanceDecay(0) => Contact::Nobody,
+            Spreading::Airborne(_) | Spreading::DistanceDecay(_) => Contact::Everyone,
+            _ => Contact::One,
+        }
+    }
+}
+
+/// Count of individuals by role in the contact model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct State {
+    /// Individuals who can be infected
+    healthy: usize,
+    /// Infectious individuals, by day of infection
+    infectious: [usize; 3],
+    /// Sick individuals
+    sick: usize,
+    /// Individuals who visit buildings but can not infect nor be infected
+    others: usize,
+}
+
+impl From<&Board> for State {
+    fn from(board: &Board) -> Self {
+        let mut state = State { healthy: 0, infectious: [0; 3], sick: 0, others: 0 };
+        for (individual, count) in board.population().counting_all() {
+            match individual {
+                Individual::Infected1 | Individual::Superspreader1 => state.infectious[0] += count,
+                Individual::Infected2 | Individual::Superspreader2 => state.infectious[1] += count,
+                Individual::Infected3 | Individual::Superspreader3 => state.infectious[2] += count,
+                Individual::Sick => state.sick += count,
+                i if i.is_healthy() => state.healthy += count,
+                _ => state.others += count,
+            }
+        }
+        state
+    }
+}
+
+impl State {
+    /// Returns the newly infected individuals per day, when `newly_infected` are infected every day.
+    fn evolve<F: Fn(&State) -> usize>(mut self, days: usize, newly_infected: F) -> Vec<usize> {
+        let mut trajectory = Vec::with_capacity(days);
+        for _ in 0..days {
+            let newly = newly_infected(&self).min(self.healthy);
+            self.healthy -= newly;
+            self.sick += self.infectious[2];
+            self.infectious = [newly, self.infectious[0], self.infectious[1]];
+            trajectory.push(newly);
+        }
+        trajectory
+    }
+
+    fn total_infectious(&self) -> usize {
+        self.infectious.iter().sum()
+    }
+
+    /// Most infections if every chosen individual can enter any building.
+    fn most_infections(&self, contact: Contact, capacities: &[usize]) -> usize {
+        let infectious = self.total_infectious();
+        match contact {
+            Contact::Nobody => 0,
+            Contact::Everyone => {
+                // One infectious individual in each of the largest buildings
+                let mut sorted = capacities.to_vec();
+                sorted.sort_unstable_by(|a, b| b.cmp(a));
+                let exposed: usize = sorted.iter()
+                    .filter(|&&capacity| capacity > 0)
+                    .take(infectious)
+                    .map(|capacity| capacity - 1)
+                    .sum();
+                exposed.min(self.healthy)
+            },
+            Contact::One => {
+                let pairs: usize = capacities.iter().map(|capacity| capacity / 2).sum();
+                pairs.min(infectious).min(self.healthy)
+            },
+        }
+    }
+
+    /// Least infections if buildings must be filled before anyone stays home.
+    fn least_infections(&self, contact: Contact, capacities: &[usize]) -> usize {
+        let infectious = self.total_infectious();
+        let total_capacity: usize = capacities.iter().sum();
+        let visitors = self.healthy + infectious + self.others;
+        let entrants = total_capacity.min(visitors);
+        // Infectious individuals that must enter a building
+        let forced = entrants.saturating_sub(visitors - infectious);
+        if forced == 0 || contact == Contact::Nobody {
+            return 0;
+        }
+        // Infectious individuals are gathered in the smallest set of buildings that can host them,
+        // which is filled with other individuals before healthy ones.
+        let dirty = smallest_sum_at_least(capacities, forced);
+        let clean = total_capacity - dirty;
+        let others_inside = self.others.min(dirty - forced);
+        let healthy_inside = (self.healthy + self.others - others_inside).saturating_sub(clean);
+        match contact {
+            Contact::Everyone => healthy_inside,
+            Contact::One => healthy_inside.min(forced),
+            Contact::Nobody => 0,
+        }
+    }
+}
+
+/// Returns the smallest sum of a subset of `values` that is at least `target`.
+///
+/// # Panics
+///
+/// If the sum of all values is smaller than `target`.
+fn smallest_sum_at_least(values: &[usize], target: usize) -> usize {
+    let total: usize = values.iter().sum();
+    let mut reachable = vec![false; total + 1];
+    reachable[0] = true;
+    for &value in values {
+        for sum in (value..=total).rev() {
+            if reachable[sum - value] {
+                reachable[sum] = true;
+            }
+        }
+    }
+    (target..=total).find(|&sum| reachable[sum]).expect("the target is larger than the total")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, ReportPlan};
+    use crate::BuildingBuilder;
+    use test_case::test_case;
+
+    #[test_case(&[4, 6, 10], 5, 6)]
+    #[test_case(&[4, 6, 10], 7, 10)]
+    #[test_case(&[4, 6, 10], 11, 14)]
+    #[test_case(&[], 0, 0)]
+    fn smallest_sum_at_least(values: &[usize], target: usize, expected: usize) {
+        assert_eq!(super::smallest_sum_at_least(values, target), expected);
+    }
+
+    #[test_case(Spreading::Everyone, vec![2, 2], vec![0, 0])]
+    #[test_case(Spreading::One, vec![1, 1], vec![0, 0])]
+    #[test_case(Spreading::Airborne(0), vec![0, 0], vec![0, 0])]
+    fn bounds(spreading: Spreading, worst: Vec<usize>, best: Vec<usize>) {
+        let board = BoardBuilder {
+            healthy: 10,
+            infected1: 1,
+            buildings: vec![(3, 1), (1, 1)],
+            spreading,
+            ..Default::default()
+        }.build();
+        let bounds = Bounds::new(&board, 2);
+        assert_eq!(bounds.worst(), &worst);
+        assert_eq!(bounds.best(), &best);
+    }
+
+    #[test]
+    fn forced() {
+        // Everyone has to enter, so the infectious individual shares the building with one healthy
+        let board = BoardBuilder {
+            healthy: 4,
+            infected1: 1,
+            buildings: vec![(2, 1), (3, 1)],
+            spreading: Spreading::Everyone,
+            ..Default::default()
+        }.build();
+        let bounds = Bounds::new(&board, 1);
+        assert_eq!(bounds.best(), &vec![1]);
+        assert_eq!(bounds.worst(), &vec![2]);
+    }
+
+    #[test]
+    fn mixed_spreading() {
+        let mut population = vec![Individual::Healthy; 10];
+        population.push(Individual::Infected1);
+        let buildings = vec![
+            BuildingBuilder::new("A").with_size(3, 1).with_spreading(Spreading::Everyone).build(),
+            BuildingBuilder::new("B").with_size(1, 1).with_spreading(Spreading::Airborne(0)).build(),
+        ];
+        let board = Board::new(population.into(), buildings);
+        let bounds = Bounds::new(&board, 2);
+        assert_eq!(bounds.worst(), &vec![2, 2]);
+        assert_eq!(bounds.best(), &vec![0, 0]);
+    }
+
+    #[test]
+    fn simulation() {
+        let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 1, days: 3, ..Default::default() });
+        let bounds = simulation.bounds();
+        assert_eq!(bounds.worst().len(), 3);
+        assert!(bounds.best_total() <= bounds.worst_total());
+    }
+}
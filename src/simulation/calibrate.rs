@@ -0,0 +1,256 @@
+use crate::building::Spreading;
+use crate::prelude::{BoardBuilder, Individual, ReportPlan, Simulation};
+use crate::simulation::statistics::Z_95;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Distance between a simulated and the observed trajectory, see `Calibration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Loss {
+    /// Mean of the squared differences per day.
+    #[default]
+    SquaredError,
+    /// Mean of the absolute differences per day, less sensitive to single outlying days.
+    AbsoluteError,
+}
+
+impl Loss {
+    /// Returns the loss of `simulated` with respect to `observed`, over the days present in both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::simulation::Loss;
+    /// assert_eq!(Loss::SquaredError.of(&[0, 2, 4], &[0, 1, 1]), 10. / 3.);
+    /// assert_eq!(Loss::AbsoluteError.of(&[0, 2, 4], &[0, 1, 1]), 4. / 3.);
+    /// ```
+    pub fn of(&self, simulated: &[usize], observed: &[usize]) -> f64 {
+        let days = simulated.len().min(observed.len());
+        if days == 0 {
+            return 0.;
+        }
+        let total: f64 = simulated.iter().zip(observed)
+            .map(|(&simulated, &observed)| {
+                let difference = simulated as f64 - observed as f64;
+                match self {
+                    Loss::SquaredError => difference * difference,
+                    Loss::AbsoluteError => difference.abs(),
+                }
+            })
+            .sum();
+        total / days as f64
+    }
+}
+
+/// Grid search of the spreading mode whose simulations best reproduce an observed series of sick individuals.
+///
+/// The observed series has the number of sick individuals per day, starting on day zero,
+/// and each candidate is simulated for as many days.
+/// The loss of a candidate is the average over realizations of the loss of each realization.
+///
+/// # Examples
+///
+/// Fit the transmission probability of an airborne spreading to a classroom game.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::Calibration;
+/// let calibration = Calibration {
+///     board_builder: BoardBuilder { healthy: 28, infected1: 2, buildings: vec![(6, 5)], ..Default::default() },
+///     observed: vec![0, 0, 0, 2, 5, 9],
+///     candidates: Calibration::airborne(10),
+///     num_simulations: 20,
+///     ..Default::default()
+/// };
+/// let results = calibration.run();
+/// let best = results.best().unwrap();
+/// assert!(matches!(best.spreading, Spreading::Airborne(_)));
+/// let (low, high) = results.transmission_interval().unwrap();
+/// assert!(low <= high);
+/// println!("{}", results);
+/// ```
+#[derive(Debug, Clone, PartialEq, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Calibration {
+    /// Board setup, whose spreading mode is replaced by each candidate
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub board_builder: BoardBuilder,
+    /// Observed number of sick individuals per day, starting on day zero
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub observed: Vec<usize>,
+    /// Spreading modes to evaluate
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub candidates: Vec<Spreading>,
+    /// Number of realizations simulated for each candidate
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub num_simulations: usize,
+    /// Distance between trajectories
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub loss: Loss,
+}
+
+impl Default for Calibration {
+    /// Returns a calibration without observations over the spatial spreading modes
+    /// and airborne spreading every 10%, with 100 realizations per candidate.
+    fn default() -> Self {
+        let mut candidates = vec![Spreading::Everyone, Spreading::One, Spreading::OneNear, Spreading::OneVeryNear];
+        candidates.append(&mut Calibration::airborne(10));
+        Calibration {
+            board_builder: BoardBuilder::default(),
+            observed: Vec::new(),
+            candidates,
+            num_simulations: 100,
+            loss: Loss::default(),
+        }
+    }
+}
+
+impl Calibration {
+    /// Returns airborne spreading modes with probabilities of infection from 0% to 100%, every `step` percentage points.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero.
+    pub fn airborne(step: u8) -> Vec<Spreading> {
+        assert!(step > 0, "the step must be positive");
+        (0..=100).step_by(usize::from(step)).map(Spreading::Airborne).collect()
+    }
+
+    /// Returns the fit of every candidate.
+    pub fn run(&self) -> CalibrationResults {
+        self.run_with(&mut rand::thread_rng())
+    }
+
+    /// Returns the fit of every candidate, using `rng` as the source of randomness.
+    pub fn run_with(&self, rng: &mut dyn rand::RngCore) -> CalibrationResults {
+        let report_plan = ReportPlan {
+            num_simulations: self.num_simulations,
+            days: self.observed.len().saturating_sub(1),
+            ..Default::default()
+        };
+        let mut fits: Vec<CalibrationFit> = self.candidates.iter()
+            .map(|&spreading| {
+                let board_builder = BoardBuilder { spreading, ..self.board_builder.clone() };
+                let report = Simulation::new(board_builder.build(), report_plan.clone()).run_with(rng);
+                let loss: average::Variance = report.individual(&Individual::Sick).into_iter()
+                    .map(|sick| self.loss.of(sick, &self.observed))
+                    .collect();
+                CalibrationFit { spreading, loss: loss.mean(), error: loss.error() }
+            })
+            .collect();
+        fits.sort_by(|a, b| a.loss.partial_cmp(&b.loss).unwrap_or(std::cmp::Ordering::Equal));
+        CalibrationResults { fits }
+    }
+}
+
+/// Loss of one candidate of a `Calibration`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalibrationFit {
+    /// Spreading mode of the candidate
+    pub spreading: Spreading,
+    /// Average loss over realizations
+    pub loss: f64,
+    /// Standard error of `loss`
+    pub error: f64,
+}
+
+/// Results of a `Calibration`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalibrationResults {
+    /// Fits of the candidates, from the smallest to the largest loss
+    pub fits: Vec<CalibrationFit>,
+}
+
+impl CalibrationResults {
+    /// Returns the candidate with the smallest loss, if there are candidates.
+    pub fn best(&self) -> Option<&CalibrationFit> {
+        self.fits.first()
+    }
+
+    /// Returns the candidates whose loss is not significantly larger than the best one,
+    /// that is, the difference is within the 95% confidence interval of the difference of both losses.
+    ///
+    /// The best candidate is always included. The more candidates are plausible, the less the observations
+    /// determine the spreading mode.
+    pub fn plausible(&self) -> Vec<&CalibrationFit> {
+        match self.best() {
+            Some(best) => self.fits.iter()
+                .filter(|fit| fit.loss - best.loss <= Z_95 * (fit.error.powi(2) + best.error.powi(2)).sqrt())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the smallest and largest probabilities of infection among the plausible airborne candidates,
+    /// if any, as an uncertainty range of the transmission probability.
+    pub fn transmission_interval(&self) -> Option<(u8, u8)> {
+        let probabilities = || self.plausible().into_iter().filter_map(|fit| match fit.spreading {
+            Spreading::Airborne(probability) => Some(probability),
+            _ => None,
+        });
+        Some((probabilities().min()?, probabilities().max()?))
+    }
+}
+
+impl core::fmt::Display for CalibrationResults {
+    /// Writes the fits as a table, marking the plausible candidates.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let plausible = self.plausible().len();
+        let names: Vec<String> = self.fits.iter().map(|fit| format!("{:?}", fit.spreading)).collect();
+        let name_width = names.iter().map(|name| name.len()).max().unwrap_or(0).max("Spreading".len());
+        writeln!(f, "Rank {:<w$} Loss", "Spreading", w = name_width)?;
+        for (rank, (fit, name)) in self.fits.iter().zip(names).enumerate() {
+            let mark = if rank < plausible { "*" } else { "" };
+            writeln!(f, "{:<4} {:<w$} {:.2} ± {:.2} {}", rank + 1, name, fit.loss, fit.error, mark, w = name_width)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibration(observed: Vec<usize>) -> Calibration {
+        Calibration {
+            board_builder: BoardBuilder { healthy: 15, infected1: 1, buildings: vec![(4, 4)], ..Default::default() },
+            observed,
+            candidates: vec![Spreading::Airborne(0), Spreading::Everyone, Spreading::Airborne(100)],
+            num_simulations: 5,
+            loss: Loss::SquaredError,
+        }
+    }
+
+    #[test]
+    fn airborne() {
+        assert_eq!(Calibration::airborne(50), vec![Spreading::Airborne(0), Spreading::Airborne(50), Spreading::Airborne(100)]);
+        assert_eq!(Calibration::airborne(30).len(), 4);
+    }
+
+    #[test]
+    fn run_with() {
+        // Without spreading, only the initially infected individual gets sick, on day three
+        let results = calibration(vec![0, 0, 0, 1, 1, 1]).run_with(&mut crate::tests::rng(1));
+        let best = results.best().unwrap();
+        assert_eq!(best.spreading, Spreading::Airborne(0));
+        assert_eq!((best.loss, best.error), (0., 0.));
+        assert_eq!(results.plausible().len(), 1);
+        assert_eq!(results.transmission_interval(), Some((0, 0)));
+        assert_eq!(results, calibration(vec![0, 0, 0, 1, 1, 1]).run_with(&mut crate::tests::rng(1)));
+    }
+
+    #[test]
+    fn indistinguishable() {
+        // Everyone and airborne spreading with certainty coincide
+        let results = calibration(vec![0, 0, 0, 1, 16, 16]).run_with(&mut crate::tests::rng(2));
+        let plausible: Vec<Spreading> = results.plausible().iter().map(|fit| fit.spreading).collect();
+        assert_eq!(plausible.len(), 2);
+        assert!(plausible.contains(&Spreading::Everyone));
+        assert_eq!(results.transmission_interval(), Some((100, 100)));
+        assert_eq!(results.fits[2].spreading, Spreading::Airborne(0));
+    }
+}
@@ -0,0 +1,428 @@
+use crate::prelude::{Individual, Report};
+use average::Estimate;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use crate::simulation::statistics::{regularized_incomplete_beta, Z_95};
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+
+/// Difference between two reports in one day, for one individual variant, see `Report::diff`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DayDifference {
+    /// Mean of this report minus mean of the other report
+    pub mean: f64,
+    /// 95% confidence interval of the mean of this report
+    pub interval: (f64, f64),
+    /// 95% confidence interval of the mean of the other report
+    pub other_interval: (f64, f64),
+    /// 95% confidence interval of the mean of the paired differences, if the reports are paired,
+    /// see `Report::is_paired_with`
+    pub paired_interval: Option<(f64, f64)>,
+}
+
+impl DayDifference {
+    /// Returns `true` if the confidence intervals of both reports overlap,
+    /// in which case the difference is not conclusive.
+    pub fn is_overlapping(&self) -> bool {
+        self.interval.0 <= self.other_interval.1 && self.other_interval.0 <= self.interval.1
+    }
+
+    /// Returns `true` if the difference is conclusive: the confidence interval of the paired differences
+    /// does not contain zero if the reports are paired, and otherwise the confidence intervals of both reports do not overlap.
+    pub fn is_conclusive(&self) -> bool {
+        match self.paired_interval {
+            Some((lower, upper)) => lower > 0. || upper < 0.,
+            None => !self.is_overlapping(),
+        }
+    }
+}
+
+/// Welch's t-test of the difference of means between two samples, see `Report::welch_test`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WelchTest {
+    /// Mean of the first sample minus mean of the second sample
+    pub difference: f64,
+    /// Test statistic
+    pub t: f64,
+    /// Welch–Satterthwaite degrees of freedom
+    pub degrees_of_freedom: f64,
+    /// Two-sided p-value of the hypothesis that both means are equal
+    pub p_value: f64,
+    /// Cohen's d: the difference divided by the root mean square of the standard deviations of both samples,
+    /// or by the standard deviation of the differences for the paired t-test
+    pub effect_size: f64,
+}
+
+impl WelchTest {
+    /// Returns `true` if the difference is significant at level `alpha`, for example `0.05`.
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Mann–Whitney U test of whether one sample tends to have larger values than another,
+/// see `Report::mann_whitney_test`.
+///
+/// Unlike Welch's t-test, it only depends on the ranks of the values, so it is robust
+/// to skewed counts, as in outbreaks that either die out or infect almost everyone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MannWhitneyTest {
+    /// Number of pairs where the value of the first sample is larger, counting ties as one half
+    pub u: f64,
+    /// Standardized statistic, with tie and continuity corrections
+    pub z: f64,
+    /// Two-sided p-value of the normal approximation of the hypothesis that neither sample tends to be larger
+    pub p_value: f64,
+    /// Rank-biserial correlation, between -1 and 1: positive if the first sample tends to be larger
+    pub effect_size: f64,
+}
+
+impl MannWhitneyTest {
+    /// Returns `true` if the difference is significant at level `alpha`, for example `0.05`.
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Comparison of two reports, see `Report::diff`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReportDiff {
+    /// Differences per day of each individual variant present in both reports
+    pub days: HashMap<Individual, Vec<DayDifference>>,
+    /// Test of the difference of the number of healthy individuals in the last day:
+    /// the paired t-test if the reports are paired, and Welch's t-test otherwise
+    pub final_healthy: WelchTest,
+    /// Whether the reports are paired, see `Report::is_paired_with`
+    pub paired: bool,
+}
+
+impl Report {
+    /// Returns the comparison of this report with `other`, for example a scenario against a baseline.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Days are compared up to the shortest of both reports.
+    /// Confidence intervals are based on the normal approximation of the mean.
+    /// If the reports are paired, the paired differences between realizations are compared as well,
+    /// see `Simulation::run_paired`.
+    ///
+    /// # Examples
+    ///
+    /// Closing every building keeps more individuals healthy.
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report_plan = ReportPlan { num_simulations: 30, days: 10, ..Default::default() };
+    /// let baseline = Simulation::new(Board::default(), report_plan.clone()).run();
+    /// let mut board = Board::default();
+    /// let names: Vec<String> = board.buildings().iter().map(|building| building.name().to_string()).collect();
+    /// for name in names {
+    ///     board.close(name).unwrap();
+    /// }
+    /// let lockdown = Simulation::new(board, report_plan).run();
+    /// let diff = lockdown.diff(&baseline);
+    /// assert!(diff.final_healthy.difference > 0.);
+    /// assert!(diff.days[&Individual::Healthy][0].is_overlapping());
+    /// ```
+    pub fn diff(&self, other: &Report) -> ReportDiff {
+        let paired = self.is_paired_with(other);
+        let days = Individual::iter()
+            .filter(|individual| !self.individual(individual).is_empty() && !other.individual(individual).is_empty())
+            .map(|individual| {
+                let paired_differences = if paired { self.paired_differences(other, &individual) } else { Vec::new() };
+                let differences = self.individual_average(&individual).iter()
+                    .zip(other.individual_average(&individual).iter())
+                    .enumerate()
+                    .map(|(day, (this, that))| DayDifference {
+                        mean: this.mean() - that.mean(),
+                        interval: (this.mean() - Z_95 * this.error(), this.mean() + Z_95 * this.error()),
+                        other_interval: (that.mean() - Z_95 * that.error(), that.mean() + Z_95 * that.error()),
+                        paired_interval: paired_differences.get(day).map(|difference| {
+                            (difference.mean() - Z_95 * difference.error(), difference.mean() + Z_95 * difference.error())
+                        }),
+                    })
+                    .collect();
+                (individual, differences)
+            })
+            .collect();
+        let final_healthy = if paired {
+            paired_test(&self.individual_last(&Individual::Healthy), &other.individual_last(&Individual::Healthy))
+        } else {
+            self.welch_test(other, &Individual::Healthy)
+        };
+        ReportDiff { days, final_healthy, paired }
+    }
+
+    /// Returns `true` if both reports have the same seed in each realization, 
+    /// so that their realizations can be compared one by one, see `Simulation::run_paired`.
+    pub fn is_paired_with(&self, other: &Report) -> bool {
+        !self.seeds.is_empty() && self.seeds == other.seeds 
+            && self.counting_tables.len() == self.seeds.len() && other.counting_tables.len() == other.seeds.len()
+    }
+
+    /// Returns the differences per day of an individual variant between realizations with the same index,
+    /// for the days present in both reports. Realizations that stopped early keep their last count, see `Report::individual_transpose`.
+    fn paired_differences(&self, other: &Report, individual: &Individual) -> Vec<average::Variance> {
+        let mut differences: Vec<average::Variance> = vec![average::Variance::new(); self.days().min(other.days())];
+        for (this, that) in self.individual(individual).into_iter().zip(other.individual(individual)) {
+            for (day, difference) in differences.iter_mut().enumerate() {
+                if let (Some(&this), Some(&that)) = (this.get(day).or_else(|| this.last()), that.get(day).or_else(|| that.last())) {
+                    difference.add(this as f64 - that as f64);
+                }
+            }
+        }
+        differences
+    }
+
+    /// Returns Welch's t-test of the difference between the last day counts
+    /// of an individual variant in this report and in `other`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Remarks
+    ///
+    /// If both samples have no variance, the p-value is one if the means are equal and zero otherwise.
+    pub fn welch_test(&self, other: &Report, individual: &Individual) -> WelchTest {
+        welch_test(&self.individual_last(individual), &other.individual_last(individual))
+    }
+
+    /// Returns the Mann–Whitney U test of whether the last day counts of an individual variant
+    /// tend to be larger in this report than in `other`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Remarks
+    ///
+    /// If all counts are equal, the p-value is one.
+    ///
+    /// # Examples
+    ///
+    /// Closing the school reduces the number of sick individuals.
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report_plan = ReportPlan { num_simulations: 30, days: 10, ..Default::default() };
+    /// let baseline = Simulation::new(Board::default(), report_plan.clone()).run();
+    /// let mut board = Board::default();
+    /// board.close("School").unwrap();
+    /// let closed = Simulation::new(board, report_plan).run();
+    /// let test = closed.mann_whitney_test(&baseline, &Individual::Sick);
+    /// assert!((-1. ..=1.).contains(&test.effect_size));
+    /// if test.is_significant(0.05) && test.effect_size < 0. {
+    ///     println!("Closing the school significantly reduces sick counts (p = {:.3})", test.p_value);
+    /// }
+    /// ```
+    pub fn mann_whitney_test(&self, other: &Report, individual: &Individual) -> MannWhitneyTest {
+        mann_whitney_test(&self.individual_last(individual), &other.individual_last(individual))
+    }
+}
+
+/// Returns the mean and unbiased sample variance of a sample.
+fn moments(sample: &[&usize]) -> (f64, f64) {
+    let n = sample.len() as f64;
+    let mean = sample.iter().map(|&&x| x as f64).sum::<f64>() / n;
+    let variance = if sample.len() < 2 {
+        0.
+    } else {
+        sample.iter().map(|&&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1.)
+    };
+    (mean, variance)
+}
+
+/// Returns the paired t-test of the differences between samples with the same index, 
+/// as a `WelchTest` with `n - 1` degrees of freedom.
+fn paired_test(first: &[&usize], second: &[&usize]) -> WelchTest {
+    let differences: Vec<f64> = first.iter().zip(second).map(|(&&x, &&y)| x as f64 - y as f64).collect();
+    if differences.is_empty() {
+        return WelchTest { difference: 0., t: 0., degrees_of_freedom: 0., p_value: 1., effect_size: 0. };
+    }
+    let n = differences.len() as f64;
+    let difference = differences.iter().sum::<f64>() / n;
+    let variance = if differences.len() < 2 {
+        0.
+    } else {
+        differences.iter().map(|d| (d - difference).powi(2)).sum::<f64>() / (n - 1.)
+    };
+    let standard_error = (variance / n).sqrt();
+    let effect_size = standardized(difference, variance.sqrt());
+    if standard_error == 0. {
+        let p_value = if difference == 0. { 1. } else { 0. };
+        return WelchTest { difference, t: 0., degrees_of_freedom: 0., p_value, effect_size };
+    }
+    let t = difference / standard_error;
+    let degrees_of_freedom = n - 1.;
+    let p_value = regularized_incomplete_beta(degrees_of_freedom / 2., 0.5, degrees_of_freedom / (degrees_of_freedom + t * t));
+    WelchTest { difference, t, degrees_of_freedom, p_value, effect_size }
+}
+
+fn welch_test(first: &[&usize], second: &[&usize]) -> WelchTest {
+    if first.is_empty() || second.is_empty() {
+        return WelchTest { difference: 0., t: 0., degrees_of_freedom: 0., p_value: 1., effect_size: 0. };
+    }
+    let (mean1, variance1) = moments(first);
+    let (mean2, variance2) = moments(second);
+    let difference = mean1 - mean2;
+    let se1 = variance1 / first.len() as f64;
+    let se2 = variance2 / second.len() as f64;
+    let standard_error = (se1 + se2).sqrt();
+    let effect_size = standardized(difference, ((variance1 + variance2) / 2.).sqrt());
+    if standard_error == 0. {
+        let p_value = if difference == 0. { 1. } else { 0. };
+        return WelchTest { difference, t: 0., degrees_of_freedom: 0., p_value, effect_size };
+    }
+    let t = difference / standard_error;
+    let degrees_of_freedom = (se1 + se2).powi(2)
+        / (se1.powi(2) / (first.len() as f64 - 1.).max(1.) + se2.powi(2) / (second.len() as f64 - 1.).max(1.));
+    let p_value = regularized_incomplete_beta(degrees_of_freedom / 2., 0.5, degrees_of_freedom / (degrees_of_freedom + t * t));
+    WelchTest { difference, t, degrees_of_freedom, p_value, effect_size }
+}
+
+/// Returns `difference` divided by `standard_deviation`, or zero if there is no difference.
+fn standardized(difference: f64, standard_deviation: f64) -> f64 {
+    if difference == 0. { 0. } else { difference / standard_deviation }
+}
+
+fn mann_whitney_test(first: &[&usize], second: &[&usize]) -> MannWhitneyTest {
+    let (n1, n2) = (first.len() as f64, second.len() as f64);
+    if first.is_empty() || second.is_empty() {
+        return MannWhitneyTest { u: 0., z: 0., p_value: 1., effect_size: 0. };
+    }
+    let mut values: Vec<(usize, bool)> = first.iter().map(|&&x| (x, true))
+        .chain(second.iter().map(|&&x| (x, false)))
+        .collect();
+    values.sort_unstable();
+    // Sum of the ranks of the first sample, averaging ties, and the correction of the variance for ties
+    let mut rank_sum = 0.;
+    let mut ties = 0.;
+    let mut start = 0;
+    while start < values.len() {
+        let end = start + values[start..].iter().take_while(|(x, _)| *x == values[start].0).count();
+        let average_rank = (start + end + 1) as f64 / 2.;
+        rank_sum += average_rank * values[start..end].iter().filter(|(_, is_first)| *is_first).count() as f64;
+        let tied = (end - start) as f64;
+        ties += tied.powi(3) - tied;
+        start = end;
+    }
+    let u = rank_sum - n1 * (n1 + 1.) / 2.;
+    let effect_size = 2. * u / (n1 * n2) - 1.;
+    let n = n1 + n2;
+    let standard_deviation = (n1 * n2 / 12. * (n + 1. - ties / (n * (n - 1.)))).sqrt();
+    if standard_deviation == 0. || standard_deviation.is_nan() {
+        return MannWhitneyTest { u, z: 0., p_value: 1., effect_size };
+    }
+    let deviation = u - n1 * n2 / 2.;
+    let z = (deviation.abs() - 0.5).max(0.).copysign(deviation) / standard_deviation;
+    let p_value = complementary_error_function(z.abs() / std::f64::consts::SQRT_2).min(1.);
+    MannWhitneyTest { u, z, p_value, effect_size }
+}
+
+/// Returns the complementary error function, by a Chebyshev approximation with relative error below `1.2e-7`.
+fn complementary_error_function(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 10] = [
+        -1.26551223, 1.00002368, 0.37409196, 0.09678418, -0.18628806,
+        0.27886807, -1.13520398, 1.48851587, -0.82215223, 0.17087277,
+    ];
+    let t = 1. / (1. + 0.5 * x.abs());
+    let polynomial = COEFFICIENTS.iter().rev().fold(0., |polynomial, coefficient| coefficient + t * polynomial);
+    let value = t * (-x * x + polynomial).exp();
+    if x >= 0. { value } else { 2. - value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::CountingTable;
+    use test_case::test_case;
+
+    fn report(finals: &[usize]) -> Report {
+        let counting_tables: Vec<CountingTable> = finals.iter()
+            .map(|&last| vec![(Individual::Healthy, vec![10, last])].into_iter().collect())
+            .collect();
+        Report::from(counting_tables)
+    }
+
+    // Reference p-values by numerical integration of the density of Student's t-distribution
+    #[test_case(&[1, 2, 3, 4, 5], &[3, 4, 5, 6, 7], -2., 8., 0.080516; "shifted")]
+    #[test_case(&[1, 2, 3, 4, 5], &[1, 2, 3, 4, 5], 0., 8., 1.; "equal")]
+    #[test_case(&[2, 4, 6, 8, 10, 12], &[1, 2, 3], 5., 6.213592, 0.021210; "unequal variance")]
+    fn welch(first: &[usize], second: &[usize], difference: f64, degrees_of_freedom: f64, p_value: f64) {
+        let test = report(first).welch_test(&report(second), &Individual::Healthy);
+        assert!((test.difference - difference).abs() < 1e-9);
+        assert!((test.degrees_of_freedom - degrees_of_freedom).abs() < 1e-6);
+        assert!((test.p_value - p_value).abs() < 1e-6);
+    }
+
+    #[test]
+    fn diff() {
+        let baseline = report(&[2, 3, 4]);
+        let scenario = report(&[8, 9, 10]);
+        let diff = scenario.diff(&baseline);
+        let healthy = &diff.days[&Individual::Healthy];
+        assert_eq!(healthy.len(), 2);
+        assert_eq!(healthy[0].mean, 0.);
+        assert!(healthy[0].is_overlapping());
+        assert_eq!(healthy[1].mean, 6.);
+        assert!(!healthy[1].is_overlapping());
+        assert!(diff.final_healthy.is_significant(0.05));
+        assert!(!diff.days.contains_key(&Individual::Sick));
+    }
+
+    #[test]
+    fn paired() {
+        let mut baseline = report(&[2, 5, 9]);
+        let mut scenario = report(&[3, 6, 10]);
+        assert!(!scenario.diff(&baseline).paired);
+        assert!(!scenario.diff(&baseline).days[&Individual::Healthy][1].is_conclusive());
+        baseline.seeds = vec![1, 2, 3];
+        scenario.seeds = vec![1, 2, 3];
+        let diff = scenario.diff(&baseline);
+        assert!(diff.paired);
+        assert_eq!(diff.days[&Individual::Healthy][1].paired_interval, Some((1., 1.)));
+        assert!(diff.days[&Individual::Healthy][1].is_conclusive());
+        assert!(!diff.days[&Individual::Healthy][0].is_conclusive());
+        assert_eq!(diff.final_healthy.difference, 1.);
+        assert_eq!(diff.final_healthy.p_value, 0.);
+        scenario.seeds = vec![1, 2, 4];
+        assert!(!scenario.diff(&baseline).paired);
+    }
+
+    #[test]
+    fn paired_test() {
+        // Differences 1, 2, 3, 4 and 5 have mean 3 and standard error 1/√2
+        let test = super::paired_test(&[&2, &4, &6, &8, &10], &[&1, &2, &3, &4, &5]);
+        assert_eq!(test.degrees_of_freedom, 4.);
+        assert!((test.t - 3. * 2f64.sqrt()).abs() < 1e-9);
+        assert!(test.is_significant(0.05));
+    }
+
+    #[test]
+    fn effect_size() {
+        // Both samples have variance 2.5
+        let test = report(&[1, 2, 3, 4, 5]).welch_test(&report(&[3, 4, 5, 6, 7]), &Individual::Healthy);
+        assert!((test.effect_size + 2. / 2.5f64.sqrt()).abs() < 1e-9);
+        // Differences 1, 2, 3, 4 and 5 have standard deviation √2.5
+        let test = super::paired_test(&[&2, &4, &6, &8, &10], &[&1, &2, &3, &4, &5]);
+        assert!((test.effect_size - 3. / 2.5f64.sqrt()).abs() < 1e-9);
+        assert_eq!(report(&[5, 5]).welch_test(&report(&[5]), &Individual::Healthy).effect_size, 0.);
+    }
+
+    // Reference p-values of the normal approximation with tie and continuity corrections
+    #[test_case(&[1, 2, 3, 4, 5], &[3, 4, 5, 6, 7], 4.5, -0.64, 0.113846; "shifted with ties")]
+    #[test_case(&[4, 5, 6], &[1, 2, 3], 9., 1., 0.080856; "separated")]
+    #[test_case(&[1, 2, 3], &[1, 2, 3], 4.5, 0., 1.; "equal")]
+    #[test_case(&[5, 5], &[5], 1., 0., 1.; "no variance")]
+    fn mann_whitney(first: &[usize], second: &[usize], u: f64, effect_size: f64, p_value: f64) {
+        let test = report(first).mann_whitney_test(&report(second), &Individual::Healthy);
+        assert_eq!(test.u, u);
+        assert!((test.effect_size - effect_size).abs() < 1e-9);
+        assert!((test.p_value - p_value).abs() < 1e-6);
+    }
+
+    #[test]
+    fn no_variance() {
+        assert_eq!(report(&[5, 5]).welch_test(&report(&[5]), &Individual::Healthy).p_value, 1.);
+        assert_eq!(report(&[5, 5]).welch_test(&report(&[4]), &Individual::Healthy).p_value, 0.);
+    }
+}
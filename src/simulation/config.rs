@@ -0,0 +1,361 @@
+use crate::errors::{ConfigError, Diagnostic};
+use crate::prelude::SimulationBuilder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+
+/// Example configuration in RON format, with a comment on each field, see `SimulationBuilder::write_template`.
+const TEMPLATE: &str = "\
+// Configuration of a simulation, see `SimulationBuilder`.
+// Fields marked as optional can be left out.
+(
+    board_builder: (
+        // Number of individuals of each variant at the start
+        healthy: 98,
+        infected1: 2,
+        infected2: 0,
+        infected3: 0,
+        sick: 0,
+        immune: 0,
+        // Optional: healthy individuals who are superspreaders once infected
+        superspreaders: 0,
+        // Rows and columns of the seats of each building
+        buildings: [(5, 4), (2, 2), (4, 4), (2, 2), (4, 3), (4, 2), (2, 2), (4, 2)],
+        // Everyone, One, OneNear, OneVeryNear, OneAdjacent or Airborne(percentage)
+        spreading: OneVeryNear,
+        // Optional: percentage of infected individuals who recover without getting sick
+        asymptomatic: 0,
+        // Optional: number of vaccines available, unlimited if None
+        vaccines: None,
+        // Optional: number of healthy individuals immunized at the start of each day
+        vaccination_rate: 0,
+        // Optional: number of individuals and percentage of each variant, used instead of the numbers of individuals above
+        composition: None,
+        // Optional: path of a population file in CSV format, used instead of the numbers of individuals above
+        population_file: None,
+    ),
+    report_plan: (
+        // Number of realizations of the game
+        num_simulations: 100,
+        // Number of days each realization lasts
+        days: 10,
+    ),
+)
+";
+
+impl SimulationBuilder {
+    /// Returns the fields of the configuration that are invalid, in the order they appear.
+    ///
+    /// Each diagnostic carries the path of the field, so that frontends can point to it.
+    /// An empty vector means the configuration is valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let simulation_builder = SimulationBuilder {
+    ///     board_builder: BoardBuilder { healthy: 10, buildings: vec![(4, 4), (2, 2), (3, 0)], ..Default::default() },
+    ///     report_plan: ReportPlan { num_simulations: 1, days: 10, ..Default::default() },
+    ///     ..Default::default()
+    /// };
+    /// let diagnostics = simulation_builder.diagnostics();
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].path, "board_builder.buildings[2].rows");
+    /// assert_eq!(diagnostics[0].to_string(), "board_builder.buildings[2].rows must be at least 1");
+    /// ```
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let board = &self.board_builder;
+
+        if self.report_plan.num_simulations == 0 {
+            diagnostics.push(Diagnostic::new("report_plan.num_simulations", "must be at least 1"));
+        }
+        crate::board::check_days(&mut diagnostics, "report_plan.days", self.report_plan.days);
+        if let crate::simulation::Aggregation::Streaming { bin_width: 0 } = self.report_plan.aggregation {
+            diagnostics.push(Diagnostic::new("report_plan.aggregation.bin_width", "must be at least 1"));
+        }
+        diagnostics.extend(board.diagnostics().into_iter().map(|diagnostic| {
+            Diagnostic::new(format!("board_builder.{}", diagnostic.path), diagnostic.message)
+        }));
+        #[cfg(feature = "unstable")]
+        for (index, intervention) in self.interventions.iter().enumerate() {
+            if intervention.day >= self.report_plan.days {
+                diagnostics.push(Diagnostic::new(
+                    format!("interventions[{}].day", index),
+                    format!("must be less than {}, the number of days", self.report_plan.days),
+                ));
+            }
+        }
+        diagnostics
+    }
+
+    /// Returns the configuration if it is valid.
+    ///
+    /// # Errors
+    ///
+    /// If there is any diagnostic, see `diagnostics`.
+    pub fn validated(self) -> Result<Self, ConfigError> {
+        let diagnostics = self.diagnostics();
+        if diagnostics.is_empty() {
+            Ok(self)
+        } else {
+            Err(ConfigError::Invalid(diagnostics))
+        }
+    }
+
+    /// Deserializes a configuration and validates it.
+    ///
+    /// # Errors
+    ///
+    /// If the configuration can not be parsed, or if it is invalid, see `diagnostics`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::errors::ConfigError;
+    /// let config = ron::to_string(&SimulationBuilder::default()).unwrap();
+    /// let mut deserializer = ron::Deserializer::from_str(&config).unwrap();
+    /// match SimulationBuilder::deserialize_validated(&mut deserializer) {
+    ///     Err(ConfigError::Invalid(diagnostics)) => assert_eq!(diagnostics[0].path, "report_plan.num_simulations"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn deserialize_validated<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, ConfigError> {
+        SimulationBuilder::deserialize(deserializer)
+            .map_err(|error| ConfigError::Parse(error.to_string()))?
+            .validated()
+    }
+
+    /// Reads a configuration file and validates it.
+    ///
+    /// The format is given by the extension of the file:
+    /// - `ron`, with the `ron` feature,
+    /// - `yaml` or `yml`, with the `yaml` feature,
+    /// - `toml`, with the `toml` feature,
+    /// - `json`, with the `json` feature.
+    ///
+    /// # Errors
+    ///
+    /// If the file can not be read, its format is not supported, 
+    /// it can not be parsed or the configuration is invalid, see `diagnostics`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use virus_alarm::prelude::*;
+    /// let simulation = SimulationBuilder::from_path("config.ron").unwrap().build();
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+        let simulation_builder: Result<SimulationBuilder, ConfigError> = match extension {
+            #[cfg(feature = "ron")]
+            "ron" => ron::from_str(&read(path)?).map_err(parse_error),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => serde_yaml::from_str(&read(path)?).map_err(parse_error),
+            #[cfg(feature = "toml")]
+            "toml" => toml::from_str(&read(path)?).map_err(parse_error),
+            #[cfg(feature = "json")]
+            "json" => serde_json::from_str(&read(path)?).map_err(parse_error),
+            _ => Err(ConfigError::UnsupportedFormat(extension.to_string())),
+        };
+        simulation_builder?.validated()
+    }
+
+    /// Writes an example configuration in RON format, with a comment on each field.
+    ///
+    /// It is a starting point for configuration files, see `from_path`.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let mut template = Vec::new();
+    /// SimulationBuilder::write_template(&mut template).unwrap();
+    /// let simulation_builder: SimulationBuilder = ron::de::from_bytes(&template).unwrap();
+    /// assert!(simulation_builder.validated().is_ok());
+    /// ```
+    pub fn write_template<W: std::io::Write>(mut writer: W) -> std::io::Result<()> {
+        writer.write_all(TEMPLATE.as_bytes())
+    }
+
+    /// Returns the JSON Schema of the configuration, see `BoardBuilder::schema`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let schema = SimulationBuilder::schema();
+    /// let object = schema.schema.object.unwrap();
+    /// assert!(object.required.contains("report_plan"));
+    /// assert!(!object.required.contains("interventions"));
+    /// ```
+    #[cfg(feature = "schema")]
+    pub fn schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(SimulationBuilder)
+    }
+}
+
+/// Reads the contents of a configuration file.
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml", feature = "ron"))]
+fn read(path: &Path) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|error| ConfigError::Io(error.to_string()))
+}
+
+/// Returns the error of a configuration that can not be parsed.
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml", feature = "ron"))]
+fn parse_error<E: std::fmt::Display>(error: E) -> ConfigError {
+    ConfigError::Parse(error.to_string())
+}
+
+#[cfg(test)]
+// Struct updates fill the fields of the `unstable` feature
+#[cfg_attr(not(feature = "unstable"), allow(clippy::needless_update))]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, ReportPlan, Spreading, Visiting};
+    use crate::simulation::Aggregation;
+
+    #[test]
+    fn diagnostics() {
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder {
+                healthy: 2,
+                buildings: vec![(0, 1)],
+                spreading: Spreading::Airborne(120),
+                visiting: Visiting::Weighted { weights: vec![1, 1], stay_home: 101 },
+                asymptomatic: 200,
+                vaccines: Some(3),
+                ..Default::default()
+            },
+            report_plan: ReportPlan { num_simulations: 0, days: 1, aggregation: Aggregation::Streaming { bin_width: 0 }, ..Default::default() },
+            ..Default::default()
+        };
+        let paths: Vec<String> = simulation_builder.diagnostics().into_iter().map(|d| d.path).collect();
+        assert_eq!(paths, vec![
+            "report_plan.num_simulations",
+            "report_plan.aggregation.bin_width",
+            "board_builder.buildings[0].columns",
+            "board_builder.spreading",
+            "board_builder.visiting.weights",
+            "board_builder.visiting.stay_home",
+            "board_builder.asymptomatic",
+            "board_builder.vaccines",
+        ]);
+        assert!(simulation_builder.validated().is_err());
+    }
+
+    #[test]
+    fn valid() {
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder { healthy: 1, ..Default::default() },
+            report_plan: ReportPlan { num_simulations: 1, days: 1, ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(simulation_builder.diagnostics(), vec![]);
+    }
+
+    fn from_path(extension: &str, contents: &str) -> Result<SimulationBuilder, ConfigError> {
+        let path = std::env::temp_dir().join(format!("virus_alarm_config_{}.{}", std::process::id(), extension));
+        std::fs::write(&path, contents).unwrap();
+        let result = SimulationBuilder::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn template() {
+        let mut template = Vec::new();
+        SimulationBuilder::write_template(&mut template).unwrap();
+        let simulation_builder: SimulationBuilder = ron::de::from_bytes(&template).unwrap();
+        assert_eq!(simulation_builder.report_plan.num_simulations, 100);
+        assert_eq!(simulation_builder.diagnostics(), vec![]);
+    }
+
+    #[test]
+    fn unsupported_format() {
+        assert_eq!(from_path("txt", ""), Err(ConfigError::UnsupportedFormat("txt".to_string())));
+        assert!(matches!(SimulationBuilder::from_path("missing.ron"), Err(ConfigError::Io(_)) | Err(ConfigError::UnsupportedFormat(_))));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn ron_file() {
+        let simulation_builder = from_path("ron", TEMPLATE).unwrap();
+        assert_eq!(simulation_builder.board_builder.healthy, 98);
+        assert!(matches!(from_path("ron", "(report_plan: 3)"), Err(ConfigError::Parse(_))));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_file() {
+        let config = "board_builder:\n  healthy: 3\n  infected1: 1\n  infected2: 0\n  infected3: 0\n  sick: 0\n  immune: 0\n  buildings: [[2, 2]]\n  spreading: Everyone\nreport_plan:\n  num_simulations: 2\n  days: 3\n";
+        let simulation_builder = from_path("yml", config).unwrap();
+        assert_eq!(simulation_builder.board_builder.buildings, vec![(2, 2)]);
+        assert!(matches!(from_path("yaml", "report_plan:\n  num_simulations: 0\n"), Err(ConfigError::Parse(_))));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_file() {
+        let config = "[board_builder]\nhealthy = 3\ninfected1 = 1\ninfected2 = 0\ninfected3 = 0\nsick = 0\nimmune = 0\nbuildings = [[2, 2]]\nspreading = \"Everyone\"\n\n[report_plan]\nnum_simulations = 0\ndays = 3\n";
+        match from_path("toml", config) {
+            Err(ConfigError::Invalid(diagnostics)) => assert_eq!(diagnostics[0].path, "report_plan.num_simulations"),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_file() {
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder { healthy: 1, ..Default::default() },
+            report_plan: ReportPlan { num_simulations: 1, days: 1, ..Default::default() },
+            ..Default::default()
+        };
+        let config = serde_json::to_string(&simulation_builder).unwrap();
+        assert_eq!(from_path("json", &config), Ok(simulation_builder));
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn schema() {
+        let schema = SimulationBuilder::schema();
+        for definition in ["BoardBuilder", "ReportPlan", "Spreading", "Stopping"] {
+            assert!(schema.definitions.contains_key(definition), "{} is not defined", definition);
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut deserializer = ron::Deserializer::from_str("(report_plan: 3)").unwrap();
+        let result = SimulationBuilder::deserialize_validated(&mut deserializer);
+        assert!(matches!(result, Err(ConfigError::Parse(_))));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::Arbitrary;
+        use rand::RngCore;
+
+        for seed in 0..20 {
+            let mut bytes = vec![0; 256];
+            crate::tests::rng(seed).fill_bytes(&mut bytes);
+            let mut u = arbitrary::Unstructured::new(&bytes);
+            let simulation_builder = SimulationBuilder {
+                board_builder: BoardBuilder::arbitrary(&mut u).unwrap(),
+                report_plan: ReportPlan::arbitrary(&mut u).unwrap(),
+                ..Default::default()
+            };
+            assert_eq!(simulation_builder.diagnostics(), vec![]);
+            let report = simulation_builder.build().run_with(&mut crate::tests::rng(seed));
+            assert!(report.days_simulated().iter().all(|&days| days <= 20));
+        }
+    }
+}
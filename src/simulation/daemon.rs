@@ -0,0 +1,113 @@
+use crate::prelude::{Report, SimulationBuilder};
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+
+/// Schedule to run a simulation repeatedly, for long-running processes.
+///
+/// Each run reads the configuration again, so that changes of parameters
+/// are reflected in the next run. See `run`.
+///
+/// Requires the `unstable` feature.
+///
+/// # Examples
+///
+/// Re-run a simulation three times, one second apart, storing the final number of sick individuals.
+/// ```no_run
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::Schedule;
+/// use std::time::Duration;
+/// let schedule = Schedule { interval: Duration::from_secs(1), runs: Some(3) };
+/// let mut store = Vec::new();
+/// schedule.run(
+///     || SimulationBuilder { report_plan: ReportPlan { num_simulations: 10, days: 5, ..Default::default() }, ..Default::default() },
+///     |_, _, report| {
+///         store.push(report.individual_last(&Individual::Sick).into_iter().sum::<usize>());
+///         Ok::<(), std::io::Error>(())
+///     },
+/// ).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Schedule {
+    /// Time between the start of consecutive runs
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub interval: Duration,
+    /// Number of runs, forever if `None`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub runs: Option<usize>,
+}
+
+impl Schedule {
+    /// Runs the simulation configured by `config` as scheduled, giving each result to `sink`.
+    ///
+    /// Before each run, `config` is called to get the current configuration.
+    /// After each run, `sink` is called with the index of the run, the configuration and the report,
+    /// for example to append them to a store of experiments.
+    ///
+    /// Returns the number of runs.
+    ///
+    /// # Errors
+    ///
+    /// If `sink` fails, in which case no more runs are done.
+    pub fn run<C, S, E>(&self, mut config: C, mut sink: S) -> Result<usize, E>
+    where
+        C: FnMut() -> SimulationBuilder,
+        S: FnMut(usize, &SimulationBuilder, Report) -> Result<(), E>,
+    {
+        let mut run = 0;
+        while self.runs.is_none_or(|runs| run < runs) {
+            let start = std::time::Instant::now();
+            let simulation_builder = config();
+            let report = simulation_builder.clone().build().run();
+            sink(run, &simulation_builder, report)?;
+            run += 1;
+            if self.runs.is_none_or(|runs| run < runs) {
+                if let Some(remaining) = self.interval.checked_sub(start.elapsed()) {
+                    std::thread::sleep(remaining);
+                }
+            }
+        }
+        Ok(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, ReportPlan};
+
+    #[test]
+    fn run() {
+        let schedule = Schedule { interval: Duration::from_millis(1), runs: Some(3) };
+        let mut healthy = 1;
+        let mut store = Vec::new();
+        let runs = schedule.run(
+            || {
+                healthy += 1;
+                SimulationBuilder {
+                    board_builder: BoardBuilder { healthy, ..Default::default() },
+                    report_plan: ReportPlan { num_simulations: 1, days: 1, ..Default::default() },
+                    ..Default::default()
+                }
+            },
+            |run, _, report| {
+                store.push((run, report.individual_first(&crate::Individual::Healthy)));
+                Ok::<(), ()>(())
+            },
+        );
+        assert_eq!(runs, Ok(3));
+        assert_eq!(store, vec![(0, 2), (1, 3), (2, 4)]);
+    }
+
+    #[test]
+    fn failing_sink() {
+        let schedule = Schedule { interval: Duration::from_secs(0), runs: None };
+        let runs = schedule.run(
+            || SimulationBuilder { report_plan: ReportPlan { num_simulations: 1, days: 1, ..Default::default() }, ..Default::default() },
+            |run, _, _| if run < 2 { Ok(()) } else { Err(run) },
+        );
+        assert_eq!(runs, Err(2));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::prelude::{Board, Report, ReportPlan};
+use crate::random::Pcg32;
+use crate::simulation::{Aggregation, RunningSummary, SimulationHandle};
+use rand::RngCore;
+#[cfg(feature = "unstable")]
+use std::sync::Arc;
+
+/// Way of computing a `Report` out of a board and a report plan.
+///
+/// Requires the `unstable` feature.
+///
+/// # Examples
+///
+/// An engine that only reports the initial state of the board.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::Engine;
+/// struct Initial;
+///
+/// impl Engine for Initial {
+///     fn run(&self, board: &Board, report_plan: &ReportPlan, _rng: &mut dyn rand::RngCore) -> Report {
+///         let counting_tables = vec![board.counting_table().clone(); *report_plan.num_simulations()];
+///         Report::from(counting_tables)
+///     }
+/// }
+///
+/// let report_plan = ReportPlan { num_simulations: 2, days: 10, ..Default::default() };
+/// let report = Initial.run(&Board::default(), &report_plan, &mut rand::thread_rng());
+/// assert_eq!(report.days(), 1);
+/// ```
+#[cfg(feature = "unstable")]
+pub trait Engine {
+    /// Returns the report of the simulation of `board` according to `report_plan`,
+    /// using `rng` as the source of randomness.
+    fn run(&self, board: &Board, report_plan: &ReportPlan, rng: &mut dyn RngCore) -> Report;
+
+    /// Returns the report of the simulation, as in `run`, stopping early if `handle` is cancelled.
+    ///
+    /// By default, nothing is run if `handle` is already cancelled, and otherwise the simulation runs to the end.
+    /// Engines that can stop in the middle of a simulation should check `handle` themselves 
+    /// and report only the completed realizations, as `MonteCarlo` does.
+    fn run_cancellable(&self, board: &Board, report_plan: &ReportPlan, rng: &mut dyn RngCore, handle: &SimulationHandle) -> Report {
+        if handle.is_cancelled() {
+            Report::default()
+        } else {
+            self.run(board, report_plan, rng)
+        }
+    }
+}
+
+/// Engine used by a `Simulation`, see `SimulationBuilder::engine`.
+///
+/// The default is `MonteCarlo`. Selected engines are shared by the builder and the simulations it builds, 
+/// so they are compared by identity, and are neither hashed nor serialized as part of a configuration.
+///
+/// Requires the `unstable` feature.
+#[cfg(feature = "unstable")]
+#[derive(Clone, Default)]
+pub struct SelectedEngine(Option<Arc<dyn Engine + Send + Sync>>);
+
+#[cfg(feature = "unstable")]
+impl SelectedEngine {
+    /// Returns the selection of `engine`.
+    pub fn new<E: Engine + Send + Sync + 'static>(engine: E) -> Self {
+        SelectedEngine(Some(Arc::new(engine)))
+    }
+
+    /// Returns `true` if the selected engine is `MonteCarlo`, the default one.
+    pub fn is_default(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns the selected engine, if it is not the default one.
+    pub(crate) fn custom(&self) -> Option<&(dyn Engine + Send + Sync)> {
+        self.0.as_deref()
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl core::fmt::Debug for SelectedEngine {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            None => write!(f, "SelectedEngine(MonteCarlo)"),
+            Some(_) => write!(f, "SelectedEngine(custom)"),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl PartialEq for SelectedEngine {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(engine), Some(other)) => Arc::ptr_eq(engine, other),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl Eq for SelectedEngine {}
+
+#[cfg(feature = "unstable")]
+impl core::hash::Hash for SelectedEngine {
+    /// Hashes nothing, since engines are not part of a configuration.
+    fn hash<H: core::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+/// Default engine, which plays the game independently for each realization.
+///
+/// Each realization draws a seed from the given source of randomness and plays with `Pcg32::new(seed)`, 
+/// so that it can be replayed on its own, see `Report::seeds` and `Simulation::replay`.
+/// It starts from a copy of the board, made with `clone_from` into
+/// the board of the previous realization to reuse its allocations, and advances it
+/// the number of days in the report plan, or until a stopping condition holds,
+/// see `ReportPlan::stop_when`. Counting tables are stored or folded into a running summary,
+/// see `ReportPlan::aggregation`.
+/// The first day of each counting table is as specified by `Recording::day_zero`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MonteCarlo;
+
+/// Seeds of the realizations of a `MonteCarlo` simulation.
+enum Streams<'a> {
+    /// Seeds are drawn from the given source of randomness, one per realization.
+    Shared(&'a mut dyn RngCore),
+    /// Seeds are given.
+    Seeded(&'a [u64]),
+}
+
+impl MonteCarlo {
+    /// Runs the simulation, calling `before_stage` with the day, the board and the source of randomness of the realization
+    /// right before each stage.
+    ///
+    /// If `handle` is cancelled, the realization being played is discarded and the rest are not played.
+    pub(crate) fn run_with<F>(&self, board: &Board, report_plan: &ReportPlan, rng: &mut dyn RngCore, handle: &SimulationHandle, before_stage: F) -> Report
+    where
+        F: FnMut(usize, &mut Board, &mut Pcg32),
+    {
+        self.run_streams(board, report_plan, Streams::Shared(rng), handle, before_stage)
+    }
+
+    /// Runs one realization per seed, instead of the number of simulations of the report plan, see `run_with`.
+    ///
+    /// Realizations with the same seed on boards that differ only in an intervention consume the same random numbers
+    /// for as long as the boards behave the same, which pairs them, see `Simulation::run_paired`.
+    pub(crate) fn run_seeded_with<F>(&self, board: &Board, report_plan: &ReportPlan, seeds: &[u64], handle: &SimulationHandle, before_stage: F) -> Report
+    where
+        F: FnMut(usize, &mut Board, &mut Pcg32),
+    {
+        self.run_streams(board, report_plan, Streams::Seeded(seeds), handle, before_stage)
+    }
+
+    fn run_streams<F>(&self, board: &Board, report_plan: &ReportPlan, mut streams: Streams<'_>, handle: &SimulationHandle, mut before_stage: F) -> Report
+    where
+        F: FnMut(usize, &mut Board, &mut Pcg32),
+    {
+        let num_simulations = match streams {
+            Streams::Shared(_) => *report_plan.num_simulations(),
+            Streams::Seeded(seeds) => seeds.len(),
+        };
+        let mut counting_tables = Vec::new();
+        let mut days_simulated = Vec::new();
+        let mut seeds = Vec::new();
+        let mut summary = match report_plan.aggregation() {
+            Aggregation::Full => None,
+            Aggregation::Streaming { bin_width } => Some(RunningSummary::new(*bin_width)),
+        };
+        let mut realization = board.clone();
+        'realizations: for simulation in 0..num_simulations {
+            if simulation > 0 {
+                realization.clone_from(board);
+            }
+            let seed = match &mut streams {
+                Streams::Shared(rng) => rng.next_u64(),
+                Streams::Seeded(seeds) => seeds[simulation],
+            };
+            let rng = &mut Pcg32::new(seed);
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("realization", index = simulation, seed).entered();
+            let board = &mut realization;
+            let mut days = 0;
+            while days < *report_plan.days() && !report_plan.should_stop(board) {
+                if handle.is_cancelled() {
+                    break 'realizations;
+                }
+                before_stage(days, board, rng);
+                board.try_advance_with(rng).expect("the board is in a corrupted state");
+                days += 1;
+            }
+            if handle.is_cancelled() {
+                break;
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(days, "realization finished");
+            let counting_table = board.recording().reported_counting_table();
+            match summary.as_mut() {
+                Some(summary) => { summary.push(&counting_table); },
+                None => counting_tables.push(counting_table),
+            }
+            days_simulated.push(days);
+            seeds.push(seed);
+        }
+        Report { counting_tables, days_simulated, summary, seeds }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl Engine for MonteCarlo {
+    fn run(&self, board: &Board, report_plan: &ReportPlan, rng: &mut dyn RngCore) -> Report {
+        self.run_cancellable(board, report_plan, rng, &SimulationHandle::new())
+    }
+
+    fn run_cancellable(&self, board: &Board, report_plan: &ReportPlan, rng: &mut dyn RngCore, handle: &SimulationHandle) -> Report {
+        self.run_with(board, report_plan, rng, handle, |_, _, _| {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, Individual};
+    use crate::simulation::Stopping;
+
+    fn run(board: &Board, report_plan: &ReportPlan, rng: &mut dyn RngCore) -> Report {
+        MonteCarlo.run_with(board, report_plan, rng, &SimulationHandle::new(), |_, _, _| {})
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let report_plan = ReportPlan { num_simulations: 3, days: 2, ..Default::default() };
+        let report = run(&Board::default(), &report_plan, &mut crate::tests::rng(1));
+        assert_eq!(report.counting_tables().len(), 3);
+        assert_eq!(report.days(), 3);
+    }
+
+    #[test]
+    fn reproducible() {
+        let report_plan = ReportPlan { num_simulations: 3, days: 5, ..Default::default() };
+        let board = Board::default();
+        let report = run(&board, &report_plan, &mut crate::tests::rng(7));
+        assert_eq!(report, run(&board, &report_plan, &mut crate::tests::rng(7)));
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn selected_engine() {
+        let engine = SelectedEngine::new(MonteCarlo);
+        assert!(SelectedEngine::default().is_default());
+        assert!(!engine.is_default());
+        assert_eq!(engine, engine.clone());
+        assert_ne!(engine, SelectedEngine::new(MonteCarlo));
+        assert_ne!(engine, SelectedEngine::default());
+    }
+
+    #[test]
+    fn cancel() {
+        let report_plan = ReportPlan { num_simulations: 5, days: 4, ..Default::default() };
+        let handle = SimulationHandle::new();
+        let mut stages = 0;
+        let report = MonteCarlo.run_with(&Board::default(), &report_plan, &mut crate::tests::rng(1), &handle, |_, _, _| {
+            stages += 1;
+            if stages == 6 {
+                handle.cancel();
+            }
+        });
+        assert_eq!(report.counting_tables().len(), 1);
+        assert_eq!(report.days_simulated(), &vec![4]);
+
+        let report = MonteCarlo.run_with(&Board::default(), &report_plan, &mut crate::tests::rng(1), &handle, |_, _, _| {});
+        assert_eq!(report, Report::default());
+    }
+
+    #[test]
+    fn seeded() {
+        let report_plan = ReportPlan { num_simulations: 5, days: 4, ..Default::default() };
+        let board = Board::default();
+        let report = MonteCarlo.run_seeded_with(&board, &report_plan, &[3, 4, 3], &SimulationHandle::new(), |_, _, _| {});
+        assert_eq!(report.counting_tables().len(), 3);
+        assert_eq!(report.counting_tables()[0], report.counting_tables()[2]);
+        assert_eq!(report.seeds(), &vec![3, 4, 3]);
+        let report = run(&board, &report_plan, &mut crate::tests::rng(1));
+        assert_eq!(report.seeds().len(), 5);
+        let seeded = MonteCarlo.run_seeded_with(&board, &report_plan, report.seeds(), &SimulationHandle::new(), |_, _, _| {});
+        assert_eq!(report, seeded);
+    }
+
+    #[test]
+    fn stopping() {
+        let board = BoardBuilder { healthy: 3, buildings: vec![(2, 2)], ..Default::default() }.build();
+        let report_plan = ReportPlan { num_simulations: 2, days: 5, stop_when: vec![Stopping::NoInfected], ..Default::default() };
+        let report = run(&board, &report_plan, &mut crate::tests::rng(1));
+        assert_eq!(report.days_simulated(), &vec![0, 0]);
+        assert_eq!(report.days(), 1);
+
+        let board = BoardBuilder { healthy: 3, infected1: 1, buildings: vec![(2, 2)], ..Default::default() }.build();
+        let report_plan = ReportPlan { num_simulations: 2, days: 5, stop_when: vec![Stopping::SickAtLeast(1)], ..Default::default() };
+        let report = run(&board, &report_plan, &mut crate::tests::rng(1));
+        assert_eq!(report.days_simulated(), &vec![3, 3]);
+        assert_eq!(report.individual_last(&Individual::Sick), vec![&1, &1]);
+    }
+}
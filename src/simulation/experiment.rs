@@ -0,0 +1,254 @@
+use crate::building::Spreading;
+use crate::prelude::{Board, BoardBuilder, Report, ReportPlan, Simulation};
+use crate::errors::ActionError;
+use crate::simulation::ProbabilityEstimate;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+/// Intervention evaluated by a `StrategyExperiment`.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::simulation::Strategy;
+/// let strategy = Strategy::new("Vaccinate 10").with_vaccinations(10);
+/// assert_eq!(strategy.vaccinations, 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Strategy {
+    /// Name of the strategy, used as the key of the results
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub name: String,
+    /// Number of individuals immunized before the first day, see `Board::immunize_many`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vaccinations: usize,
+    /// Names of the buildings closed before the first day, see `Board::close`
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub closed: Vec<String>,
+    /// Spreading mode of all buildings, instead of the one of the board builder
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spreading: Option<Spreading>,
+}
+
+impl Strategy {
+    /// Returns a strategy that does nothing, named `name`.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Strategy { name: name.into(), ..Default::default() }
+    }
+
+    /// Changes the number of individuals immunized before the first day.
+    pub fn with_vaccinations(mut self, vaccinations: usize) -> Self {
+        self.vaccinations = vaccinations;
+        self
+    }
+
+    /// Adds a building to close before the first day.
+    pub fn with_closed<S: Into<String>>(mut self, name: S) -> Self {
+        self.closed.push(name.into());
+        self
+    }
+
+    /// Changes the spreading mode of all buildings.
+    pub fn with_spreading(mut self, spreading: Spreading) -> Self {
+        self.spreading = Some(spreading);
+        self
+    }
+
+    /// Returns the board built by `board_builder` after applying the strategy.
+    ///
+    /// # Errors
+    ///
+    /// If a closed building is not in the board, see `Board::close`.
+    pub fn board(&self, board_builder: &BoardBuilder) -> Result<Board, ActionError> {
+        let mut board_builder = board_builder.clone();
+        if let Some(spreading) = self.spreading {
+            board_builder.spreading = spreading;
+        }
+        let mut board = board_builder.build();
+        board.immunize_many(self.vaccinations);
+        for name in self.closed.iter() {
+            board.close(name)?;
+        }
+        Ok(board)
+    }
+}
+
+/// Evaluation of several strategies on the same board and report plan.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::{Strategy, StrategyExperiment};
+/// let experiment = StrategyExperiment {
+///     board_builder: BoardBuilder { healthy: 20, infected1: 2, ..Default::default() },
+///     strategies: vec![
+///         Strategy::new("Nothing"),
+///         Strategy::new("Vaccinate all").with_vaccinations(20),
+///     ],
+///     report_plan: ReportPlan { num_simulations: 10, days: 5, ..Default::default() },
+/// };
+/// let results = experiment.run().unwrap();
+/// assert_eq!(results.reports.len(), 2);
+/// assert_eq!(results.reports["Vaccinate all"].outbreak_sizes(), vec![0; 10]);
+/// println!("{}", results);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StrategyExperiment {
+    /// Board setup shared by all strategies
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub board_builder: BoardBuilder,
+    /// Strategies to evaluate
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub strategies: Vec<Strategy>,
+    /// Report setup shared by all strategies
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub report_plan: ReportPlan,
+}
+
+impl StrategyExperiment {
+    /// Returns the results of simulating every strategy.
+    ///
+    /// # Remarks
+    ///
+    /// Strategies with the same name overwrite each other's report.
+    ///
+    /// # Errors
+    ///
+    /// If a strategy closes a building that is not in the board, see `Strategy::board`.
+    pub fn run(&self) -> Result<ExperimentResults, ActionError> {
+        self.run_with(&mut rand::thread_rng())
+    }
+
+    /// Returns the results of simulating every strategy, using `rng` as the source of randomness.
+    ///
+    /// # Errors
+    ///
+    /// If a strategy closes a building that is not in the board, see `Strategy::board`.
+    pub fn run_with(&self, rng: &mut dyn rand::RngCore) -> Result<ExperimentResults, ActionError> {
+        let mut reports = HashMap::new();
+        let mut ranking = Vec::new();
+        for strategy in self.strategies.iter() {
+            let simulation = Simulation::new(strategy.board(&self.board_builder)?, self.report_plan.clone());
+            let report = simulation.run_with(rng);
+            ranking.push(StrategySummary::new(strategy.name.clone(), &report));
+            reports.insert(strategy.name.clone(), report);
+        }
+        ranking.sort_by(|a, b| {
+            a.outbreak_size.partial_cmp(&b.outbreak_size)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(ExperimentResults { reports, ranking })
+    }
+}
+
+/// Summary of the report of a strategy, see `ExperimentResults::ranking`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StrategySummary {
+    /// Name of the strategy
+    pub name: String,
+    /// Average size of the outbreak, see `Report::outbreak_sizes`
+    pub outbreak_size: f64,
+    /// Standard error of `outbreak_size`
+    pub error: f64,
+    /// Probability that the outbreak is contained in the last day, see `Report::containment_probability`
+    pub containment: ProbabilityEstimate,
+}
+
+impl StrategySummary {
+    fn new(name: String, report: &Report) -> Self {
+        let outbreak_size: average::Variance = report.outbreak_sizes()
+            .into_iter()
+            .map(|size| size as f64)
+            .collect();
+        StrategySummary {
+            name,
+            outbreak_size: outbreak_size.mean(),
+            error: outbreak_size.error(),
+            containment: report.containment_probability(),
+        }
+    }
+}
+
+/// Results of a `StrategyExperiment`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentResults {
+    /// Report of each strategy, by name
+    pub reports: HashMap<String, Report>,
+    /// Summaries of the strategies, from the smallest to the largest average outbreak
+    pub ranking: Vec<StrategySummary>,
+}
+
+impl core::fmt::Display for ExperimentResults {
+    /// Writes the ranking as a table.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name_width = self.ranking.iter().map(|summary| summary.name.len()).max().unwrap_or(0).max("Strategy".len());
+        writeln!(f, "Rank {:<w$} Outbreak        Contained", "Strategy", w = name_width)?;
+        for (rank, summary) in self.ranking.iter().enumerate() {
+            writeln!(f, "{:<4} {:<w$} {:>6.2} ± {:<6.2} {:.2}",
+                rank + 1, summary.name, summary.outbreak_size, summary.error, summary.containment.probability, w = name_width)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Individual;
+
+    fn experiment() -> StrategyExperiment {
+        StrategyExperiment {
+            board_builder: BoardBuilder { healthy: 30, infected1: 2, buildings: vec![(3, 3), (3, 3)], ..Default::default() },
+            strategies: vec![
+                Strategy::new("Nothing"),
+                Strategy::new("Lockdown").with_closed("Building 1").with_closed("Building 2"),
+                Strategy::new("Vaccinate").with_vaccinations(30),
+            ],
+            report_plan: ReportPlan { num_simulations: 5, days: 4, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn board() {
+        let board_builder = BoardBuilder { healthy: 5, buildings: vec![(2, 2)], ..Default::default() };
+        let strategy = Strategy::new("All").with_vaccinations(3).with_closed("Building 1").with_spreading(Spreading::One);
+        let board = strategy.board(&board_builder).unwrap();
+        assert_eq!(board.population().counting(Individual::Immune), 3);
+        assert!(board.buildings().iter().all(|building| !building.is_open()));
+        assert_eq!(board.buildings()[0].spreading(), &Spreading::One);
+    }
+
+    #[test]
+    fn run_with() {
+        let results = experiment().run_with(&mut crate::tests::rng(1)).unwrap();
+        assert_eq!(results.reports.len(), 3);
+        let names: Vec<&str> = results.ranking.iter().map(|summary| summary.name.as_str()).collect();
+        assert_eq!(names, vec!["Lockdown", "Vaccinate", "Nothing"]);
+        assert_eq!(results.ranking[0].outbreak_size, 0.);
+        assert_eq!(results.ranking[1].containment.probability, 1.);
+        assert!(results.ranking[2].outbreak_size > 0.);
+        assert_eq!(results, experiment().run_with(&mut crate::tests::rng(1)).unwrap());
+    }
+
+    #[test]
+    fn unknown_building() {
+        let board_builder = BoardBuilder { buildings: vec![(2, 2), (3, 3)], ..Default::default() };
+        let board = Strategy::new("").board(&board_builder).unwrap();
+        let names: Vec<&str> = board.buildings().iter().map(|building| building.name()).collect();
+        assert_eq!(names, vec!["Building 1", "Building 2"]);
+        let strategy = Strategy::new("School").with_closed("School");
+        assert_eq!(strategy.board(&board_builder).unwrap_err(), ActionError::UnknownBuilding("School".to_string()));
+        let experiment = StrategyExperiment { board_builder, strategies: vec![strategy], ..Default::default() };
+        assert!(experiment.run().is_err());
+    }
+}
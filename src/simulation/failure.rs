@@ -0,0 +1,151 @@
+use crate::prelude::{Report, Simulation};
+use rand::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Pathological source of randomness, used to stress a simulation.
+///
+/// Running a simulation with a failing source of randomness gives the outcome of
+/// adversarial, instead of typical, realizations of the game.
+/// For example, every shuffle of the population is the same and
+/// individuals always take the same seats.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::Failure;
+/// let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 5, ..Default::default() });
+/// let report = simulation.run_failing(&Failure::Zeros);
+/// assert_eq!(report.counting_tables()[0], report.counting_tables()[1]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Failure {
+    /// Every draw is zero.
+    ///
+    /// Every shuffle is the same and every random event with positive probability happens.
+    Zeros,
+    /// Draws follow an arithmetic progression, starting at `start`.
+    ///
+    /// Consecutive shuffles are strongly correlated.
+    ///
+    /// # Remarks
+    ///
+    /// The step is forced to be odd, so that every value is eventually drawn.
+    Counter {
+        /// First draw
+        start: u64,
+        /// Difference between consecutive draws
+        step: u64,
+    },
+}
+
+impl Failure {
+    /// Returns the source of randomness that fails as described.
+    pub fn rng(&self) -> FailingRng {
+        FailingRng { failure: *self, state: match self {
+            Failure::Zeros => 0,
+            Failure::Counter { start, .. } => *start,
+        }}
+    }
+}
+
+/// Source of randomness that fails in a given way, see `Failure`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FailingRng {
+    failure: Failure,
+    state: u64,
+}
+
+impl RngCore for FailingRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.state;
+        if let Failure::Counter { step, .. } = self.failure {
+            self.state = self.state.wrapping_add(step | 1);
+        }
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl Simulation {
+    /// Returns the result of the simulation, where randomness fails as specified.
+    ///
+    /// Requires the `unstable` feature.
+    pub fn run_failing(&self, failure: &Failure) -> Report {
+        self.run_with(&mut failure.rng())
+    }
+
+    /// Returns the largest outbreak size among all realizations under all the failures given.
+    ///
+    /// This is an empirical upper bound to compare with the average-case given by `run`.
+    /// See `Report::outbreak_sizes`.
+    ///
+    /// Requires the `unstable` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use virus_alarm::simulation::Failure;
+    /// let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 1, days: 5, ..Default::default() });
+    /// let failures = [Failure::Zeros, Failure::Counter { start: 0, step: 1 << 40 }];
+    /// assert!(simulation.outbreak_upper_bound(&failures) <= 98);
+    /// ```
+    pub fn outbreak_upper_bound(&self, failures: &[Failure]) -> usize {
+        failures.iter()
+            .flat_map(|failure| self.run_failing(failure).outbreak_sizes())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Board, ReportPlan};
+    use rand::seq::SliceRandom;
+    use test_case::test_case;
+
+    #[test_case(Failure::Zeros)]
+    #[test_case(Failure::Counter { start: 3, step: 1 << 60 })]
+    fn same_shuffles(failure: Failure) {
+        let mut shuffled = Vec::new();
+        for _ in 0..2 {
+            let mut vec: Vec<usize> = (0..10).collect();
+            vec.shuffle(&mut failure.rng());
+            shuffled.push(vec);
+        }
+        assert_eq!(shuffled[0], shuffled[1]);
+    }
+
+    #[test]
+    fn counter() {
+        let mut rng = Failure::Counter { start: 1, step: 2 }.rng();
+        assert_eq!((rng.next_u64(), rng.next_u64()), (1, 4));
+    }
+
+    #[test]
+    fn outbreak_upper_bound() {
+        let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 1, days: 3, ..Default::default() });
+        let expected = simulation.run_failing(&Failure::Zeros).outbreak_sizes()[0];
+        assert_eq!(simulation.outbreak_upper_bound(&[Failure::Zeros]), expected);
+        assert_eq!(simulation.outbreak_upper_bound(&[]), 0);
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Handle to cancel a running simulation, see `Simulation::run_cancellable`.
+///
+/// Clones of a handle share their state, so one clone can be given to the simulation
+/// and another one kept by the caller, for example in the event loop of a user interface.
+///
+/// # Examples
+///
+/// Cancel a long simulation from another thread.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::SimulationHandle;
+/// let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 1_000_000, days: 10, ..Default::default() });
+/// let handle = SimulationHandle::new();
+/// let worker = {
+///     let handle = handle.clone();
+///     std::thread::spawn(move || simulation.run_cancellable(&handle))
+/// };
+/// handle.cancel();
+/// let report = worker.join().unwrap();
+/// assert!(report.counting_tables().len() < 1_000_000);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SimulationHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl SimulationHandle {
+    /// Returns a handle that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks the simulation to stop as soon as possible.
+    ///
+    /// The simulation checks the handle before each day of each realization,
+    /// so it stops within a day of the game.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the simulation was asked to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared() {
+        let handle = SimulationHandle::new();
+        let clone = handle.clone();
+        assert!(!handle.is_cancelled());
+        clone.cancel();
+        assert!(handle.is_cancelled());
+        assert!(!SimulationHandle::new().is_cancelled());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::prelude::{BoardBuilder, ReportPlan, Simulation};
+use crate::simulation::{ProbabilityEstimate, Strategy};
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// How the estimated containment probability is compared with the target, see `VaccinationSearch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Criterion {
+    /// The estimated probability must reach the target.
+    #[default]
+    Estimate,
+    /// The lower end of the 95% confidence interval must reach the target,
+    /// so that the number of vaccinations is enough with high confidence.
+    LowerBound,
+}
+
+impl Criterion {
+    /// Returns `true` if `estimate` reaches `target`.
+    pub fn is_met(&self, estimate: &ProbabilityEstimate, target: f64) -> bool {
+        match self {
+            Criterion::Estimate => estimate.probability >= target,
+            Criterion::LowerBound => estimate.lower >= target,
+        }
+    }
+}
+
+/// Search of the smallest number of vaccinations before the first day
+/// that contains the outbreak with a given probability, see `Report::containment_probability`.
+///
+/// # Remarks
+///
+/// The search is a binary search, so it assumes that more vaccinations never reduce
+/// the containment probability. Each evaluation runs a whole simulation, so
+/// the estimates are noisy and the result may vary between runs.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::VaccinationSearch;
+/// let search = VaccinationSearch {
+///     board_builder: BoardBuilder { healthy: 20, infected1: 2, ..Default::default() },
+///     report_plan: ReportPlan { num_simulations: 20, days: 5, ..Default::default() },
+///     target: 0.9,
+///     ..Default::default()
+/// };
+/// let optimum = search.run().unwrap();
+/// assert!(optimum.vaccinations <= 20);
+/// assert!(optimum.containment.probability >= 0.9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VaccinationSearch {
+    /// Board setup without vaccinations
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub board_builder: BoardBuilder,
+    /// Report setup used in each evaluation
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub report_plan: ReportPlan,
+    /// Containment probability to reach
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub target: f64,
+    /// How the target is checked
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub criterion: Criterion,
+}
+
+/// Result of a `VaccinationSearch`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VaccinationOptimum {
+    /// Smallest number of vaccinations found that reaches the target
+    pub vaccinations: usize,
+    /// Containment probability with that number of vaccinations
+    pub containment: ProbabilityEstimate,
+    /// Number of simulations run during the search
+    pub evaluations: usize,
+}
+
+impl VaccinationSearch {
+    /// Returns the smallest number of vaccinations that reaches the target.
+    ///
+    /// Returns `None` if not even vaccinating every healthy individual reaches the target.
+    pub fn run(&self) -> Option<VaccinationOptimum> {
+        self.run_with(&mut rand::thread_rng())
+    }
+
+    /// Returns the smallest number of vaccinations that reaches the target,
+    /// using `rng` as the source of randomness, see `run`.
+    pub fn run_with(&self, rng: &mut dyn rand::RngCore) -> Option<VaccinationOptimum> {
+        let mut evaluations = 0;
+        let mut evaluate = |vaccinations: usize| {
+            evaluations += 1;
+            let board = Strategy::new("").with_vaccinations(vaccinations).board(&self.board_builder)
+                .expect("strategies without closed buildings apply to every board");
+            Simulation::new(board, self.report_plan.clone()).run_with(rng).containment_probability()
+        };
+
+        let healthy = crate::board::vaccinatable(self.board_builder.clone().build().population());
+        let mut best = evaluate(healthy);
+        if !self.criterion.is_met(&best, self.target) {
+            return None;
+        }
+        // Invariant: `high` reaches the target, and every number below `low` does not
+        let (mut low, mut high) = (0, healthy);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let containment = evaluate(middle);
+            if self.criterion.is_met(&containment, self.target) {
+                high = middle;
+                best = containment;
+            } else {
+                low = middle + 1;
+            }
+        }
+        Some(VaccinationOptimum { vaccinations: high, containment: best, evaluations })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn search(days: usize, target: f64, criterion: Criterion) -> VaccinationSearch {
+        VaccinationSearch {
+            board_builder: BoardBuilder { healthy: 16, infected1: 1, buildings: vec![(3, 3), (3, 3)], ..Default::default() },
+            report_plan: ReportPlan { num_simulations: 10, days, ..Default::default() },
+            target,
+            criterion,
+        }
+    }
+
+    #[test_case(0., Criterion::Estimate, Some(0); "no target")]
+    #[test_case(1., Criterion::LowerBound, None; "unreachable")]
+    fn run_with(target: f64, criterion: Criterion, expected: Option<usize>) {
+        let optimum = search(4, target, criterion).run_with(&mut crate::tests::rng(1));
+        assert_eq!(optimum.map(|optimum| optimum.vaccinations), expected);
+    }
+
+    #[test]
+    fn binary_search() {
+        let optimum = search(4, 0.5, Criterion::Estimate).run_with(&mut crate::tests::rng(2)).unwrap();
+        assert!(optimum.containment.probability >= 0.5);
+        assert!(optimum.evaluations <= 1 + 5);
+        assert_eq!(optimum, search(4, 0.5, Criterion::Estimate).run_with(&mut crate::tests::rng(2)).unwrap());
+    }
+
+    #[test]
+    fn composition() {
+        use crate::population::Composition;
+        use crate::Individual;
+
+        let mut search = search(4, 1., Criterion::Estimate);
+        search.board_builder = BoardBuilder {
+            buildings: vec![(3, 3), (3, 3)],
+            composition: Some(Composition::new(10).with(Individual::Healthy, 90).with(Individual::Infected1, 10)),
+            ..Default::default()
+        };
+        let optimum = search.run_with(&mut crate::tests::rng(4)).unwrap();
+        assert!(optimum.vaccinations <= 9);
+        assert_eq!(optimum.containment.probability, 1.);
+    }
+
+    #[test]
+    fn never_contained() {
+        // The infected individual is still infectious in the last day
+        assert_eq!(search(0, 0.5, Criterion::Estimate).run_with(&mut crate::tests::rng(3)), None);
+    }
+}
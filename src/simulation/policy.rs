@@ -0,0 +1,88 @@
+use crate::prelude::{Action, Board};
+use crate::errors::ActionError;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Action scheduled for a given day of the game.
+///
+/// The action is applied right before the stage of `day` is played,
+/// that is, after `day` stages have passed.
+///
+/// # Examples
+///
+/// Close the school in the third day.
+/// ```
+/// # use virus_alarm::prelude::*;
+/// let intervention = Intervention::new(3, Action::Close("School".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Intervention {
+    /// Day in which the action is applied
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub day: usize,
+    /// Action to apply
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub action: Action,
+}
+
+impl Intervention {
+    /// Constructor
+    pub fn new(day: usize, action: Action) -> Self {
+        Intervention { day, action }
+    }
+
+    /// Applies the action to `board` if `day` is the day of the intervention.
+    ///
+    /// Returns `true` if the action was applied.
+    ///
+    /// # Errors
+    ///
+    /// If the action fails, see `Action::apply`. 
+    /// In this case, the action is applied as much as possible.
+    pub fn apply_on(&self, day: usize, board: &mut Board) -> Result<bool, ActionError> {
+        self.apply_on_with(day, board, &mut rand::thread_rng())
+    }
+
+    /// Applies the action to `board` if `day` is the day of the intervention,
+    /// using `rng` as the source of randomness, see `apply_on`.
+    ///
+    /// # Errors
+    ///
+    /// As in `apply_on`.
+    pub fn apply_on_with<R: rand::Rng + ?Sized>(&self, day: usize, board: &mut Board, rng: &mut R) -> Result<bool, ActionError> {
+        if self.day == day {
+            self.action.apply_with(board, rng)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Individual;
+
+    #[test]
+    fn apply_on() {
+        let mut board = Board::default();
+        let intervention = Intervention::new(2, Action::Immunize(1));
+        assert_eq!(intervention.apply_on(1, &mut board), Ok(false));
+        assert_eq!(board.population().counting(Individual::Immune), 0);
+        assert_eq!(intervention.apply_on(2, &mut board), Ok(true));
+        assert_eq!(board.population().counting(Individual::Immune), 1);
+    }
+
+    #[test]
+    fn apply_on_failing() {
+        let mut board = Board::default();
+        let healthy = board.population().counting(Individual::Healthy);
+        let intervention = Intervention::new(0, Action::Immunize(healthy + 1));
+        assert_eq!(intervention.apply_on(0, &mut board), Err(ActionError::NoHealthyLeft));
+        assert_eq!(board.population().counting(Individual::Immune), healthy);
+    }
+}
@@ -2,11 +2,23 @@ use crate::simulation::CountingTable;
 use ndarray::Array2;
 use crate::prelude::{Individual};
 use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 use strum::IntoEnumIterator;
 
+mod html;
+mod streaming;
+#[cfg(feature = "parquet")]
+mod columnar;
+#[cfg(feature = "polars")]
+mod dataframe;
+
+pub use streaming::*;
+
 /// Builder for `Report`.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, Setters, MutGetters, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ReportPlan {
     /// Number of simulations
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
@@ -14,6 +26,221 @@ pub struct ReportPlan {
     /// Number of days the game advances
     #[getset(get = "pub", set = "pub", get_mut = "pub")]
     pub days: usize,
+    /// Conditions to stop each realization before `days`, as soon as any of them holds
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stop_when: Vec<Stopping>,
+    /// How realizations are aggregated into the report
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub aggregation: Aggregation,
+}
+
+impl ReportPlan {
+    /// Returns `true` if any stopping condition holds for `board`, see `Stopping`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::simulation::Stopping;
+    /// let report_plan = ReportPlan { num_simulations: 1, days: 10, stop_when: vec![Stopping::SickAtLeast(2)], ..Default::default() };
+    /// let board = BoardBuilder { healthy: 1, sick: 2, ..Default::default() }.build();
+    /// assert!(report_plan.should_stop(&board));
+    /// ```
+    pub fn should_stop(&self, board: &crate::Board) -> bool {
+        self.stop_when.iter().any(|stopping| stopping.holds(board))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ReportPlan {
+    /// Returns a small valid report plan, with at most 4 simulations of at most 20 days.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let num_simulations = u.int_in_range(1..=4)?;
+        let days = u.int_in_range(0..=20)?;
+        let mut stop_when = Vec::new();
+        for _ in 0..u.int_in_range(0..=2)? {
+            stop_when.push(if u.arbitrary()? { Stopping::NoInfected } else { Stopping::SickAtLeast(u.int_in_range(0..=50)?) });
+        }
+        let aggregation = if u.arbitrary()? { Aggregation::Full } else { Aggregation::Streaming { bin_width: u.int_in_range(1..=10)? } };
+        Ok(ReportPlan { num_simulations, days, stop_when, aggregation })
+    }
+}
+
+/// Condition to stop a realization before the planned number of days, see `ReportPlan::stop_when`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Stopping {
+    /// No individual is infected, see `Individual::is_infected`.
+    NoInfected,
+    /// There are at least the given number of sick individuals.
+    SickAtLeast(usize),
+}
+
+impl Stopping {
+    /// Returns `true` if the condition holds for `board`.
+    pub fn holds(&self, board: &crate::Board) -> bool {
+        let population = board.population();
+        match self {
+            Stopping::NoInfected => Individual::iter()
+                .filter(|individual| individual.is_infected())
+                .all(|individual| population.counting(individual) == 0),
+            Stopping::SickAtLeast(cap) => population.counting(Individual::Sick) >= *cap,
+        }
+    }
+}
+
+/// Estimated probability of an outcome of the game, 
+/// see `Report::outbreak_probability` and `Report::containment_probability`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProbabilityEstimate {
+    /// Fraction of realizations with the outcome
+    pub probability: f64,
+    /// Lower end of the 95% confidence interval
+    pub lower: f64,
+    /// Upper end of the 95% confidence interval
+    pub upper: f64,
+}
+
+/// Distribution of the count of an individual variant in one day over all realizations,
+/// see `Report::individual_summary`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DaySummary {
+    /// Minimum count
+    pub min: usize,
+    /// 5% quantile
+    pub lower: f64,
+    /// Median
+    pub median: f64,
+    /// 95% quantile
+    pub upper: f64,
+    /// Maximum count
+    pub max: usize,
+}
+
+/// Mean counting table with confidence intervals of the means, see `Report::mean_with_ci`.
+///
+/// Rows follow the order of `Individual::iter` and columns are days, as in `Report::average_counting_table`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceBands {
+    /// Confidence level of the intervals
+    pub confidence: f64,
+    /// Mean count
+    pub mean: Array2<f64>,
+    /// Lower end of the confidence interval of the mean
+    pub lower: Array2<f64>,
+    /// Upper end of the confidence interval of the mean
+    pub upper: Array2<f64>,
+}
+
+/// Maximum count of an individual variant in one realization, see `Report::peaks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Peak {
+    /// First day with the maximum count
+    pub day: usize,
+    /// Maximum count
+    pub count: usize,
+}
+
+/// Average of the peaks over all realizations, see `Report::average_peak`.
+#[derive(Debug, Clone)]
+pub struct PeakAverage {
+    /// Average of the first day with the maximum count
+    pub day: average::Variance,
+    /// Average of the maximum count
+    pub count: average::Variance,
+}
+
+/// One realization of a report, see `Report::best_realizations` and `Report::worst_realizations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Realization<'a> {
+    /// Index of the realization in the report
+    pub index: usize,
+    /// Seed of the realization, if known, see `Report::seeds`
+    pub seed: Option<u64>,
+    /// Number of healthy individuals in the last day
+    pub final_healthy: usize,
+    /// Counting table of the realization
+    pub counting_table: &'a CountingTable,
+}
+
+/// Number of realizations per range of counts, see `Report::final_histogram`.
+///
+/// Bin `k` counts the realizations between `k * bin_width` and `(k + 1) * bin_width - 1`, both included.
+/// Its `Display` implementation is a text histogram, ready to print.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::simulation::Histogram;
+/// let histogram = Histogram { bin_width: 5, counts: vec![3, 0, 1] };
+/// let lines: Vec<String> = histogram.to_string().lines().map(String::from).collect();
+/// assert_eq!(lines, vec![" 0-4  | ### 3", " 5-9  | 0", "10-14 | # 1"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Histogram {
+    /// Width of each bin
+    pub bin_width: usize,
+    /// Number of realizations in each bin
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Maximum length of the bars in the text histogram.
+    const BAR_WIDTH: usize = 50;
+
+    /// Returns the smallest and largest count of bin `index`, both included.
+    pub fn bin_range(&self, index: usize) -> (usize, usize) {
+        (index * self.bin_width, (index + 1) * self.bin_width - 1)
+    }
+}
+
+impl core::fmt::Display for Histogram {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let labels: Vec<(String, String)> = (0..self.counts.len())
+            .map(|index| {
+                let (start, end) = self.bin_range(index);
+                (start.to_string(), end.to_string())
+            })
+            .collect();
+        let start_width = labels.iter().map(|(start, _)| start.len()).max().unwrap_or(0);
+        let end_width = labels.iter().map(|(_, end)| end.len()).max().unwrap_or(0);
+        let max_count = self.counts.iter().copied().max().unwrap_or(0);
+        for ((start, end), &count) in labels.iter().zip(self.counts.iter()) {
+            let bar = if max_count > Self::BAR_WIDTH { count * Self::BAR_WIDTH / max_count } else { count };
+            let bar = "#".repeat(bar);
+            let separator = if bar.is_empty() { "" } else { " " };
+            writeln!(f, "{:>sw$}-{:<ew$} | {}{}{}", start, end, bar, separator, count, sw = start_width, ew = end_width)?;
+        }
+        Ok(())
+    }
+}
+
+impl ProbabilityEstimate {
+    /// Returns the estimate for `successes` out of `trials` realizations, 
+    /// with a 95% Wilson score confidence interval.
+    fn new(successes: usize, trials: usize) -> Self {
+        if trials == 0 {
+            return ProbabilityEstimate { probability: 0., lower: 0., upper: 1. };
+        }
+        let n = trials as f64;
+        let probability = successes as f64 / n;
+        let z = 1.959963984540054_f64;
+        let denominator = 1. + z * z / n;
+        let center = (probability + z * z / (2. * n)) / denominator;
+        let margin = z * (probability * (1. - probability) / n + z * z / (4. * n * n)).sqrt() / denominator;
+        ProbabilityEstimate { 
+            probability, 
+            lower: (center - margin).max(0.), 
+            upper: (center + margin).min(1.),
+        }
+    }
 }
 
 /// Report of a simulation of a game.
@@ -22,16 +249,38 @@ pub struct Report {
     /// Counting tables.
     #[getset(get = "pub")]
     pub(crate) counting_tables: Vec<CountingTable>,
+    /// Number of days simulated in each realization,
+    /// which is less than planned if a stopping condition holds, see `ReportPlan::stop_when`.
+    #[getset(get = "pub")]
+    pub(crate) days_simulated: Vec<usize>,
+    /// Running summary of all realizations, if they were aggregated in streaming mode.
+    pub(crate) summary: Option<RunningSummary>,
+    /// Seed of the source of randomness of each realization, see `MonteCarlo` and `Simulation::replay`.
+    ///
+    /// It is empty if the report was not made by a simulation, for example if it comes from counting tables.
+    #[getset(get = "pub")]
+    pub(crate) seeds: Vec<u64>,
 }
 
 impl Report {
-    /// Returns the number of days in all simulations
+    /// Returns the number of days in the counting tables of all simulations.
+    ///
+    /// If some realizations stopped early, it is the number of days of the longest one,
+    /// see `days_simulated`. In streaming mode, it is the number of days of the summary.
     pub fn days(&self) -> usize {
-        self.counting_tables()[0].days()
+        match &self.summary {
+            Some(summary) => summary.days(),
+            None => self.counting_tables().iter().map(|counting_table| counting_table.days()).max().unwrap_or(0),
+        }
     }
 
     /// Returns the average "counting table" over all simulations. 
     ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Realizations that stopped before a day, see `ReportPlan::stop_when`, are frozen,
+    /// so they are averaged with their counts in the last day they reached.
+    ///
     /// # Remarks
     ///
     /// It can not return a `CountingTable` since the averages are `f64`, so it returns the numerical table only.
@@ -45,21 +294,103 @@ impl Report {
             let counting_tables: Vec<_> = self.counting_tables().iter().map(Array2::from).collect();
             for row in 0..individual_variants_num {
                 for col in 0..days {
-                    average_array[[row, col]] = counting_tables.iter().map(|counting_table| counting_table[[row, col]] as f64).collect();
+                    average_array[[row, col]] = counting_tables.iter()
+                        .filter_map(|counting_table| {
+                            counting_table.get([row, col]).or_else(|| counting_table.get([row, counting_table.ncols().checked_sub(1)?]))
+                        })
+                        .map(|&value| value as f64)
+                        .collect();
                 }
             }
             average_array
         }
     }
 
-    /// Returns the trajectory over time of a individual variant for all realizations. 
+    /// Returns the number of realizations, the mean and the standard error of the mean of each individual variant (rows) 
+    /// and day (columns), from the running summary in streaming mode or from the counting tables otherwise.
+    pub(crate) fn cell_statistics(&self) -> Array2<(usize, f64, f64)> {
+        match &self.summary {
+            Some(summary) => {
+                let mut cells = Array2::from_elem((Individual::iter().len(), summary.days()), (0_usize, 0., 0.));
+                for (row, individual) in Individual::iter().enumerate() {
+                    for (col, statistic) in summary.individual(&individual).iter().enumerate() {
+                        cells[[row, col]] = (statistic.len(), statistic.mean(), statistic.error());
+                    }
+                }
+                cells
+            },
+            None => self.average_counting_table().map(|variance| (variance.len() as usize, variance.mean(), variance.error())),
+        }
+    }
+
+    /// Returns the mean counting table over all simulations, with confidence intervals of the means at level `confidence`,
+    /// for example `0.95`, ready for plotting.
+    ///
+    /// Streamed reports use their running summary, see `Aggregation::Streaming`.
+    ///
+    /// Intervals follow Student's t-distribution with as many degrees of freedom as realizations in the day minus one,
+    /// so they widen when there are few realizations. With fewer than two realizations in a day, their ends are `NaN`.
+    ///
+    /// # Panics
+    ///
+    /// If `confidence` is not strictly between zero and one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use strum::IntoEnumIterator;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 10, days: 5, ..Default::default() }).run();
+    /// let bands = report.mean_with_ci(0.95);
+    /// let healthy = Individual::iter().position(|individual| individual == Individual::Healthy).unwrap();
+    /// for day in 0..report.days() {
+    ///     let mean = bands.mean[[healthy, day]];
+    ///     assert!(bands.lower[[healthy, day]] <= mean && mean <= bands.upper[[healthy, day]]);
+    /// }
+    /// ```
+    pub fn mean_with_ci(&self, confidence: f64) -> ConfidenceBands {
+        assert!(0. < confidence && confidence < 1., "the confidence level must be between zero and one");
+        let cells = self.cell_statistics();
+        let mut quantiles = std::collections::HashMap::new();
+        let half_widths = cells.map(|&(len, _, error)| {
+            if len < 2 {
+                return f64::NAN;
+            }
+            let quantile = *quantiles.entry(len)
+                .or_insert_with(|| crate::simulation::statistics::student_quantile(confidence, (len - 1) as f64));
+            quantile * error
+        });
+        let mean = cells.map(|&(_, mean, _)| mean);
+        ConfidenceBands {
+            confidence,
+            lower: &mean - &half_widths,
+            upper: &mean + &half_widths,
+            mean,
+        }
+    }
+
+    /// Returns the trajectory over time of an individual variant for all realizations. 
     /// Each element of the vector is a realization, 
     /// which consists in a vector of values that represent 
-    /// the evolution of healthy individuals over time.
+    /// the evolution of the individuals of that variant over time.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Any variant can be queried, for example to study sick or immune individuals.
     ///
     /// # Remarks
     ///
-    /// Realizations that do not have a variant individuals are omitted.
+    /// Realizations that do not have the variant are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 3, ..Default::default() }).run();
+    /// let sick = report.individual(&Individual::Sick);
+    /// assert_eq!(sick.len(), 2);
+    /// assert_eq!(sick[0][..3], [0, 0, 0]);
+    /// ```
     pub fn individual(&self, individual: &Individual) -> Vec<&Vec<usize>> {
         let mut vec = Vec::new();
         for counting_table in self.counting_tables() {
@@ -70,23 +401,39 @@ impl Report {
         vec
     }
 
-    /// Returns the trajectory over time of healthy individuals for all realizations. 
+    /// Returns the trajectory over time of an individual variant for all realizations. 
     /// Each element of the vector is a day of the game, 
     /// which has a vector of values that represent each realization.
     ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
     /// # Remarks
     ///
-    /// Realizations that do not have healthy individuals are omitted.
+    /// Realizations that do not have the variant are omitted.
+    /// Realizations that stopped before the day repeat their count in the last day they reached,
+    /// since they are frozen, see `ReportPlan::stop_when`.
     pub fn individual_transpose(&self, individual: &Individual) -> Vec<Vec<usize>> {
         let mut vec = Vec::new();
         let all = self.individual(individual);
         for day in 0..self.days() {
-            vec.push( all.iter().map(|realization| realization[day]).collect() );
+            vec.push( all.iter().filter_map(|realization| realization.get(day).or_else(|| realization.last()).copied()).collect() );
         }
         vec
     }
 
-    /// Returns the average (per day) of healthy people over all simulations. 
+    /// Returns the average (per day) of an individual variant over all simulations. 
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 5, days: 3, ..Default::default() }).run();
+    /// let sick = report.individual_average(&Individual::Sick);
+    /// assert_eq!(sick.len(), 4);
+    /// assert_eq!(sick[3].mean(), 2.);
+    /// ```
     pub fn individual_average(&self, individual: &Individual) -> Vec<average::Variance> {
         self.individual_transpose(individual)
             .into_iter()
@@ -94,35 +441,590 @@ impl Report {
             .collect()
     }
 
-    /// Returns the trajectory over time of healthy individuals for all realizations.
+    /// Returns the last day count of an individual variant for all realizations.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
     ///
     /// # Remarks
     ///
-    /// Realizations that do not have healthy individuals are omitted.
+    /// Realizations that do not have the variant are omitted.
     pub fn individual_last(&self, individual: &Individual) -> Vec<&usize> {
         self.individual(individual).into_iter()
             .map(|v| v.last().expect("Empty vector!"))
             .collect()
     }
 
-    /// Returns the initial number of healthy individuals, which is the same for all realizations.
+    /// Returns the `q` quantile (per day) of an individual variant over all simulations.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Quantiles are interpolated linearly between the closest realizations.
+    /// For example, `q = 0.5` gives the median.
+    ///
+    /// # Panics
+    ///
+    /// If `q` is not between zero and one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::recording::CountingTable;
+    /// let report = Report::from(vec![
+    ///     CountingTable::from(vec![(Individual::Healthy, vec![10, 1])]),
+    ///     CountingTable::from(vec![(Individual::Healthy, vec![10, 9])]),
+    ///     CountingTable::from(vec![(Individual::Healthy, vec![10, 8])]),
+    /// ]);
+    /// assert_eq!(report.individual_quantile(&Individual::Healthy, 0.5), vec![10., 8.]);
+    /// assert_eq!(report.individual_quantile(&Individual::Healthy, 0.25), vec![10., 4.5]);
+    /// ```
+    pub fn individual_quantile(&self, individual: &Individual, q: f64) -> Vec<f64> {
+        assert!((0. ..=1.).contains(&q), "quantiles must be between zero and one");
+        self.sorted_transpose(individual).iter()
+            .map(|values| quantile(values, q))
+            .collect()
+    }
+
+    /// Returns the minimum and the maximum (per day) of an individual variant over all simulations.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    pub fn individual_envelope(&self, individual: &Individual) -> Vec<(usize, usize)> {
+        self.sorted_transpose(individual).iter()
+            .filter_map(|values| Some((*values.first()?, *values.last()?)))
+            .collect()
+    }
+
+    /// Returns the distribution (per day) of an individual variant over all simulations.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Unlike `individual_average`, this shows whether realizations split in different outcomes,
+    /// for example outbreaks and no outbreaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 20, days: 5, ..Default::default() }).run();
+    /// let summary = report.individual_summary(&Individual::Healthy);
+    /// assert_eq!(summary.len(), 6);
+    /// assert!(summary[5].min as f64 <= summary[5].lower);
+    /// assert!(summary[5].lower <= summary[5].median);
+    /// assert!(summary[5].median <= summary[5].upper);
+    /// assert!(summary[5].upper <= summary[5].max as f64);
+    /// ```
+    pub fn individual_summary(&self, individual: &Individual) -> Vec<DaySummary> {
+        self.sorted_transpose(individual).iter()
+            .filter(|values| !values.is_empty())
+            .map(|values| DaySummary {
+                min: values[0],
+                lower: quantile(values, 0.05),
+                median: quantile(values, 0.5),
+                upper: quantile(values, 0.95),
+                max: values[values.len() - 1],
+            })
+            .collect()
+    }
+
+    /// Returns `individual_transpose` with the values of each day sorted, 
+    /// or nothing if there are no realizations.
+    fn sorted_transpose(&self, individual: &Individual) -> Vec<Vec<usize>> {
+        if self.counting_tables.is_empty() {
+            return Vec::new();
+        }
+        let mut transpose = self.individual_transpose(individual);
+        for values in transpose.iter_mut() {
+            values.sort_unstable();
+        }
+        transpose
+    }
+
+    /// Returns the trajectories of a individual variant for all realizations in long format.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Each row is a triple `(realization, day, count)`, 
+    /// which is the format usually needed for spaghetti plots and external statistical tools.
+    ///
+    /// # Remarks
+    ///
+    /// Realizations that do not have a variant individuals are omitted, 
+    /// but the realization index still refers to the position in `counting_tables`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 1, ..Default::default() }).run();
+    /// let rows = report.trajectories_long(&Individual::Immune);
+    /// assert_eq!(rows, vec![(0, 0, 0), (0, 1, 0), (1, 0, 0), (1, 1, 0)]);
+    /// ```
+    pub fn trajectories_long(&self, individual: &Individual) -> Vec<(usize, usize, usize)> {
+        let mut rows = Vec::new();
+        for (realization, counting_table) in self.counting_tables().iter().enumerate() {
+            if let Some(v) = counting_table.inner().get(individual) {
+                rows.extend(v.iter().enumerate().map(|(day, &count)| (realization, day, count)));
+            }
+        }
+        rows
+    }
+
+    /// Writes all realizations on the writer, in long format, after the metadata header block.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// The first row is the header `realization,individual,day,count`, followed by one row per 
+    /// realization, individual variant and day, in this order of priority. 
+    /// Individual variants are always in the order `Healthy`, `Infected1`, `Infected2`, `Infected3`, `Sick`, `Immune`, `HealthySuperspreader`, `Superspreader1`, `Superspreader2`, `Superspreader3`, `Vaccinated`, `Exposed`, `Recovered` and `PartiallyVaccinated`.
+    ///
+    /// See `Metadata` for the header block.
+    ///
+    /// # Remarks
+    ///
+    /// Recall that a writer needs to be flushed to show in the output stream.
+    pub fn write_with_metadata<W: std::io::Write>(&self, mut writer: W, metadata: &crate::recording::Metadata) -> csv::Result<csv::Writer<W>> {
+        metadata.write_on(&mut writer)?;
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["realization", "individual", "day", "count"])?;
+        for (realization, counting_table) in self.counting_tables().iter().enumerate() {
+            for individual in Individual::iter() {
+                if let Some(v) = counting_table.inner().get(&individual) {
+                    for (day, count) in v.iter().enumerate() {
+                        writer.serialize((realization, individual.to_string(), day, count))?;
+                    }
+                }
+            }
+        }
+        Ok(writer)
+    }
+
+    /// Returns the size of the outbreak for each realization, 
+    /// ie the number of individuals that got infected during the game, see `cumulative_infections`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Vaccinations, waning immunity and arrivals change the number of healthy individuals
+    /// without infections, so they do not count in the outbreak.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 0, ..Default::default() }).run();
+    /// assert_eq!(report.outbreak_sizes(), vec![0, 0]);
+    /// ```
+    pub fn outbreak_sizes(&self) -> Vec<usize> {
+        self.counting_tables().iter()
+            .map(|counting_table| counting_table.cumulative_infections().last().copied().unwrap_or(0))
+            .collect()
+    }
+
+    /// Returns the realization with index `index`, if the report has its counting table.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    pub fn realization(&self, index: usize) -> Option<Realization<'_>> {
+        let counting_table = self.counting_tables.get(index)?;
+        let final_healthy = counting_table.inner().get(&Individual::Healthy)
+            .and_then(|healthy| healthy.last())
+            .copied()
+            .unwrap_or(0);
+        Some(Realization { index, seed: self.seeds.get(index).copied(), final_healthy, counting_table })
+    }
+
+    /// Returns the `k` realizations with the most healthy individuals in the last day, from the best one.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Ties are broken by the index of the realization. With the seed of a realization, 
+    /// it can be replayed to see how it unfolded, see `Simulation::replay`.
+    ///
+    /// # Remarks
+    ///
+    /// In streaming mode, counting tables are not kept, so there are no realizations, see `Aggregation`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let simulation = Simulation::new(Board::default(), ReportPlan { num_simulations: 10, days: 5, ..Default::default() });
+    /// let report = simulation.run();
+    /// let best = report.best_realizations(2);
+    /// assert!(best[0].final_healthy >= best[1].final_healthy);
+    /// let replayed = simulation.replay(best[0].seed.unwrap());
+    /// assert_eq!(&replayed.recording().reported_counting_table(), best[0].counting_table);
+    /// ```
+    pub fn best_realizations(&self, k: usize) -> Vec<Realization<'_>> {
+        let mut realizations = self.realizations();
+        realizations.sort_by_key(|realization| (std::cmp::Reverse(realization.final_healthy), realization.index));
+        realizations.truncate(k);
+        realizations
+    }
+
+    /// Returns the `k` realizations with the fewest healthy individuals in the last day, from the worst one.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Ties are broken by the index of the realization, see `best_realizations`.
+    pub fn worst_realizations(&self, k: usize) -> Vec<Realization<'_>> {
+        let mut realizations = self.realizations();
+        realizations.sort_by_key(|realization| (realization.final_healthy, realization.index));
+        realizations.truncate(k);
+        realizations
+    }
+
+    fn realizations(&self) -> Vec<Realization<'_>> {
+        (0..self.counting_tables.len()).filter_map(|index| self.realization(index)).collect()
+    }
+
+    /// Returns the peak of an individual variant for each realization, 
+    /// ie its maximum count and the first day it happens.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// For example, the peak of sick individuals measures how much the curve was flattened.
+    ///
+    /// # Remarks
+    ///
+    /// Realizations that do not have the variant are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::{recording::CountingTable, simulation::Peak};
+    /// let report = Report::from(vec![CountingTable::from(vec![(Individual::Sick, vec![0, 4, 6, 6, 2])])]);
+    /// assert_eq!(report.peaks(&Individual::Sick), vec![Peak { day: 2, count: 6 }]);
+    /// ```
+    pub fn peaks(&self, individual: &Individual) -> Vec<Peak> {
+        self.individual(individual).into_iter()
+            .filter_map(|trajectory| {
+                let count = *trajectory.iter().max()?;
+                let day = trajectory.iter().position(|&value| value == count)?;
+                Some(Peak { day, count })
+            })
+            .collect()
+    }
+
+    /// Returns the average peak of an individual variant over all realizations, see `peaks`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 10, days: 10, ..Default::default() }).run();
+    /// let peak = report.average_peak(&Individual::Sick);
+    /// assert!(peak.count.mean() >= 2.);
+    /// assert!(peak.day.mean() >= 3.);
+    /// ```
+    pub fn average_peak(&self, individual: &Individual) -> PeakAverage {
+        let peaks = self.peaks(individual);
+        PeakAverage {
+            day: peaks.iter().map(|peak| peak.day as f64).collect(),
+            count: peaks.iter().map(|peak| peak.count as f64).collect(),
+        }
+    }
+
+    /// Returns the newly infected individuals per day for each realization, 
+    /// see `CountingTable::incidence`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    pub fn incidence(&self) -> Vec<Vec<usize>> {
+        self.counting_tables().iter()
+            .map(|counting_table| counting_table.incidence())
+            .collect()
+    }
+
+    /// Returns the average (per day) of the newly infected individuals over all simulations.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 5, days: 3, ..Default::default() }).run();
+    /// let incidence = report.average_incidence();
+    /// assert_eq!(incidence.len(), 4);
+    /// assert_eq!(incidence[0].mean(), 0.);
+    /// ```
+    pub fn average_incidence(&self) -> Vec<average::Variance> {
+        let incidence = self.incidence();
+        let days = incidence.iter().map(|v| v.len()).max().unwrap_or(0);
+        (0..days)
+            .map(|day| incidence.iter().filter_map(|v| v.get(day)).map(|&x| x as f64).collect())
+            .collect()
+    }
+
+    /// Returns the average (per day) of the effective reproduction number over all simulations,
+    /// see `CountingTable::reproduction_numbers`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// Each day only averages the realizations where the reproduction number is defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let board = BoardBuilder { healthy: 10, infected1: 1, buildings: vec![(3, 4)], spreading: Spreading::Everyone, ..Default::default() }.build();
+    /// let report = Simulation::new(board, ReportPlan { num_simulations: 5, days: 1, ..Default::default() }).run();
+    /// let reproduction = report.average_reproduction_numbers();
+    /// assert!(reproduction[0].is_empty());
+    /// assert_eq!(reproduction[1].mean(), 10.);
+    /// ```
+    pub fn average_reproduction_numbers(&self) -> Vec<average::Variance> {
+        let reproduction: Vec<Vec<Option<f64>>> = self.counting_tables().iter()
+            .map(|counting_table| counting_table.reproduction_numbers())
+            .collect();
+        let days = reproduction.iter().map(|v| v.len()).max().unwrap_or(0);
+        (0..days)
+            .map(|day| reproduction.iter().filter_map(|v| v.get(day).copied().flatten()).collect())
+            .collect()
+    }
+
+    /// Returns the first day when no individual is infected for each realization,
+    /// or `None` if the epidemic did not end during the game, see `CountingTable::extinction_day`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    pub fn extinction_days(&self) -> Vec<Option<usize>> {
+        self.counting_tables().iter()
+            .map(|counting_table| counting_table.extinction_day())
+            .collect()
+    }
+
+    /// Returns the average of the day the epidemic ends, over the realizations where it ends 
+    /// during the game, see `extinction_days`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let board = BoardBuilder { healthy: 10, infected3: 1, ..Default::default() }.build();
+    /// let report = Simulation::new(board, ReportPlan { num_simulations: 5, days: 3, ..Default::default() }).run();
+    /// assert_eq!(report.average_extinction_day().mean(), 1.);
+    /// ```
+    pub fn average_extinction_day(&self) -> average::Variance {
+        self.extinction_days().into_iter()
+            .flatten()
+            .map(|day| day as f64)
+            .collect()
+    }
+
+    /// Returns the fraction of realizations where the epidemic has ended by each day, 
+    /// which is the empirical distribution function of `extinction_days`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::recording::CountingTable;
+    /// let report = Report::from(vec![
+    ///     CountingTable::from(vec![(Individual::Healthy, vec![1, 1, 1]), (Individual::Infected1, vec![1, 0, 0])]),
+    ///     CountingTable::from(vec![(Individual::Healthy, vec![1, 1, 1]), (Individual::Infected1, vec![1, 1, 1])]),
+    /// ]);
+    /// assert_eq!(report.extinction_distribution(), vec![0., 0.5, 0.5]);
+    /// ```
+    pub fn extinction_distribution(&self) -> Vec<f64> {
+        if self.counting_tables.is_empty() {
+            return Vec::new();
+        }
+        let extinction_days = self.extinction_days();
+        (0..self.days())
+            .map(|day| {
+                let ended = extinction_days.iter().filter(|extinction| extinction.is_some_and(|extinction| extinction <= day)).count();
+                ended as f64 / extinction_days.len() as f64
+            })
+            .collect()
+    }
+
+    /// Returns the cumulative number of newly infected individuals per day for each realization,
+    /// see `CountingTable::cumulative_infections`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    pub fn cumulative_infections(&self) -> Vec<Vec<usize>> {
+        self.counting_tables().iter()
+            .map(|counting_table| counting_table.cumulative_infections())
+            .collect()
+    }
+
+    /// Returns the probability of an outbreak, estimated as the fraction of realizations 
+    /// whose outbreak size is larger than `threshold`, see `outbreak_sizes`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// The estimate comes with a 95% Wilson score confidence interval.
+    /// If there are no realizations, the estimate is zero and the interval is `[0, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 50, days: 0, ..Default::default() }).run();
+    /// let outbreak = report.outbreak_probability(0);
+    /// assert_eq!(outbreak.probability, 0.);
+    /// assert!(outbreak.upper < 0.1);
+    /// ```
+    pub fn outbreak_probability(&self, threshold: usize) -> ProbabilityEstimate {
+        let outbreaks = self.outbreak_sizes().into_iter()
+            .filter(|&size| size > threshold)
+            .count();
+        ProbabilityEstimate::new(outbreaks, self.counting_tables.len())
+    }
+
+    /// Returns the probability that the outbreak is contained at the end of the game,
+    /// estimated as the fraction of realizations where it is contained, see `CountingTable::is_contained`.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// The estimate comes with a 95% Wilson score confidence interval.
+    /// If there are no realizations, the estimate is zero and the interval is `[0, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let board = BoardBuilder { healthy: 10, sick: 1, ..Default::default() }.build();
+    /// let report = Simulation::new(board, ReportPlan { num_simulations: 10, days: 3, ..Default::default() }).run();
+    /// assert_eq!(report.containment_probability().probability, 1.);
+    /// ```
+    pub fn containment_probability(&self) -> ProbabilityEstimate {
+        let contained = self.counting_tables().iter()
+            .filter(|counting_table| counting_table.is_contained())
+            .count();
+        ProbabilityEstimate::new(contained, self.counting_tables.len())
+    }
+
+    /// Returns the histogram of the last day counts of an individual variant over all realizations,
+    /// with bins of width `bin_width`.
+    ///
+    /// Bins go from zero up to the bin with the largest count.
+    /// Streamed reports merge the bins of their running summary, see `Aggregation::Streaming`.
+    ///
+    /// # Panics
+    ///
+    /// If `bin_width` is zero, or if the report is streamed with bins whose width does not divide `bin_width`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 20, days: 10, ..Default::default() }).run();
+    /// let histogram = report.final_histogram(&Individual::Sick, 10);
+    /// assert_eq!(histogram.counts.iter().sum::<usize>(), 20);
+    /// println!("{}", histogram);
+    /// ```
+    pub fn final_histogram(&self, individual: &Individual, bin_width: usize) -> Histogram {
+        assert!(bin_width > 0, "bins must have positive width");
+        if let Some(summary) = &self.summary {
+            return summary.rebinned_final_histogram(individual, bin_width)
+                .expect("streamed histograms can only merge whole bins");
+        }
+        let mut counts = Vec::new();
+        for &last in self.individual_last(individual) {
+            let bin = last / bin_width;
+            if counts.len() <= bin {
+                counts.resize(bin + 1, 0);
+            }
+            counts[bin] += 1;
+        }
+        Histogram { bin_width, counts }
+    }
+
+    /// Appends the days of `additional` to the realizations of this report.
+    ///
+    /// This allows to produce long studies in stages, by resuming the boards of each realization.
+    /// The first day of each realization in `additional` must be the last day of the 
+    /// corresponding realization in this report, so it is not repeated.
+    ///
+    /// # Errors
+    ///
+    /// If any of the reports is streamed, see `Aggregation::Streaming`,
+    /// if the reports have a different number of realizations, 
+    /// or if a realization in `additional` does not start where the one in this report ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let mut board = Board::default();
+    /// let mut first = board.clone();
+    /// first.advance_many(2);
+    /// let mut report = Report::from(vec![first.counting_table().clone()]);
+    /// let mut second = Board::new(first.population().clone(), first.buildings().clone());
+    /// second.advance_many(3);
+    /// report.extend_days(&Report::from(vec![second.counting_table().clone()])).unwrap();
+    /// assert_eq!(report.days(), 6);
+    /// ```
+    pub fn extend_days(&mut self, additional: &Report) -> Result<&mut Self, crate::errors::ReportError> {
+        if self.summary.is_some() || additional.summary.is_some() {
+            return Err(crate::errors::ReportError::Streaming);
+        }
+        if self.counting_tables.len() != additional.counting_tables.len() {
+            return Err(crate::errors::ReportError::RealizationsMismatch { 
+                expected: self.counting_tables.len(), 
+                found: additional.counting_tables.len(),
+            });
+        }
+        for (realization, (counting_table, next)) in self.counting_tables.iter().zip(additional.counting_tables.iter()).enumerate() {
+            if !counting_table.is_continued_by(next) {
+                return Err(crate::errors::ReportError::Discontinuous { realization });
+            }
+        }
+        for (counting_table, next) in self.counting_tables.iter_mut().zip(additional.counting_tables.iter()) {
+            counting_table.extend(next).expect("continuity was checked");
+        }
+        for (days, additional_days) in self.days_simulated.iter_mut().zip(additional.days_simulated.iter()) {
+            *days += additional_days;
+        }
+        Ok(self)
+    }
+
+    /// Returns the initial number of individuals of a variant, which is the same for all realizations.
+    ///
+    /// Streamed reports use their running summary, see `Aggregation::Streaming`.
     ///
     /// # Panics
     ///
     /// If the number of simulations is zero.
     pub fn individual_first(&self, individual: &Individual) -> usize {
+        if let Some(summary) = &self.summary {
+            return summary.individual(individual).first()
+                .and_then(|statistic| statistic.min())
+                .expect("There is no simulation to compute the number of initial individuals!");
+        }
         let vec = self.individual(individual); 
         if vec.is_empty() {
-            panic!("There is no simulation to compute the number of initial healthy individuals!")
+            panic!("There is no simulation to compute the number of initial individuals!")
         } else {
             vec[0][0]
         }
     }
 }
 
+/// Returns the `q` quantile of sorted `values`, interpolating linearly, or `NaN` if there are no values.
+fn quantile(values: &[usize], q: f64) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    let position = q * (values.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    let weight = position - below as f64;
+    values[below] as f64 * (1. - weight) + values[above] as f64 * weight
+}
+
 impl From<Vec<CountingTable>> for Report {
+    /// Returns the report of the counting tables, 
+    /// assuming the first day of each one is the state before the first stage.
     fn from(counting_tables: Vec<CountingTable>) -> Self { 
-        Report { counting_tables } 
+        let days_simulated = counting_tables.iter().map(|counting_table| counting_table.days().saturating_sub(1)).collect();
+        Report { counting_tables, days_simulated, summary: None, seeds: Vec::new() } 
     }
 }
 
@@ -137,11 +1039,11 @@ mod tests {
             Individual::iter().map(|i| (i, vec![0])).collect(),
             Individual::iter().map(|i| (i, vec![1])).collect()
         ];
-        let report = Report { counting_tables };
+        let report = Report::from(counting_tables);
         let average_counting_table = report.average_counting_table();
         let variance: average::Variance = vec![0., 1.].into_iter().collect();
-        assert_eq!(average_counting_table.map(|v| v.mean()), Array2::from_elem((6, 1), variance.mean()));
-        assert_eq!(average_counting_table.map(|v| v.error()), Array2::from_elem((6, 1), variance.error()));
+        assert_eq!(average_counting_table.map(|v| v.mean()), Array2::from_elem((14, 1), variance.mean()));
+        assert_eq!(average_counting_table.map(|v| v.error()), Array2::from_elem((14, 1), variance.error()));
     }
 
     #[test]
@@ -150,7 +1052,7 @@ mod tests {
             Individual::iter().map(|i| (i, vec![0, 0])).collect(),
             Individual::iter().map(|i| (i, vec![1, 2])).collect()
         ];
-        let report = Report { counting_tables };
+        let report = Report::from(counting_tables);
         assert_eq!(report.individual(&Individual::Healthy), vec![&vec![0, 0], &vec![1, 2]]);
     }
 
@@ -160,7 +1062,7 @@ mod tests {
             Individual::iter().map(|i| (i, vec![0, 0])).collect(),
             Individual::iter().map(|i| (i, vec![1, 2])).collect()
         ];
-        let report = Report { counting_tables };
+        let report = Report::from(counting_tables);
         assert_eq!(report.individual_transpose(&Individual::Healthy), vec![vec![0, 1], vec![0, 2]]);
     }
 
@@ -171,7 +1073,7 @@ mod tests {
             Individual::iter().map(|i| (i, vec![8, 9])).collect(),
             Individual::iter().map(|i| (i, vec![16, 0])).collect(),
         ];
-        let report = Report { counting_tables };
+        let report = Report::from(counting_tables);
         let average_healthy = report.individual_average(&Individual::Healthy);
         assert_eq!(average_healthy.iter().map(|v| v.mean()).collect::<Vec<f64>>(), vec![8.0, 3.0]);
         assert_eq!(average_healthy.iter().map(|v| v.error()).collect::<Vec<f64>>(), vec![4.618802153517006, 3.0]);
@@ -183,17 +1085,196 @@ mod tests {
             Individual::iter().map(|i| (i, vec![0, 0])).collect(),
             Individual::iter().map(|i| (i, vec![1, 2])).collect()
         ];
-        let report = Report { counting_tables };
+        let report = Report::from(counting_tables);
         assert_eq!(report.individual_last(&Individual::Healthy), vec![&0, &2]);
     }
 
+    #[test]
+    fn individual_summary() {
+        let counting_tables: Vec<CountingTable> = (0..21)
+            .map(|k| Individual::iter().map(|i| (i, vec![5, 100 - k])).collect())
+            .collect();
+        let report = Report::from(counting_tables);
+        let summary = report.individual_summary(&Individual::Sick);
+        assert_eq!(summary[0], DaySummary { min: 5, lower: 5., median: 5., upper: 5., max: 5 });
+        assert_eq!(summary[1], DaySummary { min: 80, lower: 81., median: 90., upper: 99., max: 100 });
+        assert_eq!(report.individual_envelope(&Individual::Sick), vec![(5, 5), (80, 100)]);
+        assert_eq!(Report::default().individual_summary(&Individual::Sick), vec![]);
+    }
+
+    #[test]
+    fn final_histogram() {
+        let counting_tables: Vec<CountingTable> = vec![0, 3, 4, 12, 2]
+            .into_iter()
+            .map(|last| Individual::iter().map(|i| (i, vec![0, last])).collect())
+            .collect();
+        let report = Report::from(counting_tables);
+        let histogram = report.final_histogram(&Individual::Sick, 4);
+        assert_eq!(histogram.counts, vec![3, 1, 0, 1]);
+        assert_eq!(histogram.bin_range(3), (12, 15));
+        let wide = Histogram { bin_width: 1, counts: vec![100, 10] };
+        assert_eq!(wide.to_string(), format!("0-0 | {} 100\n1-1 | ##### 10\n", "#".repeat(50)));
+    }
+
+    #[test]
+    fn peaks() {
+        let counting_tables: Vec<CountingTable> = vec![
+            Individual::iter().map(|i| (i, vec![1, 3, 2])).collect(),
+            Individual::iter().map(|i| (i, vec![5, 5, 0])).collect(),
+        ];
+        let report = Report::from(counting_tables);
+        assert_eq!(report.peaks(&Individual::Sick), vec![Peak { day: 1, count: 3 }, Peak { day: 0, count: 5 }]);
+        let peak = report.average_peak(&Individual::Sick);
+        assert_eq!((peak.day.mean(), peak.count.mean()), (0.5, 4.));
+        assert!(Report::default().average_peak(&Individual::Sick).count.is_empty());
+    }
+
+    #[test]
+    fn extinction_days() {
+        let counting_tables: Vec<CountingTable> = vec![
+            vec![(Individual::Healthy, vec![1, 1, 1, 1]), (Individual::Exposed, vec![1, 1, 0, 0])].into_iter().collect(),
+            vec![(Individual::Healthy, vec![1, 1, 1, 1]), (Individual::Superspreader3, vec![1, 0, 0, 0])].into_iter().collect(),
+            vec![(Individual::Healthy, vec![1, 1, 1, 1]), (Individual::Infected2, vec![1, 1, 1, 1])].into_iter().collect(),
+        ];
+        let report = Report::from(counting_tables);
+        assert_eq!(report.extinction_days(), vec![Some(2), Some(1), None]);
+        assert_eq!(report.average_extinction_day().mean(), 1.5);
+        assert_eq!(report.extinction_distribution(), vec![0., 1. / 3., 2. / 3., 2. / 3.]);
+        assert_eq!(Report::default().extinction_distribution(), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn trajectories_long() {
+        let counting_tables: Vec<CountingTable> = vec![
+            Individual::iter().map(|i| (i, vec![0, 0])).collect(),
+            Individual::iter().map(|i| (i, vec![1, 2])).collect()
+        ];
+        let report = Report::from(counting_tables);
+        assert_eq!(report.trajectories_long(&Individual::Sick), vec![(0, 0, 0), (0, 1, 0), (1, 0, 1), (1, 1, 2)]);
+    }
+
+    #[test]
+    fn write_with_metadata() -> csv::Result<()> {
+        let counting_tables: Vec<CountingTable> = vec![
+            Individual::iter().map(|i| (i, vec![0])).collect(),
+        ];
+        let report = Report::from(counting_tables);
+        let metadata = crate::recording::Metadata::new();
+        let writer = report.write_with_metadata(vec![], &metadata)?;
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        let expected = format!("{}realization,individual,day,count\n0,Healthy,0,0\n0,Infected1,0,0\n0,Infected2,0,0\n0,Infected3,0,0\n0,Sick,0,0\n0,Immune,0,0\n0,HealthySuperspreader,0,0\n0,Superspreader1,0,0\n0,Superspreader2,0,0\n0,Superspreader3,0,0\n0,Vaccinated,0,0\n0,Exposed,0,0\n0,Recovered,0,0\n0,PartiallyVaccinated,0,0\n", metadata);
+        assert_eq!(data, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn stopped_realizations() {
+        // The first and last realizations stopped early, so their last counts are carried forward
+        let counting_tables: Vec<CountingTable> = vec![vec![10, 8], vec![10, 8, 6, 4], vec![10]].into_iter()
+            .map(|healthy| vec![(Individual::Healthy, healthy)].into_iter().collect())
+            .collect();
+        let report = Report::from(counting_tables);
+        let expected = vec![10., 26. / 3., 8., 22. / 3.];
+        let means: Vec<f64> = report.individual_average(&Individual::Healthy).iter().map(|variance| variance.mean()).collect();
+        assert_eq!(means, expected);
+        let row = Individual::iter().position(|individual| individual == Individual::Healthy).unwrap();
+        let average = report.average_counting_table();
+        assert_eq!(average.row(row).iter().map(|variance| variance.mean()).collect::<Vec<_>>(), expected);
+        assert!(average.row(row).iter().all(|variance| variance.len() == 3));
+        assert_eq!(report.individual_transpose(&Individual::Healthy)[3], vec![8, 4, 10]);
+    }
+
+    #[test]
+    fn mean_with_ci() {
+        let counting_tables: Vec<CountingTable> = [[4, 2], [4, 4], [4, 6]]
+            .iter()
+            .map(|healthy| vec![(Individual::Healthy, healthy.to_vec())].into_iter().collect())
+            .collect();
+        let report = Report::from(counting_tables);
+        let bands = report.mean_with_ci(0.95);
+        let row = Individual::iter().position(|individual| individual == Individual::Healthy).unwrap();
+        assert_eq!(bands.mean.row(row).to_vec(), vec![4., 4.]);
+        assert_eq!((bands.lower[[row, 0]], bands.upper[[row, 0]]), (4., 4.));
+        // Standard error of 2 / sqrt(3), with the quantile of 2 degrees of freedom
+        let half_width = 4.302653 * 2. / 3_f64.sqrt();
+        assert!((bands.upper[[row, 1]] - 4. - half_width).abs() < 1e-4);
+        assert!((4. - bands.lower[[row, 1]] - half_width).abs() < 1e-4);
+        let single: CountingTable = vec![(Individual::Healthy, vec![4])].into_iter().collect();
+        assert!(Report::from(vec![single]).mean_with_ci(0.9).upper[[row, 0]].is_nan());
+    }
+
+    #[test]
+    fn realizations() {
+        let counting_tables: Vec<CountingTable> = [3, 1, 4, 1]
+            .iter()
+            .map(|&last| vec![(Individual::Healthy, vec![5, last])].into_iter().collect())
+            .collect();
+        let mut report = Report::from(counting_tables);
+        let indices = |realizations: Vec<Realization<'_>>| realizations.iter().map(|realization| realization.index).collect::<Vec<_>>();
+        assert_eq!(indices(report.best_realizations(2)), vec![2, 0]);
+        assert_eq!(indices(report.worst_realizations(3)), vec![1, 3, 0]);
+        assert_eq!(report.best_realizations(10).len(), 4);
+        assert_eq!(report.realization(1).unwrap().seed, None);
+        report.seeds = vec![7, 8, 9, 10];
+        let worst = report.worst_realizations(1)[0];
+        assert_eq!((worst.seed, worst.final_healthy), (Some(8), 1));
+        assert_eq!(worst.counting_table, &report.counting_tables()[1]);
+    }
+
+    #[test]
+    fn outbreak_sizes() {
+        let counting_tables: Vec<CountingTable> = vec![
+            vec![(Individual::Healthy, vec![3, 1]), (Individual::Infected1, vec![0, 2])].into_iter().collect(),
+            vec![(Individual::Healthy, vec![3, 1]), (Individual::Immune, vec![0, 2])].into_iter().collect(),
+        ];
+        let report = Report::from(counting_tables);
+        assert_eq!(report.outbreak_sizes(), vec![2, 0]);
+        assert_eq!(report.outbreak_sizes(), report.cumulative_infections().iter().map(|total| total[1]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn outbreak_sizes_waning_immunity() {
+        // Immune individuals become healthy again, so there are more healthy individuals at the end
+        let board = crate::prelude::BoardBuilder { healthy: 0, immune: 5, immunity_duration: Some(1), ..Default::default() }.build();
+        let report = crate::prelude::Simulation::new(board, ReportPlan { num_simulations: 2, days: 3, ..Default::default() }).run();
+        assert!(report.individual_last(&Individual::Healthy).iter().all(|&&healthy| healthy == 5));
+        assert_eq!(report.outbreak_sizes(), vec![0, 0]);
+    }
+
+    #[test]
+    fn outbreak_probability() {
+        let counting_tables: Vec<CountingTable> = vec![
+            vec![(Individual::Healthy, vec![3, 0]), (Individual::Infected1, vec![1, 3])].into_iter().collect(),
+            vec![(Individual::Healthy, vec![3, 3]), (Individual::Infected1, vec![1, 0])].into_iter().collect(),
+        ];
+        let report = Report::from(counting_tables);
+        assert_eq!(report.cumulative_infections(), vec![vec![0, 3], vec![0, 0]]);
+        let outbreak = report.outbreak_probability(2);
+        assert_eq!(outbreak.probability, 0.5);
+        assert!(outbreak.lower < 0.5 && 0.5 < outbreak.upper);
+        assert!((outbreak.lower + outbreak.upper - 1.).abs() < 1e-12);
+        assert_eq!(report.outbreak_probability(3).probability, 0.);
+        assert_eq!(report.containment_probability().probability, 0.5);
+        assert_eq!(Report::default().outbreak_probability(0), ProbabilityEstimate { probability: 0., lower: 0., upper: 1. });
+    }
+
     #[test]
     fn healthy_initial() {
         let counting_tables: Vec<CountingTable> = vec![
             Individual::iter().map(|i| (i, vec![0, 0])).collect(),
             Individual::iter().map(|i| (i, vec![1, 2])).collect()
         ];
-        let report = Report { counting_tables };
+        let report = Report::from(counting_tables);
         assert_eq!(report.individual_first(&Individual::Healthy), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn extend_days() {
+        let mut report = Report::from(vec![Individual::iter().map(|i| (i, vec![3, 2])).collect::<CountingTable>()]);
+        let additional = Report::from(vec![Individual::iter().map(|i| (i, vec![2, 1])).collect::<CountingTable>()]);
+        report.extend_days(&additional).unwrap();
+        assert_eq!(report.individual(&Individual::Healthy), vec![&vec![3, 2, 1]]);
+        assert_eq!(report.extend_days(&additional), Err(crate::errors::ReportError::Discontinuous { realization: 0 }));
+        assert_eq!(report.extend_days(&Report::default()), Err(crate::errors::ReportError::RealizationsMismatch { expected: 1, found: 0 }));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::prelude::{Individual, Report};
+use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+use parquet::errors::Result;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
+/// Schema of the rows written by `Report::write_parquet`
+const SCHEMA: &str = "message report {
+    REQUIRED INT64 realization;
+    REQUIRED INT64 day;
+    REQUIRED BYTE_ARRAY state (UTF8);
+    REQUIRED INT64 count;
+}";
+/// Number of realizations in each row group, which bounds the memory used while writing
+const REALIZATIONS_PER_GROUP: usize = 1024;
+
+/// Columns of a row group
+#[derive(Debug, Default)]
+struct Columns {
+    realizations: Vec<i64>,
+    days: Vec<i64>,
+    states: Vec<ByteArray>,
+    counts: Vec<i64>,
+}
+
+impl Report {
+    /// Writes all realizations on the writer as a Parquet file, in long format, and returns the writer.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// The columns are `realization`, `day`, `state` and `count`, with one row per
+    /// realization, individual variant and day, in this order of priority, as in `write_with_metadata`.
+    /// The file is compressed with Snappy, and can be loaded directly with pandas, polars or DuckDB.
+    ///
+    /// Requires the `parquet` feature.
+    ///
+    /// # Errors
+    ///
+    /// If writing on the writer fails.
+    ///
+    /// # Remarks
+    ///
+    /// Realizations aggregated in streaming mode are not kept, so they are not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 3, ..Default::default() }).run();
+    /// let bytes = report.write_parquet(Vec::new()).unwrap();
+    /// assert_eq!(&bytes[..4], b"PAR1");
+    /// ```
+    pub fn write_parquet<W: std::io::Write + Send>(&self, writer: W) -> Result<W> {
+        let schema = Arc::new(parquet::schema::parser::parse_message_type(SCHEMA)?);
+        let properties = WriterProperties::builder()
+            .set_compression(parquet::basic::Compression::SNAPPY)
+            .build();
+        let mut writer = SerializedFileWriter::new(writer, schema, Arc::new(properties))?;
+        for (group, counting_tables) in self.counting_tables().chunks(REALIZATIONS_PER_GROUP).enumerate() {
+            let mut columns = Columns::default();
+            for (offset, counting_table) in counting_tables.iter().enumerate() {
+                let realization = (group * REALIZATIONS_PER_GROUP + offset) as i64;
+                for individual in Individual::iter() {
+                    if let Some(v) = counting_table.inner().get(&individual) {
+                        for (day, &count) in v.iter().enumerate() {
+                            columns.realizations.push(realization);
+                            columns.days.push(day as i64);
+                            columns.states.push(ByteArray::from(individual.to_string().as_str()));
+                            columns.counts.push(count as i64);
+                        }
+                    }
+                }
+            }
+            let mut row_group = writer.next_row_group()?;
+            for index in 0..4 {
+                let mut column = row_group.next_column()?.expect("the schema has four columns");
+                match index {
+                    0 => column.typed::<Int64Type>().write_batch(&columns.realizations, None, None)?,
+                    1 => column.typed::<Int64Type>().write_batch(&columns.days, None, None)?,
+                    2 => column.typed::<ByteArrayType>().write_batch(&columns.states, None, None)?,
+                    _ => column.typed::<Int64Type>().write_batch(&columns.counts, None, None)?,
+                };
+                column.close()?;
+            }
+            row_group.close()?;
+        }
+        writer.into_inner()
+    }
+
+    /// Writes all realizations in the Parquet file at `path`, see `write_parquet`.
+    ///
+    /// Requires the `parquet` feature.
+    ///
+    /// # Errors
+    ///
+    /// If creating or writing the file fails.
+    pub fn to_parquet<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = self.write_parquet(std::io::BufWriter::new(file))?;
+        std::io::Write::flush(&mut writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::CountingTable;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn write_parquet() {
+        let counting_tables: Vec<CountingTable> = vec![
+            Individual::iter().map(|i| (i, vec![0, 0])).collect(),
+            Individual::iter().map(|i| (i, vec![1, 2])).collect()
+        ];
+        let path = std::env::temp_dir().join("virus_alarm_write_parquet.parquet");
+        Report::from(counting_tables).to_parquet(&path).unwrap();
+        let reader = SerializedFileReader::try_from(path.as_path()).unwrap();
+        let rows: Vec<(i64, i64, String, i64)> = reader.get_row_iter(None).unwrap()
+            .map(|row| {
+                let row = row.unwrap();
+                (row.get_long(0).unwrap(), row.get_long(1).unwrap(), row.get_string(2).unwrap().clone(), row.get_long(3).unwrap())
+            })
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(rows.len(), 2 * Individual::iter().count() * 2);
+        assert_eq!(rows[0], (0, 0, Individual::Healthy.to_string(), 0));
+        assert_eq!(rows[rows.len() - 1], (1, 1, Individual::iter().next_back().unwrap().to_string(), 2));
+    }
+
+    #[test]
+    fn empty() {
+        let path = std::env::temp_dir().join("virus_alarm_empty.parquet");
+        Report::default().to_parquet(&path).unwrap();
+        let reader = SerializedFileReader::try_from(path.as_path()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 0);
+    }
+}
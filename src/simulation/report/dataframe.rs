@@ -0,0 +1,86 @@
+use crate::prelude::{Individual, Report};
+use polars::prelude::{DataFrame, NamedFrom, PolarsResult, Series};
+use strum::IntoEnumIterator;
+
+impl Report {
+    /// Returns all realizations as a polars `DataFrame`, in long format.
+    ///
+    /// Needs the counting tables of `Aggregation::Full`, see `Aggregation::Streaming`.
+    ///
+    /// The columns are `realization`, `day`, `state` and `count`, with one row per
+    /// realization, individual variant and day, in this order of priority, as in `write_parquet`.
+    ///
+    /// Requires the `polars` feature.
+    ///
+    /// # Errors
+    ///
+    /// If polars fails to build the data frame.
+    ///
+    /// # Remarks
+    ///
+    /// Realizations aggregated in streaming mode are not kept, so they are not in the data frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 3, ..Default::default() }).run();
+    /// let dataframe = report.to_dataframe().unwrap();
+    /// assert_eq!(dataframe.width(), 4);
+    /// ```
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let mut realizations = Vec::new();
+        let mut days = Vec::new();
+        let mut states = Vec::new();
+        let mut counts = Vec::new();
+        for (realization, counting_table) in self.counting_tables().iter().enumerate() {
+            for individual in Individual::iter() {
+                if let Some(v) = counting_table.inner().get(&individual) {
+                    for (day, &count) in v.iter().enumerate() {
+                        realizations.push(realization as u64);
+                        days.push(day as u64);
+                        states.push(individual.to_string());
+                        counts.push(count as u64);
+                    }
+                }
+            }
+        }
+        DataFrame::new(vec![
+            Series::new("realization".into(), realizations),
+            Series::new("day".into(), days),
+            Series::new("state".into(), states),
+            Series::new("count".into(), counts),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::CountingTable;
+    use polars::prelude::ChunkCompare;
+
+    #[test]
+    fn to_dataframe() {
+        let counting_tables: Vec<CountingTable> = vec![
+            Individual::iter().map(|i| (i, vec![0, 0])).collect(),
+            Individual::iter().map(|i| (i, vec![1, 2])).collect()
+        ];
+        let report = Report::from(counting_tables.clone());
+        let dataframe = report.to_dataframe().unwrap();
+        assert_eq!(dataframe.height(), 2 * Individual::iter().count() * 2);
+        let counts: Vec<Option<u64>> = dataframe.column("count").unwrap().u64().unwrap().into_iter().collect();
+        assert_eq!(counts[..2], [Some(0), Some(0)]);
+        assert_eq!(counts[counts.len() - 2..], [Some(1), Some(2)]);
+
+        let last = counting_tables[1].to_dataframe().unwrap();
+        let realization = dataframe.filter(&dataframe.column("realization").unwrap().u64().unwrap().equal(1)).unwrap();
+        assert_eq!(realization.drop("realization").unwrap(), last);
+    }
+
+    #[test]
+    fn empty() {
+        let dataframe = Report::default().to_dataframe().unwrap();
+        assert_eq!(dataframe.shape(), (0, 4));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::prelude::{Individual, Report};
+use crate::render::color;
+use strum::IntoEnumIterator;
+
+const WIDTH: f64 = 640.;
+const HEIGHT: f64 = 320.;
+const MARGIN: f64 = 40.;
+
+impl Report {
+    /// Returns a self-contained HTML document summarizing the report.
+    ///
+    /// The document has a summary of the setup, a chart of the average trajectory 
+    /// of each individual variant (as embedded SVG) and the average counting table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let report = Simulation::new(Board::default(), ReportPlan { num_simulations: 2, days: 3, ..Default::default() }).run();
+    /// let html = report.to_html_string();
+    /// assert!(html.starts_with("<!DOCTYPE html>"));
+    /// ```
+    pub fn to_html_string(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Virus alarm report</title>\n");
+        html += "<style>body{font-family:sans-serif;margin:2em;}table{border-collapse:collapse;}td,th{border:1px solid #ccc;padding:2px 6px;text-align:right;}</style>\n";
+        html += "</head>\n<body>\n<h1>Virus alarm report</h1>\n";
+        html += &self.html_summary();
+        html += "<h2>Average trajectories</h2>\n";
+        html += &self.svg_chart();
+        html += "<h2>Average counting table</h2>\n";
+        html += &self.html_table();
+        html += "</body>\n</html>\n";
+        html
+    }
+
+    /// Writes the HTML document of `to_html_string` in the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If writing the file fails.
+    pub fn to_html<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_html_string())
+    }
+
+    fn html_summary(&self) -> String {
+        let mut summary = String::from("<h2>Summary</h2>\n<ul>\n");
+        summary += &format!("<li>Realizations: {}</li>\n", self.days_simulated().len());
+        if !self.days_simulated().is_empty() {
+            summary += &format!("<li>Days: {}</li>\n", self.days().saturating_sub(1));
+            for individual in Individual::iter() {
+                summary += &format!("<li>Initial {}: {}</li>\n", individual, self.individual_first(&individual));
+            }
+        }
+        summary += "</ul>\n";
+        summary
+    }
+
+    fn svg_chart(&self) -> String {
+        let averages: Vec<(Individual, Vec<f64>)> = if self.days_simulated().is_empty() {
+            Vec::new()
+        } else {
+            Individual::iter()
+                .zip(self.cell_statistics().genrows())
+                .map(|(i, row)| (i, row.iter().map(|&(_, mean, _)| mean).collect()))
+                .collect()
+        };
+        let days = averages.iter().map(|(_, v)| v.len()).max().unwrap_or(0);
+        let max = averages.iter().flat_map(|(_, v)| v.iter()).cloned().fold(1., f64::max);
+        let x = |day: usize| MARGIN + (WIDTH - 2. * MARGIN) * day as f64 / (days.max(2) - 1) as f64;
+        let y = |value: f64| HEIGHT - MARGIN - (HEIGHT - 2. * MARGIN) * value / max;
+
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n", WIDTH, HEIGHT);
+        svg += &format!("<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"black\"/>\n", MARGIN, HEIGHT - MARGIN, WIDTH - MARGIN);
+        svg += &format!("<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\" stroke=\"black\"/>\n", MARGIN, MARGIN, HEIGHT - MARGIN);
+        svg += &format!("<text x=\"{}\" y=\"{}\" font-size=\"12\">{:.0}</text>\n", 4., MARGIN, max);
+        for (counter, (individual, values)) in averages.iter().enumerate() {
+            let points: Vec<String> = values.iter().enumerate()
+                .map(|(day, &value)| format!("{:.1},{:.1}", x(day), y(value)))
+                .collect();
+            svg += &format!("<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" points=\"{}\"><title>{}</title></polyline>\n", 
+                color(individual), points.join(" "), individual);
+            for (day, &value) in values.iter().enumerate() {
+                svg += &format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"><title>{} on day {}: {:.2}</title></circle>\n", 
+                    x(day), y(value), color(individual), individual, day, value);
+            }
+            svg += &format!("<text x=\"{}\" y=\"{}\" font-size=\"12\" fill=\"{}\">{}</text>\n", 
+                WIDTH - MARGIN + 4., MARGIN + 14. * counter as f64, color(individual), individual);
+        }
+        svg += "</svg>\n";
+        svg
+    }
+
+    fn html_table(&self) -> String {
+        let average = self.cell_statistics();
+        let mut table = String::from("<table>\n<tr><th>Individual\\Day</th>");
+        for day in 0..average.ncols() {
+            table += &format!("<th>{}</th>", day);
+        }
+        table += "</tr>\n";
+        for (individual, row) in Individual::iter().zip(average.genrows()) {
+            table += &format!("<tr><th>{}</th>", individual);
+            for (_, mean, error) in row {
+                table += &format!("<td>{:.2} &plusmn; {:.2}</td>", mean, error);
+            }
+            table += "</tr>\n";
+        }
+        table += "</table>\n";
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::CountingTable;
+
+    #[test]
+    fn to_html_string() {
+        let counting_tables: Vec<CountingTable> = vec![
+            Individual::iter().map(|i| (i, vec![0, 0])).collect(),
+            Individual::iter().map(|i| (i, vec![1, 2])).collect()
+        ];
+        let html = Report::from(counting_tables).to_html_string();
+        assert!(html.contains("<li>Realizations: 2</li>"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<td>0.50 &plusmn; 0.50</td><td>1.00 &plusmn; 1.00</td>"));
+    }
+
+    #[test]
+    fn streaming() {
+        use crate::prelude::{Board, ReportPlan, Simulation};
+        use crate::simulation::Aggregation;
+        let report_plan = ReportPlan { num_simulations: 3, days: 2, aggregation: Aggregation::Streaming { bin_width: 5 }, ..Default::default() };
+        let html = Simulation::new(Board::default(), report_plan).run().to_html_string();
+        assert!(html.contains("<li>Realizations: 3</li>"));
+        assert!(html.contains("<li>Days: 2</li>"));
+        assert!(html.contains("<li>Initial Healthy: 98</li>"));
+    }
+
+    #[test]
+    fn empty() {
+        let html = Report::default().to_html_string();
+        assert!(html.contains("<li>Realizations: 0</li>"));
+    }
+}
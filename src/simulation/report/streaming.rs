@@ -0,0 +1,319 @@
+use crate::prelude::{Individual, Report};
+use crate::recording::CountingTable;
+use super::Histogram;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+/// How the realizations of a simulation are aggregated into a `Report`, see `ReportPlan::aggregation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Aggregation {
+    /// Every counting table is stored in the report.
+    #[default]
+    Full,
+    /// Each realization is folded into a `RunningSummary` and its counting table is discarded.
+    ///
+    /// Histograms of the last day have bins of width `bin_width`, see `Report::final_histogram`.
+    ///
+    /// Reports served from the summary are `Report::days`, `days_simulated`, `seeds`, `mean_with_ci`, 
+    /// `individual_first`, `final_histogram` and the HTML document. The rest need the counting tables, 
+    /// so they are empty for streamed reports, and `Report::extend_days` fails.
+    Streaming {
+        /// Width of the bins of the histograms
+        bin_width: usize,
+    },
+}
+
+/// Running statistics of a quantity over realizations.
+///
+/// Sums are kept exactly, so that the statistics do not depend on the order of the realizations.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::simulation::RunningStatistic;
+/// let mut statistic = RunningStatistic::default();
+/// statistic.push(1).push(2).push(6);
+/// assert_eq!(statistic.mean(), 3.);
+/// assert_eq!(statistic.sample_variance(), 7.);
+/// assert_eq!((statistic.min(), statistic.max()), (Some(1), Some(6)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunningStatistic {
+    len: usize,
+    sum: u128,
+    sum_squares: u128,
+    min: Option<usize>,
+    max: Option<usize>,
+}
+
+impl RunningStatistic {
+    /// Adds the value of a realization.
+    pub fn push(&mut self, value: usize) -> &mut Self {
+        self.len += 1;
+        self.sum += value as u128;
+        self.sum_squares += (value as u128) * (value as u128);
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self
+    }
+
+    /// Returns the number of realizations.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no realizations.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the mean, or zero if there are no realizations.
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            0.
+        } else {
+            self.sum as f64 / self.len as f64
+        }
+    }
+
+    /// Returns the unbiased sample variance, or zero if there are less than two realizations.
+    pub fn sample_variance(&self) -> f64 {
+        if self.len < 2 {
+            0.
+        } else {
+            let n = self.len as f64;
+            let centered = self.sum_squares as f64 - (self.sum as f64) * (self.sum as f64) / n;
+            (centered / (n - 1.)).max(0.)
+        }
+    }
+
+    /// Returns the standard error of the mean, as `average::Variance::error` does.
+    pub fn error(&self) -> f64 {
+        if self.is_empty() {
+            0.
+        } else {
+            (self.sample_variance() / self.len as f64).sqrt()
+        }
+    }
+
+    /// Returns the smallest value, if there are realizations.
+    pub fn min(&self) -> Option<usize> {
+        self.min
+    }
+
+    /// Returns the largest value, if there are realizations.
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+}
+
+/// Statistics of a simulation, computed realization by realization, see `Aggregation::Streaming`.
+///
+/// # Remarks
+///
+/// Realizations that stopped early are frozen, so they contribute their last count
+/// to the days after the last one they reached, as in `Report::average_counting_table`, see `ReportPlan::stop_when`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningSummary {
+    realizations: usize,
+    cells: HashMap<Individual, Vec<RunningStatistic>>,
+    /// Statistics of the last count of each realization, which fill the days added by longer realizations
+    finals: HashMap<Individual, RunningStatistic>,
+    final_histograms: HashMap<Individual, Histogram>,
+    bin_width: usize,
+}
+
+impl RunningSummary {
+    /// Returns an empty summary, whose histograms have bins of width `bin_width`.
+    ///
+    /// # Panics
+    ///
+    /// If `bin_width` is zero.
+    pub fn new(bin_width: usize) -> Self {
+        assert!(bin_width > 0, "bins must have positive width");
+        RunningSummary { realizations: 0, cells: HashMap::new(), finals: HashMap::new(), final_histograms: HashMap::new(), bin_width }
+    }
+
+    /// Folds a realization into the summary.
+    pub fn push(&mut self, counting_table: &CountingTable) -> &mut Self {
+        self.realizations += 1;
+        let bin_width = self.bin_width;
+        for (individual, counts) in counting_table.inner() {
+            let cells = self.cells.entry(*individual).or_default();
+            let finals = self.finals.entry(*individual).or_default();
+            if cells.len() < counts.len() {
+                cells.resize(counts.len(), *finals);
+            }
+            for (day, cell) in cells.iter_mut().enumerate() {
+                if let Some(&count) = counts.get(day).or_else(|| counts.last()) {
+                    cell.push(count);
+                }
+            }
+            if let Some(&last) = counts.last() {
+                finals.push(last);
+                let histogram = self.final_histograms.entry(*individual)
+                    .or_insert_with(|| Histogram { bin_width, counts: Vec::new() });
+                let bin = last / bin_width;
+                if histogram.counts.len() <= bin {
+                    histogram.counts.resize(bin + 1, 0);
+                }
+                histogram.counts[bin] += 1;
+            }
+        }
+        self
+    }
+
+    /// Returns the number of realizations folded into the summary.
+    pub fn realizations(&self) -> usize {
+        self.realizations
+    }
+
+    /// Returns the number of days of the longest realization.
+    pub fn days(&self) -> usize {
+        self.cells.values().map(|cells| cells.len()).max().unwrap_or(0)
+    }
+
+    /// Returns the statistics of an individual variant for each day.
+    ///
+    /// It is empty if no realization has the variant.
+    pub fn individual(&self, individual: &Individual) -> &[RunningStatistic] {
+        self.cells.get(individual).map(|cells| cells.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the histogram of the last day counts of an individual variant,
+    /// as `Report::final_histogram` does.
+    pub fn final_histogram(&self, individual: &Individual) -> Histogram {
+        self.final_histograms.get(individual)
+            .cloned()
+            .unwrap_or(Histogram { bin_width: self.bin_width, counts: Vec::new() })
+    }
+
+    /// Returns the histogram of the last day counts of an individual variant with bins of width `bin_width`,
+    /// if it is a multiple of the width of the bins of the summary.
+    pub(crate) fn rebinned_final_histogram(&self, individual: &Individual, bin_width: usize) -> Option<Histogram> {
+        let factor = bin_width / self.bin_width;
+        if factor * self.bin_width != bin_width {
+            return None;
+        }
+        let mut counts = Vec::new();
+        for (bin, count) in self.final_histogram(individual).counts.into_iter().enumerate() {
+            if counts.len() <= bin / factor {
+                counts.resize(bin / factor + 1, 0);
+            }
+            counts[bin / factor] += count;
+        }
+        Some(Histogram { bin_width, counts })
+    }
+}
+
+impl Report {
+    /// Returns the running summary of the simulation, if it was aggregated in streaming mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// # use virus_alarm::simulation::Aggregation;
+    /// let report_plan = ReportPlan {
+    ///     num_simulations: 10,
+    ///     days: 3,
+    ///     aggregation: Aggregation::Streaming { bin_width: 5 },
+    ///     ..Default::default()
+    /// };
+    /// let report = Simulation::new(Board::default(), report_plan).run();
+    /// assert!(report.counting_tables().is_empty());
+    /// let summary = report.summary().unwrap();
+    /// assert_eq!(summary.realizations(), 10);
+    /// assert_eq!(summary.individual(&Individual::Sick)[3].mean(), 2.);
+    /// ```
+    pub fn summary(&self) -> Option<&RunningSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Returns the running summary of the stored counting tables,
+    /// whose histograms have bins of width `bin_width`.
+    ///
+    /// # Panics
+    ///
+    /// If `bin_width` is zero.
+    pub fn summarize(&self, bin_width: usize) -> RunningSummary {
+        let mut summary = RunningSummary::new(bin_width);
+        for counting_table in self.counting_tables() {
+            summary.push(counting_table);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Board, ReportPlan, Simulation};
+    use test_case::test_case;
+
+    #[test_case(&[], 0., 0.; "empty")]
+    #[test_case(&[4], 4., 0.; "single")]
+    #[test_case(&[1, 2, 3, 4], 2.5, 5. / 3.; "several")]
+    fn running_statistic(values: &[usize], mean: f64, sample_variance: f64) {
+        let mut statistic = RunningStatistic::default();
+        for &value in values {
+            statistic.push(value);
+        }
+        let variance: average::Variance = values.iter().map(|&value| value as f64).collect();
+        assert_eq!(statistic.len(), values.len());
+        assert!((statistic.mean() - mean).abs() < 1e-12);
+        assert!((statistic.sample_variance() - sample_variance).abs() < 1e-12);
+        assert!((statistic.error() - variance.error()).abs() < 1e-12 || values.is_empty());
+    }
+
+    #[test]
+    fn streaming_matches_full() {
+        let report_plan = ReportPlan { num_simulations: 20, days: 5, ..Default::default() };
+        let full = Simulation::new(Board::default(), report_plan.clone()).run_with(&mut crate::tests::rng(3));
+        let report_plan = ReportPlan { aggregation: Aggregation::Streaming { bin_width: 10 }, ..report_plan };
+        let streamed = Simulation::new(Board::default(), report_plan).run_with(&mut crate::tests::rng(3));
+
+        assert!(streamed.counting_tables().is_empty());
+        assert_eq!(streamed.days_simulated(), full.days_simulated());
+        assert_eq!(streamed.days(), full.days());
+        let summary = streamed.summary().unwrap();
+        assert_eq!(summary, &full.summarize(10));
+        let average = full.individual_average(&Individual::Healthy);
+        for (statistic, variance) in summary.individual(&Individual::Healthy).iter().zip(average) {
+            assert!((statistic.mean() - variance.mean()).abs() < 1e-9);
+        }
+        assert_eq!(summary.final_histogram(&Individual::Sick), full.final_histogram(&Individual::Sick, 10));
+        assert_eq!(streamed.final_histogram(&Individual::Sick, 30), full.final_histogram(&Individual::Sick, 30));
+        assert_eq!(streamed.individual_first(&Individual::Healthy), full.individual_first(&Individual::Healthy));
+        let (streamed_bands, full_bands) = (streamed.mean_with_ci(0.9), full.mean_with_ci(0.9));
+        for (streamed, full) in streamed_bands.mean.iter().zip(full_bands.mean.iter())
+            .chain(streamed_bands.lower.iter().zip(full_bands.lower.iter())) 
+        {
+            assert!((streamed - full).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn streaming_extend_days() {
+        let report_plan = ReportPlan { num_simulations: 2, days: 2, aggregation: Aggregation::Streaming { bin_width: 1 }, ..Default::default() };
+        let mut streamed = Simulation::new(Board::default(), report_plan).run();
+        let additional = streamed.clone();
+        assert_eq!(streamed.extend_days(&additional).err(), Some(crate::errors::ReportError::Streaming));
+    }
+
+    #[test]
+    fn stopped_realizations() {
+        // The first and last realizations stopped early, so their last counts are carried forward
+        let counting_tables: Vec<CountingTable> = vec![vec![10, 8], vec![10, 8, 6, 4], vec![10]].into_iter()
+            .map(|healthy| vec![(Individual::Healthy, healthy)].into_iter().collect())
+            .collect();
+        let summary = Report::from(counting_tables).summarize(1);
+        let means: Vec<f64> = summary.individual(&Individual::Healthy).iter().map(|statistic| statistic.mean()).collect();
+        assert_eq!(means, vec![10., 26. / 3., 8., 22. / 3.]);
+        assert!(summary.individual(&Individual::Healthy).iter().all(|statistic| statistic.len() == 3));
+    }
+}
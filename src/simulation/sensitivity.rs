@@ -0,0 +1,317 @@
+use crate::building::Spreading;
+use crate::prelude::{Board, BoardBuilder, Individual, Report, ReportPlan, Simulation};
+use crate::simulation::Strategy;
+use getset::{Getters, Setters, MutGetters};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+/// Parameter varied by a `SensitivityAnalysis`, with the values it takes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Parameter {
+    /// Number of individuals immunized before the first day, see `Strategy::vaccinations`
+    Vaccinations(Vec<usize>),
+    /// Factor multiplying the rows and columns of every building, rounded to at least one seat
+    BuildingScale(Vec<f64>),
+    /// Spreading mode of all buildings
+    Spreading(Vec<Spreading>),
+    /// Probability of infection, in percentage, with `Spreading::Airborne` in all buildings
+    Transmission(Vec<u8>),
+}
+
+impl Parameter {
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Parameter::Vaccinations(_) => "Vaccinations",
+            Parameter::BuildingScale(_) => "Building scale",
+            Parameter::Spreading(_) => "Spreading",
+            Parameter::Transmission(_) => "Transmission",
+        }
+    }
+
+    /// Returns the number of values of the parameter.
+    pub fn len(&self) -> usize {
+        match self {
+            Parameter::Vaccinations(values) => values.len(),
+            Parameter::BuildingScale(values) => values.len(),
+            Parameter::Spreading(values) => values.len(),
+            Parameter::Transmission(values) => values.len(),
+        }
+    }
+
+    /// Checks if the parameter has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value with index `index` as a number, or `None` if the parameter is not numeric.
+    fn value(&self, index: usize) -> Option<f64> {
+        match self {
+            Parameter::Vaccinations(values) => Some(values[index] as f64),
+            Parameter::BuildingScale(values) => Some(values[index]),
+            Parameter::Spreading(_) => None,
+            Parameter::Transmission(values) => Some(f64::from(values[index])),
+        }
+    }
+
+    /// Returns the value with index `index` as text.
+    fn label(&self, index: usize) -> String {
+        match self {
+            Parameter::Spreading(values) => format!("{:?}", values[index]),
+            _ => self.value(index).expect("numeric parameter").to_string(),
+        }
+    }
+
+    /// Returns the board built by `board_builder` with the value with index `index`.
+    fn board(&self, index: usize, board_builder: &BoardBuilder) -> Board {
+        let mut board_builder = board_builder.clone();
+        let mut strategy = Strategy::new(self.name());
+        match self {
+            Parameter::Vaccinations(values) => strategy.vaccinations = values[index],
+            Parameter::BuildingScale(values) => {
+                let scale = |side: usize| ((side as f64 * values[index]).round() as usize).max(1);
+                for (columns, rows) in board_builder.buildings.iter_mut() {
+                    *columns = scale(*columns);
+                    *rows = scale(*rows);
+                }
+            },
+            Parameter::Spreading(values) => board_builder.spreading = values[index],
+            Parameter::Transmission(values) => board_builder.spreading = Spreading::Airborne(values[index]),
+        }
+        strategy.board(&board_builder).expect("strategies without closed buildings apply to every board")
+    }
+}
+
+/// One-at-a-time sensitivity analysis of the number of healthy individuals in the last day.
+///
+/// Each parameter is varied on its own around the baseline given by the board builder,
+/// and each value is evaluated by a whole simulation.
+///
+/// # Remarks
+///
+/// Immune individuals count as healthy, so that immunizing someone does not reduce the result.
+/// Individuals that become immune after being sick, see `Convalescence::Immune`, count as well.
+///
+/// # Examples
+///
+/// ```
+/// # use virus_alarm::prelude::*;
+/// use virus_alarm::simulation::{Parameter, SensitivityAnalysis};
+/// let analysis = SensitivityAnalysis {
+///     board_builder: BoardBuilder { healthy: 30, infected1: 2, ..Default::default() },
+///     report_plan: ReportPlan { num_simulations: 10, days: 5, ..Default::default() },
+///     parameters: vec![
+///         Parameter::Vaccinations(vec![0, 10, 20]),
+///         Parameter::Spreading(vec![Spreading::One, Spreading::Everyone]),
+///     ],
+/// };
+/// let results = analysis.run();
+/// assert_eq!(results.parameters[0].points.len(), 3);
+/// assert_eq!(results.parameters[1].elasticity, None);
+/// println!("{}", results);
+/// ```
+#[derive(Debug, Clone, PartialEq, Getters, Setters, MutGetters, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SensitivityAnalysis {
+    /// Baseline board setup
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub board_builder: BoardBuilder,
+    /// Report setup used in each evaluation
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub report_plan: ReportPlan,
+    /// Parameters to vary
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    pub parameters: Vec<Parameter>,
+}
+
+impl SensitivityAnalysis {
+    /// Returns the results of simulating the baseline and every value of every parameter.
+    pub fn run(&self) -> SensitivityResults {
+        self.run_with(&mut rand::thread_rng())
+    }
+
+    /// Returns the results of simulating the baseline and every value of every parameter,
+    /// using `rng` as the source of randomness.
+    pub fn run_with(&self, rng: &mut dyn rand::RngCore) -> SensitivityResults {
+        let mut evaluate = |board: Board| {
+            let report = Simulation::new(board, self.report_plan.clone()).run_with(rng);
+            final_healthy(&report)
+        };
+        let (mean, error) = evaluate(self.board_builder.clone().build());
+        let baseline = SensitivityPoint { label: "Baseline".to_string(), value: None, final_healthy: mean, error };
+        let parameters = self.parameters.iter()
+            .map(|parameter| {
+                let points: Vec<SensitivityPoint> = (0..parameter.len())
+                    .map(|index| {
+                        let (mean, error) = evaluate(parameter.board(index, &self.board_builder));
+                        SensitivityPoint { label: parameter.label(index), value: parameter.value(index), final_healthy: mean, error }
+                    })
+                    .collect();
+                ParameterSensitivity { name: parameter.name().to_string(), elasticity: elasticity(&points), points }
+            })
+            .collect();
+        SensitivityResults { baseline, parameters }
+    }
+}
+
+/// Returns the mean and standard error of the number of healthy individuals in the last day,
+/// counting immune ones, see `SensitivityAnalysis`.
+fn final_healthy(report: &Report) -> (f64, f64) {
+    let healthy: average::Variance = report.counting_tables().iter()
+        .map(|counting_table| {
+            counting_table.last_day().into_iter()
+                .filter(|(individual, _)| individual.is_healthy() || individual == &Individual::Immune)
+                .map(|(_, count)| count as f64)
+                .sum::<f64>()
+        })
+        .collect();
+    (healthy.mean(), healthy.error())
+}
+
+/// Returns the arc elasticity between the points with the smallest and largest values,
+/// if they are numeric and the elasticity is defined.
+fn elasticity(points: &[SensitivityPoint]) -> Option<f64> {
+    let numeric = || points.iter().filter_map(|point| point.value.map(|value| (value, point.final_healthy)));
+    let (x1, y1) = numeric().min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))?;
+    let (x2, y2) = numeric().max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))?;
+    if x1 == x2 || x1 + x2 == 0. || y1 + y2 == 0. {
+        return None;
+    }
+    Some(((y2 - y1) / (y2 + y1)) / ((x2 - x1) / (x2 + x1)))
+}
+
+/// Number of healthy individuals in the last day for one value of a parameter.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SensitivityPoint {
+    /// Value of the parameter, as text
+    pub label: String,
+    /// Value of the parameter, if it is numeric
+    pub value: Option<f64>,
+    /// Average number of healthy individuals in the last day, counting immune ones
+    pub final_healthy: f64,
+    /// Standard error of `final_healthy`
+    pub error: f64,
+}
+
+/// Effect of varying one parameter, see `SensitivityResults`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParameterSensitivity {
+    /// Name of the parameter
+    pub name: String,
+    /// Result of each value, in the order they were given
+    pub points: Vec<SensitivityPoint>,
+    /// Arc elasticity of the final number of healthy individuals between the smallest and largest values,
+    /// that is, its relative change divided by the relative change of the parameter.
+    ///
+    /// It is `None` if the parameter is not numeric or takes a single value.
+    pub elasticity: Option<f64>,
+}
+
+impl ParameterSensitivity {
+    /// Returns the smallest average number of healthy individuals in the last day among all values.
+    pub fn low(&self) -> f64 {
+        self.points.iter().map(|point| point.final_healthy).fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns the largest average number of healthy individuals in the last day among all values.
+    pub fn high(&self) -> f64 {
+        self.points.iter().map(|point| point.final_healthy).fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Returns the difference between `high` and `low`, the width of the bar in a tornado diagram.
+    pub fn swing(&self) -> f64 {
+        if self.points.is_empty() { 0. } else { self.high() - self.low() }
+    }
+}
+
+/// Results of a `SensitivityAnalysis`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SensitivityResults {
+    /// Result of the baseline
+    pub baseline: SensitivityPoint,
+    /// Effect of each parameter, in the order they were given
+    pub parameters: Vec<ParameterSensitivity>,
+}
+
+impl SensitivityResults {
+    /// Returns the parameters from the largest to the smallest swing, as in a tornado diagram.
+    pub fn tornado(&self) -> Vec<&ParameterSensitivity> {
+        let mut parameters: Vec<&ParameterSensitivity> = self.parameters.iter().collect();
+        parameters.sort_by(|a, b| b.swing().partial_cmp(&a.swing()).unwrap_or(std::cmp::Ordering::Equal));
+        parameters
+    }
+}
+
+impl core::fmt::Display for SensitivityResults {
+    /// Writes the baseline and the tornado summary as a table.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "Baseline final healthy: {:.2} ± {:.2}", self.baseline.final_healthy, self.baseline.error)?;
+        let name_width = self.parameters.iter().map(|parameter| parameter.name.len()).max().unwrap_or(0).max("Parameter".len());
+        writeln!(f, "{:<w$} {:>8} {:>8} {:>8} Elasticity", "Parameter", "Low", "High", "Swing", w = name_width)?;
+        for parameter in self.tornado() {
+            let elasticity = parameter.elasticity.map_or("-".to_string(), |elasticity| format!("{:.2}", elasticity));
+            writeln!(f, "{:<w$} {:>8.2} {:>8.2} {:>8.2} {}",
+                parameter.name, parameter.low(), parameter.high(), parameter.swing(), elasticity, w = name_width)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis() -> SensitivityAnalysis {
+        SensitivityAnalysis {
+            board_builder: BoardBuilder { healthy: 30, infected1: 2, buildings: vec![(3, 3), (3, 3)], ..Default::default() },
+            report_plan: ReportPlan { num_simulations: 5, days: 4, ..Default::default() },
+            parameters: vec![
+                Parameter::Spreading(vec![Spreading::One, Spreading::Everyone]),
+                Parameter::Vaccinations(vec![0, 30]),
+                Parameter::BuildingScale(vec![1.]),
+                Parameter::Transmission(vec![0, 100]),
+            ],
+        }
+    }
+
+    #[test]
+    fn board() {
+        let board_builder = BoardBuilder { healthy: 5, buildings: vec![(2, 3)], ..Default::default() };
+        let board = Parameter::BuildingScale(vec![0.1, 2.]).board(1, &board_builder);
+        assert_eq!(board.buildings()[0].people().dim(), (6, 4));
+        let board = Parameter::BuildingScale(vec![0.1, 2.]).board(0, &board_builder);
+        assert_eq!(board.buildings()[0].people().dim(), (1, 1));
+        let board = Parameter::Transmission(vec![30]).board(0, &board_builder);
+        assert_eq!(board.buildings()[0].spreading(), &Spreading::Airborne(30));
+    }
+
+    #[test]
+    fn run_with() {
+        let results = analysis().run_with(&mut crate::tests::rng(1));
+        let vaccinations = &results.parameters[1];
+        assert_eq!(vaccinations.points[1].final_healthy, 30.);
+        assert_eq!(vaccinations.points[1].error, 0.);
+        assert!(vaccinations.elasticity.unwrap() > 0.);
+        let transmission = &results.parameters[3];
+        assert_eq!(transmission.points[0].final_healthy, 30.);
+        assert!(transmission.elasticity.unwrap() < 0.);
+        assert_eq!(results.parameters[0].elasticity, None);
+        assert_eq!(results.parameters[2].elasticity, None);
+        assert_eq!(results.parameters[2].swing(), 0.);
+        let swings: Vec<f64> = results.tornado().iter().map(|parameter| parameter.swing()).collect();
+        assert!(swings.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(results, analysis().run_with(&mut crate::tests::rng(1)));
+    }
+
+    #[test]
+    fn elasticity() {
+        let point = |value: f64, final_healthy: f64| SensitivityPoint { label: value.to_string(), value: Some(value), final_healthy, error: 0. };
+        assert_eq!(super::elasticity(&[point(1., 10.), point(3., 30.)]), Some(1.));
+        assert_eq!(super::elasticity(&[point(0., 10.), point(2., 10.)]), Some(0.));
+        assert_eq!(super::elasticity(&[point(1., 10.)]), None);
+    }
+}
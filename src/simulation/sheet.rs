@@ -0,0 +1,188 @@
+use crate::prelude::{Convalescence, Individual, SimulationBuilder, Spreading, Vaccine, VaccinationStrategy, Visiting};
+use crate::recording::DayZero;
+use std::fmt::Write;
+use strum::IntoEnumIterator;
+
+impl SimulationBuilder {
+    /// Returns a human-readable description of the scenario, in Markdown.
+    ///
+    /// The sheet describes the population, the buildings, the rules and the interventions
+    /// in prose and tables, so that handouts always match the simulated configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// let simulation_builder = SimulationBuilder {
+    ///     board_builder: BoardBuilder { healthy: 98, infected1: 2, buildings: vec![(4, 2)], ..Default::default() },
+    ///     report_plan: ReportPlan { num_simulations: 100, days: 10, ..Default::default() },
+    ///     ..Default::default()
+    /// };
+    /// let sheet = simulation_builder.scenario_sheet();
+    /// assert!(sheet.starts_with("# Scenario\n"));
+    /// assert!(sheet.contains("| Healthy | 98 |"));
+    /// ```
+    pub fn scenario_sheet(&self) -> String {
+        let mut sheet = String::new();
+        self.write_sheet(&mut sheet).expect("writing on a string can not fail");
+        sheet
+    }
+
+    fn write_sheet(&self, f: &mut String) -> std::fmt::Result {
+        let board = &self.board_builder;
+        writeln!(f, "# Scenario\n")?;
+        writeln!(f, "The game is played for {} days and simulated {} times.\n", self.report_plan.days, self.report_plan.num_simulations)?;
+
+        // Population
+        writeln!(f, "## Population\n")?;
+        writeln!(f, "| Individual | Number |")?;
+        writeln!(f, "|---|---|")?;
+        let initial = self.board_builder.clone().build();
+        let population = initial.population();
+        // Variants given by the numbers of individuals of the builder are always listed
+        for individual in Individual::iter() {
+            let number = population.counting(individual);
+            if individual <= Individual::HealthySuperspreader || number > 0 {
+                writeln!(f, "| {} | {} |", individual, number)?;
+            }
+        }
+        let total = population.len();
+        writeln!(f, "| **Total** | {} |\n", total)?;
+
+        // Buildings
+        writeln!(f, "## Buildings\n")?;
+        writeln!(f, "| Building | Size | Capacity |")?;
+        writeln!(f, "|---|---|---|")?;
+        for (index, (columns, rows)) in board.buildings.iter().enumerate() {
+            writeln!(f, "| {} | {} x {} | {} |", index + 1, columns, rows, columns * rows)?;
+        }
+        let capacity: usize = board.buildings.iter().map(|(columns, rows)| columns * rows).sum();
+        writeln!(f, "| **Total** | | {} |\n", capacity)?;
+
+        // Rules
+        writeln!(f, "## Rules\n")?;
+        writeln!(f, "- Spreading: {}", describe_spreading(&board.spreading))?;
+        writeln!(f, "- Visiting: {}", describe_visiting(&board.visiting))?;
+        writeln!(f, "- Vaccine: {}", match board.vaccine {
+            Vaccine::Perfect => "every immunized individual becomes immune.".to_string(),
+            Vaccine::Imperfect { efficacy } => format!("each immunized individual becomes immune with probability {}%.", efficacy),
+            Vaccine::TwoDoses { protection, delay } => format!("each immunized individual escapes infections with probability {}% until a second dose, {} days later, makes it immune.", protection, delay),
+        })?;
+        writeln!(f, "- Vaccination strategy: {}", match &board.vaccination_strategy {
+            VaccinationStrategy::FirstHealthy => "first healthy individuals in the population.".to_string(),
+            VaccinationStrategy::Random => "healthy individuals chosen at random.".to_string(),
+            VaccinationStrategy::ByIdentity(ids) => format!("healthy individuals among {} chosen identities.", ids.len()),
+        })?;
+        writeln!(f, "- Vaccines available: {}", match board.vaccines {
+            Some(vaccines) => format!("{}.", vaccines),
+            None => "unlimited.".to_string(),
+        })?;
+        writeln!(f, "- Vaccination campaign: {}", match board.vaccination_rate {
+            0 => "none.".to_string(),
+            rate => format!("{} healthy individuals immunized at the start of each day.", rate),
+        })?;
+        writeln!(f, "- Immunity: {}", match board.immunity_duration {
+            Some(days) => format!("lasts {} days.", days),
+            None => "lasts forever.".to_string(),
+        })?;
+        writeln!(f, "- Latent stage: {}", match board.latency {
+            Some(days) if days > 0 => format!("newly infected individuals can not infect during {} days.", days),
+            _ => "none.".to_string(),
+        })?;
+        writeln!(f, "- Asymptomatic infections: {}%.", board.asymptomatic)?;
+        writeln!(f, "- Convalescence: {}", match board.convalescence {
+            Convalescence::Forever => "sick individuals stay sick.".to_string(),
+            Convalescence::Immune { days } => format!("sick individuals become immune after {} days.", days),
+            Convalescence::Healthy { days } => format!("sick individuals become healthy after {} days.", days),
+        })?;
+        writeln!(f, "- Day zero: {}", match board.day_zero {
+            DayZero::AfterActions => "state when the game starts, after the actions taken before play.",
+            DayZero::BeforeActions => "initial population, before the actions taken before play.",
+            DayZero::Excluded => "not reported.",
+        })?;
+
+        // Interventions
+        #[cfg(feature = "unstable")]
+        {
+            writeln!(f, "\n## Interventions\n")?;
+            if self.interventions.is_empty() {
+                writeln!(f, "None.")?;
+            } else {
+                writeln!(f, "| Day | Action |")?;
+                writeln!(f, "|---|---|")?;
+                for intervention in self.interventions.iter() {
+                    writeln!(f, "| {} | {:?} |", intervention.day, intervention.action)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn describe_spreading(spreading: &Spreading) -> String {
+    match spreading {
+        Spreading::Everyone => "if someone infected is in a building, everyone in it is infected.".to_string(),
+        Spreading::One => "each infected individual infects one healthy individual in the same building.".to_string(),
+        Spreading::OneNear => "each infected individual infects one healthy individual sitting next to them, diagonals included.".to_string(),
+        Spreading::OneVeryNear => "each infected individual infects one healthy individual sitting next to them, diagonals excluded.".to_string(),
+        Spreading::OneAdjacent => "each infected individual infects one healthy individual in an adjacent seat.".to_string(),
+        Spreading::Airborne(percent) => format!("if someone infected is in a building, each healthy individual in it is infected with probability {}%.", percent),
+        Spreading::OneWithin(distance) => format!("each infected individual infects one healthy individual within {} seats.", distance),
+        Spreading::DistanceDecay(percent) => format!("each infected individual infects each healthy one with a probability that decreases with distance, {}% at distance one.", percent),
+    }
+}
+
+fn describe_visiting(visiting: &Visiting) -> String {
+    match visiting {
+        Visiting::InOrder => "buildings are filled one after the other.".to_string(),
+        Visiting::Weighted { weights, stay_home } => format!("each individual chooses a building at random with weights {:?}, or stays home with probability {}%.", weights, stay_home),
+        Visiting::Capacity { stay_home } => format!("each individual chooses a building at random, proportionally to its free seats, or stays home with probability {}%.", stay_home),
+    }
+}
+
+#[cfg(test)]
+// Struct updates fill the fields of the `unstable` feature
+#[cfg_attr(not(feature = "unstable"), allow(clippy::needless_update))]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, ReportPlan};
+
+    #[test]
+    fn scenario_sheet() {
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder {
+                healthy: 3,
+                infected1: 1,
+                buildings: vec![(2, 1), (1, 1)],
+                spreading: Spreading::Everyone,
+                vaccines: Some(2),
+                ..Default::default()
+            },
+            report_plan: ReportPlan { num_simulations: 5, days: 2, ..Default::default() },
+            ..Default::default()
+        };
+        let sheet = simulation_builder.scenario_sheet();
+        assert!(sheet.contains("The game is played for 2 days and simulated 5 times."));
+        assert!(sheet.contains("| **Total** | 4 |"));
+        assert!(sheet.contains("| 1 | 2 x 1 | 2 |\n| 2 | 1 x 1 | 1 |\n| **Total** | | 3 |"));
+        assert!(sheet.contains("- Spreading: if someone infected is in a building, everyone in it is infected."));
+        assert!(sheet.contains("- Vaccines available: 2."));
+    }
+
+    #[test]
+    fn composition() {
+        use crate::population::Composition;
+
+        let simulation_builder = SimulationBuilder {
+            board_builder: BoardBuilder {
+                healthy: 3,
+                composition: Some(Composition::new(10).with(Individual::Healthy, 80).with(Individual::Recovered, 20)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let sheet = simulation_builder.scenario_sheet();
+        assert!(sheet.contains("| Healthy | 8 |\n| Infected1 | 0 |"));
+        assert!(sheet.contains("| HealthySuperspreader | 0 |\n| Recovered | 2 |\n| **Total** | 10 |"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Statistical functions shared by reports and their analyses.
+
+/// Quantile of the standard normal distribution for 95% confidence intervals.
+#[cfg(feature = "unstable")]
+pub(crate) const Z_95: f64 = 1.959963984540054;
+
+/// Returns the two-sided quantile of Student's t-distribution with `degrees_of_freedom` degrees of freedom
+/// for confidence intervals at level `confidence`, by bisection on the distribution function.
+///
+/// It is infinite if there are no degrees of freedom.
+pub(crate) fn student_quantile(confidence: f64, degrees_of_freedom: f64) -> f64 {
+    if degrees_of_freedom <= 0. {
+        return f64::INFINITY;
+    }
+    let alpha = 1. - confidence;
+    let two_sided = |t: f64| regularized_incomplete_beta(degrees_of_freedom / 2., 0.5, degrees_of_freedom / (degrees_of_freedom + t * t));
+    let mut upper = 1.;
+    while two_sided(upper) > alpha {
+        upper *= 2.;
+    }
+    let mut lower = 0.;
+    for _ in 0..100 {
+        let middle = (lower + upper) / 2.;
+        if two_sided(middle) > alpha {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    (lower + upper) / 2.
+}
+
+/// Returns the logarithm of the gamma function, by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146, -86.50532032941677, 24.01409824083091,
+        -1.231739572450155, 0.1208650973866179e-2, -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let series = COEFFICIENTS.iter().enumerate()
+        .fold(1.000000000190015, |series, (index, coefficient)| series + coefficient / (x + 1. + index as f64));
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Returns the regularized incomplete beta function `I_x(a, b)`.
+pub(crate) fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1. - x).ln()).exp();
+    if x < (a + 1.) / (a + b + 2.) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1. - front * beta_continued_fraction(b, a, 1. - x) / b
+    }
+}
+
+/// Evaluates the continued fraction of the incomplete beta function by the modified Lentz method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.;
+    let mut d = 1. - (a + b) * x / (a + 1.);
+    d = 1. / if d.abs() < TINY { TINY } else { d };
+    let mut h = d;
+    for m in 1..300 {
+        let m = m as f64;
+        for &numerator in [
+            m * (b - m) * x / ((a + 2. * m - 1.) * (a + 2. * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2. * m) * (a + 2. * m + 1.)),
+        ].iter() {
+            d = 1. + numerator * d;
+            d = 1. / if d.abs() < TINY { TINY } else { d };
+            c = 1. + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            h *= d * c;
+        }
+        if (d * c - 1.).abs() < 1e-14 {
+            break;
+        }
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0.95, 1., 12.706205 ; "one degree of freedom")]
+    #[test_case(0.95, 10., 2.228139 ; "ten degrees of freedom")]
+    #[test_case(0.99, 5., 4.032143 ; "high confidence")]
+    #[test_case(0.95, 1e6, 1.959964 ; "normal limit")]
+    fn student_quantile(confidence: f64, degrees_of_freedom: f64, expected: f64) {
+        assert!((super::student_quantile(confidence, degrees_of_freedom) - expected).abs() < 1e-4);
+    }
+}
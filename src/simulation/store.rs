@@ -0,0 +1,320 @@
+//! Store of experiments in an SQLite database.
+//!
+//! Each run of a configuration with a seed is recorded together with the configuration, in JSON,
+//! and the outcome of each of its realizations. Configurations are identified by their hash,
+//! see `SimulationBuilder::config_hash`, so that all runs of a configuration can be queried together.
+//!
+//! The tables are:
+//! - `configs (hash, config)`, with one row per configuration,
+//! - `runs (id, config_hash, seed)`, with one row per run,
+//! - `outcomes (run, realization, days_simulated, outbreak_size)`, with one row per realization.
+//!
+//! Hashes and seeds are stored as signed integers, the only ones SQLite has, with the same bits.
+//!
+//! # Examples
+//!
+//! ```
+//! # use virus_alarm::prelude::*;
+//! use virus_alarm::simulation::ExperimentStore;
+//! let simulation_builder = SimulationBuilder {
+//!     report_plan: ReportPlan { num_simulations: 3, days: 5, ..Default::default() },
+//!     ..Default::default()
+//! };
+//! let mut store = ExperimentStore::open_in_memory().unwrap();
+//! for seed in 0..2 {
+//!     store.run(&simulation_builder, seed).unwrap();
+//! }
+//! let config_hash = simulation_builder.config_hash();
+//! assert_eq!(store.seeds(config_hash).unwrap(), vec![0, 1]);
+//! assert_eq!(store.outcomes(config_hash).unwrap().len(), 6);
+//! ```
+
+use crate::recording::config_hash;
+use crate::errors::StoreError;
+use crate::prelude::{Report, SimulationBuilder};
+use crate::random::Pcg32;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+/// Tables of the store, created if they do not exist
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS configs (
+    hash INTEGER PRIMARY KEY,
+    config TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    config_hash INTEGER NOT NULL REFERENCES configs (hash),
+    seed INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS outcomes (
+    run INTEGER NOT NULL REFERENCES runs (id),
+    realization INTEGER NOT NULL,
+    days_simulated INTEGER NOT NULL,
+    outbreak_size INTEGER NOT NULL,
+    PRIMARY KEY (run, realization)
+);
+CREATE INDEX IF NOT EXISTS runs_by_config ON runs (config_hash);
+";
+
+/// Outcome of a realization of a run, see `ExperimentStore::outcomes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Outcome {
+    /// Identifier of the run, as returned by `ExperimentStore::insert`
+    pub run: i64,
+    /// Seed of the run
+    pub seed: u64,
+    /// Index of the realization in the run
+    pub realization: usize,
+    /// Number of days simulated, see `Report::days_simulated`
+    pub days_simulated: usize,
+    /// Size of the outbreak, see `Report::outbreak_sizes`
+    pub outbreak_size: usize,
+}
+
+/// Experiments persisted in an SQLite database, see the module documentation.
+#[derive(Debug)]
+pub struct ExperimentStore {
+    connection: Connection,
+}
+
+impl ExperimentStore {
+    /// Opens the store in the database at `path`, creating it if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// If the database can not be opened or its tables can not be created.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        ExperimentStore::from_connection(Connection::open(path).map_err(sqlite_error)?)
+    }
+
+    /// Opens a store in memory, which is lost when dropped.
+    ///
+    /// # Errors
+    ///
+    /// If the tables can not be created.
+    pub fn open_in_memory() -> Result<Self, StoreError> {
+        ExperimentStore::from_connection(Connection::open_in_memory().map_err(sqlite_error)?)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, StoreError> {
+        connection.execute_batch(SCHEMA).map_err(sqlite_error)?;
+        Ok(ExperimentStore { connection })
+    }
+
+    /// Runs the simulation of `simulation_builder` with the seed `seed` of `Pcg32`,
+    /// records it and returns the identifier of the run and its report.
+    ///
+    /// Running a configuration twice with the same seed gives the same outcomes.
+    ///
+    /// # Errors
+    ///
+    /// If the run can not be recorded, see `insert`.
+    pub fn run(&mut self, simulation_builder: &SimulationBuilder, seed: u64) -> Result<(i64, Report), StoreError> {
+        let report = simulation_builder.clone().build().run_with(&mut Pcg32::new(seed));
+        let run = self.insert(simulation_builder, seed, &report)?;
+        Ok((run, report))
+    }
+
+    /// Records a run of `simulation_builder` with the seed `seed` and returns its identifier.
+    ///
+    /// # Errors
+    ///
+    /// If the configuration can not be serialized or writing on the database fails.
+    /// In that case, nothing is recorded.
+    ///
+    /// # Remarks
+    ///
+    /// Realizations aggregated in streaming mode are not kept in the report, so their outcomes are not recorded.
+    pub fn insert(&mut self, simulation_builder: &SimulationBuilder, seed: u64, report: &Report) -> Result<i64, StoreError> {
+        let config = serialize(simulation_builder)?;
+        let config_hash = simulation_builder.config_hash();
+        let transaction = self.connection.transaction().map_err(sqlite_error)?;
+        transaction.execute(
+            "INSERT OR IGNORE INTO configs (hash, config) VALUES (?1, ?2)",
+            params![config_hash as i64, config],
+        ).map_err(sqlite_error)?;
+        transaction.execute(
+            "INSERT INTO runs (config_hash, seed) VALUES (?1, ?2)",
+            params![config_hash as i64, seed as i64],
+        ).map_err(sqlite_error)?;
+        let run = transaction.last_insert_rowid();
+        {
+            let mut statement = transaction.prepare(
+                "INSERT INTO outcomes (run, realization, days_simulated, outbreak_size) VALUES (?1, ?2, ?3, ?4)"
+            ).map_err(sqlite_error)?;
+            for (realization, (days_simulated, outbreak_size)) in report.days_simulated().iter().zip(report.outbreak_sizes()).enumerate() {
+                statement.execute(params![run, realization as i64, *days_simulated as i64, outbreak_size as i64]).map_err(sqlite_error)?;
+            }
+        }
+        transaction.commit().map_err(sqlite_error)?;
+        Ok(run)
+    }
+
+    /// Returns the hashes of all recorded configurations, in increasing order of their first run.
+    ///
+    /// # Errors
+    ///
+    /// If reading the database fails.
+    pub fn config_hashes(&self) -> Result<Vec<u64>, StoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT config_hash FROM runs GROUP BY config_hash ORDER BY MIN(id)"
+        ).map_err(sqlite_error)?;
+        let rows = statement.query_map([], |row| row.get::<_, i64>(0)).map_err(sqlite_error)?;
+        let hashes = rows.map(|hash| hash.map(|hash| hash as u64)).collect::<Result<Vec<u64>, _>>().map_err(sqlite_error)?;
+        Ok(hashes)
+    }
+
+    /// Returns the configuration with hash `config_hash`, if it was recorded.
+    ///
+    /// # Errors
+    ///
+    /// If reading the database fails or the configuration can not be deserialized.
+    pub fn config(&self, config_hash: u64) -> Result<Option<SimulationBuilder>, StoreError> {
+        let config: Option<String> = self.connection.query_row(
+            "SELECT config FROM configs WHERE hash = ?1",
+            params![config_hash as i64],
+            |row| row.get(0),
+        ).optional().map_err(sqlite_error)?;
+        config.map(|config| serde_json::from_str(&config).map_err(|error| StoreError::Serialization(error.to_string())))
+            .transpose()
+    }
+
+    /// Returns the seeds of the runs of the configuration with hash `config_hash`, in the order they were recorded.
+    ///
+    /// # Errors
+    ///
+    /// If reading the database fails.
+    pub fn seeds(&self, config_hash: u64) -> Result<Vec<u64>, StoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT seed FROM runs WHERE config_hash = ?1 ORDER BY id"
+        ).map_err(sqlite_error)?;
+        let rows = statement.query_map(params![config_hash as i64], |row| row.get::<_, i64>(0)).map_err(sqlite_error)?;
+        let seeds = rows.map(|seed| seed.map(|seed| seed as u64)).collect::<Result<Vec<u64>, _>>().map_err(sqlite_error)?;
+        Ok(seeds)
+    }
+
+    /// Returns the outcomes of all realizations of the runs of the configuration with hash `config_hash`,
+    /// ordered by run and realization.
+    ///
+    /// # Errors
+    ///
+    /// If reading the database fails.
+    pub fn outcomes(&self, config_hash: u64) -> Result<Vec<Outcome>, StoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT runs.id, runs.seed, outcomes.realization, outcomes.days_simulated, outcomes.outbreak_size \
+             FROM outcomes JOIN runs ON outcomes.run = runs.id \
+             WHERE runs.config_hash = ?1 \
+             ORDER BY runs.id, outcomes.realization"
+        ).map_err(sqlite_error)?;
+        let rows = statement.query_map(params![config_hash as i64], |row| {
+            Ok(Outcome {
+                run: row.get(0)?,
+                seed: row.get::<_, i64>(1)? as u64,
+                realization: row.get::<_, i64>(2)? as usize,
+                days_simulated: row.get::<_, i64>(3)? as usize,
+                outbreak_size: row.get::<_, i64>(4)? as usize,
+            })
+        }).map_err(sqlite_error)?;
+        let outcomes = rows.collect::<Result<Vec<Outcome>, _>>().map_err(sqlite_error)?;
+        Ok(outcomes)
+    }
+}
+
+impl SimulationBuilder {
+    /// Returns the hash of the configuration, which identifies it in an `ExperimentStore`.
+    ///
+    /// It is the same hash as the one of the metadata of CSV exports, see `recording::config_hash`,
+    /// so that exports and stored runs of a configuration can be matched.
+    ///
+    /// Requires the `sqlite` feature.
+    pub fn config_hash(&self) -> u64 {
+        config_hash(self)
+    }
+}
+
+/// Returns the configuration in JSON, as stored in the `configs` table.
+fn serialize(simulation_builder: &SimulationBuilder) -> Result<String, StoreError> {
+    serde_json::to_string(simulation_builder).map_err(|error| StoreError::Serialization(error.to_string()))
+}
+
+/// Returns the error of a failed operation on the database.
+fn sqlite_error(error: rusqlite::Error) -> StoreError {
+    StoreError::Sqlite(error.to_string())
+}
+
+#[cfg(test)]
+// Struct updates fill the fields of the `unstable` feature
+#[cfg_attr(not(feature = "unstable"), allow(clippy::needless_update))]
+mod tests {
+    use super::*;
+    use crate::prelude::{BoardBuilder, ReportPlan};
+    use crate::simulation::Aggregation;
+
+    fn simulation_builder(healthy: usize) -> SimulationBuilder {
+        SimulationBuilder {
+            board_builder: BoardBuilder { healthy, infected1: 2, ..Default::default() },
+            report_plan: ReportPlan { num_simulations: 4, days: 6, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn outcomes_by_config() {
+        let mut store = ExperimentStore::open_in_memory().unwrap();
+        let (first, report) = store.run(&simulation_builder(30), 3).unwrap();
+        store.run(&simulation_builder(40), 3).unwrap();
+        store.run(&simulation_builder(30), 4).unwrap();
+
+        let config_hash = simulation_builder(30).config_hash();
+        assert_eq!(store.config_hashes().unwrap(), vec![config_hash, simulation_builder(40).config_hash()]);
+        assert_eq!(store.config(config_hash).unwrap(), Some(simulation_builder(30)));
+        assert_eq!(store.config(0).unwrap(), None);
+        assert_eq!(store.seeds(config_hash).unwrap(), vec![3, 4]);
+
+        let outcomes = store.outcomes(config_hash).unwrap();
+        assert_eq!(outcomes.len(), 8);
+        let outbreak_sizes: Vec<usize> = outcomes.iter()
+            .filter(|outcome| outcome.run == first)
+            .map(|outcome| outcome.outbreak_size)
+            .collect();
+        assert_eq!(outbreak_sizes, report.outbreak_sizes());
+    }
+
+    #[test]
+    fn metadata() {
+        let metadata = crate::recording::Metadata::new().with_config(&simulation_builder(30));
+        assert_eq!(metadata.config_hash(), &Some(simulation_builder(30).config_hash()));
+    }
+
+    #[test]
+    fn reproducible() {
+        let mut store = ExperimentStore::open_in_memory().unwrap();
+        let (_, report) = store.run(&simulation_builder(30), 7).unwrap();
+        let (_, other) = store.run(&simulation_builder(30), 7).unwrap();
+        assert_eq!(report, other);
+    }
+
+    #[test]
+    fn streaming() {
+        let mut simulation_builder = simulation_builder(30);
+        simulation_builder.report_plan.aggregation = Aggregation::Streaming { bin_width: 1 };
+        let mut store = ExperimentStore::open_in_memory().unwrap();
+        store.run(&simulation_builder, 0).unwrap();
+        let config_hash = simulation_builder.config_hash();
+        assert_eq!(store.seeds(config_hash).unwrap(), vec![0]);
+        assert!(store.outcomes(config_hash).unwrap().is_empty());
+    }
+
+    #[test]
+    fn persistent() {
+        let path = std::env::temp_dir().join("virus_alarm_persistent.sqlite");
+        std::fs::remove_file(&path).ok();
+        ExperimentStore::open(&path).unwrap().run(&simulation_builder(30), 1).unwrap();
+        let store = ExperimentStore::open(&path).unwrap();
+        let config_hash = simulation_builder(30).config_hash();
+        assert_eq!(store.seeds(config_hash).unwrap(), vec![1]);
+        drop(store);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
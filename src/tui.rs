@@ -0,0 +1,232 @@
+//! Terminal interface to play a `Game`, so that the game can be used in workshops without writing code.
+//!
+//! The interface shows the buildings, the seats of the selected building and the counting table.
+//! The keys are:
+//! - `Up` and `Down` select a building,
+//! - `c` closes or opens the selected building,
+//! - `i` immunizes one healthy individual,
+//! - `Enter` ends the turn, so the day is played,
+//! - `q` or `Esc` quits.
+//!
+//! Run it with `cargo run --example tui --features tui`.
+
+use crate::game::{Game, Outcome, Phase};
+use crate::render::{Legend, RenderOptions};
+use crate::{Action, Individual};
+use ratatui::backend::{Backend, CrosstermBackend};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::execute;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal};
+use strum::IntoEnumIterator;
+
+/// Number of days shown in the counting table
+const DAYS_SHOWN: usize = 8;
+
+/// State of the terminal interface: the game, the selected building and the last message.
+#[derive(Debug, Clone)]
+pub struct App {
+    game: Game,
+    selected: usize,
+    message: String,
+}
+
+impl App {
+    /// Starts the interface on `game`, with the first building selected.
+    pub fn new(game: Game) -> Self {
+        App { game, selected: 0, message: String::from("Press Enter to play a day, q to quit") }
+    }
+
+    /// Returns the game.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns the index of the selected building.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns the message shown at the bottom, for example the result of the last action.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Handles a key press, using `rng` when a day is played.
+    ///
+    /// Returns `false` if the interface should quit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use virus_alarm::prelude::*;
+    /// use crossterm::event::KeyCode;
+    /// use virus_alarm::game::{Game, GameRules};
+    /// use virus_alarm::tui::App;
+    /// let mut app = App::new(Game::new(Board::default(), GameRules::default()));
+    /// app.handle_key(KeyCode::Char('c'), &mut rand::thread_rng());
+    /// assert!(!app.game().board().buildings()[0].is_open());
+    /// assert!(!app.handle_key(KeyCode::Char('q'), &mut rand::thread_rng()));
+    /// ```
+    pub fn handle_key<R: rand::Rng + ?Sized>(&mut self, key: KeyCode, rng: &mut R) -> bool {
+        let buildings = self.game.board().buildings().len();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up if self.selected > 0 => self.selected -= 1,
+            KeyCode::Down if self.selected + 1 < buildings => self.selected += 1,
+            KeyCode::Char('c') if self.selected < buildings => {
+                let building = &self.game.board().buildings()[self.selected];
+                let name = building.name().to_string();
+                let action = if building.is_open() { Action::Close(name) } else { Action::Open(name) };
+                self.play(action);
+            },
+            KeyCode::Char('i') => self.play(Action::Immunize(1)),
+            KeyCode::Enter => {
+                self.message = match self.game.end_turn_with(rng) {
+                    Ok(Phase::Actions) => format!("Day {} played", self.game.day()),
+                    Ok(Phase::Over(Outcome::Won)) => String::from("The outbreak is contained, you won!"),
+                    Ok(Phase::Over(Outcome::Lost)) => String::from("The days ran out, you lost"),
+                    Err(error) => error.to_string(),
+                };
+            },
+            _ => (),
+        }
+        true
+    }
+
+    fn play(&mut self, action: Action) {
+        self.message = match self.game.play(action.clone()) {
+            Ok(_) => format!("{:?}", action),
+            Err(error) => error.to_string(),
+        };
+    }
+}
+
+/// Draws the interface on `frame`.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let board = app.game.board();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .split(frame.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(30), Constraint::Percentage(40)])
+        .split(rows[0]);
+
+    let items: Vec<ListItem> = board.buildings().iter()
+        .map(|building| {
+            let status = if building.is_open() { "" } else { " (closed)" };
+            ListItem::new(format!("{}{}", building.name(), status))
+        })
+        .collect();
+    let buildings = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Buildings"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default();
+    state.select(Some(app.selected));
+    frame.render_stateful_widget(buildings, columns[0], &mut state);
+
+    let options = RenderOptions { legend: Legend::Letters, headers: true, key: false };
+    let seats = board.buildings().get(app.selected).map(|building| building.render(&options)).unwrap_or_default();
+    frame.render_widget(Paragraph::new(seats).block(Block::default().borders(Borders::ALL).title("Seats")), columns[1]);
+
+    let counting_table = board.counting_table();
+    let first = counting_table.days().saturating_sub(DAYS_SHOWN);
+    let mut table = String::new();
+    for individual in Individual::iter() {
+        if let Some(counts) = counting_table.inner().get(&individual) {
+            if counts.iter().any(|&count| count > 0) {
+                let counts: Vec<String> = counts[first..].iter().map(|count| format!("{:>4}", count)).collect();
+                table.push_str(&format!("{} {:<20}{}\n", options.legend.symbol(&individual), individual.to_string(), counts.concat()));
+            }
+        }
+    }
+    let title = format!("Day {} of {}, {} action points left", app.game.day(), app.game.rules().days, app.game.action_points_left());
+    frame.render_widget(Paragraph::new(table).block(Block::default().borders(Borders::ALL).title(title)), columns[2]);
+
+    let help = "Up/Down select, c close/open, i immunize, Enter end turn, q quit";
+    let status = Paragraph::new(format!("{}\n{}", app.message, help));
+    frame.render_widget(status.block(Block::default().borders(Borders::TOP)), rows[1]);
+}
+
+/// Plays `game` on `terminal` until the player quits, and returns the game.
+///
+/// # Errors
+///
+/// If drawing on the terminal or reading its events fails.
+pub fn run_on<B: Backend>(terminal: &mut Terminal<B>, game: Game) -> std::io::Result<Game> {
+    let mut app = App::new(game);
+    let mut rng = rand::thread_rng();
+    loop {
+        terminal.draw(|frame| draw(frame, &app))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(key.code, &mut rng) {
+                return Ok(app.game);
+            }
+        }
+    }
+}
+
+/// Plays `game` in the terminal until the player quits, and returns the game.
+///
+/// The terminal is restored even if playing fails.
+///
+/// # Errors
+///
+/// If the terminal can not be set up, or drawing on it or reading its events fails.
+pub fn run(game: Game) -> std::io::Result<Game> {
+    enable_raw_mode()?;
+    execute!(std::io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+    let result = run_on(&mut terminal, game);
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameRules;
+    use crate::Board;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn handle_key() {
+        let mut app = App::new(Game::new(Board::default(), GameRules { action_points: 2, days: 3 }));
+        let mut rng = crate::tests::rng(1);
+        app.handle_key(KeyCode::Up, &mut rng);
+        assert_eq!(app.selected(), 0);
+        app.handle_key(KeyCode::Down, &mut rng);
+        app.handle_key(KeyCode::Char('c'), &mut rng);
+        assert!(!app.game().board().buildings()[1].is_open());
+        app.handle_key(KeyCode::Char('i'), &mut rng);
+        app.handle_key(KeyCode::Char('i'), &mut rng);
+        assert_eq!(app.message(), "There are no action points left in this turn");
+        assert!(app.handle_key(KeyCode::Enter, &mut rng));
+        assert_eq!(app.game().day(), &1);
+        assert_eq!(app.message(), "Day 1 played");
+        for _ in 0..8 {
+            app.handle_key(KeyCode::Down, &mut rng);
+        }
+        assert_eq!(app.selected(), 7);
+        assert!(!app.handle_key(KeyCode::Esc, &mut rng));
+    }
+
+    #[test]
+    fn draw() {
+        let app = App::new(Game::new(Board::default(), GameRules::default()));
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| super::draw(frame, &app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Concert Hall"));
+        assert!(text.contains("Day 0 of 10, 3 action points left"));
+        assert!(text.contains("Healthy"));
+    }
+}
@@ -0,0 +1,62 @@
+//! Golden-file tests of the games played with a given seed, see `virus_alarm::random`.
+//!
+//! A given seed must produce identical games on every platform and with every version of the crate,
+//! so the state hash of each day is compared with the one recorded in `tests/golden/determinism.txt`.
+//! If a change of the random streams is intended, announce it in the changelog and
+//! regenerate the file with `UPDATE_GOLDEN=1 cargo test --test determinism`.
+
+use virus_alarm::building::Spreading;
+use virus_alarm::prelude::*;
+use virus_alarm::random::{self, Pcg32};
+use rand::RngCore;
+
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/determinism.txt");
+const SEEDS: u64 = 3;
+const DAYS: usize = 10;
+
+fn scenarios() -> Vec<(&'static str, Board)> {
+	let mut capacity = Board::default();
+	capacity.set_visiting(Visiting::Capacity { stay_home: 10 }).set_asymptomatic(20);
+	let mut airborne = Board::default();
+	airborne.set_spreading(Spreading::Airborne(30));
+	let mut decay = Board::default();
+	decay.set_spreading(Spreading::DistanceDecay(50));
+	vec![("default", Board::default()), ("capacity", capacity), ("airborne", airborne), ("decay", decay)]
+}
+
+/// Returns the lines of the golden file: the raw draws, shuffles, and the state hash of each day of each scenario.
+fn lines() -> Vec<String> {
+	let mut lines = Vec::new();
+	for seed in 0..SEEDS {
+		let mut rng = Pcg32::new(seed);
+		let draws: Vec<String> = (0..4).map(|_| format!("{:016x}", rng.next_u64())).collect();
+		lines.push(format!("draws {} {}", seed, draws.join(" ")));
+		let mut values: Vec<usize> = (0..10).collect();
+		random::shuffle(&mut values, &mut rng);
+		lines.push(format!("shuffle {} {:?}", seed, values));
+	}
+	for (name, board) in scenarios() {
+		for seed in 0..SEEDS {
+			let mut board = board.clone();
+			let mut rng = Pcg32::new(seed);
+			for day in 1..=DAYS {
+				board.try_advance_with(&mut rng).unwrap();
+				lines.push(format!("{} {} {} {:016x}", name, seed, day, board.state_hash()));
+			}
+		}
+	}
+	lines
+}
+
+#[test]
+fn golden() {
+	let lines = lines();
+	if std::env::var_os("UPDATE_GOLDEN").is_some() {
+		std::fs::write(GOLDEN, lines.join("\n") + "\n").unwrap();
+	}
+	let golden = std::fs::read_to_string(GOLDEN).unwrap();
+	for (line, expected) in lines.iter().zip(golden.lines()) {
+		assert_eq!(line, expected, "the game diverged from {}", GOLDEN);
+	}
+	assert_eq!(lines.len(), golden.lines().count());
+}
@@ -0,0 +1,86 @@
+//! End-to-end sessions of the board game, checking that the rules compose.
+//!
+//! See also the `official_game` example.
+
+use virus_alarm::prelude::*;
+use virus_alarm::recording::Event;
+
+const VACCINES: usize = 10;
+const DAYS: usize = 10;
+
+fn rng(seed: u64) -> virus_alarm::random::Pcg32 {
+	virus_alarm::random::Pcg32::new(seed)
+}
+
+/// Plays a session, applying `decisions` before the stage of each day.
+fn play<F>(seed: u64, decisions: F) -> Board
+where
+	F: Fn(usize) -> Vec<Action>,
+{
+	let mut rng = rng(seed);
+	let mut board = Board::default();
+	board.set_collect_events(true);
+	assert_eq!(board.immunize_many(VACCINES), VACCINES);
+	for day in 1..=DAYS {
+		for action in decisions(day) {
+			action.apply(&mut board).unwrap();
+		}
+		board.try_advance_with(&mut rng).unwrap();
+		assert_eq!(board.check_census().unwrap().total(), 100);
+	}
+	board
+}
+
+fn lockdown(day: usize) -> Vec<Action> {
+	match day {
+		1 => vec![Action::Close("Concert Hall".to_string())],
+		3 => vec![Action::Close("School".to_string()), Action::Close("Restaurant".to_string())],
+		6 => vec![Action::Open("School".to_string())],
+		8 => vec![Action::Open("Concert Hall".to_string()), Action::Open("Restaurant".to_string())],
+		_ => Vec::new(),
+	}
+}
+
+#[test]
+fn full_session() {
+	let board = play(0, lockdown);
+	let counting_table = board.counting_table();
+	assert_eq!(counting_table.days(), DAYS + 1);
+	assert_eq!(counting_table.last_day()[&Individual::Immune], VACCINES);
+	assert!(counting_table.inner()[&Individual::Healthy].windows(2).all(|days| days[0] >= days[1]));
+	assert!(board.buildings().iter().all(|building| building.is_open()));
+}
+
+#[test]
+fn events_reflect_the_session() {
+	let board = play(1, lockdown);
+	let events = board.recording().events();
+	let closures = events.iter().filter(|event| matches!(event, Event::BuildingClosed { .. })).count();
+	let openings = events.iter().filter(|event| matches!(event, Event::BuildingOpened { .. })).count();
+	assert_eq!((closures, openings), (3, 3));
+	let immunizations = events.iter().filter(|event| matches!(event, Event::Immunization { day: 0 })).count();
+	assert_eq!(immunizations, VACCINES);
+	let infections = events.iter().filter(|event| matches!(event, Event::Infection { .. })).count();
+	let healthy = &board.counting_table().inner()[&Individual::Healthy];
+	assert_eq!(infections, healthy[0] - healthy[DAYS]);
+}
+
+#[test]
+fn closed_town_stops_the_outbreak() {
+	let board = play(2, |day| match day {
+		1 => board_names().into_iter().map(Action::Close).collect(),
+		_ => Vec::new(),
+	});
+	let counting_table = board.counting_table();
+	assert_eq!(counting_table.last_day()[&Individual::Healthy], counting_table.first_day()[&Individual::Healthy]);
+	assert_eq!(counting_table.last_day()[&Individual::Sick], 2);
+}
+
+#[test]
+fn reproducible() {
+	assert_eq!(play(3, lockdown).counting_table(), play(3, lockdown).counting_table());
+}
+
+fn board_names() -> Vec<String> {
+	Board::default().buildings().iter().map(|building| building.name().to_string()).collect()
+}